@@ -15,4 +15,8 @@ set -x
 cargo +"$rust_stable" build --workspace
 cargo +"$rust_stable" test --workspace -- --nocapture
 
+# The simulation engine is only built with its feature
+cargo +"$rust_stable" build -p hyperplane-sim --features engine
+cargo +"$rust_stable" test -p hyperplane-sim --features engine -- --nocapture
+
 exit 0
@@ -11,7 +11,16 @@ export RUSTBACKTRACE=1
 
 set -x
 
-# Run the viz program
-cargo +"$rust_stable" run --bin viz
+# Test the viz program, then draw every chart of the example configs
+cargo +"$rust_stable" test -p hyperplane-viz
+
+configs=programs/hyperplane/viz/configs
+for chart in invariant marginal-price slippage effective-price; do
+  cargo +"$rust_stable" run --bin viz -- curves \
+    -c "$configs/constant-product.json" \
+    -c "$configs/stable-amp-100.json" \
+    --chart "$chart" \
+    -o "target/viz-$chart.svg"
+done
 
 exit 0
@@ -0,0 +1,35 @@
+#!/usr/bin/env bash
+
+set -e
+cd "$(dirname "$0")/.."
+
+# cargo-fuzz builds with sanitizers, which requires nightly. The default CI nightly predates
+# the stable toolchain of the workspace, so use one matching 1.66.0
+RUST_NIGHTLY_VERSION=2022-12-15 source ./ci/rust-version.sh nightly
+
+cargo +"$rust_nightly" install cargo-fuzz --version=0.11.1 --locked || true
+
+usage() {
+  exitcode=0
+  if [[ -n "$1" ]]; then
+    exitcode=1
+    echo "Error: $*"
+  fi
+  echo "Usage: $0 [fuzz-target] [run-time-in-seconds]"
+  exit $exitcode
+}
+
+fuzz_target=$1
+if [[ -z $fuzz_target ]]; then
+  usage "No fuzz target provided"
+fi
+
+run_time=$2
+if [[ -z $2 ]]; then
+  usage "No runtime provided"
+fi
+
+# cargo fuzz returns an error code on crash, with the input saved under libfuzzer/artifacts.
+# The targets are their own workspace, so build them from their directory
+cd programs/hyperplane/fuzz/libfuzzer
+cargo +"$rust_nightly" fuzz run --fuzz-dir . "$fuzz_target" -- -max_total_time="$run_time"
@@ -1,23 +1,51 @@
+use std::{mem::size_of, sync::Arc};
+
 use anchor_client::{
-    anchor_lang::{prelude::Pubkey, system_program::System, AccountDeserialize, Id},
+    anchor_lang::{
+        prelude::{Clock, Pubkey},
+        system_program::System,
+        AccountDeserialize, Discriminator, Id,
+    },
+    solana_client::{
+        rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+        rpc_filter::{Memcmp, RpcFilterType},
+    },
     solana_sdk::{
+        account::from_account,
+        instruction::Instruction,
+        message::Message,
+        packet::PACKET_DATA_SIZE,
         rent::Rent,
-        signature::{Keypair, Signer},
+        signature::{Keypair, Signature, Signer},
         sysvar::SysvarId,
     },
 };
 use anchor_spl::token::TokenAccount;
-use anyhow::Result;
+use anyhow::{anyhow, bail, ensure, Result};
 use hyperplane::{
-    ix::{Initialize, UpdatePoolConfig},
-    state::SwapPool,
+    curve::{
+        base::{CurveType, SwapCurve},
+        calculator::{AorB, CurveCalculator, RoundDirection, TradeDirection},
+    },
+    ix::{Initialize, UpdatePoolConfig, WithdrawFees},
+    quote::{PoolAccountsData, PoolSnapshot},
+    state::{
+        ConstantPriceCurve, ConstantProductCurve, LiquidityLock, OffsetCurve, StableCurve, SwapPool,
+    },
     utils::seeds::{pda, pda::InitPoolPdas},
     InitialSupply,
 };
 use orbit_link::{async_client::AsyncClient, OrbitLink};
+use solana_account_decoder::UiAccountEncoding;
+use solana_address_lookup_table_program as alt;
+use spl_associated_token_account as ata;
 use tracing::info;
 
-use crate::send_tx;
+use crate::{
+    model::PoolFilters,
+    send_tx,
+    utils::{lookup_table, token},
+};
 
 pub struct HyperplaneClient<T: AsyncClient, S: Signer> {
     pub client: OrbitLink<T, S>,
@@ -68,6 +96,7 @@ where
                     initial_supply_a,
                     initial_supply_b,
                 },
+            min_liquidity,
         }: Initialize,
     ) -> Result<Pubkey> {
         let pool_kp = Keypair::new();
@@ -98,6 +127,7 @@ where
             pool_token_mint,
             token_a_fees_vault,
             token_b_fees_vault,
+            min_liquidity_vault,
         } = pda::init_pool_pdas_program_id(
             &self.config.program_id,
             &pool_kp.pubkey(),
@@ -153,6 +183,7 @@ where
                 admin_token_a_ata,
                 admin_token_b_ata,
                 admin_pool_token_ata: admin_pool_token_ata.pubkey(),
+                min_liquidity_vault,
                 system_program: System::id(),
                 rent: Rent::id(),
                 pool_token_program,
@@ -162,6 +193,7 @@ where
             hyperplane::instruction::InitializePool {
                 initial_supply_a,
                 initial_supply_b,
+                min_liquidity,
                 fees,
                 curve_parameters,
             },
@@ -193,8 +225,939 @@ where
         Ok(())
     }
 
+    pub async fn swap(
+        &self,
+        signer: Pubkey,
+        pool_pubkey: Pubkey,
+        source_mint: Pubkey,
+        amount_in: u64,
+        slippage_bps: u64,
+    ) -> Result<()> {
+        ensure!(
+            slippage_bps <= 10_000,
+            "Slippage of {} bps exceeds 100%",
+            slippage_bps
+        );
+        let snapshot = self.get_pool_snapshot(&pool_pubkey).await?;
+        let pool = snapshot.pool;
+        let (
+            trade_direction,
+            destination_mint,
+            source_vault,
+            destination_vault,
+            source_token_fees_vault,
+        ) = if source_mint == pool.token_a_mint {
+            (
+                TradeDirection::AtoB,
+                pool.token_b_mint,
+                pool.token_a_vault,
+                pool.token_b_vault,
+                pool.token_a_fees_vault,
+            )
+        } else if source_mint == pool.token_b_mint {
+            (
+                TradeDirection::BtoA,
+                pool.token_a_mint,
+                pool.token_b_vault,
+                pool.token_a_vault,
+                pool.token_b_fees_vault,
+            )
+        } else {
+            bail!(
+                "Mint {} is not a token of pool {}",
+                source_mint,
+                pool_pubkey
+            );
+        };
+        let source_token_program = self.client.client.get_account(&source_mint).await?.owner;
+        let destination_token_program = self
+            .client
+            .client
+            .get_account(&destination_mint)
+            .await?
+            .owner;
+
+        let quote = snapshot.swap(&source_mint, amount_in, false)?;
+        let minimum_amount_out = 10_000_u64
+            .checked_sub(slippage_bps)
+            .and_then(|keep_bps| u128::from(quote.amount_out).checked_mul(u128::from(keep_bps)))
+            .and_then(|amount| amount.checked_div(10_000))
+            .ok_or_else(|| anyhow!("Slippage of {} bps is out of range", slippage_bps))?;
+        let minimum_amount_out = u64::try_from(minimum_amount_out)?;
+        info!(
+            "Swap quote: trade_direction={:?}, amount_in={}, actual_amount_in={}, total_fees={}, expected_amount_out={}, minimum_amount_out={}",
+            trade_direction,
+            amount_in,
+            quote.actual_amount_in,
+            quote.total_fees,
+            quote.amount_out,
+            minimum_amount_out
+        );
+
+        let source_user_ata = ata::get_associated_token_address_with_program_id(
+            &signer,
+            &source_mint,
+            &source_token_program,
+        );
+        let destination_user_ata = ata::get_associated_token_address_with_program_id(
+            &signer,
+            &destination_mint,
+            &destination_token_program,
+        );
+        info!("Source ATA: {}", source_user_ata);
+        info!("Destination ATA: {}", destination_user_ata);
+
+        let tx = self
+            .client
+            .tx_builder()
+            .add_ix(
+                ata::instruction::create_associated_token_account_idempotent(
+                    &self.client.payer(),
+                    &signer,
+                    &source_mint,
+                    &source_token_program,
+                ),
+            )
+            .add_ix(
+                ata::instruction::create_associated_token_account_idempotent(
+                    &self.client.payer(),
+                    &signer,
+                    &destination_mint,
+                    &destination_token_program,
+                ),
+            )
+            .add_anchor_ix(
+                &self.config.program_id,
+                hyperplane::accounts::Swap {
+                    signer,
+                    pool: pool_pubkey,
+                    swap_curve: pool.swap_curve,
+                    pool_authority: pool.pool_authority,
+                    source_mint,
+                    destination_mint,
+                    source_vault,
+                    destination_vault,
+                    source_token_fees_vault,
+                    source_user_ata,
+                    destination_user_ata,
+                    source_token_host_fees_account: None,
+                    source_token_program,
+                    destination_token_program,
+                },
+                hyperplane::instruction::Swap {
+                    amount_in,
+                    minimum_amount_out,
+                },
+            );
+        send_tx!(self, tx, []);
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn deposit(
+        &self,
+        signer: Pubkey,
+        pool_pubkey: Pubkey,
+        pool_token_user_ata: Option<Pubkey>,
+        pool_token_amount: Option<u64>,
+        maximum_token_a_amount: Option<u64>,
+        maximum_token_b_amount: Option<u64>,
+    ) -> Result<()> {
+        let snapshot = self.get_pool_snapshot(&pool_pubkey).await?;
+        let pool = snapshot.pool;
+        let calculator = &snapshot.swap_curve.calculator;
+        ensure!(
+            calculator.allows_deposits(),
+            "{:?} pools do not allow deposits",
+            snapshot.swap_curve.curve_type
+        );
+
+        let (pool_token_amount, maximum_token_a_amount, maximum_token_b_amount) = match (
+            pool_token_amount,
+            maximum_token_a_amount,
+            maximum_token_b_amount,
+        ) {
+            (Some(pool_token_amount), maximum_token_a_amount, maximum_token_b_amount) => {
+                let quote = snapshot.deposit(pool_token_amount)?;
+                (
+                    quote.pool_token_amount,
+                    maximum_token_a_amount.unwrap_or(quote.token_a_amount),
+                    maximum_token_b_amount.unwrap_or(quote.token_b_amount),
+                )
+            }
+            (None, Some(maximum_token_a_amount), Some(maximum_token_b_amount)) => (
+                max_deposit_pool_tokens(
+                    calculator.as_ref(),
+                    snapshot.pool_token_supply,
+                    snapshot.token_a_vault_amount,
+                    snapshot.token_b_vault_amount,
+                    maximum_token_a_amount,
+                    maximum_token_b_amount,
+                ),
+                maximum_token_a_amount,
+                maximum_token_b_amount,
+            ),
+            _ => bail!("Either the pool token amount or both maximum token amounts are required"),
+        };
+        ensure!(
+            pool_token_amount > 0,
+            "Maximum token amounts are too small to mint any pool tokens"
+        );
+        info!(
+            "Deposit quote: pool_token_amount={}, maximum_token_a_amount={}, maximum_token_b_amount={}",
+            pool_token_amount, maximum_token_a_amount, maximum_token_b_amount
+        );
+
+        let token_a_token_program = self
+            .client
+            .client
+            .get_account(&pool.token_a_mint)
+            .await?
+            .owner;
+        let token_b_token_program = self
+            .client
+            .client
+            .get_account(&pool.token_b_mint)
+            .await?
+            .owner;
+        let pool_token_program = self
+            .client
+            .client
+            .get_account(&pool.pool_token_mint)
+            .await?
+            .owner;
+        let token_a_user_ata = ata::get_associated_token_address_with_program_id(
+            &signer,
+            &pool.token_a_mint,
+            &token_a_token_program,
+        );
+        let token_b_user_ata = ata::get_associated_token_address_with_program_id(
+            &signer,
+            &pool.token_b_mint,
+            &token_b_token_program,
+        );
+
+        let mut tx = self.client.tx_builder();
+        let pool_token_user_ata = match pool_token_user_ata {
+            Some(pool_token_user_ata) => pool_token_user_ata,
+            None => {
+                tx = tx.add_ix(
+                    ata::instruction::create_associated_token_account_idempotent(
+                        &self.client.payer(),
+                        &signer,
+                        &pool.pool_token_mint,
+                        &pool_token_program,
+                    ),
+                );
+                ata::get_associated_token_address_with_program_id(
+                    &signer,
+                    &pool.pool_token_mint,
+                    &pool_token_program,
+                )
+            }
+        };
+        info!("Pool token ATA: {}", pool_token_user_ata);
+
+        tx = tx.add_anchor_ix(
+            &self.config.program_id,
+            hyperplane::accounts::Deposit {
+                signer,
+                pool: pool_pubkey,
+                swap_curve: pool.swap_curve,
+                pool_authority: pool.pool_authority,
+                token_a_mint: pool.token_a_mint,
+                token_b_mint: pool.token_b_mint,
+                token_a_vault: pool.token_a_vault,
+                token_b_vault: pool.token_b_vault,
+                pool_token_mint: pool.pool_token_mint,
+                token_a_user_ata,
+                token_b_user_ata,
+                pool_token_user_ata,
+                pool_token_program,
+                token_a_token_program,
+                token_b_token_program,
+            },
+            hyperplane::instruction::Deposit {
+                pool_token_amount,
+                maximum_token_a_amount,
+                maximum_token_b_amount,
+            },
+        );
+        send_tx!(self, tx, []);
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn withdraw(
+        &self,
+        signer: Pubkey,
+        pool_pubkey: Pubkey,
+        pool_token_user_ata: Option<Pubkey>,
+        pool_token_amount: Option<u64>,
+        percent: Option<u8>,
+        minimum_token_a_amount: Option<u64>,
+        minimum_token_b_amount: Option<u64>,
+    ) -> Result<()> {
+        let snapshot = self.get_pool_snapshot(&pool_pubkey).await?;
+        let pool = snapshot.pool;
+
+        let token_a_token_program = self
+            .client
+            .client
+            .get_account(&pool.token_a_mint)
+            .await?
+            .owner;
+        let token_b_token_program = self
+            .client
+            .client
+            .get_account(&pool.token_b_mint)
+            .await?
+            .owner;
+        let pool_token_program = self
+            .client
+            .client
+            .get_account(&pool.pool_token_mint)
+            .await?
+            .owner;
+        let pool_token_user_ata = pool_token_user_ata.unwrap_or_else(|| {
+            ata::get_associated_token_address_with_program_id(
+                &signer,
+                &pool.pool_token_mint,
+                &pool_token_program,
+            )
+        });
+        info!("Pool token ATA: {}", pool_token_user_ata);
+
+        let pool_token_amount = match (pool_token_amount, percent) {
+            (Some(pool_token_amount), None) => pool_token_amount,
+            (None, Some(percent)) => {
+                ensure!(
+                    percent > 0 && percent <= 100,
+                    "Percent must be between 1 and 100, got {}",
+                    percent
+                );
+                let pool_token_balance =
+                    self.get_token_account_amount(&pool_token_user_ata).await?;
+                let pool_token_amount = u128::from(pool_token_balance)
+                    .checked_mul(u128::from(percent))
+                    .and_then(|amount| amount.checked_div(100))
+                    .ok_or_else(|| anyhow!("Overflow computing {}% of the pool tokens", percent))?;
+                u64::try_from(pool_token_amount)?
+            }
+            _ => bail!("Exactly one of the pool token amount or the percent is required"),
+        };
+        ensure!(pool_token_amount > 0, "Nothing to withdraw");
+
+        let quote = snapshot.withdraw(pool_token_amount)?;
+        let minimum_token_a_amount = minimum_token_a_amount.unwrap_or(quote.token_a_amount);
+        let minimum_token_b_amount = minimum_token_b_amount.unwrap_or(quote.token_b_amount);
+        info!(
+            "Withdraw quote: pool_token_amount={}, token_a_amount={}, token_b_amount={}, minimum_token_a_amount={}, minimum_token_b_amount={}",
+            pool_token_amount,
+            quote.token_a_amount,
+            quote.token_b_amount,
+            minimum_token_a_amount,
+            minimum_token_b_amount
+        );
+
+        let token_a_user_ata = ata::get_associated_token_address_with_program_id(
+            &signer,
+            &pool.token_a_mint,
+            &token_a_token_program,
+        );
+        let token_b_user_ata = ata::get_associated_token_address_with_program_id(
+            &signer,
+            &pool.token_b_mint,
+            &token_b_token_program,
+        );
+
+        let tx = self
+            .client
+            .tx_builder()
+            .add_ix(
+                ata::instruction::create_associated_token_account_idempotent(
+                    &self.client.payer(),
+                    &signer,
+                    &pool.token_a_mint,
+                    &token_a_token_program,
+                ),
+            )
+            .add_ix(
+                ata::instruction::create_associated_token_account_idempotent(
+                    &self.client.payer(),
+                    &signer,
+                    &pool.token_b_mint,
+                    &token_b_token_program,
+                ),
+            )
+            .add_anchor_ix(
+                &self.config.program_id,
+                hyperplane::accounts::Withdraw {
+                    signer,
+                    pool: pool_pubkey,
+                    swap_curve: pool.swap_curve,
+                    pool_authority: pool.pool_authority,
+                    token_a_mint: pool.token_a_mint,
+                    token_b_mint: pool.token_b_mint,
+                    token_a_vault: pool.token_a_vault,
+                    token_b_vault: pool.token_b_vault,
+                    pool_token_mint: pool.pool_token_mint,
+                    token_a_fees_vault: pool.token_a_fees_vault,
+                    token_b_fees_vault: pool.token_b_fees_vault,
+                    token_a_user_ata,
+                    token_b_user_ata,
+                    pool_token_user_ata,
+                    pool_token_program,
+                    token_a_token_program,
+                    token_b_token_program,
+                },
+                hyperplane::instruction::Withdraw {
+                    pool_token_amount,
+                    minimum_token_a_amount,
+                    minimum_token_b_amount,
+                },
+            );
+        send_tx!(self, tx, []);
+
+        Ok(())
+    }
+
+    /// Withdraw the owner fees of a pool to the admin's token accounts
+    ///
+    /// Withdraws up to `amount` from each of the given fee vaults in a single transaction, or the full
+    /// vault balances when no amount is given. Empty fee vaults are skipped.
+    pub async fn withdraw_fees(
+        &self,
+        admin: Pubkey,
+        pool_pubkey: Pubkey,
+        sides: &[AorB],
+        amount: Option<u64>,
+    ) -> Result<()> {
+        let ixs = self
+            .withdraw_fees_ixs(admin, pool_pubkey, sides, amount)
+            .await?;
+        if ixs.is_empty() {
+            info!("No fees to withdraw");
+            return Ok(());
+        }
+        let mut tx = self.client.tx_builder();
+        for ix in ixs {
+            tx = tx.add_ix(ix);
+        }
+        send_tx!(self, tx, []);
+
+        Ok(())
+    }
+
+    /// Build the instructions withdrawing the owner fees of a pool to the admin's associated token accounts,
+    /// creating them if needed. Empty fee vaults are skipped
+    pub async fn withdraw_fees_ixs(
+        &self,
+        admin: Pubkey,
+        pool_pubkey: Pubkey,
+        sides: &[AorB],
+        amount: Option<u64>,
+    ) -> Result<Vec<Instruction>> {
+        let pool: SwapPool = self.client.get_anchor_account(&pool_pubkey).await?;
+
+        let mut ixs = Vec::new();
+        for side in sides {
+            let (fees_mint, fees_vault) = match side {
+                AorB::A => (pool.token_a_mint, pool.token_a_fees_vault),
+                AorB::B => (pool.token_b_mint, pool.token_b_fees_vault),
+            };
+            let fees_vault_amount = self.get_token_account_amount(&fees_vault).await?;
+            if fees_vault_amount == 0 {
+                info!(
+                    "Token {:?} fees vault {} is empty, skipping",
+                    side, fees_vault
+                );
+                continue;
+            }
+            let requested_pool_token_amount = amount.unwrap_or(fees_vault_amount);
+            ensure!(requested_pool_token_amount > 0, "Cannot withdraw zero fees");
+
+            let fees_token_program = self.client.client.get_account(&fees_mint).await?.owner;
+            let admin_fees_ata = ata::get_associated_token_address_with_program_id(
+                &admin,
+                &fees_mint,
+                &fees_token_program,
+            );
+            info!(
+                "Withdrawing token {:?} fees: fees_vault={}, fees_vault_amount={}, requested_amount={}, admin_fees_ata={}",
+                side,
+                fees_vault,
+                fees_vault_amount,
+                requested_pool_token_amount,
+                admin_fees_ata
+            );
+
+            ixs.push(
+                ata::instruction::create_associated_token_account_idempotent(
+                    &self.client.payer(),
+                    &admin,
+                    &fees_mint,
+                    &fees_token_program,
+                ),
+            );
+            ixs.push(hyperplane::ix::withdraw_fees(
+                &self.config.program_id,
+                &admin,
+                &pool_pubkey,
+                &pool.pool_authority,
+                &fees_mint,
+                &fees_vault,
+                &admin_fees_ata,
+                &fees_token_program,
+                WithdrawFees {
+                    requested_token_amount: requested_pool_token_amount,
+                },
+            )?);
+        }
+
+        Ok(ixs)
+    }
+
+    /// Send groups of instructions in as few transactions as fit, without splitting a group
+    ///
+    /// Sizes are checked against a legacy transaction with all the signers of the instructions,
+    /// an upper bound of the size of the v0 transaction compiled against the lookup tables
+    pub async fn send_packed(&self, groups: Vec<Vec<Instruction>>) -> Result<usize> {
+        let payer = self.client.payer();
+        let mut batches: Vec<Vec<Instruction>> = Vec::new();
+        let mut batch: Vec<Instruction> = Vec::new();
+        for group in groups {
+            ensure!(
+                fits_in_tx(&payer, &group),
+                "Operation with {} instructions does not fit in a transaction",
+                group.len()
+            );
+            let mut candidate = batch.clone();
+            candidate.extend(group.iter().cloned());
+            if fits_in_tx(&payer, &candidate) {
+                batch = candidate;
+            } else {
+                batches.push(std::mem::replace(&mut batch, group));
+            }
+        }
+        if !batch.is_empty() {
+            batches.push(batch);
+        }
+
+        let count = batches.len();
+        for (i, batch) in batches.into_iter().enumerate() {
+            info!(
+                "Transaction {}/{} with {} instructions",
+                i.saturating_add(1),
+                count,
+                batch.len()
+            );
+            let mut tx = self.client.tx_builder();
+            for ix in batch {
+                tx = tx.add_ix(ix);
+            }
+            send_tx!(self, tx, []);
+        }
+
+        Ok(count)
+    }
+
+    /// Create a lookup table holding all the static accounts of a pool
+    ///
+    /// Transactions compiled against the table only reference each of these accounts by a 1 byte index
+    pub async fn create_lookup_table(&self, authority: Pubkey, pool: Pubkey) -> Result<Pubkey> {
+        let addresses = self.get_pool_static_accounts(&pool).await?;
+        let clock = self.get_clock().await?;
+        // the table address is derived from a recent slot, which must still be in the slot hashes sysvar
+        let (create_ix, lookup_table) =
+            alt::instruction::create_lookup_table(authority, self.client.payer(), clock.slot);
+        info!("Lookup table: {}", lookup_table);
+        info!("Adding {} accounts of pool {}", addresses.len(), pool);
+
+        let mut chunks = addresses.chunks(lookup_table::MAX_EXTEND_ADDRESSES);
+        let mut tx = self.client.tx_builder().add_ix(create_ix);
+        if let Some(chunk) = chunks.next() {
+            tx = tx.add_ix(alt::instruction::extend_lookup_table(
+                lookup_table,
+                authority,
+                Some(self.client.payer()),
+                chunk.to_vec(),
+            ));
+        }
+        send_tx!(self, tx, []);
+        for chunk in chunks {
+            let tx = self
+                .client
+                .tx_builder()
+                .add_ix(alt::instruction::extend_lookup_table(
+                    lookup_table,
+                    authority,
+                    Some(self.client.payer()),
+                    chunk.to_vec(),
+                ));
+            send_tx!(self, tx, []);
+        }
+
+        Ok(lookup_table)
+    }
+
+    /// Add addresses to a lookup table, skipping the ones it already holds
+    pub async fn extend_lookup_table(
+        &self,
+        authority: Pubkey,
+        lookup_table: Pubkey,
+        addresses: &[Pubkey],
+    ) -> Result<()> {
+        let existing = lookup_table::get_lookup_table_account(&self.client.client, &lookup_table)
+            .await?
+            .addresses;
+        let mut new_addresses: Vec<Pubkey> = Vec::with_capacity(addresses.len());
+        for address in addresses {
+            if !existing.contains(address) && !new_addresses.contains(address) {
+                new_addresses.push(*address);
+            }
+        }
+        if new_addresses.is_empty() {
+            info!(
+                "Lookup table {} already holds all the addresses",
+                lookup_table
+            );
+            return Ok(());
+        }
+        info!(
+            "Adding {} addresses to lookup table {}",
+            new_addresses.len(),
+            lookup_table
+        );
+
+        for chunk in new_addresses.chunks(lookup_table::MAX_EXTEND_ADDRESSES) {
+            let tx = self
+                .client
+                .tx_builder()
+                .add_ix(alt::instruction::extend_lookup_table(
+                    lookup_table,
+                    authority,
+                    Some(self.client.payer()),
+                    chunk.to_vec(),
+                ));
+            send_tx!(self, tx, []);
+        }
+
+        Ok(())
+    }
+
+    /// Get the accounts referenced by every instruction of a pool, independent of the user
+    pub async fn get_pool_static_accounts(&self, pool_pubkey: &Pubkey) -> Result<Vec<Pubkey>> {
+        let pool: SwapPool = self.client.get_anchor_account(pool_pubkey).await?;
+        let token_a_program = self
+            .client
+            .client
+            .get_account(&pool.token_a_mint)
+            .await?
+            .owner;
+        let token_b_program = self
+            .client
+            .client
+            .get_account(&pool.token_b_mint)
+            .await?
+            .owner;
+        let pool_token_program = self
+            .client
+            .client
+            .get_account(&pool.pool_token_mint)
+            .await?
+            .owner;
+
+        let mut addresses = Vec::new();
+        for address in [
+            self.config.program_id,
+            *pool_pubkey,
+            pool.swap_curve,
+            pool.pool_authority,
+            pool.token_a_mint,
+            pool.token_b_mint,
+            pool.token_a_vault,
+            pool.token_b_vault,
+            pool.token_a_fees_vault,
+            pool.token_b_fees_vault,
+            pool.pool_token_mint,
+            token_a_program,
+            token_b_program,
+            pool_token_program,
+        ] {
+            if !addresses.contains(&address) {
+                addresses.push(address);
+            }
+        }
+        Ok(addresses)
+    }
+
+    /// Get the amount held by a Token or Token-2022 account
+    pub async fn get_token_account_amount(&self, address: &Pubkey) -> Result<u64> {
+        let account = self.client.client.get_account(address).await?;
+        token::token_account_amount(&account.data)
+    }
+
+    /// Get the supply of a Token or Token-2022 mint
+    pub async fn get_mint_supply(&self, mint: &Pubkey) -> Result<u64> {
+        let account = self.client.client.get_account(mint).await?;
+        token::mint_supply(&account.data)
+    }
+
+    /// Get the decimals of a Token or Token-2022 mint
+    pub async fn get_mint_decimals(&self, mint: &Pubkey) -> Result<u8> {
+        let account = self.client.client.get_account(mint).await?;
+        token::mint_decimals(&account.data)
+    }
+
+    /// Get the cluster clock, as seen by the program
+    pub async fn get_clock(&self) -> Result<Clock> {
+        from_account(&self.client.client.get_account(&Clock::id()).await?)
+            .ok_or_else(|| anyhow!("Failed to deserialize the clock sysvar"))
+    }
+
+    /// Get a snapshot of a pool and its vaults and mints, to quote swaps, deposits and withdrawals
+    /// the same way the program executes them
+    pub async fn get_pool_snapshot(&self, pool_pubkey: &Pubkey) -> Result<PoolSnapshot> {
+        let get_data = |address: Pubkey| async move {
+            Ok::<_, anyhow::Error>(self.client.client.get_account(&address).await?.data)
+        };
+        let pool_data = get_data(*pool_pubkey).await?;
+        let pool = SwapPool::try_deserialize(&mut pool_data.as_slice())?;
+        let swap_curve = get_data(pool.swap_curve).await?;
+        let token_a_vault = get_data(pool.token_a_vault).await?;
+        let token_b_vault = get_data(pool.token_b_vault).await?;
+        let pool_token_mint = get_data(pool.pool_token_mint).await?;
+        let token_a_mint = get_data(pool.token_a_mint).await?;
+        let token_b_mint = get_data(pool.token_b_mint).await?;
+        let clock = self.get_clock().await?;
+
+        Ok(PoolSnapshot::from_accounts_data(
+            PoolAccountsData {
+                pool: &pool_data,
+                swap_curve: &swap_curve,
+                token_a_vault: &token_a_vault,
+                token_b_vault: &token_b_vault,
+                pool_token_mint: &pool_token_mint,
+                token_a_mint: &token_a_mint,
+                token_b_mint: &token_b_mint,
+            },
+            clock.epoch,
+        )?)
+    }
+
+    /// Get the swap curve of a pool, deserialized according to the pool curve type
+    pub async fn get_swap_curve(&self, pool: &SwapPool) -> Result<SwapCurve> {
+        let curve_type = CurveType::try_from(pool.curve_type)?;
+        let calculator: Arc<dyn CurveCalculator + Sync + Send> = match curve_type {
+            CurveType::ConstantProduct => Arc::new(
+                self.client
+                    .get_anchor_account::<ConstantProductCurve>(&pool.swap_curve)
+                    .await?,
+            ),
+            CurveType::ConstantPrice => Arc::new(
+                self.client
+                    .get_anchor_account::<ConstantPriceCurve>(&pool.swap_curve)
+                    .await?,
+            ),
+            CurveType::Stable => Arc::new(
+                self.client
+                    .get_anchor_account::<StableCurve>(&pool.swap_curve)
+                    .await?,
+            ),
+            CurveType::Offset => Arc::new(
+                self.client
+                    .get_anchor_account::<OffsetCurve>(&pool.swap_curve)
+                    .await?,
+            ),
+        };
+        Ok(SwapCurve {
+            curve_type,
+            calculator,
+        })
+    }
+
+    /// Find all the pools of the program matching the filters
+    pub async fn find_pools(&self, filters: &PoolFilters) -> Result<Vec<(Pubkey, SwapPool)>> {
+        let mut rpc_filters = vec![
+            RpcFilterType::DataSize(SwapPool::LEN as u64),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &SwapPool::discriminator())),
+        ];
+        if let Some(admin) = filters.admin {
+            rpc_filters.push(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                SwapPool::ADMIN_OFFSET,
+                admin.as_ref(),
+            )));
+        }
+        if let Some(token_a_mint) = filters.token_a_mint {
+            rpc_filters.push(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                SwapPool::TOKEN_A_MINT_OFFSET,
+                token_a_mint.as_ref(),
+            )));
+        }
+        if let Some(token_b_mint) = filters.token_b_mint {
+            rpc_filters.push(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                SwapPool::TOKEN_B_MINT_OFFSET,
+                token_b_mint.as_ref(),
+            )));
+        }
+        if let Some(curve_type) = filters.curve_type {
+            rpc_filters.push(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                SwapPool::CURVE_TYPE_OFFSET,
+                &u64::from(curve_type).to_le_bytes(),
+            )));
+        }
+        let config = RpcProgramAccountsConfig {
+            filters: Some(rpc_filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+        let accounts = self
+            .client
+            .client
+            .get_program_accounts_with_config(&self.config.program_id, config)
+            .await?;
+
+        accounts
+            .into_iter()
+            .map(|(pubkey, account)| {
+                let pool = SwapPool::try_deserialize(&mut account.data.as_slice())?;
+                Ok((pubkey, pool))
+            })
+            .collect()
+    }
+
+    /// Get all the liquidity locks of a pool
+    pub async fn get_liquidity_locks(&self, pool: &Pubkey) -> Result<Vec<(Pubkey, LiquidityLock)>> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![
+                RpcFilterType::DataSize(LiquidityLock::LEN as u64),
+                RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                    0,
+                    &LiquidityLock::discriminator(),
+                )),
+                // the pool is the first field after the discriminator
+                RpcFilterType::Memcmp(Memcmp::new_base58_encoded(8, pool.as_ref())),
+            ]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+        let accounts = self
+            .client
+            .client
+            .get_program_accounts_with_config(&self.config.program_id, config)
+            .await?;
+
+        accounts
+            .into_iter()
+            .map(|(pubkey, account)| {
+                let liquidity_lock = LiquidityLock::try_deserialize(&mut account.data.as_slice())?;
+                Ok((pubkey, liquidity_lock))
+            })
+            .collect()
+    }
+
     /// Get an the rpc instance used by the KLendClient
     pub fn get_rpc(&self) -> &T {
         &self.client.client
     }
 }
+
+/// Whether the instructions fit in a single legacy transaction paid by `payer`
+fn fits_in_tx(payer: &Pubkey, instructions: &[Instruction]) -> bool {
+    let message = Message::new(instructions, Some(payer));
+    // compact length of the signatures, one byte below 128 signatures
+    usize::from(message.header.num_required_signatures)
+        .checked_mul(size_of::<Signature>())
+        .and_then(|size| size.checked_add(1))
+        .and_then(|size| size.checked_add(message.serialize().len()))
+        .map_or(false, |size| size <= PACKET_DATA_SIZE)
+}
+
+/// Find the largest amount of pool tokens which can be minted without exceeding the maximum token amounts
+///
+/// Deposit amounts are monotonic in the amount of pool tokens for every curve, so a binary search
+/// over the curve's own calculation gives the exact on-chain result
+fn max_deposit_pool_tokens(
+    calculator: &dyn CurveCalculator,
+    pool_token_supply: u64,
+    token_a_vault_amount: u64,
+    token_b_vault_amount: u64,
+    maximum_token_a_amount: u64,
+    maximum_token_b_amount: u64,
+) -> u64 {
+    let within_maximums = |pool_token_amount: u64| {
+        calculator
+            .pool_tokens_to_trading_tokens(
+                u128::from(pool_token_amount),
+                u128::from(pool_token_supply),
+                u128::from(token_a_vault_amount),
+                u128::from(token_b_vault_amount),
+                RoundDirection::Ceiling,
+            )
+            .map(|results| {
+                results.token_a_amount <= u128::from(maximum_token_a_amount)
+                    && results.token_b_amount <= u128::from(maximum_token_b_amount)
+            })
+            .unwrap_or(false)
+    };
+
+    let (mut low, mut high) = (0, u64::MAX);
+    while low < high {
+        // round up so the loop terminates once `low` fits, `low < high` keeps this in range
+        let mid = low
+            .saturating_add(high.saturating_sub(low).saturating_div(2))
+            .saturating_add(1);
+        if within_maximums(mid) {
+            low = mid;
+        } else {
+            high = mid.saturating_sub(1);
+        }
+    }
+    low
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn test_max_deposit_pool_tokens() {
+        let calculator = ConstantProductCurve::default();
+
+        // proportional to the scarcer side of the deposit
+        let pool_token_amount =
+            max_deposit_pool_tokens(&calculator, 1_000_000, 10_000, 40_000, 100, 100);
+        assert_eq!(pool_token_amount, 2_500);
+
+        // the quote for the result never exceeds the maximums, one more pool token would
+        let results = calculator
+            .pool_tokens_to_trading_tokens(
+                2_500,
+                1_000_000,
+                10_000,
+                40_000,
+                RoundDirection::Ceiling,
+            )
+            .unwrap();
+        assert!(results.token_a_amount <= 100 && results.token_b_amount <= 100);
+        let results = calculator
+            .pool_tokens_to_trading_tokens(
+                2_501,
+                1_000_000,
+                10_000,
+                40_000,
+                RoundDirection::Ceiling,
+            )
+            .unwrap();
+        assert!(results.token_b_amount > 100);
+    }
+}
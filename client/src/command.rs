@@ -1,19 +1,20 @@
-use std::{path::PathBuf, str::FromStr};
+use std::{io::Write, path::PathBuf, str::FromStr};
 
 use anchor_client::{
     anchor_lang::prelude::Pubkey,
     solana_sdk::{
+        instruction::Instruction,
         program_pack::Pack,
         signature::{Keypair, Signer},
     },
 };
 use anyhow::Result;
 use hyperplane::{
-    curve::{base::CurveType, calculator::CurveCalculator},
+    curve::base::CurveType,
     ix::Initialize,
+    quote,
     state::{
-        ConstantPriceCurve, ConstantProductCurve, OffsetCurve, StableCurve, SwapPool,
-        UpdatePoolConfigMode,
+        ConstantPriceCurve, OffsetCurve, StableCurve, SwapPool, SwapState, UpdatePoolConfigMode,
     },
 };
 use orbit_link::async_client::AsyncClient;
@@ -23,7 +24,14 @@ use tokio::{fs::File, io::AsyncWriteExt};
 use tracing::info;
 
 use crate::{
-    client::HyperplaneClient, configs::PoolConfigValue, model::InitializePoolConfig, send_tx,
+    client::HyperplaneClient,
+    configs::PoolConfigValue,
+    manifest::{parse_pubkey, pool_config_values, resolve_path, Manifest, Operation},
+    model::{
+        percent, CurveView, FeePercentages, FeesMint, InitializePoolConfig, LiquidityLockView,
+        OutputFormat, PoolBalances, PoolFilters, PoolSummary, PoolView,
+    },
+    send_tx,
 };
 
 pub async fn create_ata<T: AsyncClient, S: Signer>(
@@ -155,6 +163,7 @@ pub async fn initialize_pool<T: AsyncClient, S: Signer>(
                 fees: config.fees,
                 curve_parameters: config.curve,
                 initial_supply: config.initial_supply,
+                min_liquidity: config.min_liquidity,
             },
         )
         .await?;
@@ -175,38 +184,468 @@ pub async fn update_pool<T: AsyncClient, S: Signer>(
     Ok(())
 }
 
+pub async fn swap<T: AsyncClient, S: Signer>(
+    hyperplane: &HyperplaneClient<T, S>,
+    signer: Pubkey,
+    pool: Pubkey,
+    in_mint: Pubkey,
+    amount: u64,
+    slippage_bps: u64,
+) -> Result<()> {
+    hyperplane
+        .swap(signer, pool, in_mint, amount, slippage_bps)
+        .await?;
+    Ok(())
+}
+
+pub async fn deposit<T: AsyncClient, S: Signer>(
+    hyperplane: &HyperplaneClient<T, S>,
+    signer: Pubkey,
+    pool: Pubkey,
+    pool_token_ata: Option<Pubkey>,
+    pool_tokens: Option<u64>,
+    max_a: Option<u64>,
+    max_b: Option<u64>,
+) -> Result<()> {
+    hyperplane
+        .deposit(signer, pool, pool_token_ata, pool_tokens, max_a, max_b)
+        .await?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn withdraw<T: AsyncClient, S: Signer>(
+    hyperplane: &HyperplaneClient<T, S>,
+    signer: Pubkey,
+    pool: Pubkey,
+    pool_token_ata: Option<Pubkey>,
+    pool_tokens: Option<u64>,
+    percent: Option<u8>,
+    min_a: Option<u64>,
+    min_b: Option<u64>,
+) -> Result<()> {
+    hyperplane
+        .withdraw(
+            signer,
+            pool,
+            pool_token_ata,
+            pool_tokens,
+            percent,
+            min_a,
+            min_b,
+        )
+        .await?;
+    Ok(())
+}
+
+pub async fn withdraw_fees<T: AsyncClient, S: Signer>(
+    hyperplane: &HyperplaneClient<T, S>,
+    admin: Pubkey,
+    pool: Pubkey,
+    mint: FeesMint,
+    amount: Option<u64>,
+) -> Result<()> {
+    hyperplane
+        .withdraw_fees(admin, pool, &mint.sides(), amount)
+        .await?;
+    Ok(())
+}
+
+pub async fn create_lookup_table<T: AsyncClient, S: Signer>(
+    hyperplane: &HyperplaneClient<T, S>,
+    authority: Pubkey,
+    pool: Pubkey,
+) -> Result<()> {
+    let lookup_table = hyperplane.create_lookup_table(authority, pool).await?;
+    info!(
+        "Pass --lookup-tables {} to send v0 transactions",
+        lookup_table
+    );
+    Ok(())
+}
+
+pub async fn extend_lookup_table<T: AsyncClient, S: Signer>(
+    hyperplane: &HyperplaneClient<T, S>,
+    authority: Pubkey,
+    lookup_table: Pubkey,
+    pool: Option<Pubkey>,
+    mut addresses: Vec<Pubkey>,
+) -> Result<()> {
+    if let Some(pool) = pool {
+        addresses.extend(hyperplane.get_pool_static_accounts(&pool).await?);
+    }
+    hyperplane
+        .extend_lookup_table(authority, lookup_table, &addresses)
+        .await
+}
+
+/// Operation of a manifest, resolved against the chain
+enum PlannedOperation {
+    /// Instructions sent together, packed with the neighbouring operations
+    Instructions(Vec<Instruction>),
+    /// Pool initialization, sent on its own as it needs the pool keypair signatures
+    InitPool {
+        config: PathBuf,
+        token_a_ata: Option<Pubkey>,
+        token_b_ata: Option<Pubkey>,
+    },
+}
+
+/// Apply the operations of a manifest in order, printing a preview of the changes first
+///
+/// Consecutive operations other than pool initializations are packed in as few transactions as fit.
+/// Unless `yes` is set, asks for confirmation before sending, except in dry-run and multisig modes
+/// where nothing is executed
+pub async fn apply<T: AsyncClient, S: Signer>(
+    hyperplane: &HyperplaneClient<T, S>,
+    admin: Pubkey,
+    manifest_path: PathBuf,
+    yes: bool,
+) -> Result<()> {
+    let manifest = Manifest::from_file(&manifest_path)?;
+
+    let mut plan = Vec::with_capacity(manifest.operations.len());
+    for operation in manifest.operations {
+        match operation {
+            Operation::CreateAta { mint, owner } => {
+                let mint = parse_pubkey(&mint)?;
+                let owner = owner
+                    .as_deref()
+                    .map(parse_pubkey)
+                    .transpose()?
+                    .unwrap_or(admin);
+                let token_program = hyperplane.client.client.get_account(&mint).await?.owner;
+                let address = ata::get_associated_token_address_with_program_id(
+                    &owner,
+                    &mint,
+                    &token_program,
+                );
+                println!("ata {address} (owner {owner}, mint {mint})");
+                println!("+ created if missing");
+                plan.push(PlannedOperation::Instructions(vec![
+                    ata::instruction::create_associated_token_account_idempotent(
+                        &hyperplane.client.payer(),
+                        &owner,
+                        &mint,
+                        &token_program,
+                    ),
+                ]));
+            }
+            Operation::InitPool {
+                config,
+                token_a_ata,
+                token_b_ata,
+            } => {
+                let config = resolve_path(&manifest_path, &config);
+                let pool_config: InitializePoolConfig =
+                    serde_json::from_reader(File::open(&config).await?.into_std().await)?;
+                println!("new pool ({})", config.display());
+                println!("+ token_a_mint: {}", pool_config.token_a_mint);
+                println!("+ token_b_mint: {}", pool_config.token_b_mint);
+                println!("+ curve: {:?}", pool_config.curve);
+                println!("+ fees: {:?}", pool_config.fees);
+                println!("+ initial_supply: {:?}", pool_config.initial_supply);
+                println!("+ min_liquidity: {}", pool_config.min_liquidity);
+                plan.push(PlannedOperation::InitPool {
+                    config,
+                    token_a_ata: token_a_ata.as_deref().map(parse_pubkey).transpose()?,
+                    token_b_ata: token_b_ata.as_deref().map(parse_pubkey).transpose()?,
+                });
+            }
+            Operation::UpdatePoolConfig {
+                pool,
+                withdrawals_only,
+            } => {
+                let pool_pubkey = parse_pubkey(&pool)?;
+                let pool: SwapPool = hyperplane.client.get_anchor_account(&pool_pubkey).await?;
+                println!("pool {pool_pubkey}");
+                let mut ixs = Vec::new();
+                for value in pool_config_values(withdrawals_only) {
+                    let current = value.current(&pool);
+                    if current == value {
+                        println!("  {value}");
+                        continue;
+                    }
+                    println!("- {current}");
+                    println!("+ {value}");
+                    ixs.push(hyperplane::ix::update_pool_config(
+                        &hyperplane.config.program_id,
+                        &admin,
+                        &pool_pubkey,
+                        value.into(),
+                    )?);
+                }
+                if !ixs.is_empty() {
+                    plan.push(PlannedOperation::Instructions(ixs));
+                }
+            }
+            Operation::WithdrawFees { pool, mint, amount } => {
+                let pool = parse_pubkey(&pool)?;
+                let ixs = hyperplane
+                    .withdraw_fees_ixs(admin, pool, &mint.sides(), amount)
+                    .await?;
+                println!("pool {pool}");
+                if ixs.is_empty() {
+                    println!("  fees vaults are empty");
+                } else {
+                    println!(
+                        "+ withdraw {} fees",
+                        amount.map_or("all".to_string(), |amount| amount.to_string())
+                    );
+                    plan.push(PlannedOperation::Instructions(ixs));
+                }
+            }
+        }
+    }
+
+    if plan.is_empty() {
+        println!("Nothing to apply");
+        return Ok(());
+    }
+    if !yes && !hyperplane.config.dry_run && !hyperplane.config.multisig && !confirm()? {
+        println!("Aborted");
+        return Ok(());
+    }
+
+    let mut pending = Vec::new();
+    for operation in plan {
+        match operation {
+            PlannedOperation::Instructions(ixs) => pending.push(ixs),
+            PlannedOperation::InitPool {
+                config,
+                token_a_ata,
+                token_b_ata,
+            } => {
+                if !pending.is_empty() {
+                    hyperplane.send_packed(std::mem::take(&mut pending)).await?;
+                }
+                initialize_pool(hyperplane, admin, config, token_a_ata, token_b_ata).await?;
+            }
+        }
+    }
+    if !pending.is_empty() {
+        hyperplane.send_packed(pending).await?;
+    }
+    Ok(())
+}
+
+pub async fn list_pools<T: AsyncClient, S: Signer>(
+    hyperplane: &HyperplaneClient<T, S>,
+    filters: PoolFilters,
+    output: OutputFormat,
+) -> Result<()> {
+    let pools = hyperplane.find_pools(&filters).await?;
+
+    let mut summaries = Vec::with_capacity(pools.len());
+    for (address, pool) in pools {
+        summaries.push(PoolSummary {
+            address: address.to_string(),
+            admin: pool.admin.to_string(),
+            token_a_mint: pool.token_a_mint.to_string(),
+            token_b_mint: pool.token_b_mint.to_string(),
+            curve_type: format!("{:?}", CurveType::try_from(pool.curve_type)?),
+            token_a_vault_amount: hyperplane
+                .get_token_account_amount(&pool.token_a_vault)
+                .await?,
+            token_b_vault_amount: hyperplane
+                .get_token_account_amount(&pool.token_b_vault)
+                .await?,
+            fees: pool.fees,
+            withdrawals_only: pool.withdrawals_only(),
+        });
+    }
+
+    match output {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&summaries)?),
+        OutputFormat::Yaml => println!("{}", serde_yaml::to_string(&summaries)?),
+        OutputFormat::Table => {
+            println!(
+                "{:<44} {:<44} {:<44} {:<15} {:>20} {:>20} {:>10} {:>10}",
+                "Pool",
+                "Token A mint",
+                "Token B mint",
+                "Curve",
+                "Token A amount",
+                "Token B amount",
+                "Trade fee",
+                "Owner fee"
+            );
+            for summary in summaries.iter() {
+                println!(
+                    "{:<44} {:<44} {:<44} {:<15} {:>20} {:>20} {:>9.4}% {:>9.4}%",
+                    summary.address,
+                    summary.token_a_mint,
+                    summary.token_b_mint,
+                    summary.curve_type,
+                    summary.token_a_vault_amount,
+                    summary.token_b_vault_amount,
+                    percent(
+                        summary.fees.trade_fee_numerator,
+                        summary.fees.trade_fee_denominator
+                    ),
+                    percent(
+                        summary.fees.owner_trade_fee_numerator,
+                        summary.fees.owner_trade_fee_denominator
+                    ),
+                );
+            }
+        }
+    }
+    info!("Found {} pools", summaries.len());
+
+    Ok(())
+}
+
 pub async fn print_pool<T: AsyncClient, S: Signer>(
     hyperplane: &HyperplaneClient<T, S>,
     pool_pubkey: Pubkey,
+    output: OutputFormat,
 ) -> Result<()> {
     let pool: SwapPool = hyperplane.client.get_anchor_account(&pool_pubkey).await?;
-    let curve: Box<dyn CurveCalculator> = match CurveType::try_from(pool.curve_type).unwrap() {
-        CurveType::ConstantProduct => Box::new(
-            hyperplane
-                .client
-                .get_anchor_account::<ConstantProductCurve>(&pool.swap_curve)
-                .await?,
-        ),
-        CurveType::ConstantPrice => Box::new(
-            hyperplane
+    let swap_curve = hyperplane.get_swap_curve(&pool).await?;
+    let curve = get_curve_view(hyperplane, &pool).await?;
+
+    let balances = PoolBalances {
+        token_a_vault_amount: hyperplane
+            .get_token_account_amount(&pool.token_a_vault)
+            .await?,
+        token_b_vault_amount: hyperplane
+            .get_token_account_amount(&pool.token_b_vault)
+            .await?,
+        token_a_fees_vault_amount: hyperplane
+            .get_token_account_amount(&pool.token_a_fees_vault)
+            .await?,
+        token_b_fees_vault_amount: hyperplane
+            .get_token_account_amount(&pool.token_b_fees_vault)
+            .await?,
+        pool_token_supply: hyperplane.get_mint_supply(&pool.pool_token_mint).await?,
+    };
+    let spot_price = quote::spot_price(
+        &swap_curve,
+        balances.token_a_vault_amount,
+        balances.token_b_vault_amount,
+    );
+
+    // unlocking is checked against the cluster clock, which can differ from the local one
+    let now = u64::try_from(hyperplane.get_clock().await?.unix_timestamp)?;
+    let mut liquidity_locks = vec![];
+    for (address, liquidity_lock) in hyperplane.get_liquidity_locks(&pool_pubkey).await? {
+        liquidity_locks.push(LiquidityLockView {
+            address: address.to_string(),
+            owner: liquidity_lock.owner.to_string(),
+            lock_id: liquidity_lock.lock_id,
+            locked_amount: liquidity_lock.locked_amount,
+            remaining_locked_amount: liquidity_lock.remaining_locked_amount(now)?,
+            unlockable_amount: liquidity_lock.unlockable_amount(now)?,
+            unlocked_amount: liquidity_lock.unlocked_amount,
+            vesting_start_ts: liquidity_lock.vesting_start_ts,
+            vesting_end_ts: liquidity_lock.vesting_end_ts,
+        });
+    }
+    let total_locked_pool_tokens = liquidity_locks
+        .iter()
+        .map(|liquidity_lock| liquidity_lock.remaining_locked_amount)
+        .sum();
+
+    let view = PoolView {
+        address: pool_pubkey.to_string(),
+        admin: pool.admin.to_string(),
+        pool_authority: pool.pool_authority.to_string(),
+        pool_token_mint: pool.pool_token_mint.to_string(),
+        token_a_mint: pool.token_a_mint.to_string(),
+        token_b_mint: pool.token_b_mint.to_string(),
+        token_a_decimals: hyperplane.get_mint_decimals(&pool.token_a_mint).await?,
+        token_b_decimals: hyperplane.get_mint_decimals(&pool.token_b_mint).await?,
+        token_a_vault: pool.token_a_vault.to_string(),
+        token_b_vault: pool.token_b_vault.to_string(),
+        token_a_fees_vault: pool.token_a_fees_vault.to_string(),
+        token_b_fees_vault: pool.token_b_fees_vault.to_string(),
+        swap_curve: pool.swap_curve.to_string(),
+        curve,
+        withdrawals_only: pool.withdrawals_only(),
+        min_liquidity: pool.min_liquidity,
+        balances,
+        spot_price,
+        fee_percentages: FeePercentages::from(&pool.fees),
+        fees: pool.fees,
+        liquidity_locks,
+        total_locked_pool_tokens,
+    };
+
+    match output {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&view)?),
+        OutputFormat::Yaml => println!("{}", serde_yaml::to_string(&view)?),
+        OutputFormat::Table => {
+            info!("\x1b[32mPool {}:\x1b\n\n{:#?}\n\n", pool_pubkey, pool);
+            info!(
+                "\x1b[32mCurve {}:\x1b\n\n{:#?}\n\n",
+                pool.swap_curve, view.curve
+            );
+            info!(
+                "\x1b[32mBalances:\x1b\n\n{:#?}\nspot_price={:?}\n\n",
+                view.balances, view.spot_price
+            );
+            info!("\x1b[32mFees:\x1b\n\n{:#?}\n\n", view.fee_percentages);
+            for liquidity_lock in view.liquidity_locks.iter() {
+                info!(
+                    "\x1b[32mLiquidity lock {}:\x1b\n\n{:#?}\n\n",
+                    liquidity_lock.address, liquidity_lock
+                );
+            }
+            info!(
+                "\x1b[32mLocked pool tokens:\x1b {} in {} liquidity locks\n\n",
+                view.total_locked_pool_tokens,
+                view.liquidity_locks.len()
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn get_curve_view<T: AsyncClient, S: Signer>(
+    hyperplane: &HyperplaneClient<T, S>,
+    pool: &SwapPool,
+) -> Result<CurveView> {
+    let curve = match CurveType::try_from(pool.curve_type)? {
+        CurveType::ConstantProduct => CurveView::ConstantProduct,
+        CurveType::ConstantPrice => {
+            let curve: ConstantPriceCurve = hyperplane
                 .client
-                .get_anchor_account::<ConstantPriceCurve>(&pool.swap_curve)
-                .await?,
-        ),
-        CurveType::Stable => Box::new(
-            hyperplane
+                .get_anchor_account(&pool.swap_curve)
+                .await?;
+            CurveView::ConstantPrice {
+                token_b_price: curve.token_b_price,
+            }
+        }
+        CurveType::Offset => {
+            let curve: OffsetCurve = hyperplane
                 .client
-                .get_anchor_account::<StableCurve>(&pool.swap_curve)
-                .await?,
-        ),
-        CurveType::Offset => Box::new(
-            hyperplane
+                .get_anchor_account(&pool.swap_curve)
+                .await?;
+            CurveView::Offset {
+                token_b_offset: curve.token_b_offset,
+            }
+        }
+        CurveType::Stable => {
+            let curve: StableCurve = hyperplane
                 .client
-                .get_anchor_account::<OffsetCurve>(&pool.swap_curve)
-                .await?,
-        ),
+                .get_anchor_account(&pool.swap_curve)
+                .await?;
+            CurveView::Stable {
+                amp: curve.amp,
+                token_a_factor: curve.token_a_factor,
+                token_b_factor: curve.token_b_factor,
+            }
+        }
     };
-    info!("\x1b[32mPool {}:\x1b\n\n{:#?}\n\n", pool_pubkey, pool);
-    info!("\x1b[32mCurve {}:\x1b\n\n{:#?}\n\n", pool.swap_curve, curve);
-    Ok(())
+    Ok(curve)
+}
+
+/// Ask on the terminal whether to go on, anything but yes aborts
+fn confirm() -> Result<bool> {
+    print!("Apply these changes? [y/N] ");
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
 }
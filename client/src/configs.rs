@@ -1,4 +1,6 @@
-use hyperplane::state::{UpdatePoolConfigMode, UpdatePoolConfigValue};
+use std::fmt;
+
+use hyperplane::state::{SwapPool, SwapState, UpdatePoolConfigMode, UpdatePoolConfigValue};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum PoolConfigValue {
@@ -30,6 +32,23 @@ impl PoolConfigValue {
         };
         PoolConfigValue::new(mode, parsed_value)
     }
+
+    /// Value of the same config currently set on the pool
+    pub fn current(&self, pool: &SwapPool) -> PoolConfigValue {
+        match self {
+            PoolConfigValue::WithdrawalsOnly(_) => {
+                PoolConfigValue::WithdrawalsOnly(pool.withdrawals_only())
+            }
+        }
+    }
+}
+
+impl fmt::Display for PoolConfigValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PoolConfigValue::WithdrawalsOnly(val) => write!(f, "withdrawals_only: {val}"),
+        }
+    }
 }
 
 impl From<PoolConfigValue> for hyperplane::instruction::UpdatePoolConfig {
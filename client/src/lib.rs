@@ -1,5 +1,6 @@
 pub mod client;
 pub mod command;
 pub mod configs;
+pub mod manifest;
 pub mod model;
 pub mod utils;
@@ -10,10 +10,12 @@ use anchor_client::{
 };
 use anyhow::Result;
 use clap::{Parser, Subcommand};
-use hyperplane::state::UpdatePoolConfigMode;
+use hyperplane::{curve::base::CurveType, state::UpdatePoolConfigMode};
 use hyperplane_client::{
     client::{Config, HyperplaneClient},
     command,
+    model::{FeesMint, OutputFormat, PoolFilters},
+    utils::lookup_table,
 };
 use orbit_link::OrbitLink;
 use tracing::info;
@@ -68,6 +70,18 @@ pub struct Args {
     /// Instructions which require private key signer (e.g. zero-copy account allocations) will be executed immediately
     #[clap(long, env, takes_value = false, alias = "multi", alias = "ms")]
     multisig: bool,
+
+    /// Address lookup tables to compile the transactions against, transactions are sent as v0 if any
+    /// Comma separated or repeated, see create-lookup-table
+    #[clap(
+        long,
+        env,
+        parse(try_from_str),
+        multiple_occurrences = true,
+        use_value_delimiter = true,
+        alias = "alt"
+    )]
+    lookup_tables: Vec<Pubkey>,
 }
 
 #[derive(Subcommand, Debug, PartialEq)]
@@ -110,13 +124,146 @@ pub enum Actions {
         value: String,
     },
     #[clap(arg_required_else_help = true)]
+    Swap {
+        /// Pool to swap in
+        #[clap(short, long, parse(try_from_str))]
+        pool: Pubkey,
+        /// Mint of the token to swap from, the other pool token is received
+        #[clap(long, parse(try_from_str))]
+        in_mint: Pubkey,
+        /// Amount of tokens to swap, in the smallest unit of the input mint
+        #[clap(short, long)]
+        amount: u64,
+        /// Maximum slippage from the expected amount out, in basis points
+        #[clap(long, default_value_t = 50)]
+        slippage_bps: u64,
+    },
+    #[clap(arg_required_else_help = true)]
+    Deposit {
+        /// Pool to deposit into
+        #[clap(short, long, parse(try_from_str))]
+        pool: Pubkey,
+        /// Amount of pool tokens to mint, else the most allowed by the maximum token amounts
+        #[clap(long, required_unless_present_all = &["max-a", "max-b"])]
+        pool_tokens: Option<u64>,
+        /// Maximum amount of token A to deposit, defaults to the quoted amount
+        #[clap(long)]
+        max_a: Option<u64>,
+        /// Maximum amount of token B to deposit, defaults to the quoted amount
+        #[clap(long)]
+        max_b: Option<u64>,
+        /// Pool token account to mint into, else the signer's pool token ata
+        #[clap(long, parse(try_from_str))]
+        pool_token_ata: Option<Pubkey>,
+    },
+    #[clap(arg_required_else_help = true)]
+    Withdraw {
+        /// Pool to withdraw from
+        #[clap(short, long, parse(try_from_str))]
+        pool: Pubkey,
+        /// Amount of pool tokens to burn
+        #[clap(long, required_unless_present = "percent", conflicts_with = "percent")]
+        pool_tokens: Option<u64>,
+        /// Percentage of the pool token account balance to burn
+        #[clap(long)]
+        percent: Option<u8>,
+        /// Minimum amount of token A to receive, defaults to the quoted amount
+        #[clap(long)]
+        min_a: Option<u64>,
+        /// Minimum amount of token B to receive, defaults to the quoted amount
+        #[clap(long)]
+        min_b: Option<u64>,
+        /// Pool token account to burn from, else the signer's pool token ata
+        #[clap(long, parse(try_from_str))]
+        pool_token_ata: Option<Pubkey>,
+    },
+    #[clap(arg_required_else_help = true)]
+    WithdrawFees {
+        /// Pool to withdraw the owner fees of
+        #[clap(short, long, parse(try_from_str))]
+        pool: Pubkey,
+        /// Fee vault to withdraw from
+        #[clap(short, long, arg_enum, ignore_case = true, default_value = "both")]
+        mint: FeesMint,
+        /// Amount of fees to withdraw from each fee vault, else the full vault balance
+        #[clap(short, long)]
+        amount: Option<u64>,
+    },
+    ListPools {
+        /// Only list the pools of this admin
+        #[clap(long, parse(try_from_str))]
+        pool_admin: Option<Pubkey>,
+        /// Only list the pools with this token A mint
+        #[clap(long, parse(try_from_str))]
+        token_a_mint: Option<Pubkey>,
+        /// Only list the pools with this token B mint
+        #[clap(long, parse(try_from_str))]
+        token_b_mint: Option<Pubkey>,
+        /// Only list the pools with this curve type
+        #[clap(long, parse(try_from_str = parse_curve_type))]
+        curve_type: Option<CurveType>,
+        /// Output format
+        #[clap(short, long, arg_enum, default_value = "table")]
+        output: OutputFormat,
+    },
+    /// Apply the admin operations of a manifest file, packed in as few transactions as fit
+    ///
+    /// Prints a preview of the changes and asks for confirmation before sending
+    #[clap(arg_required_else_help = true)]
+    Apply {
+        /// Yaml manifest listing the operations to apply in order
+        #[clap(short, long, parse(from_os_str))]
+        manifest: PathBuf,
+        /// Send without asking for confirmation
+        #[clap(short, long, takes_value = false)]
+        yes: bool,
+    },
+    /// Create a lookup table with all the static accounts of a pool, owned by the signer
+    #[clap(arg_required_else_help = true)]
+    CreateLookupTable {
+        /// Pool to register the accounts of
+        #[clap(short, long, parse(try_from_str))]
+        pool: Pubkey,
+    },
+    /// Add the static accounts of a pool and/or arbitrary addresses to a lookup table
+    #[clap(arg_required_else_help = true)]
+    ExtendLookupTable {
+        /// Lookup table to extend
+        #[clap(short, long, parse(try_from_str))]
+        lookup_table: Pubkey,
+        /// Pool to register the accounts of
+        #[clap(
+            short,
+            long,
+            parse(try_from_str),
+            required_unless_present = "addresses"
+        )]
+        pool: Option<Pubkey>,
+        /// Addresses to register, comma separated
+        #[clap(short, long, parse(try_from_str), use_value_delimiter = true)]
+        addresses: Vec<Pubkey>,
+    },
+    #[clap(arg_required_else_help = true)]
     PrintPool {
         /// Reserve pubkey
         #[clap(short, long, parse(try_from_str))]
         pool: Pubkey,
+        /// Output format
+        #[clap(short, long, arg_enum, default_value = "table")]
+        output: OutputFormat,
     },
 }
 
+fn parse_curve_type(s: &str) -> Result<CurveType> {
+    match s.to_lowercase().replace(['-', '_'], "").as_str() {
+        "constantproduct" => Ok(CurveType::ConstantProduct),
+        "constantprice" => Ok(CurveType::ConstantPrice),
+        "offset" => Ok(CurveType::Offset),
+        "stable" => Ok(CurveType::Stable),
+        _ => Err(anyhow::anyhow!("Unknown curve type: {}", s)),
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let args: Args = Args::parse();
@@ -129,7 +276,16 @@ async fn main() -> Result<()> {
     let commitment = CommitmentConfig::confirmed();
 
     let rpc_client = RpcClient::new_with_commitment(args.url.url().to_string(), commitment);
-    let client = OrbitLink::new(rpc_client, payer, None, commitment);
+    let lookup_tables =
+        lookup_table::get_lookup_table_accounts(&rpc_client, &args.lookup_tables).await?;
+    for lookup_table in &lookup_tables {
+        info!(
+            "Using lookup table {} with {} addresses",
+            lookup_table.key,
+            lookup_table.addresses.len()
+        );
+    }
+    let client = OrbitLink::new(rpc_client, payer, Some(lookup_tables), commitment);
     let config = Config {
         program_id: args.program,
         dry_run: args.dry_run,
@@ -160,7 +316,95 @@ async fn main() -> Result<()> {
         Actions::UpdatePool { pool, mode, value } => {
             command::update_pool(&hyperplane_client, admin, pool, mode, value).await
         }
-        Actions::PrintPool { pool } => command::print_pool(&hyperplane_client, pool).await,
+        Actions::Swap {
+            pool,
+            in_mint,
+            amount,
+            slippage_bps,
+        } => {
+            command::swap(
+                &hyperplane_client,
+                admin,
+                pool,
+                in_mint,
+                amount,
+                slippage_bps,
+            )
+            .await
+        }
+        Actions::Deposit {
+            pool,
+            pool_tokens,
+            max_a,
+            max_b,
+            pool_token_ata,
+        } => {
+            command::deposit(
+                &hyperplane_client,
+                admin,
+                pool,
+                pool_token_ata,
+                pool_tokens,
+                max_a,
+                max_b,
+            )
+            .await
+        }
+        Actions::Withdraw {
+            pool,
+            pool_tokens,
+            percent,
+            min_a,
+            min_b,
+            pool_token_ata,
+        } => {
+            command::withdraw(
+                &hyperplane_client,
+                admin,
+                pool,
+                pool_token_ata,
+                pool_tokens,
+                percent,
+                min_a,
+                min_b,
+            )
+            .await
+        }
+        Actions::WithdrawFees { pool, mint, amount } => {
+            command::withdraw_fees(&hyperplane_client, admin, pool, mint, amount).await
+        }
+        Actions::ListPools {
+            pool_admin,
+            token_a_mint,
+            token_b_mint,
+            curve_type,
+            output,
+        } => {
+            let filters = PoolFilters {
+                admin: pool_admin,
+                token_a_mint,
+                token_b_mint,
+                curve_type,
+            };
+            command::list_pools(&hyperplane_client, filters, output).await
+        }
+        Actions::Apply { manifest, yes } => {
+            command::apply(&hyperplane_client, admin, manifest, yes).await
+        }
+        Actions::CreateLookupTable { pool } => {
+            command::create_lookup_table(&hyperplane_client, admin, pool).await
+        }
+        Actions::ExtendLookupTable {
+            lookup_table,
+            pool,
+            addresses,
+        } => {
+            command::extend_lookup_table(&hyperplane_client, admin, lookup_table, pool, addresses)
+                .await
+        }
+        Actions::PrintPool { pool, output } => {
+            command::print_pool(&hyperplane_client, pool, output).await
+        }
     }
 }
 
@@ -181,6 +425,7 @@ mod test {
             program: hyperplane::ID,
             dry_run: true,
             multisig: false,
+            lookup_tables: vec![],
             signer: Some(signer),
             action: Actions::UpdatePool {
                 pool,
@@ -223,6 +468,7 @@ mod test {
             program: hyperplane::ID,
             dry_run: false,
             multisig: true,
+            lookup_tables: vec![],
             signer: Some(signer),
             action: Actions::UpdatePool {
                 pool,
@@ -279,6 +525,7 @@ mod test {
                 program: hyperplane::ID,
                 dry_run: false,
                 multisig: false,
+                lookup_tables: vec![],
                 signer: None,
                 action: Actions::UpdatePool {
                     pool,
@@ -288,4 +535,282 @@ mod test {
             }
         );
     }
+
+    #[test]
+    pub fn test_parsing_swap() {
+        let pool = Pubkey::new_unique();
+        let in_mint = Pubkey::new_unique();
+        let x = Args::parse_from([
+            "",
+            "-k",
+            "../../test/test/admin.json",
+            "swap",
+            "-p",
+            &pool.to_string(),
+            "--in-mint",
+            &in_mint.to_string(),
+            "--amount",
+            "1000000",
+        ]);
+
+        assert_eq!(
+            x.action,
+            Actions::Swap {
+                pool,
+                in_mint,
+                amount: 1_000_000,
+                slippage_bps: 50,
+            }
+        );
+    }
+
+    #[test]
+    pub fn test_parsing_deposit_and_withdraw() {
+        let pool = Pubkey::new_unique();
+        let x = Args::parse_from([
+            "",
+            "-k",
+            "../../test/test/admin.json",
+            "deposit",
+            "-p",
+            &pool.to_string(),
+            "--max-a",
+            "100",
+            "--max-b",
+            "200",
+        ]);
+        assert_eq!(
+            x.action,
+            Actions::Deposit {
+                pool,
+                pool_tokens: None,
+                max_a: Some(100),
+                max_b: Some(200),
+                pool_token_ata: None,
+            }
+        );
+
+        let x = Args::parse_from([
+            "",
+            "-k",
+            "../../test/test/admin.json",
+            "withdraw",
+            "-p",
+            &pool.to_string(),
+            "--percent",
+            "50",
+            "--min-a",
+            "10",
+        ]);
+        assert_eq!(
+            x.action,
+            Actions::Withdraw {
+                pool,
+                pool_tokens: None,
+                percent: Some(50),
+                min_a: Some(10),
+                min_b: None,
+                pool_token_ata: None,
+            }
+        );
+
+        assert!(Args::try_parse_from([
+            "",
+            "-k",
+            "../../test/test/admin.json",
+            "withdraw",
+            "-p",
+            &pool.to_string(),
+            "--pool-tokens",
+            "10",
+            "--percent",
+            "50",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    pub fn test_parsing_withdraw_fees() {
+        let pool = Pubkey::new_unique();
+        let x = Args::parse_from([
+            "",
+            "-k",
+            "../../test/test/admin.json",
+            "withdraw-fees",
+            "-p",
+            &pool.to_string(),
+        ]);
+        assert_eq!(
+            x.action,
+            Actions::WithdrawFees {
+                pool,
+                mint: FeesMint::Both,
+                amount: None,
+            }
+        );
+
+        let x = Args::parse_from([
+            "",
+            "-k",
+            "../../test/test/admin.json",
+            "withdraw-fees",
+            "-p",
+            &pool.to_string(),
+            "--mint",
+            "A",
+            "--amount",
+            "100",
+        ]);
+        assert_eq!(
+            x.action,
+            Actions::WithdrawFees {
+                pool,
+                mint: FeesMint::A,
+                amount: Some(100),
+            }
+        );
+    }
+
+    #[test]
+    pub fn test_parsing_list_pools() {
+        let token_a_mint = Pubkey::new_unique();
+        let x = Args::parse_from([
+            "",
+            "-k",
+            "../../test/test/admin.json",
+            "list-pools",
+            "--token-a-mint",
+            &token_a_mint.to_string(),
+            "--curve-type",
+            "ConstantProduct",
+            "--output",
+            "json",
+        ]);
+        assert_eq!(
+            x.action,
+            Actions::ListPools {
+                pool_admin: None,
+                token_a_mint: Some(token_a_mint),
+                token_b_mint: None,
+                curve_type: Some(CurveType::ConstantProduct),
+                output: OutputFormat::Json,
+            }
+        );
+    }
+
+    #[test]
+    pub fn test_parsing_print_pool() {
+        let pool = Pubkey::new_unique();
+        let x = Args::parse_from([
+            "",
+            "-k",
+            "../../test/test/admin.json",
+            "print-pool",
+            "-p",
+            &pool.to_string(),
+        ]);
+        assert_eq!(
+            x.action,
+            Actions::PrintPool {
+                pool,
+                output: OutputFormat::Table,
+            }
+        );
+
+        let x = Args::parse_from([
+            "",
+            "-k",
+            "../../test/test/admin.json",
+            "print-pool",
+            "-p",
+            &pool.to_string(),
+            "-o",
+            "yaml",
+        ]);
+        assert_eq!(
+            x.action,
+            Actions::PrintPool {
+                pool,
+                output: OutputFormat::Yaml,
+            }
+        );
+    }
+
+    #[test]
+    pub fn test_parsing_lookup_tables() {
+        let pool = Pubkey::new_unique();
+        let lookup_table = Pubkey::new_unique();
+        let other_lookup_table = Pubkey::new_unique();
+        let address = Pubkey::new_unique();
+        let x = Args::parse_from([
+            "",
+            "-k",
+            "../../test/test/admin.json",
+            "--lookup-tables",
+            &format!("{},{}", lookup_table, other_lookup_table),
+            "create-lookup-table",
+            "-p",
+            &pool.to_string(),
+        ]);
+        assert_eq!(x.lookup_tables, vec![lookup_table, other_lookup_table]);
+        assert_eq!(x.action, Actions::CreateLookupTable { pool });
+
+        let x = Args::parse_from([
+            "",
+            "-k",
+            "../../test/test/admin.json",
+            "extend-lookup-table",
+            "--lookup-table",
+            &lookup_table.to_string(),
+            "--addresses",
+            &address.to_string(),
+        ]);
+        assert!(x.lookup_tables.is_empty());
+        assert_eq!(
+            x.action,
+            Actions::ExtendLookupTable {
+                lookup_table,
+                pool: None,
+                addresses: vec![address],
+            }
+        );
+    }
+
+    #[test]
+    pub fn test_parsing_apply() {
+        let x = Args::parse_from([
+            "",
+            "-k",
+            "../../test/test/admin.json",
+            "--multisig",
+            "apply",
+            "--manifest",
+            "ops.yaml",
+        ]);
+        assert!(x.multisig);
+        assert_eq!(
+            x.action,
+            Actions::Apply {
+                manifest: PathBuf::from("ops.yaml"),
+                yes: false,
+            }
+        );
+
+        let x = Args::parse_from([
+            "",
+            "-k",
+            "../../test/test/admin.json",
+            "apply",
+            "--manifest",
+            "ops.yaml",
+            "--yes",
+        ]);
+        assert_eq!(
+            x.action,
+            Actions::Apply {
+                manifest: PathBuf::from("ops.yaml"),
+                yes: true,
+            }
+        );
+    }
 }
@@ -0,0 +1,158 @@
+//! Admin operations applied in batch by the `apply` command
+//!
+//! ```yaml
+//! operations:
+//!   - op: create_ata
+//!     mint: So11111111111111111111111111111111111111112
+//!   - op: init_pool
+//!     config: pools/sol-usdc.json
+//!   - op: update_pool_config
+//!     pool: 7iy6QcKMpBdLsnXo4qmzm7ZALfLPTYKKNnQbTXrjXYAp
+//!     withdrawals_only: true
+//!   - op: withdraw_fees
+//!     pool: 7iy6QcKMpBdLsnXo4qmzm7ZALfLPTYKKNnQbTXrjXYAp
+//!     mint: both
+//! ```
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{anyhow, Result};
+
+use crate::{configs::PoolConfigValue, model::FeesMint};
+
+#[derive(Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    /// Operations, applied in order
+    pub operations: Vec<Operation>,
+}
+
+#[derive(Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Operation {
+    /// Create the associated token account of the owner, else the signer, if it does not exist
+    CreateAta { mint: String, owner: Option<String> },
+    /// Initialize a pool from a pool config file, relative to the manifest
+    InitPool {
+        config: PathBuf,
+        token_a_ata: Option<String>,
+        token_b_ata: Option<String>,
+    },
+    /// Set the config of a pool, values already set on the pool are skipped
+    UpdatePoolConfig {
+        pool: String,
+        withdrawals_only: Option<bool>,
+    },
+    /// Withdraw the owner fees of a pool to the signer's associated token accounts
+    WithdrawFees {
+        pool: String,
+        #[serde(default = "all_fees_mints")]
+        mint: FeesMint,
+        amount: Option<u64>,
+    },
+}
+
+fn all_fees_mints() -> FeesMint {
+    FeesMint::Both
+}
+
+impl Manifest {
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let manifest = serde_yaml::from_str(&fs::read_to_string(path)?)
+            .map_err(|e| anyhow!("Invalid manifest {}: {}", path.display(), e))?;
+        Ok(manifest)
+    }
+}
+
+/// Config values to set on the pool of an `update_pool_config` operation
+pub fn pool_config_values(withdrawals_only: Option<bool>) -> Vec<PoolConfigValue> {
+    withdrawals_only
+        .map(PoolConfigValue::WithdrawalsOnly)
+        .into_iter()
+        .collect()
+}
+
+/// Resolve a path of the manifest relative to the manifest directory
+pub fn resolve_path(manifest: &Path, path: &Path) -> PathBuf {
+    match manifest.parent() {
+        Some(dir) if path.is_relative() => dir.join(path),
+        _ => path.to_path_buf(),
+    }
+}
+
+pub fn parse_pubkey(value: &str) -> Result<Pubkey> {
+    Pubkey::from_str(value).map_err(|e| anyhow!("Invalid pubkey {}: {}", value, e))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn test_parse_manifest() {
+        let pool = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let manifest: Manifest = serde_yaml::from_str(&format!(
+            r#"
+operations:
+  - op: create_ata
+    mint: {mint}
+  - op: init_pool
+    config: pools/pool.json
+  - op: update_pool_config
+    pool: {pool}
+    withdrawals_only: true
+  - op: withdraw_fees
+    pool: {pool}
+"#
+        ))
+        .unwrap();
+
+        assert_eq!(
+            manifest.operations,
+            vec![
+                Operation::CreateAta {
+                    mint: mint.to_string(),
+                    owner: None,
+                },
+                Operation::InitPool {
+                    config: PathBuf::from("pools/pool.json"),
+                    token_a_ata: None,
+                    token_b_ata: None,
+                },
+                Operation::UpdatePoolConfig {
+                    pool: pool.to_string(),
+                    withdrawals_only: Some(true),
+                },
+                Operation::WithdrawFees {
+                    pool: pool.to_string(),
+                    mint: FeesMint::Both,
+                    amount: None,
+                },
+            ]
+        );
+        assert_eq!(
+            resolve_path(Path::new("ops/manifest.yaml"), Path::new("pools/pool.json")),
+            PathBuf::from("ops/pools/pool.json")
+        );
+    }
+
+    #[test]
+    pub fn test_parse_manifest_unknown_field() {
+        let pool = Pubkey::new_unique();
+        assert!(serde_yaml::from_str::<Manifest>(&format!(
+            r#"
+operations:
+  - op: update_pool_config
+    pool: {pool}
+    withdrawal_only: true
+"#
+        ))
+        .is_err());
+    }
+}
@@ -1,4 +1,12 @@
-use hyperplane::{curve::fees::Fees, CurveUserParameters, InitialSupply};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use hyperplane::{
+    curve::{
+        base::CurveType,
+        calculator::{AorB, MIN_LIQUIDITY},
+        fees::Fees,
+    },
+    CurveUserParameters, InitialSupply,
+};
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct InitializePoolConfig {
@@ -7,4 +15,168 @@ pub struct InitializePoolConfig {
     pub curve: CurveUserParameters,
     pub fees: Fees,
     pub initial_supply: InitialSupply,
+    /// Pool tokens permanently locked when the pool is initialized
+    #[serde(default = "default_min_liquidity")]
+    pub min_liquidity: u64,
+}
+
+fn default_min_liquidity() -> u64 {
+    MIN_LIQUIDITY as u64
+}
+
+/// Which of the pool fee vaults to act on
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ArgEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeesMint {
+    A,
+    B,
+    Both,
+}
+
+impl FeesMint {
+    pub fn sides(&self) -> Vec<AorB> {
+        match self {
+            FeesMint::A => vec![AorB::A],
+            FeesMint::B => vec![AorB::B],
+            FeesMint::Both => vec![AorB::A, AorB::B],
+        }
+    }
+}
+
+/// Filters to narrow down the pools returned by `HyperplaneClient::find_pools`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PoolFilters {
+    pub admin: Option<Pubkey>,
+    pub token_a_mint: Option<Pubkey>,
+    pub token_b_mint: Option<Pubkey>,
+    pub curve_type: Option<CurveType>,
+}
+
+/// Format of the data printed by the CLI
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ArgEnum)]
+pub enum OutputFormat {
+    Table,
+    Json,
+    Yaml,
+}
+
+/// Overview of a pool, as listed by the CLI
+#[derive(Debug, serde::Serialize)]
+pub struct PoolSummary {
+    pub address: String,
+    pub admin: String,
+    pub token_a_mint: String,
+    pub token_b_mint: String,
+    pub curve_type: String,
+    /// Raw amount of token A in the pool vault
+    pub token_a_vault_amount: u64,
+    /// Raw amount of token B in the pool vault
+    pub token_b_vault_amount: u64,
+    pub fees: Fees,
+    pub withdrawals_only: bool,
+}
+
+/// Decoded view of a pool, its curve and balances, as printed by the CLI
+#[derive(Debug, serde::Serialize)]
+pub struct PoolView {
+    pub address: String,
+    pub admin: String,
+    pub pool_authority: String,
+    pub pool_token_mint: String,
+    pub token_a_mint: String,
+    pub token_b_mint: String,
+    pub token_a_decimals: u8,
+    pub token_b_decimals: u8,
+    pub token_a_vault: String,
+    pub token_b_vault: String,
+    pub token_a_fees_vault: String,
+    pub token_b_fees_vault: String,
+    pub swap_curve: String,
+    pub curve: CurveView,
+    pub withdrawals_only: bool,
+    pub min_liquidity: u64,
+    pub balances: PoolBalances,
+    /// Marginal price of token A in token B, in raw units
+    pub spot_price: Option<f64>,
+    pub fees: Fees,
+    pub fee_percentages: FeePercentages,
+    pub liquidity_locks: Vec<LiquidityLockView>,
+    pub total_locked_pool_tokens: u64,
+}
+
+/// Curve account decoded according to the pool curve type
+#[derive(Debug, PartialEq, serde::Serialize)]
+#[serde(tag = "type")]
+pub enum CurveView {
+    ConstantProduct,
+    ConstantPrice {
+        token_b_price: u64,
+    },
+    Offset {
+        token_b_offset: u64,
+    },
+    Stable {
+        amp: u64,
+        token_a_factor: u64,
+        token_b_factor: u64,
+    },
+}
+
+/// Raw token balances of a pool
+#[derive(Debug, serde::Serialize)]
+pub struct PoolBalances {
+    pub token_a_vault_amount: u64,
+    pub token_b_vault_amount: u64,
+    pub token_a_fees_vault_amount: u64,
+    pub token_b_fees_vault_amount: u64,
+    pub pool_token_supply: u64,
+}
+
+/// Pool fees as percentages
+#[derive(Debug, serde::Serialize)]
+pub struct FeePercentages {
+    pub trade_fee: f64,
+    pub owner_trade_fee: f64,
+    pub owner_withdraw_fee: f64,
+    /// Share of the owner trade fee sent to the host
+    pub host_fee: f64,
+}
+
+impl From<&Fees> for FeePercentages {
+    fn from(fees: &Fees) -> Self {
+        Self {
+            trade_fee: percent(fees.trade_fee_numerator, fees.trade_fee_denominator),
+            owner_trade_fee: percent(
+                fees.owner_trade_fee_numerator,
+                fees.owner_trade_fee_denominator,
+            ),
+            owner_withdraw_fee: percent(
+                fees.owner_withdraw_fee_numerator,
+                fees.owner_withdraw_fee_denominator,
+            ),
+            host_fee: percent(fees.host_fee_numerator, fees.host_fee_denominator),
+        }
+    }
+}
+
+/// Liquidity lock of a pool, with its amounts at the time of printing
+#[derive(Debug, serde::Serialize)]
+pub struct LiquidityLockView {
+    pub address: String,
+    pub owner: String,
+    pub lock_id: u64,
+    pub locked_amount: u64,
+    pub remaining_locked_amount: u64,
+    pub unlockable_amount: u64,
+    pub unlocked_amount: u64,
+    pub vesting_start_ts: u64,
+    pub vesting_end_ts: u64,
+}
+
+pub fn percent(numerator: u64, denominator: u64) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64 * 100.0
+    }
 }
@@ -0,0 +1,42 @@
+use anchor_client::solana_sdk::{
+    address_lookup_table_account::AddressLookupTableAccount, pubkey::Pubkey,
+};
+use anyhow::{anyhow, Result};
+use orbit_link::async_client::AsyncClient;
+use solana_address_lookup_table_program::state::AddressLookupTable;
+
+/// Maximum number of addresses added by a single `extend_lookup_table` instruction,
+/// keeping the transaction under the packet size limit
+pub const MAX_EXTEND_ADDRESSES: usize = 20;
+
+/// Fetch and deserialize a lookup table, to compile v0 transactions against it
+pub async fn get_lookup_table_account<T: AsyncClient>(
+    client: &T,
+    key: &Pubkey,
+) -> Result<AddressLookupTableAccount> {
+    let account = client.get_account(key).await?;
+    let addresses = lookup_table_addresses(key, &account.data)?;
+    Ok(AddressLookupTableAccount {
+        key: *key,
+        addresses,
+    })
+}
+
+/// Fetch and deserialize all the lookup tables
+pub async fn get_lookup_table_accounts<T: AsyncClient>(
+    client: &T,
+    keys: &[Pubkey],
+) -> Result<Vec<AddressLookupTableAccount>> {
+    let mut lookup_tables = Vec::with_capacity(keys.len());
+    for key in keys {
+        lookup_tables.push(get_lookup_table_account(client, key).await?);
+    }
+    Ok(lookup_tables)
+}
+
+/// Get the addresses stored in a lookup table account
+pub fn lookup_table_addresses(key: &Pubkey, data: &[u8]) -> Result<Vec<Pubkey>> {
+    let lookup_table = AddressLookupTable::deserialize(data)
+        .map_err(|e| anyhow!("Failed to deserialize lookup table {}: {}", key, e))?;
+    Ok(lookup_table.addresses.to_vec())
+}
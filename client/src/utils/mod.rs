@@ -1 +1,3 @@
+pub mod lookup_table;
 pub mod macros;
+pub mod token;
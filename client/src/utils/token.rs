@@ -0,0 +1,23 @@
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::StateWithExtensions,
+    state::{Account, Mint},
+};
+use anyhow::Result;
+
+/// Read the amount of a token account owned by either the Token or Token-2022 program
+pub fn token_account_amount(account_data: &[u8]) -> Result<u64> {
+    let account = StateWithExtensions::<Account>::unpack(account_data)?;
+    Ok(account.base.amount)
+}
+
+/// Read the supply of a mint owned by either the Token or Token-2022 program
+pub fn mint_supply(mint_data: &[u8]) -> Result<u64> {
+    let mint = StateWithExtensions::<Mint>::unpack(mint_data)?;
+    Ok(mint.base.supply)
+}
+
+/// Read the decimals of a mint owned by either the Token or Token-2022 program
+pub fn mint_decimals(mint_data: &[u8]) -> Result<u8> {
+    let mint = StateWithExtensions::<Mint>::unpack(mint_data)?;
+    Ok(mint.base.decimals)
+}
@@ -72,6 +72,112 @@ pub async fn print_pool(pool: &Pubkey) {
     println!("print_pool::success\n\n{output_str}");
 }
 
+pub async fn print_pool_json(pool: &Pubkey) -> serde_json::Value {
+    let output = cli_command("print-pool", Config::default())
+        .arg("--pool")
+        .arg(pool.to_string())
+        .arg("--output")
+        .arg("json")
+        .output()
+        .await
+        .expect("print_pool_json::exception");
+
+    if output.status.code() != Some(0) {
+        let output_str = get_string_from_stderr(&output);
+        panic!("print_pool_json::failed\n\n{output_str}");
+    }
+    let output_str = get_string_from_stdout(&output);
+    println!("print_pool_json::success\n\n{output_str}");
+    // the json document is the only output starting with a brace on its own line
+    let json_start = if output_str.starts_with("{\n") {
+        0
+    } else {
+        output_str
+            .find("\n{\n")
+            .unwrap_or_else(|| panic!("Cannot find json in print-pool response:\n\n{output_str}"))
+            + 1
+    };
+    serde_json::Deserializer::from_str(&output_str[json_start..])
+        .into_iter::<serde_json::Value>()
+        .next()
+        .unwrap()
+        .unwrap()
+}
+
+pub async fn swap(pool: &Pubkey, in_mint: &Pubkey, amount: u64, config: Config) {
+    let output = cli_command("swap", config)
+        .arg("--pool")
+        .arg(pool.to_string())
+        .arg("--in-mint")
+        .arg(in_mint.to_string())
+        .arg("--amount")
+        .arg(amount.to_string())
+        .output()
+        .await
+        .expect("swap::exception");
+
+    if output.status.code() != Some(0) {
+        let output_str = get_string_from_stderr(&output);
+        panic!("swap::failed\n\n{output_str}");
+    }
+    let output_str = get_string_from_stdout(&output);
+    println!("swap::success\n\n{output_str}");
+}
+
+pub async fn deposit(pool: &Pubkey, max_a: u64, max_b: u64, config: Config) {
+    let output = cli_command("deposit", config)
+        .arg("--pool")
+        .arg(pool.to_string())
+        .arg("--max-a")
+        .arg(max_a.to_string())
+        .arg("--max-b")
+        .arg(max_b.to_string())
+        .output()
+        .await
+        .expect("deposit::exception");
+
+    if output.status.code() != Some(0) {
+        let output_str = get_string_from_stderr(&output);
+        panic!("deposit::failed\n\n{output_str}");
+    }
+    let output_str = get_string_from_stdout(&output);
+    println!("deposit::success\n\n{output_str}");
+}
+
+pub async fn withdraw(pool: &Pubkey, percent: u8, config: Config) {
+    let output = cli_command("withdraw", config)
+        .arg("--pool")
+        .arg(pool.to_string())
+        .arg("--percent")
+        .arg(percent.to_string())
+        .output()
+        .await
+        .expect("withdraw::exception");
+
+    if output.status.code() != Some(0) {
+        let output_str = get_string_from_stderr(&output);
+        panic!("withdraw::failed\n\n{output_str}");
+    }
+    let output_str = get_string_from_stdout(&output);
+    println!("withdraw::success\n\n{output_str}");
+}
+
+pub async fn withdraw_fees(pool: &Pubkey, config: Config) {
+    let output = cli_command("withdraw-fees", config)
+        .arg("--pool")
+        .arg(pool.to_string())
+        .output()
+        .await
+        .expect("withdraw_fees::exception");
+
+    if output.status.code() != Some(0) {
+        let output_str = get_string_from_stderr(&output);
+        panic!("withdraw_fees::failed\n\n{output_str}");
+    }
+    let output_str = get_string_from_stdout(&output);
+    println!("withdraw_fees::success\n\n{output_str}");
+}
+
 fn cli_command(cmd: &str, config: Config) -> Command {
     let mut command = Command::new("cargo");
     command
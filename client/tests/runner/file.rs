@@ -3,7 +3,9 @@ use std::path::Path;
 use anchor_client::anchor_lang::prelude::*;
 
 pub mod key {
-    use anchor_client::solana_sdk::signature::{write_keypair_file, Keypair, Signer};
+    use anchor_client::solana_sdk::signature::{
+        read_keypair_file, write_keypair_file, Keypair, Signer,
+    };
 
     use super::*;
 
@@ -14,6 +16,10 @@ pub mod key {
         write_keypair_file(&admin, ADMIN_KEY_FILE).unwrap();
         admin.pubkey()
     }
+
+    pub fn get_admin_key() -> Pubkey {
+        read_keypair_file(ADMIN_KEY_FILE).unwrap().pubkey()
+    }
 }
 
 pub mod mint {
@@ -1,4 +1,5 @@
 pub mod anchor;
 pub mod cli;
 pub mod file;
+pub mod rpc;
 pub mod validator;
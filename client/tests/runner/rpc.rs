@@ -0,0 +1,27 @@
+use anchor_client::{
+    solana_client::nonblocking::rpc_client::RpcClient,
+    solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey},
+};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+fn rpc_client() -> RpcClient {
+    RpcClient::new_with_commitment(
+        "http://127.0.0.1:8899".to_string(),
+        CommitmentConfig::confirmed(),
+    )
+}
+
+/// Balance of the associated token account of `owner`, zero if it does not exist
+pub async fn token_balance(owner: &Pubkey, mint: &Pubkey) -> u64 {
+    let client = rpc_client();
+    let token_program = client
+        .get_account(mint)
+        .await
+        .expect("token_balance::mint")
+        .owner;
+    let ata = get_associated_token_address_with_program_id(owner, mint, &token_program);
+    match client.get_token_account_balance(&ata).await {
+        Ok(balance) => balance.amount.parse().unwrap(),
+        Err(_) => 0,
+    }
+}
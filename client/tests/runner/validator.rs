@@ -1,4 +1,8 @@
-use std::{process::Stdio, time::Duration};
+use std::{
+    process::Stdio,
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
 
 use tokio::process::{Child, Command};
 
@@ -7,7 +11,20 @@ use crate::runner::{
     file::key::{create_admin_keypair, ADMIN_KEY_FILE},
 };
 
-pub async fn start_and_deploy_program() -> Child {
+/// The validator ports and the test files are shared, so the tests run one validator at a time
+static VALIDATOR_LOCK: Mutex<()> = Mutex::new(());
+
+pub struct TestValidator {
+    process: Child,
+    _lock: MutexGuard<'static, ()>,
+}
+
+// every test has its own runtime, so blocking until the validator of another test is stopped is intended
+#[allow(clippy::await_holding_lock)]
+pub async fn start_and_deploy_program() -> TestValidator {
+    let lock = VALIDATOR_LOCK
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
     println!("Buidling hyperplane program...");
     anchor::build_program().await;
     println!("Starting test validator...");
@@ -15,7 +32,10 @@ pub async fn start_and_deploy_program() -> Child {
     println!("Airdropping funds to pool admin=...");
     new_admin().await;
     println!("Test validator started and program deployed!");
-    solana_test_validator
+    TestValidator {
+        process: solana_test_validator,
+        _lock: lock,
+    }
 }
 
 pub async fn pstart() -> Child {
@@ -25,6 +45,8 @@ pub async fn pstart() -> Child {
         .arg("../target/deploy/hyperplane.so")
         .arg("--reset")
         .stdout(Stdio::piped())
+        // stop the validator if the test panics
+        .kill_on_drop(true)
         .spawn()
         .expect("solana-test-validator failed to execute");
 
@@ -53,6 +75,6 @@ pub async fn new_admin() {
     println!("Funded admin account {}!", admin_key);
 }
 
-pub async fn kill(solana_test_validator: &mut Child) {
-    solana_test_validator.kill().await.unwrap();
+pub async fn kill(solana_test_validator: &mut TestValidator) {
+    solana_test_validator.process.kill().await.unwrap();
 }
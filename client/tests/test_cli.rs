@@ -1,6 +1,9 @@
 mod runner;
 
-use crate::runner::{cli, file, validator};
+use std::str::FromStr;
+
+use crate::runner::{cli, file, rpc, validator};
+use anchor_client::solana_sdk::pubkey::Pubkey;
 use hyperplane_client::client::Config;
 
 #[tokio::test]
@@ -16,3 +19,133 @@ pub async fn init_pool() {
 
     validator::kill(&mut solana_test_validator).await;
 }
+
+#[tokio::test]
+pub async fn swap() {
+    let mut solana_test_validator = validator::start_and_deploy_program().await;
+
+    // the admin keeps half of the supply to trade
+    let token_a_mint = cli::create_mint("a".to_string(), 2000000000000).await;
+    let token_b_mint = cli::create_mint("b".to_string(), 2000000000000).await;
+    let config_path = file::pool::generate_config_file(&token_a_mint, &token_b_mint);
+    let pool = cli::init_pool(config_path, Config::default()).await;
+
+    cli::swap(&pool, &token_a_mint, 1000000, Config::default()).await;
+    cli::swap(&pool, &token_b_mint, 1000000, Config::default()).await;
+
+    cli::print_pool(&pool).await;
+
+    validator::kill(&mut solana_test_validator).await;
+}
+
+#[tokio::test]
+pub async fn deposit_and_withdraw() {
+    let mut solana_test_validator = validator::start_and_deploy_program().await;
+
+    // the admin keeps half of the supply to deposit
+    let token_a_mint = cli::create_mint("a".to_string(), 2000000000000).await;
+    let token_b_mint = cli::create_mint("b".to_string(), 2000000000000).await;
+    let config_path = file::pool::generate_config_file(&token_a_mint, &token_b_mint);
+    let pool = cli::init_pool(config_path, Config::default()).await;
+
+    let admin = file::key::get_admin_key();
+    let pool_token_mint = Pubkey::from_str(
+        cli::print_pool_json(&pool).await["pool_token_mint"]
+            .as_str()
+            .unwrap(),
+    )
+    .unwrap();
+    let balances = |pool_view: &serde_json::Value| {
+        let balance = |name: &str| pool_view["balances"][name].as_u64().unwrap();
+        (
+            balance("token_a_vault_amount"),
+            balance("token_b_vault_amount"),
+            balance("pool_token_supply"),
+        )
+    };
+    let user_balances = move || async move {
+        (
+            rpc::token_balance(&admin, &token_a_mint).await,
+            rpc::token_balance(&admin, &token_b_mint).await,
+            rpc::token_balance(&admin, &pool_token_mint).await,
+        )
+    };
+
+    let (vault_a, vault_b, supply) = balances(&cli::print_pool_json(&pool).await);
+    let (user_a, user_b, user_pool_tokens) = user_balances().await;
+
+    cli::deposit(&pool, 1000000, 1000000, Config::default()).await;
+
+    let (deposit_vault_a, deposit_vault_b, deposit_supply) =
+        balances(&cli::print_pool_json(&pool).await);
+    let (deposit_user_a, deposit_user_b, deposit_user_pool_tokens) = user_balances().await;
+    let deposited_a = user_a - deposit_user_a;
+    let deposited_b = user_b - deposit_user_b;
+    assert!(deposited_a > 0 && deposited_a <= 1000000);
+    assert!(deposited_b > 0 && deposited_b <= 1000000);
+    assert_eq!(deposit_vault_a - vault_a, deposited_a);
+    assert_eq!(deposit_vault_b - vault_b, deposited_b);
+    let minted = deposit_user_pool_tokens - user_pool_tokens;
+    assert!(minted > 0);
+    assert_eq!(deposit_supply - supply, minted);
+
+    cli::withdraw(&pool, 50, Config::default()).await;
+
+    let (withdraw_vault_a, withdraw_vault_b, withdraw_supply) =
+        balances(&cli::print_pool_json(&pool).await);
+    let (withdraw_user_a, withdraw_user_b, withdraw_user_pool_tokens) = user_balances().await;
+    let burned = deposit_user_pool_tokens - withdraw_user_pool_tokens;
+    assert_eq!(burned, deposit_user_pool_tokens / 2);
+    assert_eq!(deposit_supply - withdraw_supply, burned);
+    // the pool has no owner withdraw fee, everything leaving the vaults goes to the user
+    let withdrawn_a = withdraw_user_a - deposit_user_a;
+    let withdrawn_b = withdraw_user_b - deposit_user_b;
+    assert!(withdrawn_a > 0 && withdrawn_b > 0);
+    assert_eq!(deposit_vault_a - withdraw_vault_a, withdrawn_a);
+    assert_eq!(deposit_vault_b - withdraw_vault_b, withdrawn_b);
+
+    cli::print_pool(&pool).await;
+
+    validator::kill(&mut solana_test_validator).await;
+}
+
+#[tokio::test]
+pub async fn withdraw_fees() {
+    let mut solana_test_validator = validator::start_and_deploy_program().await;
+
+    let token_a_mint = cli::create_mint("a".to_string(), 2000000000000).await;
+    let token_b_mint = cli::create_mint("b".to_string(), 2000000000000).await;
+    let config_path = file::pool::generate_config_file(&token_a_mint, &token_b_mint);
+    let pool = cli::init_pool(config_path, Config::default()).await;
+
+    // accrue owner fees in both fee vaults
+    cli::swap(&pool, &token_a_mint, 1000000, Config::default()).await;
+    cli::swap(&pool, &token_b_mint, 1000000, Config::default()).await;
+
+    cli::withdraw_fees(&pool, Config::default()).await;
+
+    cli::print_pool(&pool).await;
+
+    validator::kill(&mut solana_test_validator).await;
+}
+
+#[tokio::test]
+pub async fn print_pool_json() {
+    let mut solana_test_validator = validator::start_and_deploy_program().await;
+
+    let token_a_mint = cli::create_mint("a".to_string(), 1000000000000).await;
+    let token_b_mint = cli::create_mint("b".to_string(), 1000000000000).await;
+    let config_path = file::pool::generate_config_file(&token_a_mint, &token_b_mint);
+    let pool = cli::init_pool(config_path, Config::default()).await;
+
+    let pool_view = cli::print_pool_json(&pool).await;
+    assert_eq!(pool_view["address"], pool.to_string());
+    assert_eq!(pool_view["token_a_mint"], token_a_mint.to_string());
+    assert_eq!(pool_view["token_a_decimals"], 6);
+    assert_eq!(pool_view["token_b_decimals"], 6);
+    assert_eq!(pool_view["curve"]["type"], "Stable");
+    assert_eq!(pool_view["curve"]["amp"], 100);
+    assert!(pool_view["balances"]["pool_token_supply"].as_u64().unwrap() > 0);
+
+    validator::kill(&mut solana_test_validator).await;
+}
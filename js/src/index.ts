@@ -224,6 +224,7 @@ export class SwapPool {
    * @param swapProgramId The program ID of the token-swap program
    * @param feeNumerator Numerator of the fee ratio
    * @param feeDenominator Denominator of the fee ratio
+   * @param minLiquidity Pool tokens permanently locked when the pool is initialized
    * @return Token object for the newly minted token, Public key of the account holding the total supply of new tokens
    */
   static async createSwapPool(
@@ -245,6 +246,7 @@ export class SwapPool {
     curveType: number,
     initialSupplyA: number,
     initialSupplyB: number,
+    minLiquidity: number,
     curveParameters?: Numberu64,
     confirmOptions?: ConfirmOptions,
   ): Promise<[SwapPool, PublicKey]> {
@@ -296,6 +298,12 @@ export class SwapPool {
         TOKEN_SWAP_PROGRAM_ID,
       );
 
+    const [minLiquidityVault, _minLiquidityVaultBump] =
+      PublicKey.findProgramAddressSync(
+        [Buffer.from('mlvault'), pool.publicKey.toBuffer()],
+        TOKEN_SWAP_PROGRAM_ID,
+      );
+
     const adminAuthorityPoolTokenAta = new Keypair();
 
     const ix = Instructions.initializePool(
@@ -315,12 +323,14 @@ export class SwapPool {
         }),
         initialSupplyA: new Numberu64(initialSupplyA),
         initialSupplyB: new Numberu64(initialSupplyB),
+        minLiquidity: new Numberu64(minLiquidity),
       },
       {
         pool: pool.publicKey,
         swapCurve: swapCurve,
         admin: admin.publicKey,
         adminPoolTokenAta: adminAuthorityPoolTokenAta.publicKey,
+        minLiquidityVault,
         adminTokenAAta,
         adminTokenBAta,
         poolAuthority,
@@ -61,6 +61,8 @@ const HOST_SWAP_FEE = SWAP_PROGRAM_OWNER_FEE_ADDRESS ? Math.floor((OWNER_SWAP_FE
 
 // Pool token amount minted on init
 const DEFAULT_POOL_TOKEN_AMOUNT = 1000000000;
+// Pool token amount permanently locked in the minimum liquidity vault on init
+const MIN_LIQUIDITY = 1000;
 // Pool token amount to withdraw / deposit
 const POOL_TOKEN_AMOUNT = 10000000;
 
@@ -140,6 +142,7 @@ export async function createTokenSwap(
     curveType,
     currentSwapTokenA,
     currentSwapTokenB,
+    MIN_LIQUIDITY,
     curveParameters,
   );
 
@@ -337,7 +340,7 @@ export async function withdraw(): Promise<void> {
   swapTokenB = await getTokenAccount(connection, swapPool.tokenBVault);
 
   let info = await getTokenAccount(connection, adminAuthorityPoolTokenAta, undefined, TOKEN_2022_PROGRAM_ID);
-  expect(Number(info.amount)).eq(DEFAULT_POOL_TOKEN_AMOUNT - POOL_TOKEN_AMOUNT,);
+  expect(Number(info.amount)).eq(DEFAULT_POOL_TOKEN_AMOUNT - MIN_LIQUIDITY - POOL_TOKEN_AMOUNT,);
   expect(Number(swapTokenA.amount)).eq(currentSwapTokenA - totalTokenA);
   currentSwapTokenA -= totalTokenA;
   expect(Number(swapTokenB.amount)).eq(currentSwapTokenB - totalTokenB);
@@ -503,7 +506,7 @@ export async function swap(): Promise<void> {
   currentSwapTokenB -= SWAP_AMOUNT_OUT;
 
   info = await getTokenAccount(connection, adminAuthorityPoolTokenAta, undefined, TOKEN_2022_PROGRAM_ID);
-  expect(Number(info.amount)).eq(DEFAULT_POOL_TOKEN_AMOUNT - POOL_TOKEN_AMOUNT);
+  expect(Number(info.amount)).eq(DEFAULT_POOL_TOKEN_AMOUNT - MIN_LIQUIDITY - POOL_TOKEN_AMOUNT);
 
   info = await getTokenAccount(connection, swapPool.tokenAFeesVault, undefined, TOKEN_PROGRAM_ID);
   expect(Number(info.amount)).eq(currentAFees + (OWNER_SWAP_FEE - HOST_SWAP_FEE));
@@ -0,0 +1,6 @@
+#![no_main]
+
+use hyperplane_fuzz::{fuzz_data::FuzzData, runner::run_fuzz};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|fuzz_data: FuzzData| run_fuzz(fuzz_data));
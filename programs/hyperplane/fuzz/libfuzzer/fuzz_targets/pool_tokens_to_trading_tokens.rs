@@ -0,0 +1,71 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use hyperplane::curve::{calculator::RoundDirection, math::pool_tokens_to_trading_tokens};
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    pool_tokens: u64,
+    pool_token_supply: u64,
+    token_a_amount: u64,
+    token_b_amount: u64,
+}
+
+fuzz_target!(|input: Input| {
+    if input.pool_token_supply == 0 {
+        return;
+    }
+    let pool_token_supply = u128::from(input.pool_token_supply);
+    let pool_tokens = u128::from(input.pool_tokens) % (pool_token_supply + 1);
+    let token_a_amount = u128::from(input.token_a_amount);
+    let token_b_amount = u128::from(input.token_b_amount);
+
+    let convert = |pool_tokens, round_direction| {
+        pool_tokens_to_trading_tokens(
+            pool_tokens,
+            pool_token_supply,
+            token_a_amount,
+            token_b_amount,
+            round_direction,
+        )
+        .unwrap()
+    };
+    let floor = convert(pool_tokens, RoundDirection::Floor);
+    let ceiling = convert(pool_tokens, RoundDirection::Ceiling);
+
+    // never more than the vaults
+    assert!(floor.token_a_amount <= token_a_amount, "{input:?}");
+    assert!(floor.token_b_amount <= token_b_amount, "{input:?}");
+    assert!(ceiling.token_a_amount <= token_a_amount, "{input:?}");
+    assert!(ceiling.token_b_amount <= token_b_amount, "{input:?}");
+
+    // rounding up adds at most 1
+    assert!(ceiling.token_a_amount >= floor.token_a_amount, "{input:?}");
+    assert!(
+        ceiling.token_a_amount <= floor.token_a_amount + 1,
+        "{input:?}"
+    );
+    assert!(ceiling.token_b_amount >= floor.token_b_amount, "{input:?}");
+    assert!(
+        ceiling.token_b_amount <= floor.token_b_amount + 1,
+        "{input:?}"
+    );
+
+    // withdrawing in two parts never gets more than the vaults
+    let rest = convert(pool_token_supply - pool_tokens, RoundDirection::Floor);
+    assert!(
+        floor.token_a_amount + rest.token_a_amount <= token_a_amount,
+        "{input:?}"
+    );
+    assert!(
+        floor.token_b_amount + rest.token_b_amount <= token_b_amount,
+        "{input:?}"
+    );
+
+    // the whole supply is worth the whole vaults
+    if pool_tokens == pool_token_supply {
+        assert_eq!(floor.token_a_amount, token_a_amount, "{input:?}");
+        assert_eq!(floor.token_b_amount, token_b_amount, "{input:?}");
+    }
+});
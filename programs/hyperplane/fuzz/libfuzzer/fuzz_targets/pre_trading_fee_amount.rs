@@ -0,0 +1,58 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use hyperplane::curve::fees::Fees;
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    trade_fee_numerator: u64,
+    trade_fee_denominator: u64,
+    owner_trade_fee_numerator: u64,
+    owner_trade_fee_denominator: u64,
+    post_fee_amount: u64,
+}
+
+fuzz_target!(|input: Input| {
+    let fees = Fees {
+        trade_fee_numerator: input.trade_fee_numerator,
+        trade_fee_denominator: input.trade_fee_denominator,
+        owner_trade_fee_numerator: input.owner_trade_fee_numerator,
+        owner_trade_fee_denominator: input.owner_trade_fee_denominator,
+        ..Default::default()
+    };
+    if fees.validate().is_err() {
+        return;
+    }
+    let post_fee_amount = u128::from(input.post_fee_amount);
+
+    let pre_fee_amount = match fees.pre_trading_fee_amount(post_fee_amount) {
+        Ok(pre_fee_amount) => pre_fee_amount,
+        // the trade and owner trade fees add up to more than 100%
+        Err(_) => return,
+    };
+    // all of the amount is taken as fees
+    if pre_fee_amount == 0 {
+        return;
+    }
+    assert!(
+        pre_fee_amount >= post_fee_amount,
+        "{fees:?}: pre_fee_amount={pre_fee_amount} < post_fee_amount={post_fee_amount}"
+    );
+
+    // each fee is rounded separately, so the amount left after fees can be 1 short
+    let (trading_fee, owner_trading_fee) = match (
+        fees.trading_fee(pre_fee_amount),
+        fees.owner_trading_fee(pre_fee_amount),
+    ) {
+        (Ok(trading_fee), Ok(owner_trading_fee)) => (trading_fee, owner_trading_fee),
+        _ => return,
+    };
+    let amount_after_fees = pre_fee_amount
+        .saturating_sub(trading_fee)
+        .saturating_sub(owner_trading_fee);
+    assert!(
+        amount_after_fees + 1 >= post_fee_amount,
+        "{fees:?}: pre_fee_amount={pre_fee_amount}, amount_after_fees={amount_after_fees}, post_fee_amount={post_fee_amount}"
+    );
+});
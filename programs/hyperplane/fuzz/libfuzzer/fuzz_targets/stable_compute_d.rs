@@ -0,0 +1,52 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use hyperplane::curve::stable::{
+    fuzz::{compute_ann, compute_d},
+    MAX_AMP, MIN_AMP,
+};
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    amp: u64,
+    amount_a: u64,
+    amount_b: u64,
+}
+
+fuzz_target!(|input: Input| {
+    // amp is exclusive of both bounds
+    let amp = MIN_AMP + 1 + input.amp % (MAX_AMP - MIN_AMP - 1);
+    let ann = compute_ann(amp).unwrap();
+    let amount_a = u128::from(input.amount_a);
+    let amount_b = u128::from(input.amount_b);
+
+    let d = match compute_d(ann, amount_a, amount_b) {
+        Ok(d) => d,
+        // D is undefined if only one side of the pool is empty
+        Err(_) if amount_a == 0 || amount_b == 0 => return,
+        Err(e) => panic!("{input:?}: {e:?}"),
+    };
+
+    // D lies between the constant product and the constant sum invariants
+    assert!(d <= amount_a + amount_b, "{input:?}: d={d}");
+    assert!(
+        d >= 2 * isqrt(amount_a * amount_b),
+        "{input:?}: d={d} below the constant product invariant"
+    );
+});
+
+/// Floor of the square root
+fn isqrt(n: u128) -> u128 {
+    let mut root = (n as f64).sqrt() as u128;
+    while root.checked_mul(root).map_or(true, |square| square > n) {
+        root -= 1;
+    }
+    while (root + 1)
+        .checked_mul(root + 1)
+        .map_or(false, |square| square <= n)
+    {
+        root += 1;
+    }
+    root
+}
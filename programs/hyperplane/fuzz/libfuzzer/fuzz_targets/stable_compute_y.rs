@@ -0,0 +1,40 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use hyperplane::curve::stable::{
+    fuzz::{compute_ann, compute_d, compute_y},
+    MAX_AMP, MIN_AMP,
+};
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    amp: u64,
+    amount_a: u64,
+    amount_b: u64,
+    /// Swapped into the pool on top of `amount_a`
+    source_amount: u64,
+}
+
+fuzz_target!(|input: Input| {
+    // amp is exclusive of both bounds
+    let amp = MIN_AMP + 1 + input.amp % (MAX_AMP - MIN_AMP - 1);
+    let ann = compute_ann(amp).unwrap();
+    let amount_a = u128::from(input.amount_a);
+    let amount_b = u128::from(input.amount_b);
+    if amount_a == 0 || amount_b == 0 {
+        return;
+    }
+    let d = compute_d(ann, amount_a, amount_b).unwrap();
+
+    let y = match compute_y(ann, amount_a, d) {
+        Ok(y) => y,
+        Err(_) => return,
+    };
+    let new_y = match compute_y(ann, amount_a + u128::from(input.source_amount), d) {
+        Ok(new_y) => new_y,
+        Err(_) => return,
+    };
+    // swapping tokens into the pool never increases the other side
+    assert!(new_y <= y, "{input:?}: d={d}, y={y}, new_y={new_y}");
+});
@@ -0,0 +1,204 @@
+//! Fuzzed pool configuration and instruction sequences
+
+use arbitrary::Arbitrary;
+use hyperplane::{
+    curve::{
+        base::CurveType,
+        calculator::{AorB, TradeDirection},
+        fees::Fees,
+        stable::{MAX_AMP, MIN_AMP},
+    },
+    ix::{Deposit, Swap, Withdraw, WithdrawFees},
+    model::CurveParameters,
+};
+use spl_token_2022::extension::transfer_fee::TransferFee;
+
+use crate::native_token::TokenSpec;
+
+#[derive(Debug, Arbitrary, Clone)]
+pub struct FuzzData {
+    pub curve_type: CurveType,
+    /// Amplification coefficient of stable curves
+    pub amp: u64,
+    pub token_a: FuzzToken,
+    pub token_b: FuzzToken,
+    pub instructions: Vec<FuzzInstruction>,
+}
+
+#[derive(Debug, Arbitrary, Clone)]
+pub struct FuzzToken {
+    pub token_program: FuzzTokenProgram,
+    pub decimals: u8,
+}
+
+#[derive(Debug, Arbitrary, Clone)]
+pub enum FuzzTokenProgram {
+    Token,
+    Token2022,
+    Token2022TransferFee {
+        transfer_fee_basis_points: u16,
+        maximum_fee: u64,
+    },
+}
+
+#[derive(Debug, Arbitrary, Clone)]
+pub enum FuzzInstruction {
+    Swap {
+        token_a_id: AccountId,
+        token_b_id: AccountId,
+        trade_direction: TradeDirection,
+        instruction: Swap,
+    },
+    Deposit {
+        token_a_id: AccountId,
+        token_b_id: AccountId,
+        pool_token_id: AccountId,
+        instruction: Deposit,
+    },
+    Withdraw {
+        token_a_id: AccountId,
+        token_b_id: AccountId,
+        pool_token_id: AccountId,
+        instruction: Withdraw,
+    },
+    WithdrawFees {
+        token: AorB,
+        instruction: WithdrawFees,
+    },
+    UpdatePoolConfig {
+        withdrawals_only: bool,
+    },
+}
+
+/// Use u8 as an account id to simplify the address space and re-use accounts
+/// more often.
+pub type AccountId = u8;
+
+pub const INITIAL_SWAP_TOKEN_A_AMOUNT: u64 = 100_000_000_000;
+pub const INITIAL_SWAP_TOKEN_B_AMOUNT: u64 = 300_000_000_000;
+
+pub const INITIAL_USER_TOKEN_A_AMOUNT: u64 = 1_000_000_000;
+pub const INITIAL_USER_TOKEN_B_AMOUNT: u64 = 3_000_000_000;
+
+const MIN_DECIMALS: u8 = 5;
+const MAX_DECIMALS: u8 = 11;
+
+/// Higher transfer fees leave the vaults close to empty after initialization, which only
+/// exercises a degenerate pool
+const MAX_TRANSFER_FEE_BASIS_POINTS: u16 = 5_000;
+
+impl FuzzData {
+    pub fn token_a_spec(&self) -> TokenSpec {
+        get_token_spec(&self.token_a)
+    }
+
+    pub fn token_b_spec(&self) -> TokenSpec {
+        get_token_spec(&self.token_b)
+    }
+
+    pub fn curve_parameters(&self) -> CurveParameters {
+        get_curve_parameters(
+            self.curve_type,
+            self.amp,
+            self.token_a_spec().decimals,
+            self.token_b_spec().decimals,
+        )
+    }
+}
+
+impl FuzzInstruction {
+    pub fn token_a_id(&self) -> Option<AccountId> {
+        match self {
+            FuzzInstruction::Swap { token_a_id, .. }
+            | FuzzInstruction::Deposit { token_a_id, .. }
+            | FuzzInstruction::Withdraw { token_a_id, .. } => Some(*token_a_id),
+            FuzzInstruction::WithdrawFees { .. } | FuzzInstruction::UpdatePoolConfig { .. } => None,
+        }
+    }
+
+    pub fn token_b_id(&self) -> Option<AccountId> {
+        match self {
+            FuzzInstruction::Swap { token_b_id, .. }
+            | FuzzInstruction::Deposit { token_b_id, .. }
+            | FuzzInstruction::Withdraw { token_b_id, .. } => Some(*token_b_id),
+            FuzzInstruction::WithdrawFees { .. } | FuzzInstruction::UpdatePoolConfig { .. } => None,
+        }
+    }
+
+    pub fn pool_token_id(&self) -> Option<AccountId> {
+        match self {
+            FuzzInstruction::Deposit { pool_token_id, .. }
+            | FuzzInstruction::Withdraw { pool_token_id, .. } => Some(*pool_token_id),
+            FuzzInstruction::Swap { .. }
+            | FuzzInstruction::WithdrawFees { .. }
+            | FuzzInstruction::UpdatePoolConfig { .. } => None,
+        }
+    }
+}
+
+/// Fees of every fuzzed pool
+pub fn fees() -> Fees {
+    Fees {
+        trade_fee_numerator: 25,
+        trade_fee_denominator: 10000,
+        owner_trade_fee_numerator: 5,
+        owner_trade_fee_denominator: 10000,
+        owner_withdraw_fee_numerator: 30,
+        owner_withdraw_fee_denominator: 10000,
+        host_fee_numerator: 1,
+        host_fee_denominator: 5,
+    }
+}
+
+fn get_token_spec(token: &FuzzToken) -> TokenSpec {
+    let decimals = MIN_DECIMALS + token.decimals % (MAX_DECIMALS - MIN_DECIMALS + 1);
+    match token.token_program {
+        FuzzTokenProgram::Token => TokenSpec {
+            token_program: spl_token::id(),
+            decimals,
+            transfer_fee: None,
+        },
+        FuzzTokenProgram::Token2022 => TokenSpec {
+            token_program: spl_token_2022::id(),
+            decimals,
+            transfer_fee: None,
+        },
+        FuzzTokenProgram::Token2022TransferFee {
+            transfer_fee_basis_points,
+            maximum_fee,
+        } => TokenSpec {
+            token_program: spl_token_2022::id(),
+            decimals,
+            transfer_fee: Some(TransferFee {
+                epoch: 0.into(),
+                maximum_fee: maximum_fee.into(),
+                transfer_fee_basis_points: (transfer_fee_basis_points
+                    % (MAX_TRANSFER_FEE_BASIS_POINTS + 1))
+                    .into(),
+            }),
+        },
+    }
+}
+
+fn get_curve_parameters(
+    curve_type: CurveType,
+    amp: u64,
+    token_a_decimals: u8,
+    token_b_decimals: u8,
+) -> CurveParameters {
+    match curve_type {
+        CurveType::ConstantProduct => CurveParameters::ConstantProduct,
+        CurveType::ConstantPrice => CurveParameters::ConstantPrice {
+            token_b_price: 10_000_000,
+        },
+        CurveType::Offset => CurveParameters::Offset {
+            token_b_offset: 100_000_000_000,
+        },
+        // amp is exclusive of both bounds
+        CurveType::Stable => CurveParameters::Stable {
+            amp: MIN_AMP + 1 + amp % (MAX_AMP - MIN_AMP - 1),
+            token_a_decimals,
+            token_b_decimals,
+        },
+    }
+}
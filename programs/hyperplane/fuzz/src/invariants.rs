@@ -0,0 +1,313 @@
+//! Economic invariants checked after every fuzzed instruction
+
+use std::{collections::HashMap, fmt};
+
+use spl_math::precise_number::PreciseNumber;
+
+use crate::{
+    fuzz_data::{AccountId, FuzzInstruction},
+    native_account_data::NativeAccountData,
+    native_token::{get_mint_supply, get_token_balance, get_withheld_amount},
+    native_token_swap::NativeTokenSwap,
+    runner::FuzzAccounts,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Invariant {
+    /// The program returned an error that fuzzed inputs should not cause
+    UnexpectedError,
+    /// The normalized value of the pool per pool token decreased
+    ValuePerPoolToken,
+    /// A vault balance does not match the tokens deposited, swapped and withdrawn minus fees
+    VaultBalance,
+    /// A fee vault decreased outside of `withdraw_fees`
+    FeesVault,
+    /// Withdrawing all pool tokens of an account returned less than the value it deposited
+    WithdrawnValue,
+    /// Trading tokens were created or lost over the whole run
+    TokenConservation,
+    /// Withdrawing all liquidity and fees at the end failed
+    WithdrawAll,
+}
+
+#[derive(Clone, Debug)]
+pub struct Violation {
+    pub invariant: Invariant,
+    pub message: String,
+}
+
+impl Violation {
+    pub fn new(invariant: Invariant, message: String) -> Self {
+        Self { invariant, message }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self.invariant, self.message)
+    }
+}
+
+/// Balances of one trading token
+#[derive(Clone, Debug)]
+struct TokenBalances {
+    vault: u64,
+    fees_vault: u64,
+    /// Token accounts of the users
+    users: u128,
+    /// Host fees and fees withdrawn by the admin
+    admin: u128,
+    /// Transfer fees withheld in any of the accounts above
+    withheld: u128,
+}
+
+impl TokenBalances {
+    fn new<'a>(
+        vault: &NativeAccountData,
+        fees_vault: &NativeAccountData,
+        users: impl Iterator<Item = &'a NativeAccountData>,
+        admin: &[&NativeAccountData],
+    ) -> Self {
+        let mut withheld =
+            u128::from(get_withheld_amount(vault)) + u128::from(get_withheld_amount(fees_vault));
+        let mut users_amount = 0;
+        for account in users {
+            users_amount += u128::from(get_token_balance(account));
+            withheld += u128::from(get_withheld_amount(account));
+        }
+        let mut admin_amount = 0;
+        for account in admin {
+            admin_amount += u128::from(get_token_balance(account));
+            withheld += u128::from(get_withheld_amount(account));
+        }
+        Self {
+            vault: get_token_balance(vault),
+            fees_vault: get_token_balance(fees_vault),
+            users: users_amount,
+            admin: admin_amount,
+            withheld,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Snapshot {
+    token_a: TokenBalances,
+    token_b: TokenBalances,
+    pool_token_supply: u64,
+    /// Normalized value of the vaults
+    pool_value: PreciseNumber,
+    /// Normalized value of one more token A and B in the vaults, the most a deposit or withdrawal
+    /// rounds in favour of the pool
+    rounding_value: PreciseNumber,
+}
+
+impl Snapshot {
+    fn new(token_swap: &NativeTokenSwap, accounts: &FuzzAccounts) -> Self {
+        let token_a = TokenBalances::new(
+            &token_swap.token_a_vault_account,
+            &token_swap.token_a_fees_vault_account,
+            accounts.token_a.values(),
+            &[&token_swap.admin_token_a_ata, &accounts.fees_token_a],
+        );
+        let token_b = TokenBalances::new(
+            &token_swap.token_b_vault_account,
+            &token_swap.token_b_fees_vault_account,
+            accounts.token_b.values(),
+            &[&token_swap.admin_token_b_ata, &accounts.fees_token_b],
+        );
+        let calculator = &token_swap.swap_curve.calculator;
+        let pool_value = calculator
+            .normalized_value(token_a.vault.into(), token_b.vault.into())
+            .unwrap();
+        let rounding_value = calculator
+            .normalized_value(u128::from(token_a.vault) + 1, u128::from(token_b.vault) + 1)
+            .unwrap()
+            .checked_sub(&pool_value)
+            .unwrap_or_else(zero);
+        Self {
+            token_a,
+            token_b,
+            pool_token_supply: get_mint_supply(&token_swap.pool_token_mint_account),
+            pool_value,
+            rounding_value,
+        }
+    }
+
+    fn value_per_pool_token(&self) -> PreciseNumber {
+        self.pool_value
+            .checked_div(&PreciseNumber::new(self.pool_token_supply.into()).unwrap())
+            .unwrap()
+    }
+}
+
+/// Pool value added by the deposits of a pool token account and removed by its withdrawals
+#[derive(Debug)]
+struct LpValue {
+    deposited: PreciseNumber,
+    withdrawn: PreciseNumber,
+    rounding: PreciseNumber,
+}
+
+/// Tracks the pool between instructions and checks that:
+/// - the normalized value per pool token never decreases
+/// - the vaults only change by what the users deposit, swap and withdraw, minus fees
+/// - the fee vaults only decrease through `withdraw_fees`
+/// - withdrawing all pool tokens of an account returns at least the value it deposited, up to
+///   rounding in favour of the pool
+pub struct InvariantChecker {
+    snapshot: Snapshot,
+    lp_values: HashMap<AccountId, LpValue>,
+}
+
+impl InvariantChecker {
+    pub fn new(token_swap: &NativeTokenSwap, accounts: &FuzzAccounts) -> Self {
+        Self {
+            snapshot: Snapshot::new(token_swap, accounts),
+            lp_values: HashMap::new(),
+        }
+    }
+
+    /// Check the invariants after running `instruction`, whether it succeeded or not
+    pub fn check(
+        &mut self,
+        instruction: &FuzzInstruction,
+        succeeded: bool,
+        token_swap: &NativeTokenSwap,
+        accounts: &FuzzAccounts,
+    ) -> Result<(), Violation> {
+        let before = &self.snapshot;
+        let after = Snapshot::new(token_swap, accounts);
+
+        let value_before = before.value_per_pool_token();
+        let value_after = after.value_per_pool_token();
+        if value_after.less_than(&value_before) {
+            return Err(Violation::new(
+                Invariant::ValuePerPoolToken,
+                format!(
+                    "value per pool token decreased: before={:?}, after={:?}",
+                    value_before, value_after
+                ),
+            ));
+        }
+
+        check_vault_balance("a", &before.token_a, &after.token_a)?;
+        check_vault_balance("b", &before.token_b, &after.token_b)?;
+
+        if !matches!(instruction, FuzzInstruction::WithdrawFees { .. }) {
+            check_fees_vault("a", &before.token_a, &after.token_a)?;
+            check_fees_vault("b", &before.token_b, &after.token_b)?;
+        }
+
+        if succeeded {
+            update_lp_values(&mut self.lp_values, instruction, before, &after, accounts)?;
+        }
+
+        self.snapshot = after;
+        Ok(())
+    }
+}
+
+fn check_vault_balance(
+    token: &str,
+    before: &TokenBalances,
+    after: &TokenBalances,
+) -> Result<(), Violation> {
+    let deposited = before.users as i128 - after.users as i128;
+    let fees = (i128::from(after.fees_vault) - i128::from(before.fees_vault))
+        + (after.admin as i128 - before.admin as i128);
+    let withheld = after.withheld as i128 - before.withheld as i128;
+    let expected = i128::from(before.vault) + deposited - fees - withheld;
+    if i128::from(after.vault) != expected {
+        return Err(Violation::new(
+            Invariant::VaultBalance,
+            format!(
+                "token {} vault={} != expected={}: vault_before={}, deposited={}, fees={}, transfer_fees_withheld={}",
+                token, after.vault, expected, before.vault, deposited, fees, withheld
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn check_fees_vault(
+    token: &str,
+    before: &TokenBalances,
+    after: &TokenBalances,
+) -> Result<(), Violation> {
+    if after.fees_vault < before.fees_vault {
+        return Err(Violation::new(
+            Invariant::FeesVault,
+            format!(
+                "token {} fees vault decreased: before={}, after={}",
+                token, before.fees_vault, after.fees_vault
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn update_lp_values(
+    lp_values: &mut HashMap<AccountId, LpValue>,
+    instruction: &FuzzInstruction,
+    before: &Snapshot,
+    after: &Snapshot,
+    accounts: &FuzzAccounts,
+) -> Result<(), Violation> {
+    match instruction {
+        FuzzInstruction::Deposit { pool_token_id, .. } => {
+            let lp_value = lp_values.entry(*pool_token_id).or_insert_with(|| LpValue {
+                deposited: zero(),
+                withdrawn: zero(),
+                rounding: zero(),
+            });
+            let added = after
+                .pool_value
+                .checked_sub(&before.pool_value)
+                .unwrap_or_else(zero);
+            lp_value.deposited = lp_value.deposited.checked_add(&added).unwrap();
+            lp_value.rounding = lp_value
+                .rounding
+                .checked_add(&before.rounding_value)
+                .unwrap();
+        }
+        FuzzInstruction::Withdraw { pool_token_id, .. } => {
+            let lp_value = match lp_values.get_mut(pool_token_id) {
+                Some(lp_value) => lp_value,
+                None => return Ok(()),
+            };
+            let removed = before
+                .pool_value
+                .checked_sub(&after.pool_value)
+                .unwrap_or_else(zero);
+            lp_value.withdrawn = lp_value.withdrawn.checked_add(&removed).unwrap();
+            lp_value.rounding = lp_value
+                .rounding
+                .checked_add(&before.rounding_value)
+                .unwrap();
+
+            if get_token_balance(&accounts.pool[pool_token_id]) == 0 {
+                let lp_value = lp_values.remove(pool_token_id).unwrap();
+                // the withdraw fees are part of the withdrawn value
+                let withdrawn = lp_value.withdrawn.checked_add(&lp_value.rounding).unwrap();
+                if withdrawn.less_than(&lp_value.deposited) {
+                    return Err(Violation::new(
+                        Invariant::WithdrawnValue,
+                        format!(
+                            "pool token account {} withdrew less than it deposited: deposited={:?}, withdrawn={:?}, rounding={:?}",
+                            pool_token_id, lp_value.deposited, lp_value.withdrawn, lp_value.rounding
+                        ),
+                    ));
+                }
+            }
+        }
+        FuzzInstruction::Swap { .. }
+        | FuzzInstruction::WithdrawFees { .. }
+        | FuzzInstruction::UpdatePoolConfig { .. } => {}
+    }
+    Ok(())
+}
+
+fn zero() -> PreciseNumber {
+    PreciseNumber::new(0).unwrap()
+}
@@ -1,5 +1,9 @@
 #![allow(clippy::integer_arithmetic)]
+pub mod fuzz_data;
+pub mod invariants;
 pub mod native_account_data;
 pub mod native_processor;
 pub mod native_token;
 pub mod native_token_swap;
+pub mod repro;
+pub mod runner;
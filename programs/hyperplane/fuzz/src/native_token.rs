@@ -1,21 +1,77 @@
 use solana_program::{program_option::COption, program_pack::Pack, pubkey::Pubkey};
 use spl_token_2022::{
-    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
-    state::{Account as TokenAccount, AccountState as TokenAccountState, Mint},
+    extension::{
+        transfer_fee::{
+            instruction::initialize_transfer_fee_config, TransferFee, TransferFeeAmount,
+        },
+        BaseStateWithExtensions, ExtensionType, StateWithExtensions, StateWithExtensionsMut,
+    },
+    instruction::{initialize_account3, initialize_mint2},
+    state::{Account as TokenAccount, Mint},
 };
 
-use crate::native_account_data::NativeAccountData;
+use crate::{native_account_data::NativeAccountData, native_processor::do_process_instruction};
 
-pub fn create_mint(owner: &Pubkey, decimals: u8) -> NativeAccountData {
-    let mut account_data = NativeAccountData::new(Mint::LEN, spl_token::id());
-    let mint = Mint {
-        is_initialized: true,
-        mint_authority: COption::Some(*owner),
-        decimals,
-        ..Default::default()
-    };
-    Mint::pack(mint, &mut account_data.data[..]).unwrap();
-    account_data
+/// Token program, decimals and optional transfer fee of a trading token mint
+#[derive(Clone, Copy, Debug)]
+pub struct TokenSpec {
+    pub token_program: Pubkey,
+    pub decimals: u8,
+    /// Only supported by Token-2022 mints
+    pub transfer_fee: Option<TransferFee>,
+}
+
+pub fn create_mint(owner: &Pubkey, spec: &TokenSpec) -> NativeAccountData {
+    match spec.transfer_fee {
+        None => {
+            let mut account_data = NativeAccountData::new(Mint::LEN, spec.token_program);
+            let mint = Mint {
+                is_initialized: true,
+                mint_authority: COption::Some(*owner),
+                decimals: spec.decimals,
+                ..Default::default()
+            };
+            Mint::pack(mint, &mut account_data.data[..]).unwrap();
+            account_data
+        }
+        Some(transfer_fee) => {
+            assert_eq!(
+                spec.token_program,
+                spl_token_2022::id(),
+                "Transfer fees are only supported by Token-2022"
+            );
+            let mut account_data = NativeAccountData::new(
+                ExtensionType::get_account_len::<Mint>(&[ExtensionType::TransferFeeConfig]),
+                spl_token_2022::id(),
+            );
+            do_process_instruction(
+                initialize_transfer_fee_config(
+                    &spl_token_2022::id(),
+                    &account_data.key,
+                    Some(owner),
+                    Some(owner),
+                    transfer_fee.transfer_fee_basis_points.into(),
+                    transfer_fee.maximum_fee.into(),
+                )
+                .unwrap(),
+                &[account_data.as_account_info()],
+            )
+            .unwrap();
+            do_process_instruction(
+                initialize_mint2(
+                    &spl_token_2022::id(),
+                    &account_data.key,
+                    owner,
+                    None,
+                    spec.decimals,
+                )
+                .unwrap(),
+                &[account_data.as_account_info()],
+            )
+            .unwrap();
+            account_data
+        }
+    }
 }
 
 pub fn create_token_account(
@@ -40,22 +96,28 @@ pub fn create_token_account_with_address(
     owner: &Pubkey,
     amount: u64,
 ) -> NativeAccountData {
-    let mut mint = Mint::unpack(&mint_account.data).unwrap();
     let mut account_data = NativeAccountData::new_with_key(
         *address,
         get_token_account_space(token_program, mint_account),
         *token_program,
     );
-    let account = TokenAccount {
-        state: TokenAccountState::Initialized,
-        mint: mint_account.key,
-        owner: *owner,
-        amount,
-        ..Default::default()
-    };
-    mint.supply += amount;
-    Mint::pack(mint, &mut mint_account.data[..]).unwrap();
-    TokenAccount::pack(account, &mut account_data.data[..]).unwrap();
+    // initialize through the token program so Token-2022 accounts get the extensions required by the mint
+    do_process_instruction(
+        initialize_account3(token_program, address, &mint_account.key, owner).unwrap(),
+        &[
+            account_data.as_account_info(),
+            mint_account.as_account_info(),
+        ],
+    )
+    .unwrap();
+
+    let mut mint = StateWithExtensionsMut::<Mint>::unpack(&mut mint_account.data).unwrap();
+    mint.base.supply += amount;
+    mint.pack_base();
+    let mut account =
+        StateWithExtensionsMut::<TokenAccount>::unpack(&mut account_data.data).unwrap();
+    account.base.amount = amount;
+    account.pack_base();
     account_data
 }
 
@@ -76,8 +138,21 @@ pub fn get_token_account_space(token_program: &Pubkey, mint: &NativeAccountData)
 }
 
 pub fn get_token_balance(account_data: &NativeAccountData) -> u64 {
-    let account = TokenAccount::unpack(&account_data.data).unwrap();
-    account.amount
+    let account = StateWithExtensions::<TokenAccount>::unpack(&account_data.data).unwrap();
+    account.base.amount
+}
+
+pub fn get_mint_supply(mint_data: &NativeAccountData) -> u64 {
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data.data).unwrap();
+    mint.base.supply
+}
+
+/// Transfer fees withheld in the account, these are not part of the balance until harvested
+pub fn get_withheld_amount(account_data: &NativeAccountData) -> u64 {
+    let account = StateWithExtensions::<TokenAccount>::unpack(&account_data.data).unwrap();
+    account
+        .get_extension::<TransferFeeAmount>()
+        .map_or(0, |extension| extension.withheld_amount.into())
 }
 
 pub fn transfer(
@@ -85,11 +160,11 @@ pub fn transfer(
     to_account: &mut NativeAccountData,
     amount: u64,
 ) {
-    let mut from = TokenAccount::unpack(&from_account.data).unwrap();
-    let mut to = TokenAccount::unpack(&to_account.data).unwrap();
-    assert_eq!(from.mint, to.mint);
-    from.amount -= amount;
-    to.amount += amount;
-    TokenAccount::pack(from, &mut from_account.data[..]).unwrap();
-    TokenAccount::pack(to, &mut to_account.data[..]).unwrap();
+    let mut from = StateWithExtensionsMut::<TokenAccount>::unpack(&mut from_account.data).unwrap();
+    let mut to = StateWithExtensionsMut::<TokenAccount>::unpack(&mut to_account.data).unwrap();
+    assert_eq!(from.base.mint, to.base.mint);
+    from.base.amount -= amount;
+    to.base.amount += amount;
+    from.pack_base();
+    to.pack_base();
 }
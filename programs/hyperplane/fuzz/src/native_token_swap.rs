@@ -1,9 +1,9 @@
 //! Helpers for working with swaps in a fuzzing environment
 
 use hyperplane::{
-    curve::{base::SwapCurve, fees::Fees},
+    curve::{base::SwapCurve, calculator::MIN_LIQUIDITY, fees::Fees},
     instructions::model::CurveParameters,
-    ix::{self, Deposit, Initialize, Swap, Withdraw, WithdrawFees},
+    ix::{self, Deposit, Initialize, Swap, UpdatePoolConfig, Withdraw, WithdrawFees},
     state::{Curve, SwapPool},
     utils::seeds,
     InitialSupply,
@@ -16,8 +16,10 @@ use solana_sdk::account::create_account_for_test;
 use spl_token_2022::instruction::approve;
 
 use crate::{
-    native_account_data::NativeAccountData, native_processor::do_process_instruction, native_token,
-    native_token::get_token_account_space,
+    native_account_data::NativeAccountData,
+    native_processor::do_process_instruction,
+    native_token,
+    native_token::{get_token_account_space, TokenSpec},
 };
 
 pub struct NativeTokenSwap {
@@ -33,6 +35,7 @@ pub struct NativeTokenSwap {
     pub admin_token_a_ata: NativeAccountData,
     pub admin_token_b_ata: NativeAccountData,
     pub admin_pool_token_ata: NativeAccountData,
+    pub min_liquidity_vault: NativeAccountData,
     pub token_a_vault_account: NativeAccountData,
     pub token_a_mint_account: NativeAccountData,
     pub token_b_vault_account: NativeAccountData,
@@ -66,25 +69,16 @@ impl NativeTokenSwap {
     pub fn new(
         fees: Fees,
         curve_params: CurveParameters,
+        token_a: TokenSpec,
+        token_b: TokenSpec,
         token_a_amount: u64,
         token_b_amount: u64,
     ) -> Self {
         let mut admin_authority = NativeAccountData::new(0, system_program::id());
         admin_authority.is_signer = true;
 
-        let (token_a_decimals, token_b_decimals) = match curve_params {
-            CurveParameters::Stable {
-                token_a_decimals,
-                token_b_decimals,
-                ..
-            } => (token_a_decimals, token_b_decimals),
-            _ => (6, 6),
-        };
-
-        let mut token_a_mint_account =
-            native_token::create_mint(&admin_authority.key, token_a_decimals);
-        let mut token_b_mint_account =
-            native_token::create_mint(&admin_authority.key, token_b_decimals);
+        let mut token_a_mint_account = native_token::create_mint(&admin_authority.key, &token_a);
+        let mut token_b_mint_account = native_token::create_mint(&admin_authority.key, &token_b);
 
         let mut pool_account = NativeAccountData::new(SwapPool::LEN, hyperplane::id());
         let seeds::pda::InitPoolPdas {
@@ -95,6 +89,7 @@ impl NativeTokenSwap {
             pool_token_mint,
             token_a_fees_vault,
             token_b_fees_vault,
+            min_liquidity_vault,
         } = seeds::pda::init_pool_pdas(
             &pool_account.key,
             &token_a_mint_account.key,
@@ -107,8 +102,8 @@ impl NativeTokenSwap {
         let mut system_program_account = create_program_account(system_program::id());
         let mut rent = create_sysvar_account(&Rent::default());
         let mut pool_token_program_account = create_program_account(spl_token_2022::id());
-        let mut token_b_program_account = create_program_account(spl_token::id());
-        let mut token_a_program_account = create_program_account(spl_token::id());
+        let mut token_a_program_account = create_program_account(token_a.token_program);
+        let mut token_b_program_account = create_program_account(token_b.token_program);
         let mut pool_token_mint_account = NativeAccountData::new_with_key(
             pool_token_mint,
             spl_token_2022::state::Mint::LEN,
@@ -117,6 +112,11 @@ impl NativeTokenSwap {
 
         let mut admin_authority_pool_token_ata =
             NativeAccountData::new(spl_token_2022::state::Account::LEN, spl_token_2022::id());
+        let mut min_liquidity_vault_account = NativeAccountData::new_with_key(
+            min_liquidity_vault,
+            spl_token_2022::state::Account::LEN,
+            spl_token_2022::id(),
+        );
 
         let mut token_a_vault_account = NativeAccountData::new_with_key(
             token_a_vault,
@@ -167,6 +167,7 @@ impl NativeTokenSwap {
             &admin_authority_token_a_ata_account.key,
             &admin_authority_token_b_ata_account.key,
             &admin_authority_pool_token_ata.key,
+            &min_liquidity_vault_account.key,
             &spl_token_2022::id(),
             &token_a_program_account.key,
             &token_b_program_account.key,
@@ -174,6 +175,7 @@ impl NativeTokenSwap {
                 fees,
                 curve_parameters: curve_params.clone().into(),
                 initial_supply: InitialSupply::new(token_a_amount, token_b_amount),
+                min_liquidity: MIN_LIQUIDITY as u64,
             },
         )
         .unwrap();
@@ -195,6 +197,7 @@ impl NativeTokenSwap {
                 admin_authority_token_a_ata_account.as_account_info(),
                 admin_authority_token_b_ata_account.as_account_info(),
                 admin_authority_pool_token_ata.as_account_info(),
+                min_liquidity_vault_account.as_account_info(),
                 system_program_account.as_account_info(),
                 rent.as_account_info(),
                 pool_token_program_account.as_account_info(),
@@ -217,6 +220,7 @@ impl NativeTokenSwap {
             admin_token_a_ata: admin_authority_token_a_ata_account,
             admin_token_b_ata: admin_authority_token_b_ata_account,
             admin_pool_token_ata: admin_authority_pool_token_ata,
+            min_liquidity_vault: min_liquidity_vault_account,
             token_a_vault_account,
             token_a_mint_account,
             token_b_vault_account,
@@ -293,8 +297,8 @@ impl NativeTokenSwap {
             &user_token_a_account.key,
             &user_token_b_account.key,
             Some(&self.admin_token_a_ata.key),
-            &spl_token::id(),
-            &spl_token::id(),
+            &self.token_a_program_account.key,
+            &self.token_b_program_account.key,
             instruction,
         )
         .unwrap();
@@ -360,8 +364,8 @@ impl NativeTokenSwap {
             &user_token_b_account.key,
             &user_token_a_account.key,
             Some(&self.admin_token_b_ata.key),
-            &spl_token::id(),
-            &spl_token::id(),
+            &self.token_b_program_account.key,
+            &self.token_a_program_account.key,
             instruction,
         )
         .unwrap();
@@ -392,7 +396,7 @@ impl NativeTokenSwap {
         user_token_a_account: &mut NativeAccountData,
         user_token_b_account: &mut NativeAccountData,
         user_pool_token_account: &mut NativeAccountData,
-        mut instruction: Deposit,
+        instruction: Deposit,
     ) -> ProgramResult {
         let mut user_transfer_account = NativeAccountData::new(0, system_program::id());
         user_transfer_account.is_signer = true;
@@ -432,12 +436,6 @@ impl NativeTokenSwap {
         )
         .unwrap();
 
-        // special logic: if we only deposit 1 pool token, we can't withdraw it
-        // because we incur a withdrawal fee, so we hack it to not be 1
-        if instruction.pool_token_amount == 1 {
-            instruction.pool_token_amount = 2;
-        }
-
         let deposit_instruction = ix::deposit(
             &hyperplane::id(),
             &user_transfer_account.key,
@@ -453,8 +451,8 @@ impl NativeTokenSwap {
             &user_token_b_account.key,
             &user_pool_token_account.key,
             &self.pool_token_program_account.key,
-            &spl_token::id(),
-            &spl_token::id(),
+            &self.token_a_program_account.key,
+            &self.token_b_program_account.key,
             instruction,
         )
         .unwrap();
@@ -486,16 +484,10 @@ impl NativeTokenSwap {
         user_pool_token_account: &mut NativeAccountData,
         user_token_a_account: &mut NativeAccountData,
         user_token_b_account: &mut NativeAccountData,
-        mut instruction: Withdraw,
+        instruction: Withdraw,
     ) -> ProgramResult {
         let mut user_transfer_account = NativeAccountData::new(0, system_program::id());
         user_transfer_account.is_signer = true;
-        let pool_token_amount = native_token::get_token_balance(user_pool_token_account);
-        // special logic to avoid withdrawing down to 1 pool token, which
-        // eventually causes an error on withdrawing all
-        if pool_token_amount.saturating_sub(instruction.pool_token_amount) == 1 {
-            instruction.pool_token_amount = pool_token_amount;
-        }
         do_process_instruction(
             approve(
                 &self.pool_token_program_account.key,
@@ -531,8 +523,8 @@ impl NativeTokenSwap {
             &user_token_b_account.key,
             &user_pool_token_account.key,
             &self.pool_token_program_account.key,
-            &spl_token::id(),
-            &spl_token::id(),
+            &self.token_a_program_account.key,
+            &self.token_b_program_account.key,
             instruction,
         )
         .unwrap();
@@ -644,4 +636,22 @@ impl NativeTokenSwap {
             ],
         )
     }
+
+    pub fn update_pool_config(&mut self, instruction: UpdatePoolConfig) -> ProgramResult {
+        let update_instruction = ix::update_pool_config(
+            &hyperplane::id(),
+            &self.admin.key,
+            &self.pool_account.key,
+            instruction,
+        )
+        .unwrap();
+
+        do_process_instruction(
+            update_instruction,
+            &[
+                self.admin.as_account_info(),
+                self.pool_account.as_account_info(),
+            ],
+        )
+    }
 }
@@ -0,0 +1,470 @@
+//! Turns a failing fuzzed sequence into a `solana-program-test` test, to debug it against the
+//! deployed program with the test harness in `programs/hyperplane/tests`
+
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeSet},
+    fmt::Write,
+    hash::{Hash, Hasher},
+    path::PathBuf,
+};
+
+use hyperplane::curve::calculator::{AorB, MIN_LIQUIDITY};
+
+use crate::{
+    fuzz_data::{
+        fees, FuzzData, FuzzInstruction, INITIAL_SWAP_TOKEN_A_AMOUNT, INITIAL_SWAP_TOKEN_B_AMOUNT,
+        INITIAL_USER_TOKEN_A_AMOUNT, INITIAL_USER_TOKEN_B_AMOUNT,
+    },
+    native_token::TokenSpec,
+    runner::Failure,
+};
+
+/// Directory the reproductions are written to, relative to the working directory
+const REPRO_DIR_ENV: &str = "HYPERPLANE_FUZZ_REPRO_DIR";
+const DEFAULT_REPRO_DIR: &str = "repro";
+
+/// Write the reproduction test of `failure` and return its path
+pub fn write(fuzz_data: &FuzzData, failure: &Failure) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    format!("{fuzz_data:?}").hash(&mut hasher);
+    let name = format!("fuzz_repro_{:016x}", hasher.finish());
+
+    let dir = PathBuf::from(
+        std::env::var(REPRO_DIR_ENV).unwrap_or_else(|_| DEFAULT_REPRO_DIR.to_string()),
+    );
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(format!("tests_{name}.rs"));
+    std::fs::write(&path, render(&name, fuzz_data, failure)).unwrap();
+    path
+}
+
+/// Render the test. Steps that succeeded in the fuzzer are unwrapped and steps that failed are
+/// expected to fail, the pool state is checked after every step.
+pub fn render(name: &str, fuzz_data: &FuzzData, failure: &Failure) -> String {
+    let mut test = String::new();
+    let mut line = |indent: usize, text: &str| {
+        writeln!(test, "{:indent$}{}", "", text, indent = indent * 4).unwrap();
+    };
+
+    line(
+        0,
+        "//! Reproduction of a fuzzer failure, move to `programs/hyperplane/tests` to run",
+    );
+    line(0, "//!");
+    for message in failure.to_string().lines() {
+        line(0, &format!("//! {message}"));
+    }
+    line(0, "#![allow(unused_imports)]");
+    // keep the test as rendered, so the checked-in example can be compared with the renderer
+    line(0, "#![cfg_attr(rustfmt, rustfmt::skip)]");
+    line(0, "");
+    line(0, "mod common;");
+    line(0, "");
+    line(
+        0,
+        "use anchor_spl::{token::spl_token, token_2022::spl_token_2022};",
+    );
+    line(0, "use common::{client, runner};");
+    line(0, "use hyperplane::{");
+    line(1, "curve::{");
+    line(2, "base::SwapCurve,");
+    line(2, "calculator::{AorB, TradeDirection},");
+    line(2, "fees::Fees,");
+    line(1, "},");
+    line(
+        1,
+        "ix::{Deposit, Initialize, Swap, UpdatePoolConfig, Withdraw, WithdrawFees},",
+    );
+    line(1, "model::CurveParameters,");
+    line(1, "state::{UpdatePoolConfigMode, UpdatePoolConfigValue},");
+    line(1, "InitialSupply,");
+    line(0, "};");
+    line(0, "use solana_program_test::tokio;");
+    line(0, "use solana_sdk::{pubkey::Pubkey, signer::Signer};");
+    line(0, "use spl_math::precise_number::PreciseNumber;");
+    line(0, "");
+    line(0, "use crate::common::{");
+    line(1, "fixtures::Sol,");
+    line(1, "setup, token_operations,");
+    line(
+        1,
+        "types::{PoolUserAccounts, SwapPairSpec, SwapPoolAccounts, TestContext, TokenSpec},",
+    );
+    line(0, "};");
+    line(0, "");
+    line(0, "#[tokio::test]");
+    line(0, &format!("pub async fn test_{name}() {{"));
+    line(1, "let program = runner::program(&[]);");
+    line(1, "let mut ctx = runner::start(program).await;");
+    line(0, "");
+    line(
+        1,
+        &format!(
+            "let curve_parameters = CurveParameters::{:?};",
+            fuzz_data.curve_parameters()
+        ),
+    );
+    line(
+        1,
+        "let swap_curve = SwapCurve::new_from_params(curve_parameters.clone()).unwrap();",
+    );
+    line(
+        1,
+        &format!(
+            "let initial_supply = InitialSupply::new({INITIAL_SWAP_TOKEN_A_AMOUNT}, {INITIAL_SWAP_TOKEN_B_AMOUNT});"
+        ),
+    );
+    line(1, "let pool = setup::new_pool_accs(");
+    line(2, "&mut ctx,");
+    line(2, "SwapPairSpec::new(");
+    line(
+        3,
+        &format!("{},", render_token_spec(&fuzz_data.token_a_spec())),
+    );
+    line(
+        3,
+        &format!("{},", render_token_spec(&fuzz_data.token_b_spec())),
+    );
+    line(2, "),");
+    line(2, "&initial_supply,");
+    line(1, ")");
+    line(1, ".await;");
+    line(1, "client::initialize_pool(");
+    line(2, "&mut ctx,");
+    line(2, "&pool,");
+    line(2, "Initialize {");
+    line(3, &format!("fees: {:?},", fees()));
+    line(3, "initial_supply,");
+    line(3, "curve_parameters: curve_parameters.into(),");
+    line(3, &format!("min_liquidity: {MIN_LIQUIDITY},"));
+    line(2, "},");
+    line(1, ")");
+    line(1, ".await");
+    line(1, ".unwrap();");
+    line(0, "");
+
+    // a single user owns all the token accounts of the fuzzer
+    line(
+        1,
+        "let user = setup::new_keypair(&mut ctx, Sol::one()).await;",
+    );
+    let token_a_ids: BTreeSet<_> = fuzz_data
+        .instructions
+        .iter()
+        .filter_map(FuzzInstruction::token_a_id)
+        .collect();
+    let token_b_ids: BTreeSet<_> = fuzz_data
+        .instructions
+        .iter()
+        .filter_map(FuzzInstruction::token_b_id)
+        .collect();
+    let pool_token_ids: BTreeSet<_> = fuzz_data
+        .instructions
+        .iter()
+        .filter_map(FuzzInstruction::pool_token_id)
+        .collect();
+    for id in token_a_ids {
+        line(1, &format!("let token_a_{id} = token_operations::create_and_mint_to_token_account(&mut ctx, &pool.token_a_token_program, &user.pubkey(), &pool.token_a_mint, {INITIAL_USER_TOKEN_A_AMOUNT}).await;"));
+    }
+    for id in token_b_ids {
+        line(1, &format!("let token_b_{id} = token_operations::create_and_mint_to_token_account(&mut ctx, &pool.token_b_token_program, &user.pubkey(), &pool.token_b_mint, {INITIAL_USER_TOKEN_B_AMOUNT}).await;"));
+    }
+    for id in pool_token_ids {
+        line(1, &format!("let pool_token_{id} = token_operations::create_token_account(&mut ctx, &pool.pool_token_program, &pool.pool_token_mint, &user.pubkey()).await.unwrap();"));
+    }
+    // the fuzzer pays the host fees of every swap to the admin
+    line(
+        1,
+        "let host_fees = PoolUserAccounts::from(pool.admin.clone());",
+    );
+    line(0, "");
+    line(
+        1,
+        "let mut state = State::load(&mut ctx, &pool, &swap_curve).await;",
+    );
+
+    for (i, step) in failure.steps.iter().enumerate() {
+        line(0, "");
+        line(1, &format!("// {i}: {:?}", step.instruction));
+        let (call, withdraws_fees) = render_instruction(&step.instruction);
+        line(1, &call);
+        line(2, ".await");
+        line(
+            2,
+            if step.succeeded {
+                ".unwrap();"
+            } else {
+                ".unwrap_err();"
+            },
+        );
+        line(1, "let before = state;");
+        line(
+            1,
+            "state = State::load(&mut ctx, &pool, &swap_curve).await;",
+        );
+        line(1, &format!("state.check(&before, {withdraws_fees});"));
+    }
+    line(0, "}");
+    line(0, "");
+
+    line(
+        0,
+        "/// Pool state the fuzzer checks its invariants against. Vault balances, the value",
+    );
+    line(0, "/// withdrawn per pool token account and the final withdrawal are only checked by the fuzzer.");
+    line(0, "struct State {");
+    line(1, "value_per_pool_token: PreciseNumber,");
+    line(1, "token_a_fees: u64,");
+    line(1, "token_b_fees: u64,");
+    line(0, "}");
+    line(0, "");
+    line(0, "impl State {");
+    line(1, "async fn load(ctx: &mut TestContext, pool: &SwapPoolAccounts, swap_curve: &SwapCurve) -> Self {");
+    line(
+        2,
+        "let token_a_amount = token_operations::balance(ctx, &pool.token_a_vault).await;",
+    );
+    line(
+        2,
+        "let token_b_amount = token_operations::balance(ctx, &pool.token_b_vault).await;",
+    );
+    line(
+        2,
+        "let pool_token_supply = token_operations::supply(ctx, &pool.pool_token_mint).await;",
+    );
+    line(2, "Self {");
+    line(3, "value_per_pool_token: swap_curve");
+    line(4, ".calculator");
+    line(
+        4,
+        ".normalized_value(token_a_amount.into(), token_b_amount.into())",
+    );
+    line(4, ".unwrap()");
+    line(
+        4,
+        ".checked_div(&PreciseNumber::new(pool_token_supply.into()).unwrap())",
+    );
+    line(4, ".unwrap(),");
+    line(
+        3,
+        "token_a_fees: token_operations::balance(ctx, &pool.token_a_fees_vault).await,",
+    );
+    line(
+        3,
+        "token_b_fees: token_operations::balance(ctx, &pool.token_b_fees_vault).await,",
+    );
+    line(2, "}");
+    line(1, "}");
+    line(0, "");
+    line(1, "fn check(&self, before: &State, withdraws_fees: bool) {");
+    line(2, "assert!(");
+    line(3, "before");
+    line(4, ".value_per_pool_token");
+    line(4, ".less_than_or_equal(&self.value_per_pool_token),");
+    line(3, "\"value per pool token decreased\"");
+    line(2, ");");
+    line(2, "if !withdraws_fees {");
+    line(
+        3,
+        "assert!(self.token_a_fees >= before.token_a_fees, \"token a fees vault decreased\");",
+    );
+    line(
+        3,
+        "assert!(self.token_b_fees >= before.token_b_fees, \"token b fees vault decreased\");",
+    );
+    line(2, "}");
+    line(1, "}");
+    line(0, "}");
+
+    test
+}
+
+fn render_token_spec(spec: &TokenSpec) -> String {
+    match spec.transfer_fee {
+        None if spec.token_program == spl_token::id() => {
+            format!("TokenSpec::new({}, 0, spl_token::id())", spec.decimals)
+        }
+        None => format!("TokenSpec::new({}, 0, spl_token_2022::id())", spec.decimals),
+        Some(transfer_fee) => format!(
+            "TokenSpec::new({}, {}, spl_token_2022::id()).with_maximum_transfer_fee({})",
+            spec.decimals,
+            u16::from(transfer_fee.transfer_fee_basis_points),
+            u64::from(transfer_fee.maximum_fee)
+        ),
+    }
+}
+
+/// Client call of the instruction and whether it withdraws fees
+fn render_instruction(instruction: &FuzzInstruction) -> (String, bool) {
+    match instruction {
+        FuzzInstruction::Swap {
+            token_a_id,
+            token_b_id,
+            trade_direction,
+            instruction,
+        } => (
+            // swaps don't use the pool token account
+            format!(
+                "client::swap_with_host_fees(&mut ctx, &pool, &PoolUserAccounts::new(user.clone(), token_a_{token_a_id}, token_b_{token_b_id}, Pubkey::default()), Some(&host_fees), TradeDirection::{trade_direction:?}, Swap::new({}, {}))",
+                instruction.amount_in, instruction.minimum_amount_out
+            ),
+            false,
+        ),
+        FuzzInstruction::Deposit {
+            token_a_id,
+            token_b_id,
+            pool_token_id,
+            instruction,
+        } => (
+            format!(
+                "client::deposit(&mut ctx, &pool, &PoolUserAccounts::new(user.clone(), token_a_{token_a_id}, token_b_{token_b_id}, pool_token_{pool_token_id}), Deposit::new({}, {}, {}))",
+                instruction.pool_token_amount,
+                instruction.maximum_token_a_amount,
+                instruction.maximum_token_b_amount
+            ),
+            false,
+        ),
+        FuzzInstruction::Withdraw {
+            token_a_id,
+            token_b_id,
+            pool_token_id,
+            instruction,
+        } => (
+            format!(
+                "client::withdraw(&mut ctx, &pool, &PoolUserAccounts::new(user.clone(), token_a_{token_a_id}, token_b_{token_b_id}, pool_token_{pool_token_id}), Withdraw::new({}, {}, {}))",
+                instruction.pool_token_amount,
+                instruction.minimum_token_a_amount,
+                instruction.minimum_token_b_amount
+            ),
+            false,
+        ),
+        FuzzInstruction::WithdrawFees { token, instruction } => (
+            format!(
+                "client::withdraw_fees(&mut ctx, &pool, AorB::{}, WithdrawFees::new({}))",
+                match token {
+                    AorB::A => "A",
+                    AorB::B => "B",
+                },
+                instruction.requested_token_amount
+            ),
+            true,
+        ),
+        FuzzInstruction::UpdatePoolConfig { withdrawals_only } => (
+            format!(
+                "client::update_pool_config(&mut ctx, &pool, UpdatePoolConfig::new(UpdatePoolConfigMode::WithdrawalsOnly, UpdatePoolConfigValue::Bool({withdrawals_only})))"
+            ),
+            false,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperplane::{
+        curve::{base::CurveType, calculator::TradeDirection},
+        ix::{Deposit, Swap, WithdrawFees},
+    };
+
+    use super::*;
+    use crate::{
+        fuzz_data::{FuzzToken, FuzzTokenProgram},
+        invariants::{Invariant, Violation},
+        runner::Step,
+    };
+
+    /// Rendered from `fuzz_data` and `failure` below, compiled and run with the program tests
+    const EXAMPLE: &str = include_str!("../../tests/tests_fuzz_repro_example.rs");
+
+    fn fuzz_data(instructions: Vec<FuzzInstruction>) -> FuzzData {
+        FuzzData {
+            curve_type: CurveType::ConstantProduct,
+            amp: 0,
+            token_a: FuzzToken {
+                token_program: FuzzTokenProgram::Token,
+                decimals: 1,
+            },
+            token_b: FuzzToken {
+                token_program: FuzzTokenProgram::Token2022,
+                decimals: 3,
+            },
+            instructions,
+        }
+    }
+
+    fn failure(steps: Vec<Step>) -> Failure {
+        Failure {
+            instruction_index: Some(steps.len() - 1),
+            violation: Violation::new(
+                Invariant::UnexpectedError,
+                "example of a rendered reproduction".to_string(),
+            ),
+            steps,
+        }
+    }
+
+    fn swap(trade_direction: TradeDirection, minimum_amount_out: u64) -> FuzzInstruction {
+        FuzzInstruction::Swap {
+            token_a_id: 0,
+            token_b_id: 0,
+            trade_direction,
+            instruction: Swap::new(100_000, minimum_amount_out),
+        }
+    }
+
+    fn step(instruction: &FuzzInstruction, succeeded: bool) -> Step {
+        Step {
+            instruction: instruction.clone(),
+            succeeded,
+        }
+    }
+
+    #[test]
+    fn render_matches_example() {
+        let instructions = vec![
+            swap(TradeDirection::AtoB, 0),
+            swap(TradeDirection::BtoA, u64::MAX),
+        ];
+        let failure = failure(vec![
+            step(&instructions[0], true),
+            step(&instructions[1], false),
+        ]);
+
+        let test = render("fuzz_repro_example", &fuzz_data(instructions), &failure);
+
+        assert_eq!(test, EXAMPLE);
+    }
+
+    #[test]
+    fn render_creates_accounts_and_checks_fees_withdrawal() {
+        let instructions = vec![
+            FuzzInstruction::Deposit {
+                token_a_id: 1,
+                token_b_id: 2,
+                pool_token_id: 3,
+                instruction: Deposit::new(1_000, u64::MAX, u64::MAX),
+            },
+            FuzzInstruction::WithdrawFees {
+                token: AorB::B,
+                instruction: WithdrawFees::new(5),
+            },
+        ];
+        let failure = failure(vec![
+            step(&instructions[0], true),
+            step(&instructions[1], true),
+        ]);
+
+        let test = render("fuzz_repro", &fuzz_data(instructions), &failure);
+
+        assert!(test.contains("pub async fn test_fuzz_repro() {"));
+        assert!(test.contains("let token_a_1 = "));
+        assert!(test.contains("let token_b_2 = "));
+        assert!(test.contains("let pool_token_3 = "));
+        assert!(test.contains("Deposit::new(1000, 18446744073709551615, 18446744073709551615))"));
+        assert!(
+            test.contains("client::withdraw_fees(&mut ctx, &pool, AorB::B, WithdrawFees::new(5))")
+        );
+        assert_eq!(test.matches("state.check(&before, false);").count(), 1);
+        assert_eq!(test.matches("state.check(&before, true);").count(), 1);
+        assert!(!test.contains(".unwrap_err();"));
+    }
+}
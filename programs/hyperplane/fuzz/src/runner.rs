@@ -0,0 +1,534 @@
+//! Runs fuzzed instruction sequences against a native pool, checking the invariants after every
+//! instruction
+
+use std::{collections::HashMap, fmt};
+
+use hyperplane::{
+    curve::calculator::{AorB, TradeDirection},
+    error::SwapError,
+    ix::{UpdatePoolConfig, WithdrawFees},
+    state::{UpdatePoolConfigMode, UpdatePoolConfigValue},
+};
+use solana_program::{entrypoint::ProgramResult, program_error::ProgramError};
+use spl_token::error::TokenError;
+
+use crate::{
+    fuzz_data::{
+        fees, AccountId, FuzzData, FuzzInstruction, INITIAL_SWAP_TOKEN_A_AMOUNT,
+        INITIAL_SWAP_TOKEN_B_AMOUNT, INITIAL_USER_TOKEN_A_AMOUNT, INITIAL_USER_TOKEN_B_AMOUNT,
+    },
+    invariants::{Invariant, InvariantChecker, Violation},
+    native_account_data::NativeAccountData,
+    native_token::{get_token_balance, get_withheld_amount, transfer},
+    native_token_swap::NativeTokenSwap,
+    repro,
+};
+
+/// User accounts referenced by the fuzzed instructions
+pub struct FuzzAccounts {
+    pub token_a: HashMap<AccountId, NativeAccountData>,
+    pub token_b: HashMap<AccountId, NativeAccountData>,
+    pub pool: HashMap<AccountId, NativeAccountData>,
+    /// Admin accounts receiving the withdrawn fees
+    pub fees_token_a: NativeAccountData,
+    pub fees_token_b: NativeAccountData,
+}
+
+impl FuzzAccounts {
+    fn new(token_swap: &mut NativeTokenSwap, instructions: &[FuzzInstruction]) -> Self {
+        let mut token_a = HashMap::new();
+        let mut token_b = HashMap::new();
+        let mut pool = HashMap::new();
+        for instruction in instructions {
+            if let Some(token_a_id) = instruction.token_a_id() {
+                token_a.entry(token_a_id).or_insert_with(|| {
+                    token_swap.create_token_a_account(INITIAL_USER_TOKEN_A_AMOUNT)
+                });
+            }
+            if let Some(token_b_id) = instruction.token_b_id() {
+                token_b.entry(token_b_id).or_insert_with(|| {
+                    token_swap.create_token_b_account(INITIAL_USER_TOKEN_B_AMOUNT)
+                });
+            }
+            if let Some(pool_token_id) = instruction.pool_token_id() {
+                pool.entry(pool_token_id)
+                    .or_insert_with(|| token_swap.create_pool_account());
+            }
+        }
+        Self {
+            token_a,
+            token_b,
+            pool,
+            fees_token_a: token_swap.create_token_a_account(0),
+            fees_token_b: token_swap.create_token_b_account(0),
+        }
+    }
+}
+
+/// Instruction as sent to the pool, after adjusting the fuzzed amounts
+#[derive(Clone, Debug)]
+pub struct Step {
+    pub instruction: FuzzInstruction,
+    pub succeeded: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct Failure {
+    /// Index of the instruction that broke an invariant, `None` if the final withdrawal of all
+    /// liquidity and fees did
+    pub instruction_index: Option<usize>,
+    pub violation: Violation,
+    /// Steps run up to the failure
+    pub steps: Vec<Step>,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.instruction_index {
+            Some(index) => write!(
+                f,
+                "{} after instruction {} - {:?}",
+                self.violation,
+                index,
+                self.steps.last().map(|step| &step.instruction)
+            ),
+            None => write!(f, "{} after withdrawing everything", self.violation),
+        }
+    }
+}
+
+/// Entry point of the fuzz targets: run the fuzzed instructions and on failure, write a minimized
+/// reproduction test and panic
+pub fn run_fuzz(fuzz_data: FuzzData) {
+    if let Err(failure) = run(&fuzz_data) {
+        let (fuzz_data, failure) = minimize(fuzz_data, failure);
+        let path = repro::write(&fuzz_data, &failure);
+        panic!(
+            "Invariant violated: {}\nReproduction written to {}",
+            failure,
+            path.display()
+        );
+    }
+}
+
+/// Run the fuzzed instructions, then withdraw all liquidity and fees
+pub fn run(fuzz_data: &FuzzData) -> Result<(), Failure> {
+    let mut token_swap = NativeTokenSwap::new(
+        fees(),
+        fuzz_data.curve_parameters(),
+        fuzz_data.token_a_spec(),
+        fuzz_data.token_b_spec(),
+        INITIAL_SWAP_TOKEN_A_AMOUNT,
+        INITIAL_SWAP_TOKEN_B_AMOUNT,
+    );
+    let mut accounts = FuzzAccounts::new(&mut token_swap, &fuzz_data.instructions);
+
+    // to ensure that we never create or remove base tokens
+    let before_total_token_a =
+        INITIAL_SWAP_TOKEN_A_AMOUNT + accounts.token_a.len() as u64 * INITIAL_USER_TOKEN_A_AMOUNT;
+    let before_total_token_b =
+        INITIAL_SWAP_TOKEN_B_AMOUNT + accounts.token_b.len() as u64 * INITIAL_USER_TOKEN_B_AMOUNT;
+
+    let mut checker = InvariantChecker::new(&token_swap, &accounts);
+    let mut steps = vec![];
+    for (index, fuzz_instruction) in fuzz_data.instructions.iter().enumerate() {
+        let instruction = adjust_instruction(fuzz_instruction, &accounts);
+        let result = run_fuzz_instruction(&instruction, &mut token_swap, &mut accounts);
+        steps.push(Step {
+            instruction: instruction.clone(),
+            succeeded: result.is_ok(),
+        });
+        let checked = match result {
+            Err(e) if !is_expected_error(&e) => Err(Violation::new(
+                Invariant::UnexpectedError,
+                format!("Fuzzer returned error - {e:?}"),
+            )),
+            result => checker.check(&instruction, result.is_ok(), &token_swap, &accounts),
+        };
+        checked.map_err(|violation| Failure {
+            instruction_index: Some(index),
+            violation,
+            steps: steps.clone(),
+        })?;
+    }
+
+    let fail = |invariant, message: String| Failure {
+        instruction_index: None,
+        violation: Violation::new(invariant, message),
+        steps: steps.clone(),
+    };
+
+    // check total token a and b amounts, including the transfer fees withheld in every account
+    let after_total_token_a = get_total_amount(accounts.token_a.values().chain([
+        &token_swap.token_a_vault_account,
+        &token_swap.token_a_fees_vault_account,
+        &accounts.fees_token_a,
+        &token_swap.admin_token_a_ata, // admin takes host fees
+    ]));
+    let after_total_token_b = get_total_amount(accounts.token_b.values().chain([
+        &token_swap.token_b_vault_account,
+        &token_swap.token_b_fees_vault_account,
+        &accounts.fees_token_b,
+        &token_swap.admin_token_b_ata, // admin takes host fees
+    ]));
+    check_token_conservation(
+        before_total_token_a,
+        after_total_token_a,
+        before_total_token_b,
+        after_total_token_b,
+    )
+    .map_err(|message| fail(Invariant::TokenConservation, message))?;
+
+    // Final check to make sure that withdrawing everything works
+    //
+    // 1) transfer all pool tokens to the admin pool token account
+    let mut admin_pool_token_ata = token_swap.admin_pool_token_ata.clone();
+    for pool_account in accounts.pool.values_mut() {
+        let pool_token_amount = get_token_balance(pool_account);
+        if pool_token_amount > 0 {
+            transfer(pool_account, &mut admin_pool_token_ata, pool_token_amount);
+        }
+    }
+
+    // 2) Now burn all pool tokens from the admin pool token account
+    // This will produce withdraw fees which we will withdraw next
+    let mut withdrawn_token_a_account = token_swap.create_token_a_account(0);
+    let mut withdrawn_token_b_account = token_swap.create_token_b_account(0);
+    token_swap
+        .withdraw_all(
+            &mut admin_pool_token_ata,
+            &mut withdrawn_token_a_account,
+            &mut withdrawn_token_b_account,
+        )
+        .map_err(|e| fail(Invariant::WithdrawAll, format!("withdraw failed {e:?}")))?;
+
+    // 3) withdraw all fees to the admin atas
+    let token_a_fees = get_token_balance(&token_swap.token_a_fees_vault_account);
+    if token_a_fees > 0 {
+        token_swap
+            .withdraw_a_fees(
+                &mut withdrawn_token_a_account,
+                WithdrawFees {
+                    requested_token_amount: token_a_fees,
+                },
+            )
+            .map_err(|e| {
+                fail(
+                    Invariant::WithdrawAll,
+                    format!("withdraw_fees (token a) failed {e:?}"),
+                )
+            })?;
+    }
+    let token_b_fees = get_token_balance(&token_swap.token_b_fees_vault_account);
+    if token_b_fees > 0 {
+        token_swap
+            .withdraw_b_fees(
+                &mut withdrawn_token_b_account,
+                WithdrawFees {
+                    requested_token_amount: token_b_fees,
+                },
+            )
+            .map_err(|e| {
+                fail(
+                    Invariant::WithdrawAll,
+                    format!("withdraw_fees (token b) failed {e:?}"),
+                )
+            })?;
+    }
+
+    // We should have all the tokens we started with, except for the share of the minimum liquidity left in the vaults
+    let after_total_token_a = get_total_amount(accounts.token_a.values().chain([
+        &withdrawn_token_a_account,
+        &token_swap.token_a_vault_account,
+        &token_swap.token_a_fees_vault_account,
+        &accounts.fees_token_a,
+        &token_swap.admin_token_a_ata, // admin takes host fees
+    ]));
+    // todo - Constant price curves don't return all tokens when everything is burned - this seems like a bug and needs investigating further
+    let after_total_token_b = get_total_amount(accounts.token_b.values().chain([
+        &withdrawn_token_b_account,
+        &token_swap.token_b_vault_account,
+        &token_swap.token_b_fees_vault_account,
+        &accounts.fees_token_b,
+        &token_swap.admin_token_b_ata, // admin takes host fees
+    ]));
+    check_token_conservation(
+        before_total_token_a,
+        after_total_token_a,
+        before_total_token_b,
+        after_total_token_b,
+    )
+    .map_err(|message| fail(Invariant::TokenConservation, message))
+}
+
+/// Shrink a failing sequence: drop the instructions after the failure, then every instruction
+/// that is not needed to break the same invariant
+pub fn minimize(fuzz_data: FuzzData, failure: Failure) -> (FuzzData, Failure) {
+    minimize_with(fuzz_data, failure, run)
+}
+
+fn minimize_with(
+    mut fuzz_data: FuzzData,
+    mut failure: Failure,
+    run: impl Fn(&FuzzData) -> Result<(), Failure>,
+) -> (FuzzData, Failure) {
+    if let Some(index) = failure.instruction_index {
+        fuzz_data.instructions.truncate(index + 1);
+    }
+    let mut i = 0;
+    while i < fuzz_data.instructions.len() {
+        let mut candidate = fuzz_data.clone();
+        candidate.instructions.remove(i);
+        match run(&candidate) {
+            Err(candidate_failure)
+                if candidate_failure.violation.invariant == failure.violation.invariant =>
+            {
+                if let Some(index) = candidate_failure.instruction_index {
+                    candidate.instructions.truncate(index + 1);
+                }
+                fuzz_data = candidate;
+                failure = candidate_failure;
+            }
+            _ => i += 1,
+        }
+    }
+    (fuzz_data, failure)
+}
+
+/// Adjust fuzzed amounts that only lead to known dead ends
+fn adjust_instruction(
+    fuzz_instruction: &FuzzInstruction,
+    accounts: &FuzzAccounts,
+) -> FuzzInstruction {
+    let mut adjusted = fuzz_instruction.clone();
+    match &mut adjusted {
+        FuzzInstruction::Deposit { instruction, .. } => {
+            // special logic: if we only deposit 1 pool token, we can't withdraw it
+            // because we incur a withdrawal fee, so we hack it to not be 1
+            if instruction.pool_token_amount == 1 {
+                instruction.pool_token_amount = 2;
+            }
+        }
+        FuzzInstruction::Withdraw {
+            pool_token_id,
+            instruction,
+            ..
+        } => {
+            // special logic to avoid withdrawing down to 1 pool token, which
+            // eventually causes an error on withdrawing all
+            let pool_token_amount = get_token_balance(&accounts.pool[&*pool_token_id]);
+            if pool_token_amount.saturating_sub(instruction.pool_token_amount) == 1 {
+                instruction.pool_token_amount = pool_token_amount;
+            }
+        }
+        FuzzInstruction::Swap { .. }
+        | FuzzInstruction::WithdrawFees { .. }
+        | FuzzInstruction::UpdatePoolConfig { .. } => {}
+    }
+    adjusted
+}
+
+fn run_fuzz_instruction(
+    fuzz_instruction: &FuzzInstruction,
+    token_swap: &mut NativeTokenSwap,
+    accounts: &mut FuzzAccounts,
+) -> ProgramResult {
+    match fuzz_instruction.clone() {
+        FuzzInstruction::Swap {
+            token_a_id,
+            token_b_id,
+            trade_direction,
+            instruction,
+        } => {
+            let token_a_account = accounts.token_a.get_mut(&token_a_id).unwrap();
+            let token_b_account = accounts.token_b.get_mut(&token_b_id).unwrap();
+            match trade_direction {
+                TradeDirection::AtoB => {
+                    token_swap.swap_a_to_b(token_a_account, token_b_account, instruction)
+                }
+                TradeDirection::BtoA => {
+                    token_swap.swap_b_to_a(token_b_account, token_a_account, instruction)
+                }
+            }
+        }
+        FuzzInstruction::Deposit {
+            token_a_id,
+            token_b_id,
+            pool_token_id,
+            instruction,
+        } => {
+            let token_a_account = accounts.token_a.get_mut(&token_a_id).unwrap();
+            let token_b_account = accounts.token_b.get_mut(&token_b_id).unwrap();
+            let pool_account = accounts.pool.get_mut(&pool_token_id).unwrap();
+            token_swap.deposit(token_a_account, token_b_account, pool_account, instruction)
+        }
+        FuzzInstruction::Withdraw {
+            token_a_id,
+            token_b_id,
+            pool_token_id,
+            instruction,
+        } => {
+            let token_a_account = accounts.token_a.get_mut(&token_a_id).unwrap();
+            let token_b_account = accounts.token_b.get_mut(&token_b_id).unwrap();
+            let pool_account = accounts.pool.get_mut(&pool_token_id).unwrap();
+            token_swap.withdraw(pool_account, token_a_account, token_b_account, instruction)
+        }
+        FuzzInstruction::WithdrawFees { token, instruction } => match token {
+            AorB::A => token_swap.withdraw_a_fees(&mut accounts.fees_token_a, instruction),
+            AorB::B => token_swap.withdraw_b_fees(&mut accounts.fees_token_b, instruction),
+        },
+        FuzzInstruction::UpdatePoolConfig { withdrawals_only } => {
+            token_swap.update_pool_config(UpdatePoolConfig {
+                mode: UpdatePoolConfigMode::WithdrawalsOnly,
+                value: UpdatePoolConfigValue::Bool(withdrawals_only),
+            })
+        }
+    }
+}
+
+/// Errors the program is expected to return for some fuzzed inputs
+fn is_expected_error(e: &ProgramError) -> bool {
+    *e == SwapError::CalculationFailure.into()
+        || *e == SwapError::ConversionFailure.into()
+        || *e == SwapError::FeeCalculationFailure.into()
+        || *e == SwapError::ExceededSlippage.into()
+        || *e == SwapError::ZeroTradingTokens.into()
+        || *e == SwapError::UnsupportedCurveOperation.into()
+        || *e == SwapError::InsufficientPoolTokenFunds.into()
+        || *e == SwapError::WithdrawalsOnlyMode.into()
+        || *e == TokenError::InsufficientFunds.into()
+        // OwnerMismatch can happen due to delegation and 2 transfers (fee and swap)
+        // If the swap transfer uses the entire delegated amount,
+        // then the delegate is removed the and second (fee) transfer will fail
+        || *e == TokenError::OwnerMismatch.into()
+}
+
+fn check_token_conservation(
+    before_total_token_a: u64,
+    after_total_token_a: u64,
+    before_total_token_b: u64,
+    after_total_token_b: u64,
+) -> Result<(), String> {
+    if before_total_token_a != after_total_token_a {
+        return Err(format!(
+            "token a: before={before_total_token_a}, after={after_total_token_a}"
+        ));
+    }
+    if before_total_token_b != after_total_token_b {
+        return Err(format!(
+            "token b: before={before_total_token_b}, after={after_total_token_b}"
+        ));
+    }
+    Ok(())
+}
+
+/// Balance including the transfer fees withheld in the accounts, which are not lost but only
+/// claimable by the mint's withdraw authority
+fn get_total_amount<'a>(accounts: impl Iterator<Item = &'a NativeAccountData>) -> u64 {
+    accounts
+        .map(|account| get_token_balance(account) + get_withheld_amount(account))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperplane::{curve::base::CurveType, ix::Swap};
+
+    use super::*;
+    use crate::fuzz_data::{FuzzToken, FuzzTokenProgram};
+
+    fn swap(amount_in: u64) -> FuzzInstruction {
+        FuzzInstruction::Swap {
+            token_a_id: 0,
+            token_b_id: 0,
+            trade_direction: TradeDirection::AtoB,
+            instruction: Swap::new(amount_in, 0),
+        }
+    }
+
+    fn withdraw_fees() -> FuzzInstruction {
+        FuzzInstruction::WithdrawFees {
+            token: AorB::A,
+            instruction: WithdrawFees::new(1),
+        }
+    }
+
+    fn withdrawals_only() -> FuzzInstruction {
+        FuzzInstruction::UpdatePoolConfig {
+            withdrawals_only: true,
+        }
+    }
+
+    /// Breaks the fees vault invariant on withdrawing fees in withdrawals only mode, and the
+    /// withdraw all invariant on withdrawing fees otherwise
+    fn run_stub(fuzz_data: &FuzzData) -> Result<(), Failure> {
+        let mut only_withdrawals = false;
+        let mut steps = vec![];
+        for (index, instruction) in fuzz_data.instructions.iter().enumerate() {
+            steps.push(Step {
+                instruction: instruction.clone(),
+                succeeded: true,
+            });
+            match instruction {
+                FuzzInstruction::UpdatePoolConfig { withdrawals_only } => {
+                    only_withdrawals = *withdrawals_only
+                }
+                FuzzInstruction::WithdrawFees { .. } => {
+                    let invariant = if only_withdrawals {
+                        Invariant::FeesVault
+                    } else {
+                        Invariant::WithdrawAll
+                    };
+                    return Err(Failure {
+                        instruction_index: Some(index),
+                        violation: Violation::new(invariant, String::new()),
+                        steps,
+                    });
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn minimize_keeps_the_instructions_breaking_the_same_invariant() {
+        let fuzz_data = FuzzData {
+            curve_type: CurveType::ConstantProduct,
+            amp: 0,
+            token_a: FuzzToken {
+                token_program: FuzzTokenProgram::Token,
+                decimals: 0,
+            },
+            token_b: FuzzToken {
+                token_program: FuzzTokenProgram::Token,
+                decimals: 0,
+            },
+            instructions: vec![
+                swap(1),
+                withdrawals_only(),
+                swap(2),
+                withdraw_fees(),
+                swap(3),
+                withdraw_fees(),
+            ],
+        };
+        let failure = run_stub(&fuzz_data).unwrap_err();
+        assert_eq!(failure.instruction_index, Some(3));
+
+        let (fuzz_data, failure) = minimize_with(fuzz_data, failure, run_stub);
+
+        assert!(matches!(
+            fuzz_data.instructions[..],
+            [
+                FuzzInstruction::UpdatePoolConfig {
+                    withdrawals_only: true
+                },
+                FuzzInstruction::WithdrawFees { .. },
+            ]
+        ));
+        assert_eq!(failure.instruction_index, Some(1));
+        assert_eq!(failure.violation.invariant, Invariant::FeesVault);
+        assert_eq!(failure.steps.len(), 2);
+    }
+}
@@ -0,0 +1,252 @@
+use hyperplane::curve::{base::SwapResult, calculator::TradeDirection};
+use rand::Rng;
+
+use crate::engine::pool::SimPool;
+
+/// Trades the pool towards the external price whenever the profit after fees exceeds `min_profit`
+#[derive(Clone, Debug, Default)]
+pub struct Arbitrageur {
+    /// Minimum profit in raw token B units
+    pub min_profit: f64,
+    /// Cumulative profit in raw token B units
+    pub profit: f64,
+}
+
+impl Arbitrageur {
+    pub fn new(min_profit: f64) -> Self {
+        Self {
+            min_profit,
+            profit: 0.0,
+        }
+    }
+
+    /// `price` is the external price of one raw unit of token A in raw units of token B
+    pub fn act(&mut self, pool: &mut SimPool, price: f64) -> Option<SwapResult> {
+        let (trade_direction, amount_in, profit) = [TradeDirection::AtoB, TradeDirection::BtoA]
+            .into_iter()
+            .filter_map(|trade_direction| {
+                let (amount_in, profit) = best_trade(pool, trade_direction, price);
+                (profit > self.min_profit).then_some((trade_direction, amount_in, profit))
+            })
+            .max_by(|(_, _, a), (_, _, b)| a.total_cmp(b))?;
+        let result = pool.swap(trade_direction, amount_in).ok()?;
+        self.profit += profit;
+        Some(result)
+    }
+}
+
+/// Profit of a swap in raw token B units, valuing token A at `price`
+fn trade_profit(
+    pool: &SimPool,
+    trade_direction: TradeDirection,
+    amount_in: u64,
+    price: f64,
+) -> f64 {
+    match pool.quote_swap(trade_direction, amount_in) {
+        Ok(result) => match trade_direction {
+            TradeDirection::AtoB => {
+                result.destination_amount_swapped as f64 - amount_in as f64 * price
+            }
+            TradeDirection::BtoA => {
+                result.destination_amount_swapped as f64 * price - amount_in as f64
+            }
+        },
+        Err(_) => f64::NEG_INFINITY,
+    }
+}
+
+/// Ternary search for the most profitable input amount, the profit is concave in the input for
+/// all curves
+fn best_trade(pool: &SimPool, trade_direction: TradeDirection, price: f64) -> (u64, f64) {
+    let source_amount = match trade_direction {
+        TradeDirection::AtoB => pool.token_a_amount,
+        TradeDirection::BtoA => pool.token_b_amount,
+    };
+    let profit = |amount_in| trade_profit(pool, trade_direction, amount_in, price);
+    let mut lo = 1u64;
+    let mut hi = source_amount.saturating_mul(16).max(2);
+    while hi - lo > 2 {
+        let m1 = lo + (hi - lo) / 3;
+        let m2 = hi - (hi - lo) / 3;
+        if profit(m1) < profit(m2) {
+            lo = m1;
+        } else {
+            hi = m2;
+        }
+    }
+    (lo..=hi)
+        .map(|amount_in| (amount_in, profit(amount_in)))
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .unwrap()
+}
+
+/// Uninformed trader swapping a random amount in a random direction
+#[derive(Clone, Debug)]
+pub struct NoiseTrader {
+    /// Probability of trading on each step
+    pub probability: f64,
+    /// Maximum trade size as a fraction of the pool source vault
+    pub max_trade_fraction: f64,
+}
+
+impl NoiseTrader {
+    pub fn new(probability: f64, max_trade_fraction: f64) -> Self {
+        Self {
+            probability,
+            max_trade_fraction,
+        }
+    }
+
+    pub fn act(&mut self, pool: &mut SimPool, rng: &mut impl Rng) -> Option<SwapResult> {
+        if !rng.gen_bool(self.probability) {
+            return None;
+        }
+        let (trade_direction, source_amount) = if rng.gen_bool(0.5) {
+            (TradeDirection::AtoB, pool.token_a_amount)
+        } else {
+            (TradeDirection::BtoA, pool.token_b_amount)
+        };
+        let max_amount = (source_amount as f64 * self.max_trade_fraction) as u64;
+        if max_amount == 0 {
+            return None;
+        }
+        let amount_in = rng.gen_range(1..=max_amount);
+        pool.swap(trade_direction, amount_in).ok()
+    }
+}
+
+/// Liquidity provider seeding the pool, optionally withdrawing and re-depositing part of its
+/// position. PnL is measured against holding the initial supply.
+#[derive(Clone, Debug)]
+pub struct LiquidityProvider {
+    pub pool_tokens: u64,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    /// Tokens held at the start of the simulation
+    pub hodl_a: u64,
+    pub hodl_b: u64,
+    /// Probability of withdrawing or depositing on each step
+    pub churn_probability: f64,
+    /// Fraction of the position or wallet moved on each churn
+    pub churn_fraction: f64,
+}
+
+impl LiquidityProvider {
+    /// The provider holding the initial supply of a new pool
+    pub fn new(pool_tokens: u64, initial_supply_a: u64, initial_supply_b: u64) -> Self {
+        Self {
+            pool_tokens,
+            token_a_amount: 0,
+            token_b_amount: 0,
+            hodl_a: initial_supply_a,
+            hodl_b: initial_supply_b,
+            churn_probability: 0.0,
+            churn_fraction: 0.0,
+        }
+    }
+
+    pub fn with_churn(mut self, churn_probability: f64, churn_fraction: f64) -> Self {
+        self.churn_probability = churn_probability;
+        self.churn_fraction = churn_fraction;
+        self
+    }
+
+    pub fn act(&mut self, pool: &mut SimPool, rng: &mut impl Rng) {
+        if self.churn_probability <= 0.0 || !rng.gen_bool(self.churn_probability) {
+            return;
+        }
+        if rng.gen_bool(0.5) {
+            self.withdraw(pool);
+        } else {
+            self.deposit(pool);
+        }
+    }
+
+    fn withdraw(&mut self, pool: &mut SimPool) {
+        let withdrawable = self
+            .pool_tokens
+            .min(pool.pool_token_supply.saturating_sub(pool.min_liquidity));
+        let pool_token_amount = (withdrawable as f64 * self.churn_fraction) as u64;
+        if pool_token_amount == 0 {
+            return;
+        }
+        if let Ok((token_a_amount, token_b_amount)) = pool.withdraw(pool_token_amount) {
+            self.pool_tokens -= pool_token_amount;
+            self.token_a_amount += token_a_amount;
+            self.token_b_amount += token_b_amount;
+        }
+    }
+
+    fn deposit(&mut self, pool: &mut SimPool) {
+        if pool.token_a_amount == 0 || pool.token_b_amount == 0 {
+            return;
+        }
+        let supply = u128::from(pool.pool_token_supply);
+        let affordable = std::cmp::min(
+            u128::from(self.token_a_amount) * supply / u128::from(pool.token_a_amount),
+            u128::from(self.token_b_amount) * supply / u128::from(pool.token_b_amount),
+        );
+        // leave room for the deposit rounding up
+        let pool_token_amount = (affordable as f64 * self.churn_fraction * 0.999) as u64;
+        if pool_token_amount == 0 {
+            return;
+        }
+        let (token_a_amount, token_b_amount) = match pool.quote_deposit(pool_token_amount) {
+            Ok(amounts) => amounts,
+            Err(_) => return,
+        };
+        if token_a_amount > self.token_a_amount || token_b_amount > self.token_b_amount {
+            return;
+        }
+        if pool.deposit(pool_token_amount).is_ok() {
+            self.pool_tokens += pool_token_amount;
+            self.token_a_amount -= token_a_amount;
+            self.token_b_amount -= token_b_amount;
+        }
+    }
+
+    /// Tokens held in the wallet and redeemable from the pool
+    pub fn holdings(&self, pool: &SimPool) -> (u64, u64) {
+        let (pool_a, pool_b) = match pool.redeem(self.pool_tokens) {
+            Ok((token_a_amount, token_b_amount, _, _)) => (token_a_amount, token_b_amount),
+            Err(_) => (0, 0),
+        };
+        (self.token_a_amount + pool_a, self.token_b_amount + pool_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperplane::{
+        curve::{calculator::MIN_LIQUIDITY, fees::Fees},
+        model::CurveParameters,
+    };
+
+    use super::*;
+
+    #[test]
+    fn arbitrageur_moves_pool_to_price() {
+        let (mut pool, _) = SimPool::new(
+            CurveParameters::ConstantProduct,
+            Fees {
+                trade_fee_numerator: 25,
+                trade_fee_denominator: 10_000,
+                ..Default::default()
+            },
+            1_000_000_000,
+            1_000_000_000,
+            MIN_LIQUIDITY as u64,
+        )
+        .unwrap();
+        let mut arbitrageur = Arbitrageur::new(0.0);
+
+        assert!(arbitrageur.act(&mut pool, 4.0).is_some());
+
+        // the pool was selling token A too cheap, so the arbitrageur buys A with B
+        assert!(pool.token_a_amount < 1_000_000_000);
+        assert!(pool.token_b_amount > 1_000_000_000);
+        let pool_price = pool.token_b_amount as f64 / pool.token_a_amount as f64;
+        assert!((pool_price - 4.0).abs() / 4.0 < 0.01);
+        assert!(arbitrageur.profit > 0.0);
+    }
+}
@@ -0,0 +1,224 @@
+//! Agent-based market simulation
+//!
+//! Plays an external price path against a `SimPool`, which drives the on-chain `SwapCurve` and
+//! `Fees`. On every step the arbitrageur trades the pool towards the external price, then the noise
+//! traders and the liquidity provider act. The liquidity provider seeds the pool and is measured
+//! against holding its initial supply.
+//!
+//! `replay` re-runs historical pool events from program logs against alternative parameters.
+
+pub mod agent;
+pub mod pool;
+pub mod price;
+pub mod replay;
+pub mod report;
+
+use rand::Rng;
+
+use crate::engine::{
+    agent::{Arbitrageur, LiquidityProvider, NoiseTrader},
+    pool::SimPool,
+    report::StepReport,
+};
+
+pub struct Simulation<R: Rng> {
+    pub pool: SimPool,
+    pub arbitrageur: Option<Arbitrageur>,
+    pub noise_traders: Vec<NoiseTrader>,
+    pub liquidity_provider: LiquidityProvider,
+    rng: R,
+    /// Raw token B units per raw token A unit at a price of 1
+    price_scale: f64,
+    /// Raw token B units per token B
+    value_scale: f64,
+    fee_revenue: f64,
+    lp_fee_revenue: f64,
+    cumulative_volume: f64,
+}
+
+impl<R: Rng> Simulation<R> {
+    pub fn new(
+        pool: SimPool,
+        liquidity_provider: LiquidityProvider,
+        token_a_decimals: u8,
+        token_b_decimals: u8,
+        rng: R,
+    ) -> Self {
+        Self {
+            pool,
+            arbitrageur: None,
+            noise_traders: vec![],
+            liquidity_provider,
+            rng,
+            price_scale: 10f64.powi(i32::from(token_b_decimals) - i32::from(token_a_decimals)),
+            value_scale: 10f64.powi(i32::from(token_b_decimals)),
+            fee_revenue: 0.0,
+            lp_fee_revenue: 0.0,
+            cumulative_volume: 0.0,
+        }
+    }
+
+    pub fn with_arbitrageur(mut self, arbitrageur: Arbitrageur) -> Self {
+        self.arbitrageur = Some(arbitrageur);
+        self
+    }
+
+    pub fn with_noise_traders(mut self, noise_traders: Vec<NoiseTrader>) -> Self {
+        self.noise_traders = noise_traders;
+        self
+    }
+
+    /// Run a step for every price of the path
+    pub fn run(&mut self, prices: &[f64]) -> Vec<StepReport> {
+        prices
+            .iter()
+            .enumerate()
+            .map(|(step, price)| self.step(step, *price))
+            .collect()
+    }
+
+    /// Let all agents act at the external `price` of token A in token B
+    pub fn step(&mut self, step: usize, price: f64) -> StepReport {
+        let raw_price = price * self.price_scale;
+        let trade_fees_before = (self.pool.trade_fees_a, self.pool.trade_fees_b);
+        let volume_before = (self.pool.volume_a, self.pool.volume_b);
+        let lp_share = if self.pool.pool_token_supply > 0 {
+            self.liquidity_provider.pool_tokens as f64 / self.pool.pool_token_supply as f64
+        } else {
+            0.0
+        };
+
+        if let Some(arbitrageur) = self.arbitrageur.as_mut() {
+            arbitrageur.act(&mut self.pool, raw_price);
+        }
+        for noise_trader in self.noise_traders.iter_mut() {
+            noise_trader.act(&mut self.pool, &mut self.rng);
+        }
+        self.liquidity_provider.act(&mut self.pool, &mut self.rng);
+
+        let value = |a: f64, b: f64| (a * raw_price + b) / self.value_scale;
+        let step_fees = value(
+            (self.pool.trade_fees_a - trade_fees_before.0) as f64,
+            (self.pool.trade_fees_b - trade_fees_before.1) as f64,
+        );
+        let volume = value(
+            (self.pool.volume_a - volume_before.0) as f64,
+            (self.pool.volume_b - volume_before.1) as f64,
+        );
+        self.fee_revenue += step_fees;
+        self.lp_fee_revenue += step_fees * lp_share;
+        self.cumulative_volume += volume;
+
+        let lp = &self.liquidity_provider;
+        let (lp_a, lp_b) = lp.holdings(&self.pool);
+        let lp_value = value(lp_a as f64, lp_b as f64);
+        let hodl_value = value(lp.hodl_a as f64, lp.hodl_b as f64);
+
+        StepReport {
+            step,
+            price,
+            pool_price: self
+                .pool
+                .marginal_price()
+                .map_or(f64::NAN, |p| p / self.price_scale),
+            token_a_amount: self.pool.token_a_amount,
+            token_b_amount: self.pool.token_b_amount,
+            pool_token_supply: self.pool.pool_token_supply,
+            pool_value: value(
+                self.pool.token_a_amount as f64,
+                self.pool.token_b_amount as f64,
+            ),
+            lp_value,
+            hodl_value,
+            lp_pnl: lp_value - hodl_value,
+            impermanent_loss: (lp_value - self.lp_fee_revenue) / hodl_value - 1.0,
+            fee_revenue: self.fee_revenue,
+            lp_fee_revenue: self.lp_fee_revenue,
+            owner_fees_a: self.pool.owner_fees_a,
+            owner_fees_b: self.pool.owner_fees_b,
+            owner_fee_value: value(self.pool.owner_fees_a as f64, self.pool.owner_fees_b as f64),
+            volume,
+            cumulative_volume: self.cumulative_volume,
+            arbitrage_profit: self
+                .arbitrageur
+                .as_ref()
+                .map_or(0.0, |a| a.profit / self.value_scale),
+            swaps: self.pool.swaps,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperplane::{
+        curve::{calculator::MIN_LIQUIDITY, fees::Fees},
+        model::CurveParameters,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::engine::price::PricePath;
+
+    fn simulation(fees: Fees, noise_traders: Vec<NoiseTrader>) -> Simulation<StdRng> {
+        let (pool, pool_tokens) = SimPool::new(
+            CurveParameters::ConstantProduct,
+            fees,
+            1_000_000_000_000,
+            2_000_000_000_000,
+            MIN_LIQUIDITY as u64,
+        )
+        .unwrap();
+        let liquidity_provider =
+            LiquidityProvider::new(pool_tokens, 1_000_000_000_000, 2_000_000_000_000);
+        Simulation::new(pool, liquidity_provider, 6, 6, StdRng::seed_from_u64(0))
+            .with_arbitrageur(Arbitrageur::new(0.0))
+            .with_noise_traders(noise_traders)
+    }
+
+    #[test]
+    fn constant_price_path_has_no_loss() {
+        let mut sim = simulation(Fees::default(), vec![]);
+
+        let reports = sim.run(&[2.0; 10]);
+
+        for report in reports {
+            assert_eq!(report.swaps, 0);
+            assert!((report.pool_price - 2.0).abs() < 1e-4);
+            assert!(report.lp_pnl.abs() < 1e-6);
+            assert!(report.impermanent_loss.abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn fees_accrue_to_lp_and_owner() {
+        let fees = Fees {
+            trade_fee_numerator: 30,
+            trade_fee_denominator: 10_000,
+            owner_trade_fee_numerator: 5,
+            owner_trade_fee_denominator: 10_000,
+            ..Default::default()
+        };
+        let mut sim = simulation(fees, vec![NoiseTrader::new(0.5, 0.01)]);
+        let prices = PricePath::Gbm {
+            initial_price: 2.0,
+            drift: 0.0,
+            volatility: 0.01,
+        }
+        .generate(200, &mut StdRng::seed_from_u64(1));
+
+        let reports = sim.run(&prices);
+
+        let last = reports.last().unwrap();
+        assert_eq!(reports.len(), 200);
+        assert!(last.swaps > 0);
+        assert!(last.cumulative_volume > 0.0);
+        assert!(last.fee_revenue > 0.0);
+        // the liquidity provider owns every pool token but the locked minimum liquidity
+        let lp_share =
+            sim.liquidity_provider.pool_tokens as f64 / sim.pool.pool_token_supply as f64;
+        assert!(lp_share < 1.0);
+        assert!((last.lp_fee_revenue - last.fee_revenue * lp_share).abs() < 1e-6);
+        assert!(last.owner_fees_a > 0 && last.owner_fees_b > 0);
+        assert!(reports.iter().all(|r| r.lp_value.is_finite()));
+    }
+}
@@ -0,0 +1,336 @@
+use hyperplane::{
+    anchor_lang::prelude::*,
+    curve::{
+        base::{SwapCurve, SwapResult},
+        calculator::TradeDirection,
+        fees::Fees,
+    },
+    error::SwapError,
+    model::CurveParameters,
+    quote::{self, PoolSnapshot},
+    state::SwapPool,
+};
+
+/// An in-memory pool driving the on-chain `SwapCurve` and `Fees`, mirroring the token movements of
+/// the swap, deposit and withdraw instructions. Deposits, withdrawals and compounding are quoted
+/// with the off-chain `PoolSnapshot`
+#[derive(Clone, Debug)]
+pub struct SimPool {
+    pub swap_curve: SwapCurve,
+    pub fees: Fees,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub pool_token_supply: u64,
+    pub min_liquidity: u64,
+    /// Owner trading and withdraw fees accrued to the fee vaults
+    pub owner_fees_a: u64,
+    pub owner_fees_b: u64,
+    /// Trading fees left in the vaults for liquidity providers
+    pub trade_fees_a: u64,
+    pub trade_fees_b: u64,
+    /// Source tokens swapped into the pool, including fees
+    pub volume_a: u128,
+    pub volume_b: u128,
+    pub swaps: u64,
+}
+
+impl SimPool {
+    /// Initialize a pool, returns the pool and the pool tokens minted to the admin
+    pub fn new(
+        curve_params: CurveParameters,
+        fees: Fees,
+        initial_supply_a: u64,
+        initial_supply_b: u64,
+        min_liquidity: u64,
+    ) -> Result<(Self, u64)> {
+        let swap_curve = SwapCurve::new_from_params(curve_params)?;
+        swap_curve
+            .calculator
+            .validate_supply(initial_supply_a, initial_supply_b)?;
+        fees.validate()?;
+        swap_curve.calculator.validate()?;
+
+        let new_pool_supply = swap_curve.calculator.new_pool_supply();
+        require!(
+            min_liquidity > 0 && u128::from(min_liquidity) < new_pool_supply,
+            SwapError::InvalidMinLiquidity
+        );
+        let initial_amount = to_u64(new_pool_supply - u128::from(min_liquidity))?;
+
+        let pool = Self {
+            swap_curve,
+            fees,
+            token_a_amount: initial_supply_a,
+            token_b_amount: initial_supply_b,
+            // the minimum liquidity is minted to the locked vault, on top of the admin pool tokens
+            pool_token_supply: to_u64(new_pool_supply)?,
+            min_liquidity,
+            owner_fees_a: 0,
+            owner_fees_b: 0,
+            trade_fees_a: 0,
+            trade_fees_b: 0,
+            volume_a: 0,
+            volume_b: 0,
+            swaps: 0,
+        };
+        Ok((pool, initial_amount))
+    }
+
+    /// Calculate a swap of `amount_in` source tokens without changing the pool
+    pub fn quote_swap(
+        &self,
+        trade_direction: TradeDirection,
+        amount_in: u64,
+    ) -> Result<SwapResult> {
+        let (pool_source_amount, pool_destination_amount) = match trade_direction {
+            TradeDirection::AtoB => (self.token_a_amount, self.token_b_amount),
+            TradeDirection::BtoA => (self.token_b_amount, self.token_a_amount),
+        };
+        let result = self
+            .swap_curve
+            .swap(
+                u128::from(amount_in),
+                u128::from(pool_source_amount),
+                u128::from(pool_destination_amount),
+                trade_direction,
+                &self.fees,
+            )
+            .map_err(|_| error!(SwapError::ZeroTradingTokens))?;
+        require!(
+            result.destination_amount_swapped > 0
+                && result.destination_amount_swapped < u128::from(pool_destination_amount),
+            SwapError::ZeroTradingTokens
+        );
+        Ok(result)
+    }
+
+    /// Swap `amount_in` source tokens, returns the amounts moved
+    pub fn swap(&mut self, trade_direction: TradeDirection, amount_in: u64) -> Result<SwapResult> {
+        let result = self.quote_swap(trade_direction, amount_in)?;
+        let to_vault = to_u64(result.source_amount_to_vault)?;
+        let from_vault = to_u64(result.destination_amount_swapped)?;
+        let owner_fee = to_u64(result.owner_fee)?;
+        let trade_fee = to_u64(result.trade_fee)?;
+        match trade_direction {
+            TradeDirection::AtoB => {
+                self.token_a_amount += to_vault;
+                self.token_b_amount -= from_vault;
+                self.owner_fees_a += owner_fee;
+                self.trade_fees_a += trade_fee;
+                self.volume_a += result.total_source_amount_swapped;
+            }
+            TradeDirection::BtoA => {
+                self.token_b_amount += to_vault;
+                self.token_a_amount -= from_vault;
+                self.owner_fees_b += owner_fee;
+                self.trade_fees_b += trade_fee;
+                self.volume_b += result.total_source_amount_swapped;
+            }
+        }
+        self.swaps += 1;
+        Ok(result)
+    }
+
+    /// Marginal price of token A in token B in raw units, without fees
+    pub fn marginal_price(&self) -> Option<f64> {
+        quote::spot_price(&self.swap_curve, self.token_a_amount, self.token_b_amount)
+    }
+
+    /// Calculate the token amounts required to mint `pool_token_amount` pool tokens
+    pub fn quote_deposit(&self, pool_token_amount: u64) -> Result<(u64, u64)> {
+        let quote = self
+            .snapshot(self.min_liquidity)
+            .deposit(pool_token_amount)?;
+        Ok((quote.token_a_amount, quote.token_b_amount))
+    }
+
+    /// Mint `pool_token_amount` pool tokens, returns the token amounts deposited
+    pub fn deposit(&mut self, pool_token_amount: u64) -> Result<(u64, u64)> {
+        let (token_a_amount, token_b_amount) = self.quote_deposit(pool_token_amount)?;
+        self.token_a_amount += token_a_amount;
+        self.token_b_amount += token_b_amount;
+        self.pool_token_supply += pool_token_amount;
+        Ok((token_a_amount, token_b_amount))
+    }
+
+    /// Calculate the token amounts received for burning `pool_token_amount` pool tokens, net of
+    /// the owner withdraw fee. Returns `(token_a_amount, token_b_amount, token_a_fees, token_b_fees)`
+    pub fn quote_withdraw(&self, pool_token_amount: u64) -> Result<(u64, u64, u64, u64)> {
+        let quote = self
+            .snapshot(self.min_liquidity)
+            .withdraw(pool_token_amount)?;
+        Ok((
+            quote.token_a_amount,
+            quote.token_b_amount,
+            quote.token_a_fees,
+            quote.token_b_fees,
+        ))
+    }
+
+    /// Value `pool_token_amount` pool tokens as a withdrawal would, ignoring the minimum liquidity
+    /// so that locked positions can be valued as well
+    pub fn redeem(&self, pool_token_amount: u64) -> Result<(u64, u64, u64, u64)> {
+        let quote = self.snapshot(0).withdraw(pool_token_amount)?;
+        Ok((
+            quote.token_a_amount,
+            quote.token_b_amount,
+            quote.token_a_fees,
+            quote.token_b_fees,
+        ))
+    }
+
+    /// Burn `pool_token_amount` pool tokens, returns the token amounts received
+    pub fn withdraw(&mut self, pool_token_amount: u64) -> Result<(u64, u64)> {
+        let (token_a_amount, token_b_amount, token_a_fees, token_b_fees) =
+            self.quote_withdraw(pool_token_amount)?;
+        self.token_a_amount -= token_a_amount + token_a_fees;
+        self.token_b_amount -= token_b_amount + token_b_fees;
+        self.owner_fees_a += token_a_fees;
+        self.owner_fees_b += token_b_fees;
+        self.pool_token_supply -= pool_token_amount;
+        Ok((token_a_amount, token_b_amount))
+    }
+
+    /// Deposit the owner fees back into the pool at the current ratio of the pool vaults, as the
+    /// compound fees instruction would. Returns `(token_a_amount, token_b_amount, pool_token_amount)`
+    pub fn compound_fees(&mut self) -> Result<(u64, u64, u64)> {
+        let quote = self
+            .snapshot(self.min_liquidity)
+            .compound_fees(self.owner_fees_a, self.owner_fees_b)?;
+        self.owner_fees_a -= quote.token_a_transfer_amount;
+        self.owner_fees_b -= quote.token_b_transfer_amount;
+        self.token_a_amount += quote.token_a_amount;
+        self.token_b_amount += quote.token_b_amount;
+        self.pool_token_supply += quote.pool_token_amount;
+        Ok((
+            quote.token_a_amount,
+            quote.token_b_amount,
+            quote.pool_token_amount,
+        ))
+    }
+
+    /// The pool as seen by the off-chain quotes, the sim mints have no transfer fees
+    fn snapshot(&self, min_liquidity: u64) -> PoolSnapshot {
+        PoolSnapshot {
+            pool: SwapPool {
+                fees: self.fees,
+                min_liquidity,
+                ..SwapPool::default()
+            },
+            swap_curve: self.swap_curve.clone(),
+            token_a_vault_amount: self.token_a_amount,
+            token_b_vault_amount: self.token_b_amount,
+            pool_token_supply: self.pool_token_supply,
+            token_a_transfer_fee_config: None,
+            token_b_transfer_fee_config: None,
+            epoch: 0,
+        }
+    }
+}
+
+fn to_u64(val: u128) -> Result<u64> {
+    u64::try_from(val).map_err(|_| error!(SwapError::ConversionFailure))
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperplane::curve::calculator::MIN_LIQUIDITY;
+
+    use super::*;
+
+    fn fees() -> Fees {
+        Fees {
+            trade_fee_numerator: 25,
+            trade_fee_denominator: 10_000,
+            owner_trade_fee_numerator: 5,
+            owner_trade_fee_denominator: 10_000,
+            owner_withdraw_fee_numerator: 1,
+            owner_withdraw_fee_denominator: 1_000,
+            host_fee_numerator: 0,
+            host_fee_denominator: 0,
+        }
+    }
+
+    #[test]
+    fn swap_moves_fees_to_vaults() {
+        let (mut pool, _) = SimPool::new(
+            CurveParameters::ConstantProduct,
+            fees(),
+            1_000_000_000,
+            1_000_000_000,
+            MIN_LIQUIDITY as u64,
+        )
+        .unwrap();
+
+        let result = pool.swap(TradeDirection::AtoB, 1_000_000).unwrap();
+
+        assert_eq!(
+            u128::from(pool.token_a_amount),
+            1_000_000_000 + result.source_amount_to_vault
+        );
+        assert_eq!(
+            u128::from(pool.token_b_amount),
+            1_000_000_000 - result.destination_amount_swapped
+        );
+        assert_eq!(u128::from(pool.owner_fees_a), result.owner_fee);
+        assert_eq!(u128::from(pool.trade_fees_a), result.trade_fee);
+        assert_eq!(pool.volume_a, 1_000_000);
+        assert_eq!(
+            u128::from(pool.token_a_amount + pool.owner_fees_a),
+            1_000_000_000 + 1_000_000
+        );
+    }
+
+    #[test]
+    fn deposit_then_withdraw_charges_withdraw_fee() {
+        let (mut pool, admin_pool_tokens) = SimPool::new(
+            CurveParameters::ConstantProduct,
+            fees(),
+            1_000_000_000,
+            1_000_000_000,
+            MIN_LIQUIDITY as u64,
+        )
+        .unwrap();
+
+        let (deposit_a, deposit_b) = pool.deposit(admin_pool_tokens).unwrap();
+        let (withdraw_a, withdraw_b) = pool.withdraw(admin_pool_tokens).unwrap();
+
+        assert!(withdraw_a < deposit_a);
+        assert!(withdraw_b < deposit_b);
+        assert_eq!(
+            pool.token_a_amount + pool.owner_fees_a + withdraw_a,
+            1_000_000_000 + deposit_a
+        );
+        assert_eq!(
+            pool.token_b_amount + pool.owner_fees_b + withdraw_b,
+            1_000_000_000 + deposit_b
+        );
+    }
+
+    #[test]
+    fn compound_fees_moves_owner_fees_to_vaults() {
+        let (mut pool, _) = SimPool::new(
+            CurveParameters::ConstantProduct,
+            fees(),
+            1_000_000_000,
+            1_000_000_000,
+            MIN_LIQUIDITY as u64,
+        )
+        .unwrap();
+        pool.swap(TradeDirection::AtoB, 1_000_000).unwrap();
+        pool.swap(TradeDirection::BtoA, 1_000_000).unwrap();
+        let before = pool.clone();
+
+        let (token_a_amount, token_b_amount, pool_token_amount) = pool.compound_fees().unwrap();
+
+        assert!(pool_token_amount > 0);
+        assert_eq!(pool.owner_fees_a, before.owner_fees_a - token_a_amount);
+        assert_eq!(pool.owner_fees_b, before.owner_fees_b - token_b_amount);
+        assert_eq!(pool.token_a_amount, before.token_a_amount + token_a_amount);
+        assert_eq!(pool.token_b_amount, before.token_b_amount + token_b_amount);
+        assert_eq!(
+            pool.pool_token_supply,
+            before.pool_token_supply + pool_token_amount
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use std::{fs, path::Path};
+
+use rand::Rng;
+
+/// External market price of token A in token B, one value per simulation step
+#[derive(Clone, Debug)]
+pub enum PricePath {
+    /// Geometric brownian motion, `drift` and `volatility` are per step
+    Gbm {
+        initial_price: f64,
+        drift: f64,
+        volatility: f64,
+    },
+    /// Prices replayed in order, the simulation stops at the end of the path
+    Replay(Vec<f64>),
+}
+
+impl PricePath {
+    /// Load a price path from a CSV file, the price is the last column of each row. A header row
+    /// and empty lines are skipped.
+    pub fn from_csv(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read price path {}: {}", path.display(), e))?;
+        let prices = parse_csv(&contents)?;
+        if prices.is_empty() {
+            return Err(format!("Price path {} is empty", path.display()));
+        }
+        Ok(PricePath::Replay(prices))
+    }
+
+    /// Generate the prices for `steps` steps, replays are truncated to `steps`
+    pub fn generate(&self, steps: usize, rng: &mut impl Rng) -> Vec<f64> {
+        match self {
+            PricePath::Gbm {
+                initial_price,
+                drift,
+                volatility,
+            } => {
+                let mut price = *initial_price;
+                let mut prices = Vec::with_capacity(steps);
+                for _ in 0..steps {
+                    prices.push(price);
+                    let z = standard_normal(rng);
+                    price *= ((drift - volatility * volatility / 2.0) + volatility * z).exp();
+                }
+                prices
+            }
+            PricePath::Replay(prices) => prices.iter().take(steps).copied().collect(),
+        }
+    }
+}
+
+fn parse_csv(contents: &str) -> Result<Vec<f64>, String> {
+    let mut prices = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let field = line.rsplit(',').next().unwrap_or(line).trim();
+        match field.parse::<f64>() {
+            Ok(price) if price.is_finite() && price > 0.0 => prices.push(price),
+            Ok(price) => return Err(format!("Invalid price {} on line {}", price, i + 1)),
+            Err(_) if prices.is_empty() => continue,
+            Err(e) => return Err(format!("Invalid price on line {}: {}", i + 1, e)),
+        }
+    }
+    Ok(prices)
+}
+
+/// Box-Muller transform of two uniform samples
+fn standard_normal(rng: &mut impl Rng) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn parse_csv_skips_header() {
+        let prices = parse_csv("step,price\n0,1.5\n\n1,2\n").unwrap();
+        assert_eq!(prices, vec![1.5, 2.0]);
+    }
+
+    #[test]
+    fn parse_csv_rejects_invalid_rows() {
+        assert!(parse_csv("1.5\nabc\n").is_err());
+        assert!(parse_csv("1.5\n-1\n").is_err());
+    }
+
+    #[test]
+    fn gbm_starts_at_initial_price() {
+        let path = PricePath::Gbm {
+            initial_price: 2.0,
+            drift: 0.0,
+            volatility: 0.01,
+        };
+        let prices = path.generate(100, &mut StdRng::seed_from_u64(0));
+        assert_eq!(prices.len(), 100);
+        assert_eq!(prices[0], 2.0);
+        assert!(prices.iter().all(|p| *p > 0.0));
+    }
+}
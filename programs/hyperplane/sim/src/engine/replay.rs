@@ -0,0 +1,493 @@
+//! Replay of historical pool events against alternative pool parameters
+//!
+//! Events are decoded from program logs, i.e. the `Program data:` lines written by `emitted!`. The
+//! `Swap` event does not record the trade direction, so it is taken from the `Swap inputs:` line
+//! logged by the same instruction, along with the amount given to the curve. The logs must belong
+//! to a single pool. Token-2022 transfer fees are not modelled.
+
+use std::io::{self, Write};
+
+use hyperplane::{
+    anchor_lang::{AnchorDeserialize, Discriminator},
+    curve::calculator::TradeDirection,
+    event,
+};
+
+use crate::engine::pool::SimPool;
+
+const PROGRAM_DATA: &str = "Program data: ";
+const SWAP_INPUTS: &str = "Swap inputs: ";
+
+/// A pool event decoded from the program logs
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReplayEvent {
+    Swap {
+        trade_direction: TradeDirection,
+        /// Source amount given to the curve, net of transfer fees
+        actual_amount_in: u64,
+        event: event::Swap,
+    },
+    Deposit(event::Deposit),
+    Withdraw(event::Withdraw),
+    CompoundFees(event::CompoundFees),
+}
+
+impl ReplayEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReplayEvent::Swap { .. } => "swap",
+            ReplayEvent::Deposit(_) => "deposit",
+            ReplayEvent::Withdraw(_) => "withdraw",
+            ReplayEvent::CompoundFees(_) => "compound_fees",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ParsedLogs {
+    pub events: Vec<ReplayEvent>,
+    /// Swap events without a preceding `Swap inputs:` log, which cannot be replayed
+    pub skipped_swaps: usize,
+}
+
+/// Decode the replayable events from program logs, one log line per line. Other events and log
+/// lines are ignored.
+pub fn parse_logs(logs: &str) -> Result<ParsedLogs, String> {
+    let mut parsed = ParsedLogs::default();
+    let mut swap_inputs = None;
+    for (i, line) in logs.lines().enumerate() {
+        if let Some(inputs) = find_after(line, SWAP_INPUTS) {
+            swap_inputs = Some(
+                parse_swap_inputs(inputs)
+                    .map_err(|e| format!("Invalid swap inputs on line {}: {}", i + 1, e))?,
+            );
+            continue;
+        }
+        let data = match find_after(line, PROGRAM_DATA) {
+            Some(data) => data.trim(),
+            None => continue,
+        };
+        let data = base64::decode(data)
+            .map_err(|e| format!("Invalid program data on line {}: {}", i + 1, e))?;
+        let event =
+            decode_event(&data).map_err(|e| format!("Invalid event on line {}: {}", i + 1, e))?;
+        match event {
+            Some(DecodedEvent::Swap(event)) => match swap_inputs.take() {
+                Some((trade_direction, actual_amount_in)) => {
+                    parsed.events.push(ReplayEvent::Swap {
+                        trade_direction,
+                        actual_amount_in,
+                        event,
+                    })
+                }
+                None => parsed.skipped_swaps += 1,
+            },
+            Some(DecodedEvent::Replay(event)) => parsed.events.push(event),
+            None => {}
+        }
+    }
+    Ok(parsed)
+}
+
+enum DecodedEvent {
+    Swap(event::Swap),
+    Replay(ReplayEvent),
+}
+
+fn decode_event(data: &[u8]) -> Result<Option<DecodedEvent>, String> {
+    if data.len() < 8 {
+        return Ok(None);
+    }
+    let (discriminator, mut data) = data.split_at(8);
+    let event = if discriminator == event::Swap::discriminator() {
+        DecodedEvent::Swap(deserialize(&mut data)?)
+    } else if discriminator == event::Deposit::discriminator() {
+        DecodedEvent::Replay(ReplayEvent::Deposit(deserialize(&mut data)?))
+    } else if discriminator == event::Withdraw::discriminator() {
+        DecodedEvent::Replay(ReplayEvent::Withdraw(deserialize(&mut data)?))
+    } else if discriminator == event::CompoundFees::discriminator() {
+        DecodedEvent::Replay(ReplayEvent::CompoundFees(deserialize(&mut data)?))
+    } else {
+        return Ok(None);
+    };
+    Ok(Some(event))
+}
+
+fn deserialize<T: AnchorDeserialize>(data: &mut &[u8]) -> Result<T, String> {
+    T::deserialize(data).map_err(|e| e.to_string())
+}
+
+fn find_after<'a>(line: &'a str, pattern: &str) -> Option<&'a str> {
+    line.find(pattern).map(|i| &line[i + pattern.len()..])
+}
+
+/// Parse `trade_direction=AtoB, amount_in=1, actual_amount_in=1, minimum_amount_out=0`
+fn parse_swap_inputs(inputs: &str) -> Result<(TradeDirection, u64), String> {
+    let mut trade_direction = None;
+    let mut actual_amount_in = None;
+    for field in inputs.split(", ") {
+        match field.trim().split_once('=') {
+            Some(("trade_direction", "AtoB")) => trade_direction = Some(TradeDirection::AtoB),
+            Some(("trade_direction", "BtoA")) => trade_direction = Some(TradeDirection::BtoA),
+            Some(("trade_direction", other)) => {
+                return Err(format!("unknown trade direction {}", other))
+            }
+            Some(("actual_amount_in", amount)) => {
+                actual_amount_in = Some(amount.parse::<u64>().map_err(|e| e.to_string())?)
+            }
+            _ => {}
+        }
+    }
+    match (trade_direction, actual_amount_in) {
+        (Some(trade_direction), Some(actual_amount_in)) => Ok((trade_direction, actual_amount_in)),
+        _ => Err("missing trade_direction or actual_amount_in".to_string()),
+    }
+}
+
+/// Pool state and cumulative flows after replaying events
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReplayTotals {
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub pool_token_supply: u64,
+    /// Trading fees left in the vaults for liquidity providers
+    pub trade_fees_a: u64,
+    pub trade_fees_b: u64,
+    /// Owner trading and withdraw fees
+    pub owner_fees_a: u64,
+    pub owner_fees_b: u64,
+    /// Tokens paid out to swappers
+    pub swapped_out_a: u64,
+    pub swapped_out_b: u64,
+    pub swaps: u64,
+}
+
+/// Pool state after an event, on both pools
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReplayStep {
+    pub index: usize,
+    pub event: &'static str,
+    pub actual_token_a_amount: u64,
+    pub actual_token_b_amount: u64,
+    pub alternative_token_a_amount: u64,
+    pub alternative_token_b_amount: u64,
+    /// Tokens paid out by a swap
+    pub actual_amount_out: u64,
+    pub alternative_amount_out: u64,
+    /// The event failed on the alternative pool and was skipped
+    pub alternative_failed: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReplayResult {
+    pub steps: Vec<ReplayStep>,
+    pub actual: ReplayTotals,
+    pub alternative: ReplayTotals,
+    pub alternative_failures: usize,
+}
+
+/// Replay `events` on the actual pool, starting from its state before the first event, and on the
+/// `alternative` pool. Deposits and withdrawals mint and burn the same pool token amounts, swaps
+/// give the same amounts to the curve, and fee compounding deposits each pool's own owner fees.
+/// The actual amounts saturate at zero if the starting state is off.
+pub fn replay(
+    events: &[ReplayEvent],
+    mut actual: ReplayTotals,
+    mut alternative: SimPool,
+) -> ReplayResult {
+    let mut swapped_out = (0u64, 0u64);
+    let mut alternative_failures = 0;
+    let mut steps = Vec::with_capacity(events.len());
+
+    for (index, replay_event) in events.iter().enumerate() {
+        let mut actual_amount_out = 0;
+        let mut alternative_amount_out = 0;
+        let alternative_result = match replay_event {
+            ReplayEvent::Swap {
+                trade_direction,
+                actual_amount_in,
+                event,
+            } => {
+                apply_swap(&mut actual, *trade_direction, *actual_amount_in, event);
+                actual_amount_out = event.token_out_amount;
+                alternative
+                    .swap(*trade_direction, *actual_amount_in)
+                    .map(|result| {
+                        alternative_amount_out = result.destination_amount_swapped as u64;
+                        match trade_direction {
+                            TradeDirection::AtoB => swapped_out.1 += alternative_amount_out,
+                            TradeDirection::BtoA => swapped_out.0 += alternative_amount_out,
+                        }
+                    })
+            }
+            ReplayEvent::Deposit(event) => {
+                actual.token_a_amount += event.token_a_amount;
+                actual.token_b_amount += event.token_b_amount;
+                actual.pool_token_supply += event.pool_token_amount;
+                alternative.deposit(event.pool_token_amount).map(|_| ())
+            }
+            ReplayEvent::CompoundFees(event) => {
+                // the owner fees move back into the pool, so each pool compounds its own fees
+                actual.token_a_amount += event.token_a_amount;
+                actual.token_b_amount += event.token_b_amount;
+                actual.owner_fees_a = actual.owner_fees_a.saturating_sub(event.token_a_amount);
+                actual.owner_fees_b = actual.owner_fees_b.saturating_sub(event.token_b_amount);
+                actual.pool_token_supply += event.pool_token_amount;
+                alternative.compound_fees().map(|_| ())
+            }
+            ReplayEvent::Withdraw(event) => {
+                actual.token_a_amount = actual
+                    .token_a_amount
+                    .saturating_sub(event.token_a_amount + event.token_a_fees);
+                actual.token_b_amount = actual
+                    .token_b_amount
+                    .saturating_sub(event.token_b_amount + event.token_b_fees);
+                actual.owner_fees_a += event.token_a_fees;
+                actual.owner_fees_b += event.token_b_fees;
+                actual.pool_token_supply = actual
+                    .pool_token_supply
+                    .saturating_sub(event.pool_token_amount);
+                alternative.withdraw(event.pool_token_amount).map(|_| ())
+            }
+        };
+        let alternative_failed = alternative_result.is_err();
+        if alternative_failed {
+            alternative_failures += 1;
+        }
+        steps.push(ReplayStep {
+            index,
+            event: replay_event.name(),
+            actual_token_a_amount: actual.token_a_amount,
+            actual_token_b_amount: actual.token_b_amount,
+            alternative_token_a_amount: alternative.token_a_amount,
+            alternative_token_b_amount: alternative.token_b_amount,
+            actual_amount_out,
+            alternative_amount_out,
+            alternative_failed,
+        });
+    }
+
+    let alternative = ReplayTotals {
+        token_a_amount: alternative.token_a_amount,
+        token_b_amount: alternative.token_b_amount,
+        pool_token_supply: alternative.pool_token_supply,
+        trade_fees_a: alternative.trade_fees_a,
+        trade_fees_b: alternative.trade_fees_b,
+        owner_fees_a: alternative.owner_fees_a,
+        owner_fees_b: alternative.owner_fees_b,
+        swapped_out_a: swapped_out.0,
+        swapped_out_b: swapped_out.1,
+        swaps: alternative.swaps,
+    };
+    ReplayResult {
+        steps,
+        actual,
+        alternative,
+        alternative_failures,
+    }
+}
+
+fn apply_swap(
+    totals: &mut ReplayTotals,
+    trade_direction: TradeDirection,
+    actual_amount_in: u64,
+    event: &event::Swap,
+) {
+    // the owner and host fees are the part of the input that did not go to the vault
+    let owner_fee = actual_amount_in.saturating_sub(event.token_in_amount);
+    let trade_fee = event.total_fees.saturating_sub(owner_fee);
+    match trade_direction {
+        TradeDirection::AtoB => {
+            totals.token_a_amount += event.token_in_amount;
+            totals.token_b_amount = totals.token_b_amount.saturating_sub(event.token_out_amount);
+            totals.trade_fees_a += trade_fee;
+            totals.owner_fees_a += owner_fee;
+            totals.swapped_out_b += event.token_out_amount;
+        }
+        TradeDirection::BtoA => {
+            totals.token_b_amount += event.token_in_amount;
+            totals.token_a_amount = totals.token_a_amount.saturating_sub(event.token_out_amount);
+            totals.trade_fees_b += trade_fee;
+            totals.owner_fees_b += owner_fee;
+            totals.swapped_out_a += event.token_out_amount;
+        }
+    }
+    totals.swaps += 1;
+}
+
+/// Write the replay steps as CSV with a header row
+pub fn write_csv(writer: &mut impl Write, steps: &[ReplayStep]) -> io::Result<()> {
+    writeln!(
+        writer,
+        "index,event,actual_token_a_amount,actual_token_b_amount,alternative_token_a_amount,\
+alternative_token_b_amount,actual_amount_out,alternative_amount_out,alternative_failed"
+    )?;
+    for s in steps {
+        writeln!(
+            writer,
+            "{},{},{},{},{},{},{},{},{}",
+            s.index,
+            s.event,
+            s.actual_token_a_amount,
+            s.actual_token_b_amount,
+            s.alternative_token_a_amount,
+            s.alternative_token_b_amount,
+            s.actual_amount_out,
+            s.alternative_amount_out,
+            s.alternative_failed,
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperplane::{
+        anchor_lang::AnchorSerialize,
+        curve::{base::SwapCurve, calculator::MIN_LIQUIDITY, fees::Fees},
+        model::CurveParameters,
+    };
+
+    use super::*;
+
+    fn program_data<T: AnchorSerialize + Discriminator>(event: &T) -> String {
+        let mut data = T::discriminator().to_vec();
+        event.serialize(&mut data).unwrap();
+        format!("Program data: {}", base64::encode(data))
+    }
+
+    fn fees() -> Fees {
+        Fees {
+            trade_fee_numerator: 25,
+            trade_fee_denominator: 10_000,
+            owner_trade_fee_numerator: 5,
+            owner_trade_fee_denominator: 10_000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parse_logs_pairs_swaps_with_inputs() {
+        let swap = event::Swap {
+            token_in_amount: 997,
+            token_out_amount: 990,
+            total_fees: 3,
+        };
+        let deposit = event::Deposit {
+            token_a_amount: 10,
+            token_b_amount: 20,
+            pool_token_amount: 5,
+        };
+        let logs = [
+            "Program log: Instruction: Swap".to_string(),
+            "Program log: Swap inputs: trade_direction=BtoA, amount_in=1000, actual_amount_in=1000, minimum_amount_out=0".to_string(),
+            program_data(&swap),
+            program_data(&swap),
+            program_data(&deposit),
+            program_data(&event::WithdrawFees { withdraw_amount: 1 }),
+        ]
+        .join("\n");
+
+        let parsed = parse_logs(&logs).unwrap();
+
+        assert_eq!(
+            parsed.events,
+            vec![
+                ReplayEvent::Swap {
+                    trade_direction: TradeDirection::BtoA,
+                    actual_amount_in: 1000,
+                    event: swap,
+                },
+                ReplayEvent::Deposit(deposit),
+            ]
+        );
+        assert_eq!(parsed.skipped_swaps, 1);
+    }
+
+    #[test]
+    fn replay_with_same_parameters_matches_actual() {
+        let (pool, _) = SimPool::new(
+            CurveParameters::ConstantProduct,
+            fees(),
+            1_000_000,
+            1_000_000,
+            MIN_LIQUIDITY as u64,
+        )
+        .unwrap();
+        let actual = ReplayTotals {
+            token_a_amount: pool.token_a_amount,
+            token_b_amount: pool.token_b_amount,
+            pool_token_supply: pool.pool_token_supply,
+            ..Default::default()
+        };
+
+        // events as the swap instruction would emit them
+        let curve = SwapCurve::new_from_params(CurveParameters::ConstantProduct).unwrap();
+        let result = curve
+            .swap(10_000, 1_000_000, 1_000_000, TradeDirection::AtoB, &fees())
+            .unwrap();
+        let events = vec![ReplayEvent::Swap {
+            trade_direction: TradeDirection::AtoB,
+            actual_amount_in: 10_000,
+            event: event::Swap {
+                token_in_amount: result.source_amount_to_vault as u64,
+                token_out_amount: result.destination_amount_swapped as u64,
+                total_fees: result.total_fees as u64,
+            },
+        }];
+
+        let replayed = replay(&events, actual, pool);
+
+        assert_eq!(replayed.alternative_failures, 0);
+        assert_eq!(replayed.actual, replayed.alternative);
+        assert_eq!(replayed.actual.owner_fees_a, result.owner_fee as u64);
+    }
+
+    #[test]
+    fn replay_compounds_the_alternative_owner_fees() {
+        let (pool, _) = SimPool::new(
+            CurveParameters::ConstantProduct,
+            fees(),
+            1_000_000,
+            1_000_000,
+            MIN_LIQUIDITY as u64,
+        )
+        .unwrap();
+        let actual = ReplayTotals {
+            token_a_amount: pool.token_a_amount,
+            token_b_amount: pool.token_b_amount,
+            pool_token_supply: pool.pool_token_supply,
+            ..Default::default()
+        };
+
+        // events as the program would emit them, from a pool with the same parameters
+        let mut program = pool.clone();
+        let mut events = vec![];
+        for trade_direction in [TradeDirection::AtoB, TradeDirection::BtoA] {
+            let result = program.swap(trade_direction, 10_000).unwrap();
+            events.push(ReplayEvent::Swap {
+                trade_direction,
+                actual_amount_in: 10_000,
+                event: event::Swap {
+                    token_in_amount: result.source_amount_to_vault as u64,
+                    token_out_amount: result.destination_amount_swapped as u64,
+                    total_fees: result.total_fees as u64,
+                },
+            });
+        }
+        let (token_a_amount, token_b_amount, pool_token_amount) = program.compound_fees().unwrap();
+        events.push(ReplayEvent::CompoundFees(event::CompoundFees {
+            token_a_amount,
+            token_b_amount,
+            pool_token_amount,
+        }));
+
+        let replayed = replay(&events, actual, pool);
+
+        assert_eq!(replayed.alternative_failures, 0);
+        assert_eq!(replayed.actual, replayed.alternative);
+        assert_eq!(replayed.actual.owner_fees_a, program.owner_fees_a);
+        assert_eq!(replayed.actual.owner_fees_b, program.owner_fees_b);
+        assert_eq!(replayed.actual.pool_token_supply, program.pool_token_supply);
+    }
+}
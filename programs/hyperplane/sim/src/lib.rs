@@ -11,6 +11,10 @@
 ///
 extern crate core;
 
+#[cfg(feature = "engine")]
+pub mod engine;
+pub mod model;
+
 use num_bigint::BigInt;
 use num_traits::{One, ToPrimitive, Zero};
 
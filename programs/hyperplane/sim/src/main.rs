@@ -0,0 +1,339 @@
+#![allow(clippy::integer_arithmetic)]
+
+use std::{fs, fs::File, io, path::PathBuf};
+
+use clap::{Args as ClapArgs, Parser, Subcommand};
+use hyperplane::{curve::fees::Fees, model::CurveParameters};
+use hyperplane_sim::engine::{
+    agent::{Arbitrageur, LiquidityProvider, NoiseTrader},
+    pool::SimPool,
+    price::PricePath,
+    replay::{self, ReplayResult, ReplayTotals},
+    report::{self, StepReport},
+    Simulation,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+const BPS_DENOMINATOR: u64 = 10_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ArgEnum)]
+pub enum Curve {
+    ConstantProduct,
+    ConstantPrice,
+    Offset,
+    Stable,
+}
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+pub struct Args {
+    #[clap(subcommand)]
+    action: Actions,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Actions {
+    /// Simulate a pool against an external price path and write the step reports as CSV
+    Simulate(SimulateArgs),
+    /// Replay pool events from program logs against other pool parameters and write the steps as CSV
+    Replay(ReplayArgs),
+}
+
+/// Curve, fees and initial supply of the simulated pool
+#[derive(ClapArgs, Debug)]
+pub struct PoolArgs {
+    #[clap(long, arg_enum, default_value = "constant-product")]
+    curve: Curve,
+
+    /// Price of token B in token A raw units, for the constant price curve
+    #[clap(long, default_value_t = 1)]
+    token_b_price: u64,
+
+    /// Virtual token B amount, for the offset curve
+    #[clap(long, default_value_t = 0)]
+    token_b_offset: u64,
+
+    /// Amplification coefficient, for the stable curve
+    #[clap(long, default_value_t = 100)]
+    amp: u64,
+
+    #[clap(long, default_value_t = 6)]
+    token_a_decimals: u8,
+
+    #[clap(long, default_value_t = 6)]
+    token_b_decimals: u8,
+
+    /// Initial token A supply in raw units
+    #[clap(long, default_value_t = 1_000_000_000_000)]
+    initial_supply_a: u64,
+
+    /// Initial token B supply in raw units
+    #[clap(long, default_value_t = 1_000_000_000_000)]
+    initial_supply_b: u64,
+
+    /// Pool tokens permanently locked when the pool is initialized
+    #[clap(long, default_value_t = 1_000)]
+    min_liquidity: u64,
+
+    #[clap(long, default_value_t = 25)]
+    trade_fee_bps: u64,
+
+    #[clap(long, default_value_t = 5)]
+    owner_trade_fee_bps: u64,
+
+    #[clap(long, default_value_t = 0)]
+    owner_withdraw_fee_bps: u64,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct SimulateArgs {
+    #[clap(flatten)]
+    pool: PoolArgs,
+
+    /// Number of steps, replays stop at the end of the price file
+    #[clap(long, default_value_t = 1_000)]
+    steps: usize,
+
+    #[clap(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Initial price of token A in token B, for the GBM price path
+    #[clap(long, default_value_t = 1.0)]
+    initial_price: f64,
+
+    /// Drift per step, for the GBM price path
+    #[clap(long, default_value_t = 0.0)]
+    drift: f64,
+
+    /// Volatility per step, for the GBM price path
+    #[clap(long, default_value_t = 0.01)]
+    volatility: f64,
+
+    /// Replay the prices of token A in token B from a CSV file instead of GBM
+    #[clap(long, parse(from_os_str))]
+    prices: Option<PathBuf>,
+
+    /// Do not arbitrage the pool towards the external price
+    #[clap(long)]
+    no_arbitrageur: bool,
+
+    /// Minimum profit in token B for the arbitrageur to trade
+    #[clap(long, default_value_t = 0.0)]
+    arbitrage_min_profit: f64,
+
+    #[clap(long, default_value_t = 1)]
+    noise_traders: usize,
+
+    /// Probability of each noise trader trading on a step
+    #[clap(long, default_value_t = 0.5)]
+    noise_probability: f64,
+
+    /// Maximum noise trade as a fraction of the pool source vault
+    #[clap(long, default_value_t = 0.01)]
+    noise_max_trade: f64,
+
+    /// Probability of the LP withdrawing or depositing on a step
+    #[clap(long, default_value_t = 0.0)]
+    lp_churn_probability: f64,
+
+    /// Fraction of the LP position or wallet moved on each churn
+    #[clap(long, default_value_t = 0.1)]
+    lp_churn_fraction: f64,
+
+    /// Write the CSV to a file instead of stdout
+    #[clap(short, long, parse(from_os_str))]
+    output: Option<PathBuf>,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct ReplayArgs {
+    /// Alternative parameters, the initial supply is the actual pool state before the first event
+    #[clap(flatten)]
+    pool: PoolArgs,
+
+    /// Program logs of the pool, one log line per line
+    #[clap(short, long, parse(from_os_str))]
+    logs: PathBuf,
+
+    /// Actual pool token supply before the first event, defaults to the supply of a new pool
+    #[clap(long)]
+    pool_token_supply: Option<u64>,
+
+    /// Write the CSV to a file instead of stdout
+    #[clap(short, long, parse(from_os_str))]
+    output: Option<PathBuf>,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    match args.action {
+        Actions::Simulate(args) => simulate(args),
+        Actions::Replay(args) => replay(args),
+    }
+}
+
+fn new_pool(args: &PoolArgs) -> Result<(SimPool, u64), String> {
+    let curve_params = match args.curve {
+        Curve::ConstantProduct => CurveParameters::ConstantProduct,
+        Curve::ConstantPrice => CurveParameters::ConstantPrice {
+            token_b_price: args.token_b_price,
+        },
+        Curve::Offset => CurveParameters::Offset {
+            token_b_offset: args.token_b_offset,
+        },
+        Curve::Stable => CurveParameters::Stable {
+            amp: args.amp,
+            token_a_decimals: args.token_a_decimals,
+            token_b_decimals: args.token_b_decimals,
+        },
+    };
+    let fees = Fees {
+        trade_fee_numerator: args.trade_fee_bps,
+        trade_fee_denominator: BPS_DENOMINATOR,
+        owner_trade_fee_numerator: args.owner_trade_fee_bps,
+        owner_trade_fee_denominator: BPS_DENOMINATOR,
+        owner_withdraw_fee_numerator: args.owner_withdraw_fee_bps,
+        owner_withdraw_fee_denominator: BPS_DENOMINATOR,
+        host_fee_numerator: 0,
+        host_fee_denominator: 0,
+    };
+    SimPool::new(
+        curve_params,
+        fees,
+        args.initial_supply_a,
+        args.initial_supply_b,
+        args.min_liquidity,
+    )
+    .map_err(|e| format!("Invalid pool parameters: {}", e))
+}
+
+fn simulate(args: SimulateArgs) -> Result<(), Box<dyn std::error::Error>> {
+    for (name, probability) in [
+        ("noise-probability", args.noise_probability),
+        ("lp-churn-probability", args.lp_churn_probability),
+    ] {
+        if !(0.0..=1.0).contains(&probability) {
+            return Err(format!("{} must be between 0 and 1", name).into());
+        }
+    }
+
+    let (pool, pool_tokens) = new_pool(&args.pool)?;
+
+    let mut rng = StdRng::seed_from_u64(args.seed);
+    let price_path = match &args.prices {
+        Some(path) => PricePath::from_csv(path)?,
+        None => PricePath::Gbm {
+            initial_price: args.initial_price,
+            drift: args.drift,
+            volatility: args.volatility,
+        },
+    };
+    let prices = price_path.generate(args.steps, &mut rng);
+
+    let liquidity_provider = LiquidityProvider::new(
+        pool_tokens,
+        args.pool.initial_supply_a,
+        args.pool.initial_supply_b,
+    )
+    .with_churn(args.lp_churn_probability, args.lp_churn_fraction);
+    let noise_traders = (0..args.noise_traders)
+        .map(|_| NoiseTrader::new(args.noise_probability, args.noise_max_trade))
+        .collect();
+    let mut sim = Simulation::new(
+        pool,
+        liquidity_provider,
+        args.pool.token_a_decimals,
+        args.pool.token_b_decimals,
+        rng,
+    )
+    .with_noise_traders(noise_traders);
+    if !args.no_arbitrageur {
+        let min_profit =
+            args.arbitrage_min_profit * 10f64.powi(i32::from(args.pool.token_b_decimals));
+        sim = sim.with_arbitrageur(Arbitrageur::new(min_profit));
+    }
+
+    let reports = sim.run(&prices);
+
+    match &args.output {
+        Some(path) => report::write_csv(&mut File::create(path)?, &reports)?,
+        None => report::write_csv(&mut io::stdout().lock(), &reports)?,
+    }
+    if let Some(last) = reports.last() {
+        print_simulation_summary(last);
+    }
+
+    Ok(())
+}
+
+fn replay(args: ReplayArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let logs = fs::read_to_string(&args.logs)
+        .map_err(|e| format!("Failed to read logs {}: {}", args.logs.display(), e))?;
+    let parsed = replay::parse_logs(&logs)?;
+
+    let (mut alternative, _) = new_pool(&args.pool)?;
+    let pool_token_supply = args
+        .pool_token_supply
+        .unwrap_or(alternative.pool_token_supply);
+    alternative.pool_token_supply = pool_token_supply;
+    let actual = ReplayTotals {
+        token_a_amount: args.pool.initial_supply_a,
+        token_b_amount: args.pool.initial_supply_b,
+        pool_token_supply,
+        ..Default::default()
+    };
+
+    let result = replay::replay(&parsed.events, actual, alternative);
+
+    match &args.output {
+        Some(path) => replay::write_csv(&mut File::create(path)?, &result.steps)?,
+        None => replay::write_csv(&mut io::stdout().lock(), &result.steps)?,
+    }
+    if parsed.skipped_swaps > 0 {
+        eprintln!(
+            "skipped {} swap events without swap inputs in the logs",
+            parsed.skipped_swaps
+        );
+    }
+    print_replay_summary(&result);
+
+    Ok(())
+}
+
+fn print_simulation_summary(last: &StepReport) {
+    eprintln!("steps: {}", last.step + 1);
+    eprintln!("swaps: {}", last.swaps);
+    eprintln!("volume: {:.6}", last.cumulative_volume);
+    eprintln!("lp pnl vs hodl: {:.6}", last.lp_pnl);
+    eprintln!("impermanent loss: {:.4}%", last.impermanent_loss * 100.0);
+    eprintln!("lp fee revenue: {:.6}", last.lp_fee_revenue);
+    eprintln!("owner fees: {:.6}", last.owner_fee_value);
+}
+
+fn print_replay_summary(result: &ReplayResult) {
+    let rows: [(&str, fn(&ReplayTotals) -> u64); 10] = [
+        ("token_a_amount", |t| t.token_a_amount),
+        ("token_b_amount", |t| t.token_b_amount),
+        ("pool_token_supply", |t| t.pool_token_supply),
+        ("trade_fees_a", |t| t.trade_fees_a),
+        ("trade_fees_b", |t| t.trade_fees_b),
+        ("owner_fees_a", |t| t.owner_fees_a),
+        ("owner_fees_b", |t| t.owner_fees_b),
+        ("swapped_out_a", |t| t.swapped_out_a),
+        ("swapped_out_b", |t| t.swapped_out_b),
+        ("swaps", |t| t.swaps),
+    ];
+    eprintln!("{:<20}{:>24}{:>24}", "", "actual", "alternative");
+    for (name, value) in rows {
+        eprintln!(
+            "{:<20}{:>24}{:>24}",
+            name,
+            value(&result.actual),
+            value(&result.alternative)
+        );
+    }
+    eprintln!(
+        "events failed on the alternative pool: {}",
+        result.alternative_failures
+    );
+}
@@ -0,0 +1,420 @@
+//! Arbitrary precision reference models of the swap curves
+//!
+//! Every model computes the exact result of an operation and rounds it once, in favour of the pool:
+//! swap and withdraw outputs are rounded down, deposit inputs are rounded up. The on-chain calculators
+//! are checked against them in differential tests.
+
+use num_bigint::BigInt;
+use num_traits::{One, ToPrimitive, Zero};
+
+use crate::StableSwapModel;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TradeDirection {
+    AtoB,
+    BtoA,
+}
+
+pub trait CurveModel {
+    /// Amount of destination tokens received for `source_amount` source tokens, rounded down
+    fn swap(
+        &self,
+        source_amount: u128,
+        pool_source_amount: u128,
+        pool_destination_amount: u128,
+        trade_direction: TradeDirection,
+    ) -> u128;
+
+    /// Amounts of token A and B required to mint `pool_tokens`, rounded up
+    fn deposit(
+        &self,
+        pool_tokens: u128,
+        pool_token_supply: u128,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+    ) -> (u128, u128);
+
+    /// Amounts of token A and B received for burning `pool_tokens`, rounded down
+    fn withdraw(
+        &self,
+        pool_tokens: u128,
+        pool_token_supply: u128,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+    ) -> (u128, u128);
+
+    /// Value of the curve invariant, which a swap must never decrease
+    fn invariant(&self, pool_token_a_amount: u128, pool_token_b_amount: u128) -> BigInt;
+}
+
+/// `x * y = k`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConstantProductModel;
+
+/// `x * (y + offset) = k`, where the offset is virtual token B liquidity
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OffsetModel {
+    pub token_b_offset: u128,
+}
+
+/// `x + y * price = k`, where the price is the amount of token A for one token B
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConstantPriceModel {
+    pub token_b_price: u128,
+}
+
+/// Stable swap invariant, solved with [StableSwapModel]
+///
+/// Swaps are only as exact as the convergence of Newton's method, within 1 of the exact result
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StableModel {
+    pub amp: u128,
+    pub token_a_factor: u128,
+    pub token_b_factor: u128,
+}
+
+impl CurveModel for ConstantProductModel {
+    fn swap(
+        &self,
+        source_amount: u128,
+        pool_source_amount: u128,
+        pool_destination_amount: u128,
+        _trade_direction: TradeDirection,
+    ) -> u128 {
+        constant_product_swap(source_amount, pool_source_amount, pool_destination_amount)
+    }
+
+    fn deposit(
+        &self,
+        pool_tokens: u128,
+        pool_token_supply: u128,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+    ) -> (u128, u128) {
+        (
+            share(
+                pool_tokens,
+                pool_token_supply,
+                pool_token_a_amount,
+                Round::Up,
+            ),
+            share(
+                pool_tokens,
+                pool_token_supply,
+                pool_token_b_amount,
+                Round::Up,
+            ),
+        )
+    }
+
+    fn withdraw(
+        &self,
+        pool_tokens: u128,
+        pool_token_supply: u128,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+    ) -> (u128, u128) {
+        (
+            share(
+                pool_tokens,
+                pool_token_supply,
+                pool_token_a_amount,
+                Round::Down,
+            ),
+            share(
+                pool_tokens,
+                pool_token_supply,
+                pool_token_b_amount,
+                Round::Down,
+            ),
+        )
+    }
+
+    fn invariant(&self, pool_token_a_amount: u128, pool_token_b_amount: u128) -> BigInt {
+        BigInt::from(pool_token_a_amount) * BigInt::from(pool_token_b_amount)
+    }
+}
+
+impl OffsetModel {
+    fn virtual_token_b_amount(&self, pool_token_b_amount: u128) -> u128 {
+        pool_token_b_amount + self.token_b_offset
+    }
+}
+
+impl CurveModel for OffsetModel {
+    fn swap(
+        &self,
+        source_amount: u128,
+        pool_source_amount: u128,
+        pool_destination_amount: u128,
+        trade_direction: TradeDirection,
+    ) -> u128 {
+        let (pool_source_amount, pool_destination_amount) = match trade_direction {
+            TradeDirection::AtoB => (
+                pool_source_amount,
+                self.virtual_token_b_amount(pool_destination_amount),
+            ),
+            TradeDirection::BtoA => (
+                self.virtual_token_b_amount(pool_source_amount),
+                pool_destination_amount,
+            ),
+        };
+        constant_product_swap(source_amount, pool_source_amount, pool_destination_amount)
+    }
+
+    /// Pool tokens are a share of the virtual token B liquidity as well
+    fn deposit(
+        &self,
+        pool_tokens: u128,
+        pool_token_supply: u128,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+    ) -> (u128, u128) {
+        ConstantProductModel.deposit(
+            pool_tokens,
+            pool_token_supply,
+            pool_token_a_amount,
+            self.virtual_token_b_amount(pool_token_b_amount),
+        )
+    }
+
+    /// Pool tokens are a share of the virtual token B liquidity as well
+    fn withdraw(
+        &self,
+        pool_tokens: u128,
+        pool_token_supply: u128,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+    ) -> (u128, u128) {
+        ConstantProductModel.withdraw(
+            pool_tokens,
+            pool_token_supply,
+            pool_token_a_amount,
+            self.virtual_token_b_amount(pool_token_b_amount),
+        )
+    }
+
+    fn invariant(&self, pool_token_a_amount: u128, pool_token_b_amount: u128) -> BigInt {
+        ConstantProductModel.invariant(
+            pool_token_a_amount,
+            self.virtual_token_b_amount(pool_token_b_amount),
+        )
+    }
+}
+
+impl ConstantPriceModel {
+    /// Total value of the pool in token A
+    fn value(&self, pool_token_a_amount: u128, pool_token_b_amount: u128) -> BigInt {
+        BigInt::from(pool_token_a_amount)
+            + BigInt::from(pool_token_b_amount) * BigInt::from(self.token_b_price)
+    }
+
+    /// Half the value of the pool tokens is paid in token A and the other half in token B
+    fn value_share(
+        &self,
+        pool_tokens: u128,
+        pool_token_supply: u128,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+        round: Round,
+    ) -> (u128, u128) {
+        let value =
+            BigInt::from(pool_tokens) * self.value(pool_token_a_amount, pool_token_b_amount);
+        let denominator = BigInt::from(2) * BigInt::from(pool_token_supply);
+        (
+            round.div(&value, &denominator),
+            round.div(&value, &(denominator * BigInt::from(self.token_b_price))),
+        )
+    }
+}
+
+impl CurveModel for ConstantPriceModel {
+    fn swap(
+        &self,
+        source_amount: u128,
+        _pool_source_amount: u128,
+        _pool_destination_amount: u128,
+        trade_direction: TradeDirection,
+    ) -> u128 {
+        match trade_direction {
+            TradeDirection::AtoB => source_amount / self.token_b_price,
+            TradeDirection::BtoA => source_amount * self.token_b_price,
+        }
+    }
+
+    fn deposit(
+        &self,
+        pool_tokens: u128,
+        pool_token_supply: u128,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+    ) -> (u128, u128) {
+        self.value_share(
+            pool_tokens,
+            pool_token_supply,
+            pool_token_a_amount,
+            pool_token_b_amount,
+            Round::Up,
+        )
+    }
+
+    fn withdraw(
+        &self,
+        pool_tokens: u128,
+        pool_token_supply: u128,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+    ) -> (u128, u128) {
+        self.value_share(
+            pool_tokens,
+            pool_token_supply,
+            pool_token_a_amount,
+            pool_token_b_amount,
+            Round::Down,
+        )
+    }
+
+    fn invariant(&self, pool_token_a_amount: u128, pool_token_b_amount: u128) -> BigInt {
+        self.value(pool_token_a_amount, pool_token_b_amount)
+    }
+}
+
+impl StableModel {
+    fn model(&self, balances: Vec<u128>, rates: Vec<u128>) -> StableSwapModel {
+        StableSwapModel::new(self.amp, balances, rates, 2)
+    }
+}
+
+impl CurveModel for StableModel {
+    fn swap(
+        &self,
+        source_amount: u128,
+        pool_source_amount: u128,
+        pool_destination_amount: u128,
+        trade_direction: TradeDirection,
+    ) -> u128 {
+        let rates = match trade_direction {
+            TradeDirection::AtoB => vec![self.token_a_factor, self.token_b_factor],
+            TradeDirection::BtoA => vec![self.token_b_factor, self.token_a_factor],
+        };
+        self.model(vec![pool_source_amount, pool_destination_amount], rates)
+            .sim_exchange(0, 1, source_amount)
+    }
+
+    fn deposit(
+        &self,
+        pool_tokens: u128,
+        pool_token_supply: u128,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+    ) -> (u128, u128) {
+        ConstantProductModel.deposit(
+            pool_tokens,
+            pool_token_supply,
+            pool_token_a_amount,
+            pool_token_b_amount,
+        )
+    }
+
+    fn withdraw(
+        &self,
+        pool_tokens: u128,
+        pool_token_supply: u128,
+        pool_token_a_amount: u128,
+        pool_token_b_amount: u128,
+    ) -> (u128, u128) {
+        ConstantProductModel.withdraw(
+            pool_tokens,
+            pool_token_supply,
+            pool_token_a_amount,
+            pool_token_b_amount,
+        )
+    }
+
+    fn invariant(&self, pool_token_a_amount: u128, pool_token_b_amount: u128) -> BigInt {
+        BigInt::from(
+            self.model(
+                vec![pool_token_a_amount, pool_token_b_amount],
+                vec![self.token_a_factor, self.token_b_factor],
+            )
+            .sim_d(),
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Round {
+    Down,
+    Up,
+}
+
+impl Round {
+    fn div(&self, numerator: &BigInt, denominator: &BigInt) -> u128 {
+        let quotient = match self {
+            Round::Down => numerator / denominator,
+            Round::Up => (numerator + denominator - BigInt::one()) / denominator,
+        };
+        quotient.to_u128().unwrap()
+    }
+}
+
+/// `destination * source_amount / (source + source_amount)`, rounded down
+fn constant_product_swap(
+    source_amount: u128,
+    pool_source_amount: u128,
+    pool_destination_amount: u128,
+) -> u128 {
+    let new_pool_source_amount = BigInt::from(pool_source_amount) + BigInt::from(source_amount);
+    if new_pool_source_amount.is_zero() {
+        return 0;
+    }
+    Round::Down.div(
+        &(BigInt::from(pool_destination_amount) * BigInt::from(source_amount)),
+        &new_pool_source_amount,
+    )
+}
+
+/// `amount * pool_tokens / pool_token_supply`
+fn share(pool_tokens: u128, pool_token_supply: u128, amount: u128, round: Round) -> u128 {
+    round.div(
+        &(BigInt::from(pool_tokens) * BigInt::from(amount)),
+        &BigInt::from(pool_token_supply),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constant_product_swap_rounds_down() {
+        assert_eq!(
+            ConstantProductModel.swap(1, 2, 100, TradeDirection::AtoB),
+            33
+        );
+        assert_eq!(ConstantProductModel.deposit(1, 3, 100, 200), (34, 67));
+        assert_eq!(ConstantProductModel.withdraw(1, 3, 100, 200), (33, 66));
+    }
+
+    #[test]
+    fn offset_uses_virtual_liquidity() {
+        let model = OffsetModel {
+            token_b_offset: 100,
+        };
+        assert_eq!(
+            model.swap(1, 1, 0, TradeDirection::AtoB),
+            ConstantProductModel.swap(1, 1, 100, TradeDirection::AtoB)
+        );
+        assert_eq!(model.invariant(2, 0), BigInt::from(200));
+    }
+
+    #[test]
+    fn constant_price_splits_value() {
+        let model = ConstantPriceModel { token_b_price: 3 };
+        assert_eq!(model.swap(10, 0, 0, TradeDirection::AtoB), 3);
+        assert_eq!(model.swap(10, 0, 0, TradeDirection::BtoA), 30);
+        // pool value of 9 + 1 * 3 = 12 token A, a third of it is 2 token A and 2 / 3 token B
+        assert_eq!(model.withdraw(1, 3, 9, 1), (2, 0));
+        assert_eq!(model.deposit(1, 3, 9, 1), (2, 1));
+    }
+}
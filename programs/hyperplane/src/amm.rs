@@ -0,0 +1,286 @@
+//! Adapter for aggregators routing through Hyperplane pools
+//!
+//! Follows the interface aggregators use for AMMs: construct from the pool account, fetch the accounts
+//! returned by [Amm::get_accounts_to_update] and pass them to [Amm::update], then [Amm::quote] and build the
+//! swap with [Amm::get_swap_and_account_metas]. Quotes come from [crate::quote], so they match the amounts
+//! the `swap` instruction transfers, including Token-2022 transfer fees.
+
+use std::collections::HashMap;
+
+use anchor_lang::{
+    error::ErrorCode,
+    prelude::*,
+    solana_program::{instruction::AccountMeta, sysvar::SysvarId},
+};
+use arrayref::array_ref;
+
+use crate::{
+    curve::calculator::{AorB, TradeDirection},
+    error::SwapError,
+    instruction_builder,
+    instruction_builder::PoolKeys,
+    ix,
+    quote::{PoolAccountsData, PoolSnapshot},
+    state::SwapPool,
+};
+
+/// Owner and data of an account, as fetched from the chain
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccountData {
+    pub owner: Pubkey,
+    pub data: Vec<u8>,
+}
+
+/// Accounts fetched by the aggregator, by address
+pub type AccountMap = HashMap<Pubkey, AccountData>;
+
+/// An account and its address
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KeyedAccount {
+    pub key: Pubkey,
+    pub account: AccountData,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuoteParams {
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub in_amount: u64,
+    /// Quote as if a host fees account is passed to the swap - affects rounding of input transfer fees
+    pub with_host_fee: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Quote {
+    pub in_amount: u64,
+    /// Amount of output tokens received by the user, after transfer fees
+    pub out_amount: u64,
+    /// Trading, owner and host fees paid in input tokens
+    pub fee_amount: u64,
+    pub fee_mint: Pubkey,
+    /// Fees as a fraction of the input amount
+    pub fee_pct: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SwapParams {
+    pub source_mint: Pubkey,
+    pub destination_mint: Pubkey,
+    pub user_source_token_account: Pubkey,
+    pub user_destination_token_account: Pubkey,
+    pub user_transfer_authority: Pubkey,
+    pub in_amount: u64,
+    pub minimum_amount_out: u64,
+    /// Optional token account of the source mint receiving the host share of the owner fees
+    pub host_fee_account: Option<Pubkey>,
+}
+
+/// Accounts and data of a `swap` instruction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SwapAndAccountMetas {
+    pub account_metas: Vec<AccountMeta>,
+    pub data: Vec<u8>,
+}
+
+pub trait Amm {
+    fn from_keyed_account(keyed_account: &KeyedAccount) -> Result<Self>
+    where
+        Self: Sized;
+    /// Label of the AMM, for display in routes
+    fn label(&self) -> String;
+    fn program_id(&self) -> Pubkey;
+    /// Address of the pool
+    fn key(&self) -> Pubkey;
+    fn get_reserve_mints(&self) -> Vec<Pubkey>;
+    /// Accounts required by [Amm::update] to quote the pool
+    fn get_accounts_to_update(&self) -> Vec<Pubkey>;
+    fn update(&mut self, account_map: &AccountMap) -> Result<()>;
+    fn quote(&self, quote_params: &QuoteParams) -> Result<Quote>;
+    fn get_swap_and_account_metas(&self, swap_params: &SwapParams) -> Result<SwapAndAccountMetas>;
+    fn clone_amm(&self) -> Box<dyn Amm + Send + Sync>;
+}
+
+#[derive(Clone, Debug)]
+pub struct HyperplaneAmm {
+    key: Pubkey,
+    program_id: Pubkey,
+    pool: SwapPool,
+    /// Set by the first [Amm::update], the token programs are the owners of the fetched mints
+    keys: Option<PoolKeys>,
+    /// Set by the first [Amm::update]
+    snapshot: Option<PoolSnapshot>,
+}
+
+impl HyperplaneAmm {
+    fn snapshot(&self) -> Result<&PoolSnapshot> {
+        self.snapshot.as_ref().ok_or_else(|| {
+            msg!("The pool must be updated before quoting or swapping");
+            error!(ErrorCode::AccountNotInitialized)
+        })
+    }
+
+    fn a_or_b(&self, mint: &Pubkey) -> Result<AorB> {
+        if *mint == self.pool.token_a_mint {
+            Ok(AorB::A)
+        } else if *mint == self.pool.token_b_mint {
+            Ok(AorB::B)
+        } else {
+            err!(SwapError::IncorrectTradingMint)
+        }
+    }
+}
+
+impl Amm for HyperplaneAmm {
+    fn from_keyed_account(keyed_account: &KeyedAccount) -> Result<Self> {
+        let pool = SwapPool::try_deserialize(&mut keyed_account.account.data.as_slice())?;
+        Ok(Self {
+            key: keyed_account.key,
+            program_id: keyed_account.account.owner,
+            pool,
+            keys: None,
+            snapshot: None,
+        })
+    }
+
+    fn label(&self) -> String {
+        "Hyperplane".to_string()
+    }
+
+    fn program_id(&self) -> Pubkey {
+        self.program_id
+    }
+
+    fn key(&self) -> Pubkey {
+        self.key
+    }
+
+    fn get_reserve_mints(&self) -> Vec<Pubkey> {
+        vec![self.pool.token_a_mint, self.pool.token_b_mint]
+    }
+
+    fn get_accounts_to_update(&self) -> Vec<Pubkey> {
+        vec![
+            self.key,
+            self.pool.swap_curve,
+            self.pool.token_a_vault,
+            self.pool.token_b_vault,
+            self.pool.pool_token_mint,
+            self.pool.token_a_mint,
+            self.pool.token_b_mint,
+            Clock::id(),
+        ]
+    }
+
+    fn update(&mut self, account_map: &AccountMap) -> Result<()> {
+        let get_account = |address: &Pubkey| {
+            account_map.get(address).ok_or_else(|| {
+                msg!("Missing account {} to update the pool", address);
+                error!(ErrorCode::AccountNotInitialized)
+            })
+        };
+        let pool = get_account(&self.key)?;
+        let token_a_mint = get_account(&self.pool.token_a_mint)?;
+        let token_b_mint = get_account(&self.pool.token_b_mint)?;
+        let pool_token_mint = get_account(&self.pool.pool_token_mint)?;
+        let snapshot = PoolSnapshot::from_accounts_data(
+            PoolAccountsData {
+                pool: &pool.data,
+                swap_curve: &get_account(&self.pool.swap_curve)?.data,
+                token_a_vault: &get_account(&self.pool.token_a_vault)?.data,
+                token_b_vault: &get_account(&self.pool.token_b_vault)?.data,
+                pool_token_mint: &pool_token_mint.data,
+                token_a_mint: &token_a_mint.data,
+                token_b_mint: &token_b_mint.data,
+            },
+            epoch_from_clock_data(&get_account(&Clock::id())?.data)?,
+        )?;
+
+        self.keys = Some(PoolKeys::from_pool(
+            &self.program_id,
+            &self.key,
+            &snapshot.pool,
+            &pool_token_mint.owner,
+            &token_a_mint.owner,
+            &token_b_mint.owner,
+        ));
+        self.pool = snapshot.pool;
+        self.snapshot = Some(snapshot);
+        Ok(())
+    }
+
+    fn quote(&self, quote_params: &QuoteParams) -> Result<Quote> {
+        let snapshot = self.snapshot()?;
+        self.a_or_b(&quote_params.output_mint)?;
+        require!(
+            quote_params.input_mint != quote_params.output_mint,
+            SwapError::RepeatedMint
+        );
+        let quote = snapshot.swap(
+            &quote_params.input_mint,
+            quote_params.in_amount,
+            quote_params.with_host_fee,
+        )?;
+        let fee_pct = if quote.amount_in > 0 {
+            quote.total_fees as f64 / quote.amount_in as f64
+        } else {
+            0.0
+        };
+        Ok(Quote {
+            in_amount: quote.amount_in,
+            out_amount: quote.amount_out,
+            fee_amount: quote.total_fees,
+            fee_mint: quote_params.input_mint,
+            fee_pct,
+        })
+    }
+
+    fn get_swap_and_account_metas(&self, swap_params: &SwapParams) -> Result<SwapAndAccountMetas> {
+        // token programs are only known once the mints are fetched
+        self.snapshot()?;
+        let keys = self
+            .keys
+            .as_ref()
+            .ok_or_else(|| error!(ErrorCode::AccountNotInitialized))?;
+        let trade_direction = match self.a_or_b(&swap_params.source_mint)? {
+            AorB::A => TradeDirection::AtoB,
+            AorB::B => TradeDirection::BtoA,
+        };
+        self.a_or_b(&swap_params.destination_mint)?;
+        require!(
+            swap_params.source_mint != swap_params.destination_mint,
+            SwapError::RepeatedMint
+        );
+        let instruction = instruction_builder::swap(
+            keys,
+            &swap_params.user_transfer_authority,
+            trade_direction,
+            &swap_params.user_source_token_account,
+            &swap_params.user_destination_token_account,
+            swap_params.host_fee_account.as_ref(),
+            ix::Swap {
+                amount_in: swap_params.in_amount,
+                minimum_amount_out: swap_params.minimum_amount_out,
+            },
+        )?;
+        Ok(SwapAndAccountMetas {
+            account_metas: instruction.accounts,
+            data: instruction.data,
+        })
+    }
+
+    fn clone_amm(&self) -> Box<dyn Amm + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+/// Read the epoch from the data of the clock sysvar account
+///
+/// `Clock` is bincode serialized as its fields in order: slot, epoch_start_timestamp, epoch, ...
+fn epoch_from_clock_data(data: &[u8]) -> Result<u64> {
+    require_gte!(
+        data.len(),
+        std::mem::size_of::<Clock>(),
+        ErrorCode::AccountDidNotDeserialize
+    );
+    Ok(u64::from_le_bytes(*array_ref![data, 16, 8]))
+}
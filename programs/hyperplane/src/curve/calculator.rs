@@ -15,6 +15,13 @@ use crate::{error::SwapError, require_msg};
 /// input amounts, and Balancer uses 100 * 10 ^ 18.
 pub const INITIAL_SWAP_POOL_AMOUNT: u128 = 1_000_000_000;
 
+/// Default amount of pool tokens permanently locked when a pool is initialized,
+/// so the pool token supply can never return to zero.
+/// Similar to the `MINIMUM_LIQUIDITY` burned by Uniswap v2, this prevents the
+/// first depositor from inflating the value of a pool token.
+/// The amount is chosen by the pool admin, and must be less than the new pool supply.
+pub const MIN_LIQUIDITY: u128 = 1_000;
+
 /// Hardcode the number of token types in a pool, used to calculate the
 /// equivalent pool tokens for the owner trading fee.
 pub const TOKENS_IN_POOL: u128 = 2;
@@ -32,6 +39,7 @@ pub enum TradeDirection {
 }
 
 /// Utility to represent either token A or token B
+#[cfg_attr(feature = "fuzz", derive(Arbitrary))]
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum AorB {
     A,
@@ -169,6 +177,7 @@ pub trait CurveCalculator: Debug + DynAccountSerialize {
 /// Test helpers for curves
 #[cfg(test)]
 pub mod test {
+    use hyperplane_sim::model::CurveModel;
     use proptest::prelude::*;
     use spl_math::uint::U256;
 
@@ -332,6 +341,176 @@ pub mod test {
             .greater_than_or_equal(&value.checked_mul(&new_pool_token_supply).unwrap()));
     }
 
+    /// Trade direction of the reference models
+    pub fn model_direction(
+        trade_direction: TradeDirection,
+    ) -> hyperplane_sim::model::TradeDirection {
+        match trade_direction {
+            TradeDirection::AtoB => hyperplane_sim::model::TradeDirection::AtoB,
+            TradeDirection::BtoA => hyperplane_sim::model::TradeDirection::BtoA,
+        }
+    }
+
+    /// Test function checking a swap against the exact result of the reference model.
+    ///
+    /// The pool must never give more than the exact amount for the source tokens
+    /// it takes, and the swap must never decrease the invariant of the model.
+    ///
+    /// Returns the swap result and the amount of destination tokens the pool
+    /// kept from rounding, or `None` if the swap is rejected.
+    pub fn check_swap_against_model(
+        curve: &dyn CurveCalculator,
+        model: &dyn CurveModel,
+        source_token_amount: u128,
+        swap_source_amount: u128,
+        swap_destination_amount: u128,
+        trade_direction: TradeDirection,
+    ) -> Option<(SwapWithoutFeesResult, u128)> {
+        let results = curve
+            .swap_without_fees(
+                source_token_amount,
+                swap_source_amount,
+                swap_destination_amount,
+                trade_direction,
+            )
+            .ok()?;
+        // the transfer out of the pool would fail
+        if results.destination_amount_swapped > swap_destination_amount {
+            return None;
+        }
+        assert!(results.source_amount_swapped <= source_token_amount);
+
+        let model_destination_amount = model.swap(
+            results.source_amount_swapped,
+            swap_source_amount,
+            swap_destination_amount,
+            model_direction(trade_direction),
+        );
+        assert!(
+            results.destination_amount_swapped <= model_destination_amount,
+            "result={:?}, model={}",
+            results,
+            model_destination_amount
+        );
+
+        let (
+            swap_token_a_amount,
+            swap_token_b_amount,
+            new_swap_token_a_amount,
+            new_swap_token_b_amount,
+        ) = match trade_direction {
+            TradeDirection::AtoB => (
+                swap_source_amount,
+                swap_destination_amount,
+                swap_source_amount + results.source_amount_swapped,
+                swap_destination_amount - results.destination_amount_swapped,
+            ),
+            TradeDirection::BtoA => (
+                swap_destination_amount,
+                swap_source_amount,
+                swap_destination_amount - results.destination_amount_swapped,
+                swap_source_amount + results.source_amount_swapped,
+            ),
+        };
+        assert!(
+            model.invariant(new_swap_token_a_amount, new_swap_token_b_amount)
+                >= model.invariant(swap_token_a_amount, swap_token_b_amount)
+        );
+
+        let rounding = model_destination_amount - results.destination_amount_swapped;
+        Some((results, rounding))
+    }
+
+    /// Test function checking a deposit against the exact result of the reference model.
+    ///
+    /// The depositor must pay at least the exact amounts, unless the amount is
+    /// zero, which is rejected when processing the deposit.
+    ///
+    /// Returns the amounts of token A and B the depositor overpaid from rounding.
+    pub fn check_deposit_against_model(
+        curve: &dyn CurveCalculator,
+        model: &dyn CurveModel,
+        pool_token_amount: u128,
+        pool_token_supply: u128,
+        swap_token_a_amount: u128,
+        swap_token_b_amount: u128,
+    ) -> (u128, u128) {
+        let results = curve
+            .pool_tokens_to_trading_tokens(
+                pool_token_amount,
+                pool_token_supply,
+                swap_token_a_amount,
+                swap_token_b_amount,
+                RoundDirection::Ceiling,
+            )
+            .unwrap();
+        let (model_token_a_amount, model_token_b_amount) = model.deposit(
+            pool_token_amount,
+            pool_token_supply,
+            swap_token_a_amount,
+            swap_token_b_amount,
+        );
+        let overpaid = |amount: u128, model_amount: u128| {
+            if amount == 0 {
+                return 0;
+            }
+            assert!(
+                amount >= model_amount,
+                "result={:?}, model=({}, {})",
+                results,
+                model_token_a_amount,
+                model_token_b_amount
+            );
+            amount - model_amount
+        };
+        (
+            overpaid(results.token_a_amount, model_token_a_amount),
+            overpaid(results.token_b_amount, model_token_b_amount),
+        )
+    }
+
+    /// Test function checking a withdrawal against the exact result of the reference model.
+    ///
+    /// The pool must never give more than the exact amounts.
+    ///
+    /// Returns the amounts of token A and B the pool kept from rounding.
+    pub fn check_withdraw_against_model(
+        curve: &dyn CurveCalculator,
+        model: &dyn CurveModel,
+        pool_token_amount: u128,
+        pool_token_supply: u128,
+        swap_token_a_amount: u128,
+        swap_token_b_amount: u128,
+    ) -> (u128, u128) {
+        let results = curve
+            .pool_tokens_to_trading_tokens(
+                pool_token_amount,
+                pool_token_supply,
+                swap_token_a_amount,
+                swap_token_b_amount,
+                RoundDirection::Floor,
+            )
+            .unwrap();
+        let (model_token_a_amount, model_token_b_amount) = model.withdraw(
+            pool_token_amount,
+            pool_token_supply,
+            swap_token_a_amount,
+            swap_token_b_amount,
+        );
+        assert!(
+            results.token_a_amount <= model_token_a_amount
+                && results.token_b_amount <= model_token_b_amount,
+            "result={:?}, model=({}, {})",
+            results,
+            model_token_a_amount,
+            model_token_b_amount
+        );
+        (
+            model_token_a_amount - results.token_a_amount,
+            model_token_b_amount - results.token_b_amount,
+        )
+    }
+
     prop_compose! {
         pub fn total_and_intermediate(max_value: u64)(total in 1..max_value)
                         (intermediate in 1..total, total in Just(total))
@@ -197,12 +197,16 @@ mod tests {
     use std::borrow::BorrowMut;
 
     use anchor_lang::AccountDeserialize;
+    use hyperplane_sim::model::ConstantPriceModel;
     use proptest::prelude::*;
 
     use super::*;
     use crate::{
         curve::calculator::{
-            test::{check_curve_value_from_swap, total_and_intermediate},
+            test::{
+                check_curve_value_from_swap, check_swap_against_model,
+                check_withdraw_against_model, total_and_intermediate,
+            },
             INITIAL_SWAP_POOL_AMOUNT,
         },
         state::Curve,
@@ -474,4 +478,55 @@ mod tests {
             assert!(new_value.checked_mul(&pool_token_supply).unwrap().greater_than_or_equal(&value.checked_mul(&new_pool_token_supply).unwrap()));
         }
     }
+
+    proptest! {
+        #[test]
+        fn swap_matches_model(
+            source_token_amount in 1..u64::MAX,
+            swap_source_amount in 1..u64::MAX,
+            swap_destination_amount in 1..u64::MAX,
+            token_b_price in 1..u32::MAX, // kept small to avoid overflowing swaps from b to a
+            a_to_b in any::<bool>(),
+        ) {
+            let curve = ConstantPriceCurve { token_b_price: token_b_price as u64, ..Default::default() };
+            let model = ConstantPriceModel { token_b_price: token_b_price as u128 };
+            let trade_direction = if a_to_b { TradeDirection::AtoB } else { TradeDirection::BtoA };
+            if let Some((_, rounding)) = check_swap_against_model(
+                &curve,
+                &model,
+                source_token_amount as u128,
+                swap_source_amount as u128,
+                swap_destination_amount as u128,
+                trade_direction,
+            ) {
+                // the source amount is floored to a multiple of the price, so nothing is lost
+                prop_assert_eq!(rounding, 0);
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn withdraw_matches_model(
+            (pool_token_supply, pool_token_amount) in total_and_intermediate(u64::MAX),
+            swap_token_a_amount in 1..u64::MAX,
+            swap_token_b_amount in 1..u32::MAX, // kept small to avoid overflowing the pool value
+            token_b_price in 1..u32::MAX,
+        ) {
+            let curve = ConstantPriceCurve { token_b_price: token_b_price as u64, ..Default::default() };
+            let model = ConstantPriceModel { token_b_price: token_b_price as u128 };
+            let (token_a_rounding, token_b_rounding) = check_withdraw_against_model(
+                &curve,
+                &model,
+                pool_token_amount as u128,
+                pool_token_supply as u128,
+                swap_token_a_amount as u128,
+                swap_token_b_amount as u128,
+            );
+            // the normalized value of the pool is floored to half a token A before being shared,
+            // deposits are not checked as that rounding goes in favour of the depositor
+            prop_assert!(token_a_rounding <= 1);
+            prop_assert!(token_b_rounding <= 1);
+        }
+    }
 }
@@ -120,14 +120,16 @@ mod tests {
     use std::borrow::BorrowMut;
 
     use anchor_lang::AccountDeserialize;
+    use hyperplane_sim::model::ConstantProductModel;
     use proptest::prelude::*;
 
     use super::*;
     use crate::{
         curve::calculator::{
             test::{
-                check_curve_value_from_swap, check_pool_value_from_deposit,
-                check_pool_value_from_withdraw, total_and_intermediate,
+                check_curve_value_from_swap, check_deposit_against_model,
+                check_pool_value_from_deposit, check_pool_value_from_withdraw,
+                check_swap_against_model, check_withdraw_against_model, total_and_intermediate,
             },
             RoundDirection, INITIAL_SWAP_POOL_AMOUNT,
         },
@@ -338,4 +340,65 @@ mod tests {
             );
         }
     }
+
+    proptest! {
+        #[test]
+        fn swap_matches_model(
+            source_token_amount in 1..u64::MAX,
+            swap_source_amount in 1..u64::MAX,
+            swap_destination_amount in 1..u64::MAX,
+        ) {
+            let curve = ConstantProductCurve { ..Default::default() };
+            let swap_source_amount = swap_source_amount as u128;
+            let swap_destination_amount = swap_destination_amount as u128;
+            if let Some((results, rounding)) = check_swap_against_model(
+                &curve,
+                &ConstantProductModel,
+                source_token_amount as u128,
+                swap_source_amount,
+                swap_destination_amount,
+                TradeDirection::AtoB,
+            ) {
+                // the pool keeps less than one source token worth of destination tokens,
+                // at the price after the swap
+                let new_swap_source_amount = swap_source_amount + results.source_amount_swapped;
+                let new_swap_destination_amount = swap_destination_amount - results.destination_amount_swapped;
+                prop_assert!(rounding <= 1 + new_swap_destination_amount / new_swap_source_amount);
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn deposit_and_withdraw_match_model(
+            (pool_token_supply, pool_token_amount) in total_and_intermediate(u64::MAX),
+            swap_token_a_amount in 1..u64::MAX,
+            swap_token_b_amount in 1..u64::MAX,
+        ) {
+            let curve = ConstantProductCurve { ..Default::default() };
+            // proportional amounts are only rounded once
+            prop_assert_eq!(
+                check_deposit_against_model(
+                    &curve,
+                    &ConstantProductModel,
+                    pool_token_amount as u128,
+                    pool_token_supply as u128,
+                    swap_token_a_amount as u128,
+                    swap_token_b_amount as u128,
+                ),
+                (0, 0)
+            );
+            prop_assert_eq!(
+                check_withdraw_against_model(
+                    &curve,
+                    &ConstantProductModel,
+                    pool_token_amount as u128,
+                    pool_token_supply as u128,
+                    swap_token_a_amount as u128,
+                    swap_token_b_amount as u128,
+                ),
+                (0, 0)
+            );
+        }
+    }
 }
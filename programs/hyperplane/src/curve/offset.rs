@@ -122,13 +122,15 @@ mod tests {
     use std::borrow::BorrowMut;
 
     use anchor_lang::AccountDeserialize;
+    use hyperplane_sim::model::OffsetModel;
     use proptest::prelude::*;
 
     use super::*;
     use crate::{
         curve::calculator::test::{
             check_curve_value_from_swap, check_pool_value_from_deposit,
-            check_pool_value_from_withdraw, total_and_intermediate,
+            check_pool_value_from_withdraw, check_swap_against_model, check_withdraw_against_model,
+            total_and_intermediate,
         },
         state::Curve,
     };
@@ -394,4 +396,72 @@ mod tests {
             );
         }
     }
+
+    proptest! {
+        #[test]
+        fn swap_matches_model(
+            source_token_amount in 1..u64::MAX,
+            swap_token_a_amount in 1..u64::MAX,
+            swap_token_b_amount in 1..u64::MAX,
+            token_b_offset in 1..u64::MAX,
+            a_to_b in any::<bool>(),
+        ) {
+            let curve = OffsetCurve { token_b_offset, ..Default::default() };
+            let model = OffsetModel { token_b_offset: token_b_offset as u128 };
+            let swap_token_a_amount = swap_token_a_amount as u128;
+            let swap_token_b_amount = swap_token_b_amount as u128;
+            let token_b_offset = token_b_offset as u128;
+            let (trade_direction, swap_source_amount, swap_destination_amount) = if a_to_b {
+                (TradeDirection::AtoB, swap_token_a_amount, swap_token_b_amount)
+            } else {
+                (TradeDirection::BtoA, swap_token_b_amount, swap_token_a_amount)
+            };
+            if let Some((results, rounding)) = check_swap_against_model(
+                &curve,
+                &model,
+                source_token_amount as u128,
+                swap_source_amount,
+                swap_destination_amount,
+                trade_direction,
+            ) {
+                // the pool keeps less than one source token worth of destination tokens,
+                // at the price of the virtual liquidity after the swap
+                let (new_source_amount, new_destination_amount) = match trade_direction {
+                    TradeDirection::AtoB => (
+                        swap_token_a_amount + results.source_amount_swapped,
+                        swap_token_b_amount + token_b_offset - results.destination_amount_swapped,
+                    ),
+                    TradeDirection::BtoA => (
+                        swap_token_b_amount + token_b_offset + results.source_amount_swapped,
+                        swap_token_a_amount - results.destination_amount_swapped,
+                    ),
+                };
+                prop_assert!(rounding <= 1 + new_destination_amount / new_source_amount);
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn withdraw_matches_model(
+            (pool_token_supply, pool_token_amount) in total_and_intermediate(u64::MAX),
+            swap_token_a_amount in 1..u64::MAX,
+            (swap_token_b_amount, token_b_offset) in values_sum_within_u64(),
+        ) {
+            let curve = OffsetCurve { token_b_offset, ..Default::default() };
+            let model = OffsetModel { token_b_offset: token_b_offset as u128 };
+            // deposits are disabled for offset curves
+            prop_assert_eq!(
+                check_withdraw_against_model(
+                    &curve,
+                    &model,
+                    pool_token_amount as u128,
+                    pool_token_supply as u128,
+                    swap_token_a_amount as u128,
+                    swap_token_b_amount as u128,
+                ),
+                (0, 0)
+            );
+        }
+    }
 }
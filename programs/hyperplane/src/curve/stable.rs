@@ -462,6 +462,24 @@ impl DynAccountSerialize for StableCurve {
     }
 }
 
+/// Invariant math exposed to the fuzz targets
+#[cfg(feature = "fuzz")]
+pub mod fuzz {
+    use anchor_lang::Result;
+
+    pub fn compute_ann(amp: u64) -> Result<u64> {
+        super::compute_ann(amp)
+    }
+
+    pub fn compute_d(ann: u64, amount_a: u128, amount_b: u128) -> Result<u128> {
+        super::compute_d(ann, amount_a, amount_b)
+    }
+
+    pub fn compute_y(ann: u64, x: u128, d: u128) -> Result<u128> {
+        super::compute_y(ann, x, d)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -471,15 +489,16 @@ mod tests {
     use std::{borrow::BorrowMut, cmp::Ordering};
 
     use anchor_lang::AccountDeserialize;
-    use hyperplane_sim::StableSwapModel;
+    use hyperplane_sim::{model::StableModel, StableSwapModel};
     use proptest::prelude::*;
 
     use super::*;
     use crate::{
         curve::calculator::{
             test::{
-                check_curve_value_from_swap, check_pool_value_from_deposit,
-                check_pool_value_from_withdraw, total_and_intermediate,
+                check_curve_value_from_swap, check_deposit_against_model,
+                check_pool_value_from_deposit, check_pool_value_from_withdraw,
+                check_swap_against_model, check_withdraw_against_model, total_and_intermediate,
             },
             RoundDirection, INITIAL_SWAP_POOL_AMOUNT,
         },
@@ -1545,4 +1564,64 @@ mod tests {
             );
         }
     }
+
+    proptest! {
+        #[test]
+        fn swap_matches_model(
+            source_token_amount in 1..u64::MAX,
+            swap_source_amount in 1..u64::MAX,
+            swap_destination_amount in 1..u64::MAX,
+            amp in MIN_AMP..MAX_AMP,
+        ) {
+            let curve = StableCurve { amp, token_a_factor: 1, token_b_factor: 1, ..Default::default() };
+            let model = StableModel { amp: amp.into(), token_a_factor: 1, token_b_factor: 1 };
+            if let Some((_, rounding)) = check_swap_against_model(
+                &curve,
+                &model,
+                source_token_amount as u128,
+                swap_source_amount as u128,
+                swap_destination_amount as u128,
+                TradeDirection::AtoB,
+            ) {
+                // both solve y with Newton's method, rounded up on-chain and down in the model
+                prop_assert!(rounding <= 1);
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn deposit_and_withdraw_match_model(
+            (pool_token_supply, pool_token_amount) in total_and_intermediate(u64::MAX),
+            swap_token_a_amount in 1..u64::MAX,
+            swap_token_b_amount in 1..u64::MAX,
+            amp in MIN_AMP..MAX_AMP,
+        ) {
+            let curve = StableCurve { amp, token_a_factor: 1, token_b_factor: 1, ..Default::default() };
+            let model = StableModel { amp: amp.into(), token_a_factor: 1, token_b_factor: 1 };
+            // proportional amounts are only rounded once
+            prop_assert_eq!(
+                check_deposit_against_model(
+                    &curve,
+                    &model,
+                    pool_token_amount as u128,
+                    pool_token_supply as u128,
+                    swap_token_a_amount as u128,
+                    swap_token_b_amount as u128,
+                ),
+                (0, 0)
+            );
+            prop_assert_eq!(
+                check_withdraw_against_model(
+                    &curve,
+                    &model,
+                    pool_token_amount as u128,
+                    pool_token_supply as u128,
+                    swap_token_a_amount as u128,
+                    swap_token_b_amount as u128,
+                ),
+                (0, 0)
+            );
+        }
+    }
 }
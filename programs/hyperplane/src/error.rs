@@ -77,6 +77,28 @@ pub enum SwapError {
     InvaliPoolAdmin,
     #[msg("Token 2022 extension is not supported")]
     InvalidTokenExtension,
+    #[msg("Fee recipient shares are invalid")]
+    InvalidFeeRecipients,
+    #[msg("Fee recipient token account is incorrect")]
+    IncorrectFeeRecipientAccount,
+    #[msg("Reward mint is not a reward of the reward vault")]
+    IncorrectRewardMint,
+
+    // 25.
+    #[msg("Reward vault has no unused reward slots")]
+    RewardVaultFull,
+    #[msg("Stake amount must be greater than zero")]
+    InvalidStakeAmount,
+    #[msg("Liquidity lock amount or vesting schedule is invalid")]
+    InvalidLiquidityLock,
+    #[msg("No vested liquidity to unlock")]
+    LiquidityLocked,
+    #[msg("Pool token supply cannot fall below the minimum liquidity")]
+    BelowMinimumLiquidity,
+
+    // 30.
+    #[msg("Minimum liquidity must be greater than zero and less than the new pool supply")]
+    InvalidMinLiquidity,
 }
 
 impl From<SwapError> for ProgramError {
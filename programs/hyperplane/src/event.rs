@@ -1,4 +1,8 @@
-use anchor_lang::{event, prelude::borsh, AnchorDeserialize, AnchorSerialize};
+use anchor_lang::{
+    event,
+    prelude::{borsh, Pubkey},
+    AnchorDeserialize, AnchorSerialize,
+};
 
 use crate::state::{UpdatePoolConfigMode, UpdatePoolConfigValue};
 
@@ -35,6 +39,81 @@ pub struct WithdrawFees {
     pub withdraw_amount: u64,
 }
 
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompoundFees {
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub pool_token_amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DistributeFees {
+    /// Owner of the token account the fees were paid to
+    pub recipient: Pubkey,
+    pub fees_mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FundRewards {
+    pub reward_mint: Pubkey,
+    /// Amount credited to the reward vault, net of any transfer fees
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetRewardEmissions {
+    pub reward_mint: Pubkey,
+    pub emissions_per_second: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StakeLp {
+    pub amount: u64,
+    /// Amount staked by the owner after staking
+    pub staked_amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnstakeLp {
+    pub amount: u64,
+    /// Amount staked by the owner after unstaking
+    pub staked_amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClaimRewards {
+    pub reward_mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LockLiquidity {
+    pub owner: Pubkey,
+    pub lock_id: u64,
+    pub amount: u64,
+    pub vesting_start_ts: u64,
+    pub vesting_end_ts: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnlockLiquidity {
+    pub owner: Pubkey,
+    pub lock_id: u64,
+    pub amount: u64,
+    /// Amount of pool tokens left in the lock after unlocking
+    pub remaining_amount: u64,
+}
+
 #[event]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct UpdatePoolConfig {
@@ -0,0 +1,743 @@
+//! Instruction builders for integrators
+//!
+//! Builds complete instructions from the addresses of a pool: every PDA is derived with
+//! [crate::utils::seeds::pda] and each mint is paired with its own token program (Token or
+//! Token-2022). [PoolKeys::user_token_accounts] derives the associated token accounts of a user
+//! with the right token program.
+
+#![allow(clippy::too_many_arguments)]
+
+use anchor_lang::solana_program::{
+    instruction::Instruction, program_error::ProgramError, pubkey::Pubkey,
+};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+
+use crate::{
+    curve::calculator::{AorB, TradeDirection},
+    ix,
+    ix::{
+        AddReward, Deposit, FundRewards, Initialize, InitializeFeeRecipients, LockLiquidity,
+        SetRewardEmissions, StakeLp, Swap, UnstakeLp, UpdateFeeRecipients, UpdatePoolConfig,
+        Withdraw, WithdrawFees,
+    },
+    state::SwapPool,
+    utils::seeds::pda,
+};
+
+/// Addresses of a pool, its PDAs and the token program of each of its mints
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PoolKeys {
+    pub program_id: Pubkey,
+    pub pool: Pubkey,
+    pub admin: Pubkey,
+    pub swap_curve: Pubkey,
+    pub pool_authority: Pubkey,
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    pub token_a_vault: Pubkey,
+    pub token_b_vault: Pubkey,
+    pub pool_token_mint: Pubkey,
+    pub token_a_fees_vault: Pubkey,
+    pub token_b_fees_vault: Pubkey,
+    pub min_liquidity_vault: Pubkey,
+    pub pool_token_program: Pubkey,
+    pub token_a_token_program: Pubkey,
+    pub token_b_token_program: Pubkey,
+}
+
+/// Token accounts of a user for the mints of a pool
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UserTokenAccounts {
+    pub token_a: Pubkey,
+    pub token_b: Pubkey,
+    pub pool_token: Pubkey,
+}
+
+impl UserTokenAccounts {
+    pub fn side(&self, a_or_b: AorB) -> Pubkey {
+        match a_or_b {
+            AorB::A => self.token_a,
+            AorB::B => self.token_b,
+        }
+    }
+}
+
+/// Accounts of one side of a pool
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PoolSide {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub fees_vault: Pubkey,
+    pub token_program: Pubkey,
+}
+
+impl PoolKeys {
+    /// Derive the keys of a new pool
+    ///
+    /// The token A and B programs must be the owners of the mint accounts, the pool token mint is
+    /// created by `initialize` with `pool_token_program`
+    pub fn new(
+        program_id: &Pubkey,
+        pool: &Pubkey,
+        admin: &Pubkey,
+        token_a_mint: &Pubkey,
+        token_b_mint: &Pubkey,
+        pool_token_program: &Pubkey,
+        token_a_token_program: &Pubkey,
+        token_b_token_program: &Pubkey,
+    ) -> Self {
+        let pdas = pda::init_pool_pdas_program_id(program_id, pool, token_a_mint, token_b_mint);
+        Self {
+            program_id: *program_id,
+            pool: *pool,
+            admin: *admin,
+            swap_curve: pdas.curve,
+            pool_authority: pdas.authority,
+            token_a_mint: *token_a_mint,
+            token_b_mint: *token_b_mint,
+            token_a_vault: pdas.token_a_vault,
+            token_b_vault: pdas.token_b_vault,
+            pool_token_mint: pdas.pool_token_mint,
+            token_a_fees_vault: pdas.token_a_fees_vault,
+            token_b_fees_vault: pdas.token_b_fees_vault,
+            min_liquidity_vault: pdas.min_liquidity_vault,
+            pool_token_program: *pool_token_program,
+            token_a_token_program: *token_a_token_program,
+            token_b_token_program: *token_b_token_program,
+        }
+    }
+
+    /// Keys of an existing pool, read from its account
+    ///
+    /// The token programs are the owners of the pool token, token A and token B mint accounts
+    pub fn from_pool(
+        program_id: &Pubkey,
+        pool_key: &Pubkey,
+        pool: &SwapPool,
+        pool_token_program: &Pubkey,
+        token_a_token_program: &Pubkey,
+        token_b_token_program: &Pubkey,
+    ) -> Self {
+        Self {
+            program_id: *program_id,
+            pool: *pool_key,
+            admin: pool.admin,
+            swap_curve: pool.swap_curve,
+            pool_authority: pool.pool_authority,
+            token_a_mint: pool.token_a_mint,
+            token_b_mint: pool.token_b_mint,
+            token_a_vault: pool.token_a_vault,
+            token_b_vault: pool.token_b_vault,
+            pool_token_mint: pool.pool_token_mint,
+            token_a_fees_vault: pool.token_a_fees_vault,
+            token_b_fees_vault: pool.token_b_fees_vault,
+            min_liquidity_vault: pda::min_liquidity_vault_pda_program_id(program_id, pool_key).0,
+            pool_token_program: *pool_token_program,
+            token_a_token_program: *token_a_token_program,
+            token_b_token_program: *token_b_token_program,
+        }
+    }
+
+    pub fn side(&self, a_or_b: AorB) -> PoolSide {
+        match a_or_b {
+            AorB::A => PoolSide {
+                mint: self.token_a_mint,
+                vault: self.token_a_vault,
+                fees_vault: self.token_a_fees_vault,
+                token_program: self.token_a_token_program,
+            },
+            AorB::B => PoolSide {
+                mint: self.token_b_mint,
+                vault: self.token_b_vault,
+                fees_vault: self.token_b_fees_vault,
+                token_program: self.token_b_token_program,
+            },
+        }
+    }
+
+    /// Associated token account of `owner` for token A or B
+    pub fn user_ata(&self, owner: &Pubkey, a_or_b: AorB) -> Pubkey {
+        let side = self.side(a_or_b);
+        get_associated_token_address_with_program_id(owner, &side.mint, &side.token_program)
+    }
+
+    /// Associated token account of `owner` for the pool token
+    pub fn user_pool_token_ata(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(
+            owner,
+            &self.pool_token_mint,
+            &self.pool_token_program,
+        )
+    }
+
+    /// Associated token accounts of `owner` for token A, token B and the pool token
+    pub fn user_token_accounts(&self, owner: &Pubkey) -> UserTokenAccounts {
+        UserTokenAccounts {
+            token_a: self.user_ata(owner, AorB::A),
+            token_b: self.user_ata(owner, AorB::B),
+            pool_token: self.user_pool_token_ata(owner),
+        }
+    }
+
+    fn fee_recipients(&self) -> Pubkey {
+        pda::fee_recipients_pda_program_id(&self.program_id, &self.pool).0
+    }
+
+    fn reward_vault(&self) -> Pubkey {
+        pda::reward_vault_pda_program_id(&self.program_id, &self.pool).0
+    }
+
+    fn staked_lp_vault(&self) -> Pubkey {
+        pda::staked_lp_vault_pda_program_id(&self.program_id, &self.pool).0
+    }
+
+    fn reward_token_vault(&self, reward_mint: &Pubkey) -> Pubkey {
+        pda::reward_token_vault_pda_program_id(&self.program_id, &self.pool, reward_mint).0
+    }
+
+    fn lp_stake(&self, owner: &Pubkey) -> Pubkey {
+        pda::lp_stake_pda_program_id(&self.program_id, &self.reward_vault(), owner).0
+    }
+}
+
+/// Creates an 'initialize' instruction, the admin pool token account is created by the instruction
+/// and must sign it
+pub fn initialize_pool(
+    keys: &PoolKeys,
+    admin_token_accounts: &UserTokenAccounts,
+    initialize: Initialize,
+) -> Result<Instruction, ProgramError> {
+    ix::initialize_pool(
+        &keys.program_id,
+        &keys.admin,
+        &keys.pool,
+        &keys.swap_curve,
+        &keys.token_a_mint,
+        &keys.token_b_mint,
+        &keys.token_a_vault,
+        &keys.token_b_vault,
+        &keys.pool_authority,
+        &keys.pool_token_mint,
+        &keys.token_a_fees_vault,
+        &keys.token_b_fees_vault,
+        &admin_token_accounts.token_a,
+        &admin_token_accounts.token_b,
+        &admin_token_accounts.pool_token,
+        &keys.min_liquidity_vault,
+        &keys.pool_token_program,
+        &keys.token_a_token_program,
+        &keys.token_b_token_program,
+        initialize,
+    )
+}
+
+/// Creates a 'deposit' instruction
+pub fn deposit(
+    keys: &PoolKeys,
+    user: &Pubkey,
+    user_token_accounts: &UserTokenAccounts,
+    deposit: Deposit,
+) -> Result<Instruction, ProgramError> {
+    ix::deposit(
+        &keys.program_id,
+        user,
+        &keys.pool,
+        &keys.swap_curve,
+        &keys.pool_authority,
+        &keys.token_a_mint,
+        &keys.token_b_mint,
+        &keys.token_a_vault,
+        &keys.token_b_vault,
+        &keys.pool_token_mint,
+        &user_token_accounts.token_a,
+        &user_token_accounts.token_b,
+        &user_token_accounts.pool_token,
+        &keys.pool_token_program,
+        &keys.token_a_token_program,
+        &keys.token_b_token_program,
+        deposit,
+    )
+}
+
+/// Creates a 'withdraw' instruction
+pub fn withdraw(
+    keys: &PoolKeys,
+    user: &Pubkey,
+    user_token_accounts: &UserTokenAccounts,
+    withdraw: Withdraw,
+) -> Result<Instruction, ProgramError> {
+    ix::withdraw(
+        &keys.program_id,
+        user,
+        &keys.pool,
+        &keys.swap_curve,
+        &keys.pool_authority,
+        &keys.token_a_mint,
+        &keys.token_b_mint,
+        &keys.token_a_vault,
+        &keys.token_b_vault,
+        &keys.pool_token_mint,
+        &keys.token_a_fees_vault,
+        &keys.token_b_fees_vault,
+        &user_token_accounts.token_a,
+        &user_token_accounts.token_b,
+        &user_token_accounts.pool_token,
+        &keys.pool_token_program,
+        &keys.token_a_token_program,
+        &keys.token_b_token_program,
+        withdraw,
+    )
+}
+
+/// Creates a 'swap' instruction
+///
+/// `host_fees_account` is a token account of the source mint receiving the host share of the owner fees
+pub fn swap(
+    keys: &PoolKeys,
+    user: &Pubkey,
+    trade_direction: TradeDirection,
+    user_source_account: &Pubkey,
+    user_destination_account: &Pubkey,
+    host_fees_account: Option<&Pubkey>,
+    swap: Swap,
+) -> Result<Instruction, ProgramError> {
+    let (source, destination) = match trade_direction {
+        TradeDirection::AtoB => (keys.side(AorB::A), keys.side(AorB::B)),
+        TradeDirection::BtoA => (keys.side(AorB::B), keys.side(AorB::A)),
+    };
+    ix::swap(
+        &keys.program_id,
+        user,
+        &keys.pool,
+        &keys.swap_curve,
+        &keys.pool_authority,
+        &source.mint,
+        &destination.mint,
+        &source.vault,
+        &destination.vault,
+        &source.fees_vault,
+        user_source_account,
+        user_destination_account,
+        host_fees_account,
+        &source.token_program,
+        &destination.token_program,
+        swap,
+    )
+}
+
+/// Creates a 'withdraw_fees' instruction
+pub fn withdraw_fees(
+    keys: &PoolKeys,
+    a_or_b: AorB,
+    admin_fees_account: &Pubkey,
+    withdraw_fees: WithdrawFees,
+) -> Result<Instruction, ProgramError> {
+    let side = keys.side(a_or_b);
+    ix::withdraw_fees(
+        &keys.program_id,
+        &keys.admin,
+        &keys.pool,
+        &keys.pool_authority,
+        &side.mint,
+        &side.fees_vault,
+        admin_fees_account,
+        &side.token_program,
+        withdraw_fees,
+    )
+}
+
+/// Creates a 'compound fees' instruction
+pub fn compound_fees(
+    keys: &PoolKeys,
+    admin_pool_token_account: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    ix::compound_fees(
+        &keys.program_id,
+        &keys.admin,
+        &keys.pool,
+        &keys.swap_curve,
+        &keys.pool_authority,
+        &keys.token_a_mint,
+        &keys.token_b_mint,
+        &keys.token_a_vault,
+        &keys.token_b_vault,
+        &keys.pool_token_mint,
+        &keys.token_a_fees_vault,
+        &keys.token_b_fees_vault,
+        admin_pool_token_account,
+        &keys.pool_token_program,
+        &keys.token_a_token_program,
+        &keys.token_b_token_program,
+    )
+}
+
+/// Creates an 'update pool config' instruction
+pub fn update_pool_config(
+    keys: &PoolKeys,
+    update_pool_config: UpdatePoolConfig,
+) -> Result<Instruction, ProgramError> {
+    ix::update_pool_config(
+        &keys.program_id,
+        &keys.admin,
+        &keys.pool,
+        update_pool_config,
+    )
+}
+
+/// Creates an 'initialize fee recipients' instruction
+pub fn initialize_fee_recipients(
+    keys: &PoolKeys,
+    initialize_fee_recipients: InitializeFeeRecipients,
+) -> Result<Instruction, ProgramError> {
+    ix::initialize_fee_recipients(
+        &keys.program_id,
+        &keys.admin,
+        &keys.pool,
+        &keys.fee_recipients(),
+        initialize_fee_recipients,
+    )
+}
+
+/// Creates an 'update fee recipients' instruction
+pub fn update_fee_recipients(
+    keys: &PoolKeys,
+    update_fee_recipients: UpdateFeeRecipients,
+) -> Result<Instruction, ProgramError> {
+    ix::update_fee_recipients(
+        &keys.program_id,
+        &keys.admin,
+        &keys.pool,
+        &keys.fee_recipients(),
+        update_fee_recipients,
+    )
+}
+
+/// Creates a 'distribute fees' instruction
+///
+/// `recipient_token_accounts` must be in the same order as the recipients in the fee recipients account
+pub fn distribute_fees(
+    keys: &PoolKeys,
+    a_or_b: AorB,
+    recipient_token_accounts: &[Pubkey],
+) -> Result<Instruction, ProgramError> {
+    let side = keys.side(a_or_b);
+    ix::distribute_fees(
+        &keys.program_id,
+        &keys.pool,
+        &keys.pool_authority,
+        &keys.fee_recipients(),
+        &side.mint,
+        &side.fees_vault,
+        recipient_token_accounts,
+        &side.token_program,
+    )
+}
+
+/// Creates an 'initialize reward vault' instruction
+pub fn initialize_reward_vault(keys: &PoolKeys) -> Result<Instruction, ProgramError> {
+    ix::initialize_reward_vault(
+        &keys.program_id,
+        &keys.admin,
+        &keys.pool,
+        &keys.pool_authority,
+        &keys.pool_token_mint,
+        &keys.reward_vault(),
+        &keys.staked_lp_vault(),
+        &keys.pool_token_program,
+    )
+}
+
+/// Creates an 'add reward' instruction, `reward_token_program` is the owner of the reward mint
+pub fn add_reward(
+    keys: &PoolKeys,
+    reward_mint: &Pubkey,
+    reward_token_program: &Pubkey,
+    add_reward: AddReward,
+) -> Result<Instruction, ProgramError> {
+    ix::add_reward(
+        &keys.program_id,
+        &keys.admin,
+        &keys.pool,
+        &keys.pool_authority,
+        &keys.reward_vault(),
+        reward_mint,
+        &keys.reward_token_vault(reward_mint),
+        reward_token_program,
+        add_reward,
+    )
+}
+
+/// Creates a 'fund rewards' instruction
+pub fn fund_rewards(
+    keys: &PoolKeys,
+    reward_mint: &Pubkey,
+    reward_token_program: &Pubkey,
+    admin_reward_account: &Pubkey,
+    fund_rewards: FundRewards,
+) -> Result<Instruction, ProgramError> {
+    ix::fund_rewards(
+        &keys.program_id,
+        &keys.admin,
+        &keys.pool,
+        &keys.reward_vault(),
+        reward_mint,
+        &keys.reward_token_vault(reward_mint),
+        admin_reward_account,
+        reward_token_program,
+        fund_rewards,
+    )
+}
+
+/// Creates a 'set reward emissions' instruction
+pub fn set_reward_emissions(
+    keys: &PoolKeys,
+    reward_mint: &Pubkey,
+    set_reward_emissions: SetRewardEmissions,
+) -> Result<Instruction, ProgramError> {
+    ix::set_reward_emissions(
+        &keys.program_id,
+        &keys.admin,
+        &keys.pool,
+        &keys.reward_vault(),
+        reward_mint,
+        set_reward_emissions,
+    )
+}
+
+/// Creates an 'initialize lp stake' instruction
+pub fn initialize_lp_stake(keys: &PoolKeys, owner: &Pubkey) -> Result<Instruction, ProgramError> {
+    ix::initialize_lp_stake(
+        &keys.program_id,
+        owner,
+        &keys.reward_vault(),
+        &keys.lp_stake(owner),
+    )
+}
+
+/// Creates a 'stake lp' instruction
+pub fn stake_lp(
+    keys: &PoolKeys,
+    owner: &Pubkey,
+    owner_pool_token_account: &Pubkey,
+    stake_lp: StakeLp,
+) -> Result<Instruction, ProgramError> {
+    ix::stake_lp(
+        &keys.program_id,
+        owner,
+        &keys.pool,
+        &keys.reward_vault(),
+        &keys.lp_stake(owner),
+        &keys.pool_token_mint,
+        &keys.staked_lp_vault(),
+        owner_pool_token_account,
+        &keys.pool_token_program,
+        stake_lp,
+    )
+}
+
+/// Creates an 'unstake lp' instruction
+pub fn unstake_lp(
+    keys: &PoolKeys,
+    owner: &Pubkey,
+    owner_pool_token_account: &Pubkey,
+    unstake_lp: UnstakeLp,
+) -> Result<Instruction, ProgramError> {
+    ix::unstake_lp(
+        &keys.program_id,
+        owner,
+        &keys.pool,
+        &keys.pool_authority,
+        &keys.reward_vault(),
+        &keys.lp_stake(owner),
+        &keys.pool_token_mint,
+        &keys.staked_lp_vault(),
+        owner_pool_token_account,
+        &keys.pool_token_program,
+        unstake_lp,
+    )
+}
+
+/// Creates a 'claim rewards' instruction
+pub fn claim_rewards(
+    keys: &PoolKeys,
+    owner: &Pubkey,
+    reward_mint: &Pubkey,
+    reward_token_program: &Pubkey,
+    owner_reward_account: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    ix::claim_rewards(
+        &keys.program_id,
+        owner,
+        &keys.pool,
+        &keys.pool_authority,
+        &keys.reward_vault(),
+        &keys.lp_stake(owner),
+        reward_mint,
+        &keys.reward_token_vault(reward_mint),
+        owner_reward_account,
+        reward_token_program,
+    )
+}
+
+/// Creates a 'lock liquidity' instruction
+pub fn lock_liquidity(
+    keys: &PoolKeys,
+    owner: &Pubkey,
+    owner_pool_token_account: &Pubkey,
+    lock_liquidity: LockLiquidity,
+) -> Result<Instruction, ProgramError> {
+    let liquidity_lock = pda::liquidity_lock_pda_program_id(
+        &keys.program_id,
+        &keys.pool,
+        owner,
+        lock_liquidity.lock_id,
+    )
+    .0;
+    ix::lock_liquidity(
+        &keys.program_id,
+        owner,
+        &keys.pool,
+        &keys.pool_authority,
+        &liquidity_lock,
+        &pda::liquidity_lock_vault_pda_program_id(&keys.program_id, &liquidity_lock).0,
+        &keys.pool_token_mint,
+        owner_pool_token_account,
+        &keys.pool_token_program,
+        lock_liquidity,
+    )
+}
+
+/// Creates an 'unlock liquidity' instruction
+pub fn unlock_liquidity(
+    keys: &PoolKeys,
+    owner: &Pubkey,
+    owner_pool_token_account: &Pubkey,
+    lock_id: u64,
+) -> Result<Instruction, ProgramError> {
+    let liquidity_lock =
+        pda::liquidity_lock_pda_program_id(&keys.program_id, &keys.pool, owner, lock_id).0;
+    ix::unlock_liquidity(
+        &keys.program_id,
+        owner,
+        &keys.pool,
+        &keys.pool_authority,
+        &liquidity_lock,
+        &pda::liquidity_lock_vault_pda_program_id(&keys.program_id, &liquidity_lock).0,
+        &keys.pool_token_mint,
+        owner_pool_token_account,
+        &keys.pool_token_program,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::solana_program::instruction::AccountMeta;
+    use anchor_spl::{token::spl_token, token_2022::spl_token_2022};
+
+    use super::*;
+
+    fn keys() -> PoolKeys {
+        PoolKeys::new(
+            &crate::id(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &spl_token::id(),
+            &spl_token::id(),
+            &spl_token_2022::id(),
+        )
+    }
+
+    #[test]
+    fn test_new_derives_pool_pdas() {
+        let keys = keys();
+        assert_eq!(keys.pool_authority, pda::pool_authority_pda(&keys.pool).0);
+        assert_eq!(
+            keys.token_b_vault,
+            pda::token_b_vault_pda(&keys.pool, &keys.token_b_mint).0
+        );
+        assert_eq!(
+            keys.min_liquidity_vault,
+            pda::min_liquidity_vault_pda(&keys.pool).0
+        );
+    }
+
+    #[test]
+    fn test_swap_picks_token_programs_per_mint() {
+        let keys = keys();
+        let user = Pubkey::new_unique();
+        let host_fees_account = Pubkey::new_unique();
+        let swap = swap(
+            &keys,
+            &user,
+            TradeDirection::BtoA,
+            &keys.user_ata(&user, AorB::B),
+            &keys.user_ata(&user, AorB::A),
+            Some(&host_fees_account),
+            Swap::new(100, 90),
+        )
+        .unwrap();
+
+        let expected = crate::accounts::Swap {
+            signer: user,
+            pool: keys.pool,
+            swap_curve: keys.swap_curve,
+            pool_authority: keys.pool_authority,
+            source_mint: keys.token_b_mint,
+            destination_mint: keys.token_a_mint,
+            source_vault: keys.token_b_vault,
+            destination_vault: keys.token_a_vault,
+            source_token_fees_vault: keys.token_b_fees_vault,
+            source_user_ata: get_associated_token_address_with_program_id(
+                &user,
+                &keys.token_b_mint,
+                &spl_token_2022::id(),
+            ),
+            destination_user_ata: get_associated_token_address_with_program_id(
+                &user,
+                &keys.token_a_mint,
+                &spl_token::id(),
+            ),
+            source_token_host_fees_account: Some(host_fees_account),
+            source_token_program: spl_token_2022::id(),
+            destination_token_program: spl_token::id(),
+        };
+        assert_eq!(
+            swap.accounts,
+            anchor_lang::ToAccountMetas::to_account_metas(&expected, None)
+        );
+        assert!(swap
+            .accounts
+            .contains(&AccountMeta::new(host_fees_account, false)));
+    }
+
+    #[test]
+    fn test_from_pool_matches_new() {
+        let keys = keys();
+        let pool = SwapPool {
+            admin: keys.admin,
+            pool_authority: keys.pool_authority,
+            swap_curve: keys.swap_curve,
+            token_a_mint: keys.token_a_mint,
+            token_b_mint: keys.token_b_mint,
+            token_a_vault: keys.token_a_vault,
+            token_b_vault: keys.token_b_vault,
+            pool_token_mint: keys.pool_token_mint,
+            token_a_fees_vault: keys.token_a_fees_vault,
+            token_b_fees_vault: keys.token_b_fees_vault,
+            ..SwapPool::default()
+        };
+        assert_eq!(
+            PoolKeys::from_pool(
+                &crate::id(),
+                &keys.pool,
+                &pool,
+                &spl_token::id(),
+                &spl_token::id(),
+                &spl_token_2022::id(),
+            ),
+            keys
+        );
+    }
+}
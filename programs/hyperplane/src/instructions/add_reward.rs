@@ -0,0 +1,75 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    error::SwapError,
+    state::{RewardVault, SwapPool},
+    utils::{instructions::unix_timestamp, seeds},
+};
+
+/// Add a reward mint to the reward vault of a pool. Nothing is emitted until the reward is funded.
+pub fn handler(ctx: Context<AddReward>, emissions_per_second: u64) -> Result<()> {
+    let reward_vault = &mut ctx.accounts.reward_vault;
+    reward_vault.update_rewards(unix_timestamp()?)?;
+
+    let index = reward_vault.add_reward(
+        ctx.accounts.reward_mint.key(),
+        ctx.accounts.reward_token_vault.key(),
+        emissions_per_second,
+    )?;
+
+    msg!(
+        "Add reward: index={}, reward_mint={}, emissions_per_second={}",
+        index,
+        ctx.accounts.reward_mint.key(),
+        emissions_per_second
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddReward<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin,
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    #[account(mut,
+        seeds = [seeds::REWARD_VAULT, pool.key().as_ref()],
+        bump,
+        has_one = pool,
+    )]
+    pub reward_vault: Box<Account<'info, RewardVault>>,
+
+    #[account(
+        mint::token_program = reward_token_program,
+    )]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Token account the rewards are funded into - owned by the pool authority
+    #[account(init,
+        seeds = [seeds::REWARD_TOKEN_VAULT, pool.key().as_ref(), reward_mint.key().as_ref()],
+        bump,
+        payer = admin,
+        token::mint = reward_mint,
+        token::authority = pool_authority,
+        token::token_program = reward_token_program,
+    )]
+    pub reward_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    /// The token program for the reward mint
+    pub reward_token_program: Interface<'info, TokenInterface>,
+}
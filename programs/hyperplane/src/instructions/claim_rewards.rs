@@ -0,0 +1,133 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    claim_rewards::utils::validate_inputs,
+    emitted,
+    error::SwapError,
+    event, require_msg,
+    state::{LpStake, RewardVault, SwapPool, SwapState},
+    utils::{instructions::unix_timestamp, seeds, swap_token},
+};
+
+/// Pay out the unclaimed rewards of a single reward mint to the owner of the stake
+pub fn handler(ctx: Context<ClaimRewards>) -> Result<event::ClaimRewards> {
+    let pool = ctx.accounts.pool.load()?;
+    let reward_index = validate_inputs(&ctx)?;
+
+    let reward_vault = &mut ctx.accounts.reward_vault;
+    let lp_stake = &mut ctx.accounts.lp_stake;
+    reward_vault.update_rewards(unix_timestamp()?)?;
+    lp_stake.settle_rewards(reward_vault)?;
+
+    let amount = lp_stake.unclaimed_rewards[reward_index];
+    lp_stake.unclaimed_rewards[reward_index] = 0;
+
+    msg!(
+        "Claim rewards: reward_mint={}, amount={}",
+        ctx.accounts.reward_mint.key(),
+        amount
+    );
+
+    if amount > 0 {
+        swap_token::transfer_from_vault(
+            ctx.accounts.reward_token_program.to_account_info(),
+            ctx.accounts.pool.to_account_info(),
+            ctx.accounts.reward_token_vault.to_account_info(),
+            ctx.accounts.reward_mint.to_account_info(),
+            ctx.accounts.owner_reward_ata.to_account_info(),
+            ctx.accounts.pool_authority.to_account_info(),
+            pool.bump_seed(),
+            amount,
+            ctx.accounts.reward_mint.decimals,
+        )?;
+    }
+
+    emitted!(event::ClaimRewards {
+        reward_mint: ctx.accounts.reward_mint.key(),
+        amount,
+    });
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    #[account(mut,
+        seeds = [seeds::REWARD_VAULT, pool.key().as_ref()],
+        bump,
+        has_one = pool,
+    )]
+    pub reward_vault: Box<Account<'info, RewardVault>>,
+
+    #[account(mut,
+        seeds = [seeds::LP_STAKE, reward_vault.key().as_ref(), owner.key().as_ref()],
+        bump,
+        has_one = reward_vault,
+        has_one = owner,
+    )]
+    pub lp_stake: Box<Account<'info, LpStake>>,
+
+    /// CHECK: checked in the handler
+    #[account(
+        mint::token_program = reward_token_program,
+    )]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: checked in the handler
+    #[account(mut,
+        token::token_program = reward_token_program,
+    )]
+    pub reward_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Owner's token account to pay the rewards to
+    #[account(mut,
+        token::mint = reward_mint,
+        token::authority = owner,
+        token::token_program = reward_token_program,
+    )]
+    pub owner_reward_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token program for the reward mint
+    pub reward_token_program: Interface<'info, TokenInterface>,
+}
+
+mod utils {
+    use super::*;
+
+    /// Returns the index of the reward being claimed
+    pub fn validate_inputs(ctx: &Context<ClaimRewards>) -> Result<usize> {
+        let reward_vault = &ctx.accounts.reward_vault;
+        let reward_index = reward_vault
+            .reward_index(&ctx.accounts.reward_mint.key())
+            .ok_or_else(|| {
+                msg!(
+                    "IncorrectRewardMint: reward_mint ({}) is not a reward of the reward vault",
+                    ctx.accounts.reward_mint.key()
+                );
+                error!(SwapError::IncorrectRewardMint)
+            })?;
+        let reward_token_vault = reward_vault.rewards[reward_index].vault;
+        require_msg!(
+            reward_token_vault == ctx.accounts.reward_token_vault.key(),
+            SwapError::IncorrectRewardMint,
+            &format!(
+                "IncorrectRewardMint: reward.vault ({}) != reward_token_vault.key ({})",
+                reward_token_vault,
+                ctx.accounts.reward_token_vault.key(),
+            )
+        );
+        Ok(reward_index)
+    }
+}
@@ -0,0 +1,270 @@
+use std::cmp;
+
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    compound_fees::utils::{
+        add_inverse_transfer_fee, max_pool_tokens_for_fees, sub_transfer_fee, validate_inputs,
+    },
+    curve,
+    curve::{base::SwapCurve, calculator::RoundDirection},
+    emitted,
+    error::SwapError,
+    event, require_msg,
+    state::{SwapPool, SwapState},
+    to_u64,
+    utils::{pool_token, swap_token, transfer_fees},
+};
+
+/// Deposit the balances of the fees vaults back into the pool at the current ratio of the pool vaults,
+/// minting the pool tokens to the admin. Any unbalanced remainder is left in the fees vaults.
+///
+/// Pool tokens are priced on the amounts received by the pool vaults, net of transfer fees.
+pub fn handler(ctx: Context<CompoundFees>) -> Result<event::CompoundFees> {
+    let pool = ctx.accounts.pool.load()?;
+    validate_inputs(&pool)?;
+
+    let swap_curve = curve!(ctx.accounts.swap_curve, pool);
+
+    let calculator = &swap_curve.calculator;
+    require!(
+        calculator.allows_deposits(),
+        SwapError::UnsupportedCurveOperation
+    );
+
+    msg!(
+        "Compound fees inputs: swap_type={:?}, token_a_balance={}, token_b_balance={}, pool_token_supply={}, token_a_fees={}, token_b_fees={}",
+        swap_curve.curve_type,
+        ctx.accounts.token_a_vault.amount,
+        ctx.accounts.token_b_vault.amount,
+        ctx.accounts.pool_token_mint.supply,
+        ctx.accounts.token_a_fees_vault.amount,
+        ctx.accounts.token_b_fees_vault.amount,
+    );
+
+    let pool_mint_supply = u128::from(ctx.accounts.pool_token_mint.supply);
+    let token_a_vault_amount = u128::from(ctx.accounts.token_a_vault.amount);
+    let token_b_vault_amount = u128::from(ctx.accounts.token_b_vault.amount);
+    require_msg!(
+        pool_mint_supply > 0 && token_a_vault_amount > 0 && token_b_vault_amount > 0,
+        SwapError::EmptySupply,
+        "Cannot compound fees into an empty pool"
+    );
+
+    // what the pool vaults would receive for the whole fees vaults
+    let token_a_fees = sub_transfer_fee(
+        &ctx.accounts.token_a_mint.to_account_info(),
+        ctx.accounts.token_a_fees_vault.amount,
+    )?;
+    let token_b_fees = sub_transfer_fee(
+        &ctx.accounts.token_b_mint.to_account_info(),
+        ctx.accounts.token_b_fees_vault.amount,
+    )?;
+    let pool_token_amount = cmp::min(
+        max_pool_tokens_for_fees(token_a_fees, token_a_vault_amount, pool_mint_supply)?,
+        max_pool_tokens_for_fees(token_b_fees, token_b_vault_amount, pool_mint_supply)?,
+    );
+
+    let results = calculator
+        .pool_tokens_to_trading_tokens(
+            pool_token_amount,
+            pool_mint_supply,
+            token_a_vault_amount,
+            token_b_vault_amount,
+            RoundDirection::Ceiling,
+        )
+        .map_err(|_| error!(SwapError::ZeroTradingTokens))?;
+
+    let token_a_amount = to_u64!(results.token_a_amount)?;
+    let token_b_amount = to_u64!(results.token_b_amount)?;
+    let pool_token_amount = to_u64!(pool_token_amount)?;
+
+    msg!(
+        "Compound fees outputs: token_a_to_deposit={}, token_b_to_deposit={}, pool_tokens_to_mint={}",
+        token_a_amount,
+        token_b_amount,
+        pool_token_amount,
+    );
+
+    require_msg!(
+        pool_token_amount > 0 && token_a_amount > 0 && token_b_amount > 0,
+        SwapError::ZeroTradingTokens,
+        &format!(
+            "Not enough fees to compound: token_a_amount={}, token_b_amount={}, pool_token_amount={}",
+            token_a_amount, token_b_amount, pool_token_amount
+        )
+    );
+
+    // send enough for the pool vaults to receive the compounded amounts after transfer fees
+    let token_a_transfer_amount =
+        add_inverse_transfer_fee(&ctx.accounts.token_a_mint.to_account_info(), token_a_amount)?;
+    let token_b_transfer_amount =
+        add_inverse_transfer_fee(&ctx.accounts.token_b_mint.to_account_info(), token_b_amount)?;
+    require_msg!(
+        token_a_transfer_amount <= ctx.accounts.token_a_fees_vault.amount
+            && token_b_transfer_amount <= ctx.accounts.token_b_fees_vault.amount,
+        SwapError::CalculationFailure,
+        &format!(
+            "Compounded amounts exceed the fees vaults: token_a_transfer_amount={}, token_b_transfer_amount={}",
+            token_a_transfer_amount, token_b_transfer_amount
+        )
+    );
+
+    swap_token::transfer_from_vault(
+        ctx.accounts.token_a_token_program.to_account_info(),
+        ctx.accounts.pool.to_account_info(),
+        ctx.accounts.token_a_fees_vault.to_account_info(),
+        ctx.accounts.token_a_mint.to_account_info(),
+        ctx.accounts.token_a_vault.to_account_info(),
+        ctx.accounts.pool_authority.to_account_info(),
+        pool.bump_seed(),
+        token_a_transfer_amount,
+        ctx.accounts.token_a_mint.decimals,
+    )?;
+    swap_token::transfer_from_vault(
+        ctx.accounts.token_b_token_program.to_account_info(),
+        ctx.accounts.pool.to_account_info(),
+        ctx.accounts.token_b_fees_vault.to_account_info(),
+        ctx.accounts.token_b_mint.to_account_info(),
+        ctx.accounts.token_b_vault.to_account_info(),
+        ctx.accounts.pool_authority.to_account_info(),
+        pool.bump_seed(),
+        token_b_transfer_amount,
+        ctx.accounts.token_b_mint.decimals,
+    )?;
+
+    pool_token::mint(
+        ctx.accounts.pool_token_program.to_account_info(),
+        ctx.accounts.pool.to_account_info(),
+        ctx.accounts.pool_token_mint.to_account_info(),
+        ctx.accounts.pool_authority.to_account_info(),
+        pool.bump_seed(),
+        ctx.accounts.admin_pool_token_ata.to_account_info(),
+        pool_token_amount,
+    )?;
+
+    emitted!(event::CompoundFees {
+        token_a_amount,
+        token_b_amount,
+        pool_token_amount,
+    });
+}
+
+#[derive(Accounts)]
+pub struct CompoundFees<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(mut,
+        has_one = admin,
+        has_one = swap_curve,
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+        has_one = token_a_mint,
+        has_one = token_b_mint,
+        has_one = token_a_vault @ SwapError::IncorrectSwapAccount,
+        has_one = token_b_vault @ SwapError::IncorrectSwapAccount,
+        has_one = pool_token_mint @ SwapError::IncorrectPoolMint,
+        has_one = token_a_fees_vault @ SwapError::IncorrectFeeAccount,
+        has_one = token_b_fees_vault @ SwapError::IncorrectFeeAccount,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub swap_curve: UncheckedAccount<'info>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    /// CHECK: has_one constraint on the pool
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: has_one constraint on the pool
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_a_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: has_one constraint on the pool
+    #[account(mut)]
+    pub token_b_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Admin's pool token account to mint the compounded pool tokens to
+    #[account(mut,
+        token::mint = pool_token_mint,
+        token::authority = admin,
+        token::token_program = pool_token_program,
+    )]
+    pub admin_pool_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the pool token mint
+    pub pool_token_program: Interface<'info, TokenInterface>,
+    /// Token program for the token A mint
+    pub token_a_token_program: Interface<'info, TokenInterface>,
+    /// Token program for the token B mint
+    pub token_b_token_program: Interface<'info, TokenInterface>,
+}
+
+mod utils {
+    use std::cell::Ref;
+
+    use super::*;
+    use crate::utils::math::TryMath;
+
+    pub fn validate_inputs(pool: &Ref<SwapPool>) -> Result<()> {
+        require_msg!(
+            !pool.withdrawals_only(),
+            SwapError::WithdrawalsOnlyMode,
+            "The pool is in withdrawals only mode"
+        );
+        Ok(())
+    }
+
+    /// Subtract token mint transfer fees for the amount received by the pool vault
+    pub fn sub_transfer_fee(mint_acc_info: &AccountInfo, amount: u64) -> Result<u64> {
+        let transfer_fee_config =
+            transfer_fees::get_transfer_fee_config(&mint_acc_info.data.borrow())?;
+        transfer_fees::sub_transfer_fee(transfer_fee_config.as_ref(), Clock::get()?.epoch, amount)
+    }
+
+    /// Add token mint transfer fees for the amount sent from the fees vault
+    pub fn add_inverse_transfer_fee(
+        mint_acc_info: &AccountInfo,
+        post_fee_amount: u64,
+    ) -> Result<u64> {
+        let transfer_fee_config =
+            transfer_fees::get_transfer_fee_config(&mint_acc_info.data.borrow())?;
+        transfer_fees::add_inverse_transfer_fee(
+            transfer_fee_config.as_ref(),
+            Clock::get()?.epoch,
+            post_fee_amount,
+        )
+    }
+
+    /// The largest amount of pool tokens whose (ceiling rounded) value in trading tokens can be paid from the fees vault
+    pub fn max_pool_tokens_for_fees(
+        fees_amount: u64,
+        pool_vault_amount: u128,
+        pool_token_supply: u128,
+    ) -> Result<u128> {
+        u128::from(fees_amount)
+            .try_mul(pool_token_supply)?
+            .try_div(pool_vault_amount)
+    }
+}
@@ -0,0 +1,182 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    distribute_fees::utils::{validate_inputs, validate_recipient_token_account},
+    error::SwapError,
+    event, require_msg,
+    state::{FeeRecipients, SwapPool, SwapState, FEE_RECIPIENTS_TOTAL_BPS},
+    to_u64,
+    utils::{math::TryMath, seeds, swap_token},
+};
+
+/// Permissionless crank which splits the fees vault between the fee recipients, pro rata to their share.
+/// The token accounts of the recipients are passed as remaining accounts, in the order of the recipients.
+/// Any dust left over from rounding down stays in the fees vault for the next distribution.
+pub fn handler(ctx: Context<DistributeFees>) -> Result<()> {
+    let pool = ctx.accounts.pool.load()?;
+    validate_inputs(&ctx, &pool)?;
+
+    let recipients: Vec<_> = ctx
+        .accounts
+        .fee_recipients
+        .active_recipients()
+        .copied()
+        .collect();
+    require_msg!(
+        ctx.remaining_accounts.len() == recipients.len(),
+        SwapError::IncorrectFeeRecipientAccount,
+        &format!(
+            "IncorrectFeeRecipientAccount: expected {} recipient token accounts, got {}",
+            recipients.len(),
+            ctx.remaining_accounts.len()
+        )
+    );
+
+    let vault_amount = ctx.accounts.fees_vault.amount;
+
+    msg!(
+        "Distributing fees vault: vault_amount={}, recipients={}",
+        vault_amount,
+        recipients.len()
+    );
+
+    for (recipient, recipient_token_account) in recipients.iter().zip(ctx.remaining_accounts) {
+        validate_recipient_token_account(&ctx, recipient, recipient_token_account)?;
+
+        let amount = u128::from(vault_amount)
+            .try_mul(u128::from(recipient.share_bps))?
+            .try_div(u128::from(FEE_RECIPIENTS_TOTAL_BPS))?;
+        let amount = to_u64!(amount)?;
+        if amount == 0 {
+            continue;
+        }
+
+        msg!(
+            "Paying fees to recipient: recipient={}, share_bps={}, amount={}",
+            recipient.owner,
+            recipient.share_bps,
+            amount
+        );
+
+        swap_token::transfer_from_vault(
+            ctx.accounts.fees_token_program.to_account_info(),
+            ctx.accounts.pool.to_account_info(),
+            ctx.accounts.fees_vault.to_account_info(),
+            ctx.accounts.fees_mint.to_account_info(),
+            recipient_token_account.clone(),
+            ctx.accounts.pool_authority.to_account_info(),
+            pool.bump_seed(),
+            amount,
+            ctx.accounts.fees_mint.decimals,
+        )?;
+
+        emit!(event::DistributeFees {
+            recipient: recipient.owner,
+            fees_mint: ctx.accounts.fees_mint.key(),
+            amount,
+        });
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DistributeFees<'info> {
+    #[account(
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    #[account(
+        seeds = [seeds::FEE_RECIPIENTS, pool.key().as_ref()],
+        bump,
+        has_one = pool,
+    )]
+    pub fee_recipients: Box<Account<'info, FeeRecipients>>,
+
+    /// CHECK: checked in the handler
+    #[account(
+        mint::token_program = fees_token_program,
+    )]
+    pub fees_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Fee vault to distribute
+    /// CHECK: checked in the handler
+    #[account(mut,
+        constraint = fees_vault.amount > 0 @ SwapError::ZeroTradingTokens,
+        token::mint = fees_mint,
+        token::token_program = fees_token_program,
+    )]
+    pub fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the fee token mint
+    pub fees_token_program: Interface<'info, TokenInterface>,
+}
+
+mod utils {
+    use std::cell::Ref;
+
+    use super::*;
+    use crate::{curve::calculator::AorB, state::FeeRecipient};
+
+    pub fn validate_inputs(ctx: &Context<DistributeFees>, pool: &Ref<SwapPool>) -> Result<()> {
+        let (pool_fees_vault, a_or_b) = if ctx.accounts.fees_mint.key() == pool.token_a_mint {
+            (pool.token_a_fees_vault.key(), AorB::A)
+        } else if ctx.accounts.fees_mint.key() == pool.token_b_mint {
+            (pool.token_b_fees_vault.key(), AorB::B)
+        } else {
+            return err!(SwapError::IncorrectTradingMint);
+        };
+        require_msg!(
+            pool_fees_vault == ctx.accounts.fees_vault.key(),
+            SwapError::IncorrectFeeAccount,
+            &format!(
+                "IncorrectFeeAccount: pool_fees_vault.key ({}) != fees_vault.key ({}), a_or_b={:?}",
+                pool_fees_vault,
+                ctx.accounts.fees_vault.key(),
+                a_or_b,
+            )
+        );
+        Ok(())
+    }
+
+    pub fn validate_recipient_token_account(
+        ctx: &Context<DistributeFees>,
+        recipient: &FeeRecipient,
+        recipient_token_account: &AccountInfo,
+    ) -> Result<()> {
+        require_msg!(
+            *recipient_token_account.owner == ctx.accounts.fees_token_program.key(),
+            SwapError::IncorrectFeeRecipientAccount,
+            &format!(
+                "IncorrectFeeRecipientAccount: token account {} is owned by {}, expected {}",
+                recipient_token_account.key,
+                recipient_token_account.owner,
+                ctx.accounts.fees_token_program.key()
+            )
+        );
+        let data = recipient_token_account.try_borrow_data()?;
+        let token_account = TokenAccount::try_deserialize(&mut &data[..])?;
+        require_msg!(
+            token_account.mint == ctx.accounts.fees_mint.key()
+                && token_account.owner == recipient.owner,
+            SwapError::IncorrectFeeRecipientAccount,
+            &format!(
+                "IncorrectFeeRecipientAccount: token account {} has mint={} owner={}, expected mint={} owner={}",
+                recipient_token_account.key,
+                token_account.mint,
+                token_account.owner,
+                ctx.accounts.fees_mint.key(),
+                recipient.owner
+            )
+        );
+        Ok(())
+    }
+}
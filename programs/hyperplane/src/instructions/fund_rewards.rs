@@ -0,0 +1,135 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    emitted,
+    error::SwapError,
+    event,
+    fund_rewards::utils::validate_inputs,
+    require_msg,
+    state::{RewardVault, SwapPool},
+    try_math,
+    utils::{instructions::unix_timestamp, math::TryMath, seeds, swap_token},
+};
+
+/// Transfer reward tokens from the admin into the reward token vault, to be emitted to the stakers.
+/// Only the amount received by the vault (net of any token 2022 transfer fees) is credited.
+pub fn handler(ctx: Context<FundRewards>, amount: u64) -> Result<event::FundRewards> {
+    let reward_index = validate_inputs(&ctx)?;
+
+    require_msg!(
+        amount > 0,
+        SwapError::ZeroTradingTokens,
+        "Cannot fund zero rewards"
+    );
+
+    ctx.accounts
+        .reward_vault
+        .update_rewards(unix_timestamp()?)?;
+
+    let vault_amount_before = ctx.accounts.reward_token_vault.amount;
+    swap_token::transfer_from_user(
+        ctx.accounts.reward_token_program.to_account_info(),
+        ctx.accounts.admin_reward_ata.to_account_info(),
+        ctx.accounts.reward_mint.to_account_info(),
+        ctx.accounts.reward_token_vault.to_account_info(),
+        ctx.accounts.admin.to_account_info(),
+        amount,
+        ctx.accounts.reward_mint.decimals,
+    )?;
+    ctx.accounts.reward_token_vault.reload()?;
+    let funded_amount = try_math!(ctx
+        .accounts
+        .reward_token_vault
+        .amount
+        .try_sub(vault_amount_before))?;
+
+    let reward = &mut ctx.accounts.reward_vault.rewards[reward_index];
+    reward.unemitted_amount = try_math!(reward.unemitted_amount.try_add(funded_amount))?;
+
+    msg!(
+        "Fund rewards: reward_mint={}, amount={}, funded_amount={}, unemitted_amount={}",
+        reward.mint,
+        amount,
+        funded_amount,
+        reward.unemitted_amount
+    );
+
+    emitted!(event::FundRewards {
+        reward_mint: ctx.accounts.reward_mint.key(),
+        amount: funded_amount,
+    });
+}
+
+#[derive(Accounts)]
+pub struct FundRewards<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    #[account(mut,
+        seeds = [seeds::REWARD_VAULT, pool.key().as_ref()],
+        bump,
+        has_one = pool,
+    )]
+    pub reward_vault: Box<Account<'info, RewardVault>>,
+
+    /// CHECK: checked in the handler
+    #[account(
+        mint::token_program = reward_token_program,
+    )]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: checked in the handler
+    #[account(mut,
+        token::token_program = reward_token_program,
+    )]
+    pub reward_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Admin's token account to fund the rewards from
+    #[account(mut,
+        token::mint = reward_mint,
+        token::authority = admin,
+        token::token_program = reward_token_program,
+    )]
+    pub admin_reward_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token program for the reward mint
+    pub reward_token_program: Interface<'info, TokenInterface>,
+}
+
+mod utils {
+    use super::*;
+
+    /// Returns the index of the reward being funded
+    pub fn validate_inputs(ctx: &Context<FundRewards>) -> Result<usize> {
+        let reward_vault = &ctx.accounts.reward_vault;
+        let reward_index = reward_vault
+            .reward_index(&ctx.accounts.reward_mint.key())
+            .ok_or_else(|| {
+                msg!(
+                    "IncorrectRewardMint: reward_mint ({}) is not a reward of the reward vault",
+                    ctx.accounts.reward_mint.key()
+                );
+                error!(SwapError::IncorrectRewardMint)
+            })?;
+        let reward_token_vault = reward_vault.rewards[reward_index].vault;
+        require_msg!(
+            reward_token_vault == ctx.accounts.reward_token_vault.key(),
+            SwapError::IncorrectRewardMint,
+            &format!(
+                "IncorrectRewardMint: reward.vault ({}) != reward_token_vault.key ({})",
+                reward_token_vault,
+                ctx.accounts.reward_token_vault.key(),
+            )
+        );
+        Ok(reward_index)
+    }
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{FeeRecipient, FeeRecipients, SwapPool},
+    utils::seeds,
+};
+
+pub fn handler(ctx: Context<InitializeFeeRecipients>, recipients: Vec<FeeRecipient>) -> Result<()> {
+    msg!("Initialize fee recipients: recipients={:?}", recipients);
+
+    let fee_recipients = &mut ctx.accounts.fee_recipients;
+    fee_recipients.pool = ctx.accounts.pool.key();
+    fee_recipients.set_recipients(&recipients)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeFeeRecipients<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    #[account(init,
+        seeds = [seeds::FEE_RECIPIENTS, pool.key().as_ref()],
+        bump,
+        payer = admin,
+        space = FeeRecipients::LEN,
+    )]
+    pub fee_recipients: Box<Account<'info, FeeRecipients>>,
+
+    pub system_program: Program<'info, System>,
+}
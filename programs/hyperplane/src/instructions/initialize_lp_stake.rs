@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{LpStake, RewardVault},
+    utils::seeds,
+};
+
+/// Create the stake account of an owner in a reward vault, required before staking pool tokens
+pub fn handler(ctx: Context<InitializeLpStake>) -> Result<()> {
+    msg!(
+        "Initialize lp stake: reward_vault={}, owner={}",
+        ctx.accounts.reward_vault.key(),
+        ctx.accounts.owner.key()
+    );
+
+    let lp_stake = &mut ctx.accounts.lp_stake;
+    lp_stake.reward_vault = ctx.accounts.reward_vault.key();
+    lp_stake.owner = ctx.accounts.owner.key();
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeLpStake<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub reward_vault: Box<Account<'info, RewardVault>>,
+
+    #[account(init,
+        seeds = [seeds::LP_STAKE, reward_vault.key().as_ref(), owner.key().as_ref()],
+        bump,
+        payer = owner,
+        space = LpStake::LEN,
+    )]
+    pub lp_stake: Box<Account<'info, LpStake>>,
+
+    pub system_program: Program<'info, System>,
+}
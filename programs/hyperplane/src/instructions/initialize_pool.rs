@@ -14,9 +14,10 @@ use crate::{
     constraints::SWAP_CONSTRAINTS,
     curve::{base::SwapCurve, fees::Fees},
     error::SwapError,
+    require_msg,
     state::{Curve, SwapPool},
-    to_u64,
-    utils::{pool_token, seeds, swap_token},
+    to_u64, try_math,
+    utils::{math::TryMath, pool_token, seeds, swap_token},
 };
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -40,6 +41,7 @@ pub fn handler(
     curve_parameters: CurveUserParameters,
     fees: Fees,
     initial_supply: InitialSupply,
+    min_liquidity: u64,
 ) -> Result<()> {
     let InitialSupply {
         initial_supply_a,
@@ -54,10 +56,11 @@ pub fn handler(
     let swap_curve = SwapCurve::new_from_params(curve_parameters)?;
 
     msg!(
-        "Initialize parameters: swap_curve={:?}, initial_supply_a={}, initial_supply_b={}",
+        "Initialize parameters: swap_curve={:?}, initial_supply_a={}, initial_supply_b={}, min_liquidity={}",
         swap_curve,
         initial_supply_a,
-        initial_supply_b
+        initial_supply_b,
+        min_liquidity
     );
 
     swap_curve
@@ -80,7 +83,16 @@ pub fn handler(
     fees.validate()?;
     swap_curve.calculator.validate()?;
 
-    let initial_amount = swap_curve.calculator.new_pool_supply();
+    let new_pool_supply = swap_curve.calculator.new_pool_supply();
+    require_msg!(
+        min_liquidity > 0 && u128::from(min_liquidity) < new_pool_supply,
+        SwapError::InvalidMinLiquidity,
+        &format!(
+            "Minimum liquidity must be greater than zero and less than the new pool supply: min_liquidity={}, new_pool_supply={}",
+            min_liquidity, new_pool_supply
+        )
+    );
+    let initial_amount = try_math!(new_pool_supply.try_sub(u128::from(min_liquidity)))?;
     let pool_authority_bump = *ctx.bumps.get("pool_authority").unwrap();
 
     let pool = &mut ctx.accounts.pool.load_init()?;
@@ -97,6 +109,7 @@ pub fn handler(
     pool.fees = fees;
     pool.curve_type = swap_curve.curve_type.into();
     pool.swap_curve = ctx.accounts.swap_curve.key();
+    pool.min_liquidity = min_liquidity;
 
     swap_token::transfer_from_user(
         ctx.accounts.token_a_token_program.to_account_info(),
@@ -126,6 +139,15 @@ pub fn handler(
         ctx.accounts.admin_pool_token_ata.to_account_info(),
         to_u64!(initial_amount)?,
     )?;
+    pool_token::mint(
+        ctx.accounts.pool_token_program.to_account_info(),
+        ctx.accounts.pool.to_account_info(),
+        ctx.accounts.pool_token_mint.to_account_info(),
+        ctx.accounts.pool_authority.to_account_info(),
+        pool_authority_bump,
+        ctx.accounts.min_liquidity_vault.to_account_info(),
+        min_liquidity,
+    )?;
 
     // Serialize the curve with a layout that is specific to the curve type
     swap_curve
@@ -257,6 +279,18 @@ pub struct InitializePool<'info> {
     )]
     pub admin_pool_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// Token account the minimum liquidity is minted into - its authority is the pool token mint,
+    /// which never signs, so these pool tokens can never be withdrawn
+    #[account(init,
+        seeds=[seeds::MIN_LIQUIDITY_VAULT, pool.key().as_ref()],
+        bump,
+        payer = admin,
+        token::mint = pool_token_mint,
+        token::authority = pool_token_mint,
+        token::token_program = pool_token_program,
+    )]
+    pub min_liquidity_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
     /// The token program for the pool token mint
@@ -0,0 +1,76 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    error::SwapError,
+    state::{RewardVault, SwapPool},
+    utils::{instructions::unix_timestamp, seeds},
+};
+
+/// Create the reward vault of a pool, and the vault the staked pool tokens are locked in.
+/// Rewards are added to the reward vault separately with `add_reward`.
+pub fn handler(ctx: Context<InitializeRewardVault>) -> Result<()> {
+    let now = unix_timestamp()?;
+
+    msg!(
+        "Initialize reward vault: pool={}, staked_lp_vault={}, now={}",
+        ctx.accounts.pool.key(),
+        ctx.accounts.staked_lp_vault.key(),
+        now
+    );
+
+    let reward_vault = &mut ctx.accounts.reward_vault;
+    reward_vault.pool = ctx.accounts.pool.key();
+    reward_vault.staked_lp_vault = ctx.accounts.staked_lp_vault.key();
+    reward_vault.last_update_ts = now;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeRewardVault<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin,
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+        has_one = pool_token_mint @ SwapError::IncorrectPoolMint,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    #[account(
+        mint::token_program = pool_token_program,
+    )]
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(init,
+        seeds = [seeds::REWARD_VAULT, pool.key().as_ref()],
+        bump,
+        payer = admin,
+        space = RewardVault::LEN,
+    )]
+    pub reward_vault: Box<Account<'info, RewardVault>>,
+
+    /// Token account the staked pool tokens are locked in - owned by the pool authority
+    #[account(init,
+        seeds = [seeds::STAKED_LP_VAULT, pool.key().as_ref()],
+        bump,
+        payer = admin,
+        token::mint = pool_token_mint,
+        token::authority = pool_authority,
+        token::token_program = pool_token_program,
+    )]
+    pub staked_lp_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    /// The token program for the pool token mint
+    pub pool_token_program: Interface<'info, TokenInterface>,
+}
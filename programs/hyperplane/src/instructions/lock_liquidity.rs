@@ -0,0 +1,128 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    emitted,
+    error::SwapError,
+    event, require_msg,
+    state::{LiquidityLock, SwapPool},
+    utils::{instructions::unix_timestamp, seeds, swap_token},
+};
+
+/// Lock pool tokens of the owner until they are vested. The pool tokens vest linearly between
+/// `vesting_start_ts` and `vesting_end_ts`, or all at once if both timestamps are equal.
+pub fn handler(
+    ctx: Context<LockLiquidity>,
+    lock_id: u64,
+    amount: u64,
+    vesting_start_ts: u64,
+    vesting_end_ts: u64,
+) -> Result<event::LockLiquidity> {
+    let now = unix_timestamp()?;
+
+    require_msg!(
+        amount > 0,
+        SwapError::InvalidLiquidityLock,
+        "Cannot lock zero pool tokens"
+    );
+    require_msg!(
+        vesting_start_ts <= vesting_end_ts && vesting_end_ts > now,
+        SwapError::InvalidLiquidityLock,
+        &format!(
+            "Vesting must end in the future and not before it starts: vesting_start_ts={}, vesting_end_ts={}, now={}",
+            vesting_start_ts, vesting_end_ts, now
+        )
+    );
+
+    msg!(
+        "Lock liquidity: owner={}, lock_id={}, amount={}, vesting_start_ts={}, vesting_end_ts={}",
+        ctx.accounts.owner.key(),
+        lock_id,
+        amount,
+        vesting_start_ts,
+        vesting_end_ts
+    );
+
+    swap_token::transfer_from_user(
+        ctx.accounts.pool_token_program.to_account_info(),
+        ctx.accounts.owner_pool_token_ata.to_account_info(),
+        ctx.accounts.pool_token_mint.to_account_info(),
+        ctx.accounts.lock_vault.to_account_info(),
+        ctx.accounts.owner.to_account_info(),
+        amount,
+        ctx.accounts.pool_token_mint.decimals,
+    )?;
+
+    let liquidity_lock = &mut ctx.accounts.liquidity_lock;
+    liquidity_lock.pool = ctx.accounts.pool.key();
+    liquidity_lock.owner = ctx.accounts.owner.key();
+    liquidity_lock.lock_id = lock_id;
+    liquidity_lock.lock_vault = ctx.accounts.lock_vault.key();
+    liquidity_lock.locked_amount = amount;
+    liquidity_lock.vesting_start_ts = vesting_start_ts;
+    liquidity_lock.vesting_end_ts = vesting_end_ts;
+
+    emitted!(event::LockLiquidity {
+        owner: ctx.accounts.owner.key(),
+        lock_id,
+        amount,
+        vesting_start_ts,
+        vesting_end_ts,
+    });
+}
+
+#[derive(Accounts)]
+#[instruction(lock_id: u64)]
+pub struct LockLiquidity<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+        has_one = pool_token_mint @ SwapError::IncorrectPoolMint,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    #[account(init,
+        seeds = [seeds::LIQUIDITY_LOCK, pool.key().as_ref(), owner.key().as_ref(), &lock_id.to_le_bytes()],
+        bump,
+        payer = owner,
+        space = LiquidityLock::LEN,
+    )]
+    pub liquidity_lock: Box<Account<'info, LiquidityLock>>,
+
+    /// Token account the locked pool tokens are held in - owned by the pool authority
+    #[account(init,
+        seeds = [seeds::LIQUIDITY_LOCK_VAULT, liquidity_lock.key().as_ref()],
+        bump,
+        payer = owner,
+        token::mint = pool_token_mint,
+        token::authority = pool_authority,
+        token::token_program = pool_token_program,
+    )]
+    pub lock_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mint::token_program = pool_token_program,
+    )]
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Owner's pool token account to lock from
+    #[account(mut,
+        token::mint = pool_token_mint,
+        token::authority = owner,
+        token::token_program = pool_token_program,
+    )]
+    pub owner_pool_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    /// The token program for the pool token mint
+    pub pool_token_program: Interface<'info, TokenInterface>,
+}
@@ -1,6 +1,20 @@
+pub mod add_reward;
+pub mod claim_rewards;
+pub mod compound_fees;
 pub mod deposit;
+pub mod distribute_fees;
+pub mod fund_rewards;
+pub mod initialize_fee_recipients;
+pub mod initialize_lp_stake;
 pub mod initialize_pool;
+pub mod initialize_reward_vault;
+pub mod lock_liquidity;
+pub mod set_reward_emissions;
+pub mod stake_lp;
 pub mod swap;
+pub mod unlock_liquidity;
+pub mod unstake_lp;
+pub mod update_fee_recipients;
 pub mod update_pool_config;
 pub mod withdraw;
 pub mod withdraw_fees;
@@ -8,9 +22,23 @@ pub mod withdraw_fees;
 #[cfg(test)]
 pub mod test;
 
+pub use add_reward::*;
+pub use claim_rewards::*;
+pub use compound_fees::*;
 pub use deposit::*;
+pub use distribute_fees::*;
+pub use fund_rewards::*;
+pub use initialize_fee_recipients::*;
+pub use initialize_lp_stake::*;
 pub use initialize_pool::*;
+pub use initialize_reward_vault::*;
+pub use lock_liquidity::*;
+pub use set_reward_emissions::*;
+pub use stake_lp::*;
 pub use swap::*;
+pub use unlock_liquidity::*;
+pub use unstake_lp::*;
+pub use update_fee_recipients::*;
 pub use update_pool_config::*;
 pub use withdraw::*;
 pub use withdraw_fees::*;
@@ -0,0 +1,62 @@
+use anchor_lang::{accounts::interface_account::InterfaceAccount, prelude::*};
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    emitted,
+    error::SwapError,
+    event,
+    state::{RewardVault, SwapPool},
+    utils::{instructions::unix_timestamp, seeds},
+};
+
+/// Change the emission rate of a reward. Rewards accrued at the previous rate are settled first.
+pub fn handler(
+    ctx: Context<SetRewardEmissions>,
+    emissions_per_second: u64,
+) -> Result<event::SetRewardEmissions> {
+    let reward_mint = ctx.accounts.reward_mint.key();
+    let reward_vault = &mut ctx.accounts.reward_vault;
+    let reward_index = reward_vault.reward_index(&reward_mint).ok_or_else(|| {
+        msg!(
+            "IncorrectRewardMint: reward_mint ({}) is not a reward of the reward vault",
+            reward_mint
+        );
+        error!(SwapError::IncorrectRewardMint)
+    })?;
+
+    reward_vault.update_rewards(unix_timestamp()?)?;
+
+    let reward = &mut reward_vault.rewards[reward_index];
+    msg!(
+        "Set reward emissions: reward_mint={}, previous={}, emissions_per_second={}",
+        reward_mint,
+        reward.emissions_per_second,
+        emissions_per_second
+    );
+    reward.emissions_per_second = emissions_per_second;
+
+    emitted!(event::SetRewardEmissions {
+        reward_mint,
+        emissions_per_second,
+    });
+}
+
+#[derive(Accounts)]
+pub struct SetRewardEmissions<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    #[account(mut,
+        seeds = [seeds::REWARD_VAULT, pool.key().as_ref()],
+        bump,
+        has_one = pool,
+    )]
+    pub reward_vault: Box<Account<'info, RewardVault>>,
+
+    /// CHECK: checked in the handler
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+}
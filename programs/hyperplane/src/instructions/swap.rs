@@ -2,12 +2,7 @@ use anchor_lang::{
     accounts::{interface::Interface, interface_account::InterfaceAccount},
     prelude::*,
 };
-use anchor_spl::{
-    token_2022::spl_token_2022::extension::{
-        transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions,
-    },
-    token_interface::{Mint, TokenAccount, TokenInterface},
-};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
     curve,
@@ -18,7 +13,7 @@ use crate::{
     state::{SwapPool, SwapState},
     swap::utils::validate_inputs,
     to_u64, try_math,
-    utils::{math::TryMath, swap_token},
+    utils::{math::TryMath, swap_token, transfer_fees},
 };
 
 pub fn handler(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u64) -> Result<event::Swap> {
@@ -332,94 +327,29 @@ mod utils {
 
     /// Subtract token mint transfer fees for actual amount received by the user post-transfer fees
     pub fn sub_transfer_fee(mint_acc_info: &AccountInfo, amount: u64) -> Result<u64> {
-        let mint_data = mint_acc_info.data.borrow();
-        let mint =
-            StateWithExtensions::<anchor_spl::token_2022::spl_token_2022::state::Mint>::unpack(
-                &mint_data,
-            )?;
-        let amount = if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() {
-            let transfer_fee = transfer_fee_config
-                .calculate_epoch_fee(Clock::get()?.epoch, amount)
-                .ok_or_else(|| error!(SwapError::FeeCalculationFailure))?;
-            let amount_sub_fee = try_math!(amount.try_sub(transfer_fee))?;
-            msg!(
-                "Subtract token transfer fee: fee={}, amount={}, amount_sub_fee={}",
-                transfer_fee,
-                amount,
-                amount_sub_fee
-            );
-            amount_sub_fee
-        } else {
-            amount
-        };
-        Ok(amount)
+        let transfer_fee_config =
+            transfer_fees::get_transfer_fee_config(&mint_acc_info.data.borrow())?;
+        transfer_fees::sub_transfer_fee(transfer_fee_config.as_ref(), Clock::get()?.epoch, amount)
     }
 
     /// Subtract token mint transfer fees for actual amount received by the pool post-transfer fees
     ///
-    /// There are potentially 3 input transfers:
-    /// 1. User -> Pool
-    /// 2. User -> Fees
-    /// 3. User -> Host Fees (optional)
-    ///
-    /// At low token amounts, the fees on each transfer rounding up can result in the user paying more than the amount_in, causing an unexpected `ExceededSlippage` error
+    /// See [transfer_fees::sub_input_transfer_fees]
     pub fn sub_input_transfer_fees(
         mint_acc_info: &AccountInfo,
         fees: &Fees,
         amount_in: u64,
         host_fee: bool,
     ) -> Result<u64> {
-        let mint_data = mint_acc_info.data.borrow();
-        let mint =
-            StateWithExtensions::<anchor_spl::token_2022::spl_token_2022::state::Mint>::unpack(
-                &mint_data,
-            )?;
-        let amount = if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() {
-            let owner_and_host_fee = fees.owner_trading_fee(amount_in.into())?;
-            let epoch = Clock::get()?.epoch;
-            let (host_fee, host_transfer_fee) = if host_fee {
-                let host_fee = fees.host_fee(owner_and_host_fee)?;
-                (
-                    host_fee,
-                    transfer_fee_config
-                        .calculate_epoch_fee(epoch, to_u64!(host_fee)?)
-                        .ok_or_else(|| error!(SwapError::FeeCalculationFailure))?,
-                )
-            } else {
-                (0, 0)
-            };
-            let owner_fee = try_math!(owner_and_host_fee.try_sub(host_fee))?;
-            let owner_transfer_fee = transfer_fee_config
-                .calculate_epoch_fee(epoch, to_u64!(owner_fee)?)
-                .ok_or_else(|| error!(SwapError::FeeCalculationFailure))?;
-
-            let vault_amount_in = try_math!(amount_in.try_sub(to_u64!(owner_and_host_fee)?))?;
-            let vault_transfer_fee = transfer_fee_config
-                .calculate_epoch_fee(epoch, vault_amount_in)
-                .ok_or_else(|| error!(SwapError::FeeCalculationFailure))?;
-
-            let amount_sub_fees = try_math!(try_math!(try_math!(
-                amount_in.try_sub(vault_transfer_fee)
-            )?
-            .try_sub(owner_transfer_fee))?
-            .try_sub(host_transfer_fee))?;
-
-            msg!(
-                "Subtract input token transfer fee: vault_transfer_amount={}, vault_transfer_fee={}, owner_fee={}, owner_fee_transfer_fee={}, host_fee={}, host_fee_transfer_fee={} amount={}, input_amount_sub_transfer_fees={}",
-                vault_amount_in,
-                vault_transfer_fee,
-                owner_fee,
-                owner_transfer_fee,
-                host_fee,
-                host_transfer_fee,
-                amount_in,
-                amount_sub_fees
-            );
-            amount_sub_fees
-        } else {
-            amount_in
-        };
-        Ok(amount)
+        let transfer_fee_config =
+            transfer_fees::get_transfer_fee_config(&mint_acc_info.data.borrow())?;
+        transfer_fees::sub_input_transfer_fees(
+            transfer_fee_config.as_ref(),
+            Clock::get()?.epoch,
+            fees,
+            amount_in,
+            host_fee,
+        )
     }
 
     /// Add token mint transfer fees for actual amount sent pre-transfer fees
@@ -427,27 +357,13 @@ mod utils {
         mint_acc_info: &AccountInfo,
         post_fee_amount: u64,
     ) -> Result<u64> {
-        let mint_data = mint_acc_info.data.borrow();
-        let mint =
-            StateWithExtensions::<anchor_spl::token_2022::spl_token_2022::state::Mint>::unpack(
-                &mint_data,
-            )?;
-        let amount = if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() {
-            let transfer_fee = transfer_fee_config
-                .calculate_inverse_epoch_fee(Clock::get()?.epoch, post_fee_amount)
-                .ok_or_else(|| error!(SwapError::FeeCalculationFailure))?;
-            let amount_add_fee = try_math!(post_fee_amount.try_add(transfer_fee))?;
-            msg!(
-                "Add token transfer fee: fee={}, amount={}, amount_add_fee={}",
-                transfer_fee,
-                post_fee_amount,
-                amount_add_fee
-            );
-            amount_add_fee
-        } else {
-            post_fee_amount
-        };
-        Ok(amount)
+        let transfer_fee_config =
+            transfer_fees::get_transfer_fee_config(&mint_acc_info.data.borrow())?;
+        transfer_fees::add_inverse_transfer_fee(
+            transfer_fee_config.as_ref(),
+            Clock::get()?.epoch,
+            post_fee_amount,
+        )
     }
 
     #[cfg(test)]
@@ -456,7 +372,10 @@ mod utils {
         use anchor_spl::token_2022::{
             spl_token_2022,
             spl_token_2022::{
-                extension::{transfer_fee::TransferFee, ExtensionType, StateWithExtensionsMut},
+                extension::{
+                    transfer_fee::{TransferFee, TransferFeeConfig},
+                    ExtensionType, StateWithExtensionsMut,
+                },
                 pod::OptionalNonZeroPubkey,
             },
         };
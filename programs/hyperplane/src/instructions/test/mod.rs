@@ -1,5 +1,7 @@
+mod test_amm;
 mod test_deposit;
 mod test_initialize_pool;
+mod test_quote;
 mod test_swap;
 mod test_withdraw;
 
@@ -13,7 +13,7 @@ use solana_sdk::account::{create_account_for_test, Account as SolanaAccount, Wri
 
 use crate::{
     constraints::{SwapConstraints, SWAP_CONSTRAINTS},
-    curve::{base::SwapCurve, fees::Fees},
+    curve::{base::SwapCurve, calculator::MIN_LIQUIDITY, fees::Fees},
     instructions::{
         model::CurveParameters,
         test::runner::{syscall_stubs::test_syscall_stubs, token},
@@ -39,6 +39,7 @@ pub struct SwapAccountInfo {
     pub pool_authority: Pubkey,
     pub fees: Fees,
     pub initial_supply: InitialSupply,
+    pub min_liquidity: u64,
     pub transfer_fees: SwapTransferFees,
     pub pool: Pubkey,
     pub pool_account: SolanaAccount,
@@ -58,6 +59,8 @@ pub struct SwapAccountInfo {
     pub admin_authority_token_b_ata_account: SolanaAccount,
     pub admin_authority_pool_token_ata_key: Pubkey,
     pub admin_authority_pool_token_ata_account: SolanaAccount,
+    pub min_liquidity_vault_key: Pubkey,
+    pub min_liquidity_vault_account: SolanaAccount,
     pub token_a_vault_key: Pubkey,
     pub token_a_vault_account: SolanaAccount,
     pub token_a_mint_key: Pubkey,
@@ -111,6 +114,14 @@ impl SwapAccountInfo {
             pool_token_program_id, // this should be system but we no-op the system program calls
         );
 
+        let (min_liquidity_vault_key, _min_liquidity_vault_bump_seed) =
+            seeds::pda::min_liquidity_vault_pda(&pool);
+        let min_liquidity_vault_account = SolanaAccount::new(
+            u32::MAX as u64,
+            spl_token_2022::state::Account::LEN,
+            pool_token_program_id, // this should be system but we no-op the system program calls
+        );
+
         let (token_a_decimals, token_b_decimals) = match curve_params {
             CurveParameters::Stable {
                 token_a_decimals,
@@ -189,6 +200,7 @@ impl SwapAccountInfo {
             pool_authority,
             fees,
             initial_supply,
+            min_liquidity: MIN_LIQUIDITY as u64,
             transfer_fees,
             pool,
             pool_account,
@@ -208,6 +220,8 @@ impl SwapAccountInfo {
             admin_authority_token_b_ata_account,
             admin_authority_pool_token_ata_key,
             admin_authority_pool_token_ata_account,
+            min_liquidity_vault_key,
+            min_liquidity_vault_account,
             token_a_vault_key,
             token_a_vault_account,
             token_a_mint_key,
@@ -242,6 +256,7 @@ impl SwapAccountInfo {
                 &self.admin_authority_token_a_ata_key,
                 &self.admin_authority_token_b_ata_key,
                 &self.admin_authority_pool_token_ata_key,
+                &self.min_liquidity_vault_key,
                 &self.pool_token_program_id,
                 &self.token_a_program_id,
                 &self.token_b_program_id,
@@ -249,6 +264,7 @@ impl SwapAccountInfo {
                     fees: self.fees,
                     initial_supply: self.initial_supply.clone(),
                     curve_parameters: self.curve_params.clone().into(),
+                    min_liquidity: self.min_liquidity,
                 },
             )
             .unwrap(),
@@ -267,6 +283,7 @@ impl SwapAccountInfo {
                 &mut self.admin_authority_token_a_ata_account,
                 &mut self.admin_authority_token_b_ata_account,
                 &mut self.admin_authority_pool_token_ata_account,
+                &mut self.min_liquidity_vault_account,
                 &mut exe.clone(), // system_program
                 &mut create_account_for_test(&Rent::default()),
                 &mut exe.clone(), // pool_token_program
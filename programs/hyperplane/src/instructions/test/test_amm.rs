@@ -0,0 +1,221 @@
+use anchor_lang::{prelude::*, solana_program::sysvar::SysvarId};
+use anchor_spl::{
+    token::spl_token,
+    token_2022::{
+        spl_token_2022,
+        spl_token_2022::{
+            extension::{transfer_fee::TransferFee, StateWithExtensions},
+            state::Account,
+        },
+    },
+};
+use solana_sdk::account::{create_account_for_test, Account as SolanaAccount};
+use test_case::test_case;
+
+use crate::{
+    amm::{AccountData, AccountMap, Amm, HyperplaneAmm, KeyedAccount, QuoteParams, SwapParams},
+    curve::fees::Fees,
+    instructions::test::runner::processor::{SwapAccountInfo, SwapTransferFees},
+    model::CurveParameters,
+    InitialSupply,
+};
+
+#[test_case(spl_token::id(), spl_token::id(), spl_token::id(); "all-token")]
+#[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token_2022::id(); "all-token-2022")]
+#[test_case(spl_token::id(), spl_token_2022::id(), spl_token::id(); "a-only-token-2022")]
+fn test_amm_quote_matches_swap(
+    pool_token_program_id: Pubkey,
+    token_a_program_id: Pubkey,
+    token_b_program_id: Pubkey,
+) {
+    let user_key = Pubkey::new_unique();
+    let trader_key = Pubkey::new_unique();
+    let mut accounts = SwapAccountInfo::new(
+        &user_key,
+        Fees {
+            trade_fee_numerator: 25,
+            trade_fee_denominator: 10_000,
+            owner_trade_fee_numerator: 5,
+            owner_trade_fee_denominator: 10_000,
+            host_fee_numerator: 20,
+            host_fee_denominator: 100,
+            ..Fees::default()
+        },
+        SwapTransferFees {
+            _pool_token: TransferFee::default(),
+            token_a: TransferFee {
+                epoch: 0.into(),
+                transfer_fee_basis_points: 100.into(),
+                maximum_fee: 1_000_000_000.into(),
+            },
+            token_b: TransferFee::default(),
+        },
+        CurveParameters::ConstantProduct,
+        InitialSupply::new(10_000_000_000, 50_000_000_000),
+        &pool_token_program_id,
+        &token_a_program_id,
+        &token_b_program_id,
+    );
+    accounts.initialize_pool().unwrap();
+    let (token_a_key, mut token_a_account, token_b_key, mut token_b_account, _, _) =
+        accounts.setup_token_accounts(&user_key, &trader_key, 1_000_000_000, 0, 0);
+
+    let mut amm = HyperplaneAmm::from_keyed_account(&KeyedAccount {
+        key: accounts.pool,
+        account: account_data(&accounts.pool_account),
+    })
+    .unwrap();
+    assert_eq!(amm.key(), accounts.pool);
+    assert_eq!(amm.program_id(), crate::id());
+    assert_eq!(
+        amm.get_reserve_mints(),
+        vec![accounts.token_a_mint_key, accounts.token_b_mint_key]
+    );
+
+    let quote_params = QuoteParams {
+        input_mint: accounts.token_a_mint_key,
+        output_mint: accounts.token_b_mint_key,
+        in_amount: 100_000_000,
+        with_host_fee: false,
+    };
+    // quotes require the pool accounts
+    assert!(amm.quote(&quote_params).is_err());
+
+    amm.update(&account_map(&amm, &accounts)).unwrap();
+    let quote = amm.quote(&quote_params).unwrap();
+    assert_eq!(quote.in_amount, 100_000_000);
+    assert_eq!(quote.fee_mint, accounts.token_a_mint_key);
+    assert!(quote.fee_amount > 0);
+
+    let token_a_vault_key = accounts.token_a_vault_key;
+    let token_a_fees_vault_key = accounts.token_a_fees_vault_key;
+    let token_b_vault_key = accounts.token_b_vault_key;
+    accounts
+        .swap(
+            &trader_key,
+            &token_a_key,
+            &mut token_a_account,
+            &token_a_vault_key,
+            &token_a_fees_vault_key,
+            &token_b_vault_key,
+            &token_b_key,
+            &mut token_b_account,
+            quote.in_amount,
+            quote.out_amount,
+        )
+        .unwrap();
+    let token_b = StateWithExtensions::<Account>::unpack(&token_b_account.data).unwrap();
+    assert_eq!(token_b.base.amount, quote.out_amount);
+
+    // the next quote sees the new vault balances
+    let stale_quote = quote;
+    amm.update(&account_map(&amm, &accounts)).unwrap();
+    assert!(amm.quote(&quote_params).unwrap().out_amount < stale_quote.out_amount);
+}
+
+#[test_case(None; "without-host-fees")]
+#[test_case(Some(Pubkey::new_unique()); "with-host-fees")]
+fn test_amm_swap_account_metas(host_fee_account: Option<Pubkey>) {
+    let user_key = Pubkey::new_unique();
+    let mut accounts = SwapAccountInfo::new(
+        &user_key,
+        Fees::default(),
+        SwapTransferFees::default(),
+        CurveParameters::ConstantProduct,
+        InitialSupply::new(1_000_000, 1_000_000),
+        &spl_token::id(),
+        &spl_token::id(),
+        &spl_token_2022::id(),
+    );
+    accounts.initialize_pool().unwrap();
+    let mut amm = HyperplaneAmm::from_keyed_account(&KeyedAccount {
+        key: accounts.pool,
+        account: account_data(&accounts.pool_account),
+    })
+    .unwrap();
+    amm.update(&account_map(&amm, &accounts)).unwrap();
+
+    let swap_params = SwapParams {
+        source_mint: accounts.token_b_mint_key,
+        destination_mint: accounts.token_a_mint_key,
+        user_source_token_account: Pubkey::new_unique(),
+        user_destination_token_account: Pubkey::new_unique(),
+        user_transfer_authority: Pubkey::new_unique(),
+        in_amount: 1_000,
+        minimum_amount_out: 900,
+        host_fee_account,
+    };
+    let swap = amm.get_swap_and_account_metas(&swap_params).unwrap();
+
+    let expected_accounts = crate::accounts::Swap {
+        signer: swap_params.user_transfer_authority,
+        pool: accounts.pool,
+        swap_curve: accounts.swap_curve_key,
+        pool_authority: accounts.pool_authority,
+        source_mint: accounts.token_b_mint_key,
+        destination_mint: accounts.token_a_mint_key,
+        source_vault: accounts.token_b_vault_key,
+        destination_vault: accounts.token_a_vault_key,
+        source_token_fees_vault: accounts.token_b_fees_vault_key,
+        source_user_ata: swap_params.user_source_token_account,
+        destination_user_ata: swap_params.user_destination_token_account,
+        source_token_host_fees_account: host_fee_account,
+        source_token_program: spl_token_2022::id(),
+        destination_token_program: spl_token::id(),
+    }
+    .to_account_metas(None);
+    assert_eq!(swap.account_metas, expected_accounts);
+    assert_eq!(
+        swap.data,
+        anchor_lang::InstructionData::data(&crate::instruction::Swap {
+            amount_in: 1_000,
+            minimum_amount_out: 900,
+        })
+    );
+
+    // mints of other pools are rejected
+    assert!(amm
+        .get_swap_and_account_metas(&SwapParams {
+            source_mint: Pubkey::new_unique(),
+            ..swap_params
+        })
+        .is_err());
+}
+
+fn account_data(account: &SolanaAccount) -> AccountData {
+    AccountData {
+        owner: account.owner,
+        data: account.data.clone(),
+    }
+}
+
+fn account_map(amm: &HyperplaneAmm, accounts: &SwapAccountInfo) -> AccountMap {
+    let account_by_key = [
+        (accounts.pool, &accounts.pool_account),
+        (accounts.swap_curve_key, &accounts.swap_curve_account),
+        (accounts.token_a_vault_key, &accounts.token_a_vault_account),
+        (accounts.token_b_vault_key, &accounts.token_b_vault_account),
+        (
+            accounts.pool_token_mint_key,
+            &accounts.pool_token_mint_account,
+        ),
+        (accounts.token_a_mint_key, &accounts.token_a_mint_account),
+        (accounts.token_b_mint_key, &accounts.token_b_mint_account),
+    ];
+    let clock_account = create_account_for_test(&Clock::default());
+    amm.get_accounts_to_update()
+        .into_iter()
+        .map(|key| {
+            let account = if key == Clock::id() {
+                &clock_account
+            } else {
+                account_by_key
+                    .iter()
+                    .find(|(account_key, _)| *account_key == key)
+                    .map(|(_, account)| *account)
+                    .unwrap()
+            };
+            (key, account_data(account))
+        })
+        .collect()
+}
@@ -834,11 +834,16 @@ fn test_deposit(
             &accounts.admin_authority_pool_token_ata_account.data,
         )
         .unwrap();
+        let min_liquidity_account =
+            StateWithExtensions::<Account>::unpack(&accounts.min_liquidity_vault_account.data)
+                .unwrap();
         let pool_mint =
             StateWithExtensions::<Mint>::unpack(&accounts.pool_token_mint_account.data).unwrap();
         assert_eq!(
             pool_mint.base.supply,
-            pool_account.base.amount + swap_pool_account.base.amount
+            pool_account.base.amount
+                + swap_pool_account.base.amount
+                + min_liquidity_account.base.amount
         );
     }
 }
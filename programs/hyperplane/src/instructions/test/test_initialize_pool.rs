@@ -17,7 +17,7 @@ use test_case::test_case;
 
 use crate::{
     constraints::SwapConstraints,
-    curve::{base::CurveType, fees::Fees, stable::MAX_AMP},
+    curve::{base::CurveType, calculator::MIN_LIQUIDITY, fees::Fees, stable::MAX_AMP},
     error::SwapError,
     instructions::test::runner::{
         processor::{
@@ -397,6 +397,7 @@ fn test_initialize(
                     &accounts.admin_authority_token_a_ata_key,
                     &accounts.admin_authority_token_b_ata_key,
                     &accounts.admin_authority_pool_token_ata_key,
+                    &accounts.min_liquidity_vault_key,
                     &wrong_pool_token_program_id,
                     &accounts.token_a_program_id,
                     &accounts.token_b_program_id,
@@ -404,6 +405,7 @@ fn test_initialize(
                         fees: accounts.fees,
                         initial_supply: accounts.initial_supply.clone(),
                         curve_parameters: accounts.curve_params.clone().into(),
+                        min_liquidity: accounts.min_liquidity,
                     },
                 )
                 .unwrap(),
@@ -422,6 +424,7 @@ fn test_initialize(
                     &mut accounts.admin_authority_token_a_ata_account,
                     &mut accounts.admin_authority_token_b_ata_account,
                     &mut accounts.admin_authority_pool_token_ata_account,
+                    &mut accounts.min_liquidity_vault_account,
                     &mut exe.clone(), // system_program
                     &mut create_account_for_test(&Rent::default()),
                     &mut exe.clone(), // pool_token_program
@@ -861,6 +864,7 @@ fn test_initialize(
                 &accounts.admin_authority_token_a_ata_key,
                 &accounts.admin_authority_token_b_ata_key,
                 &accounts.admin_authority_pool_token_ata_key,
+                &accounts.min_liquidity_vault_key,
                 &accounts.pool_token_program_id,
                 &accounts.token_a_program_id,
                 &accounts.token_b_program_id,
@@ -868,6 +872,7 @@ fn test_initialize(
                     fees: accounts.fees,
                     initial_supply: accounts.initial_supply.clone(),
                     curve_parameters: accounts.curve_params.clone().into(),
+                    min_liquidity: accounts.min_liquidity,
                 },
             )
             .unwrap(),
@@ -886,6 +891,7 @@ fn test_initialize(
                 &mut accounts.admin_authority_token_a_ata_account,
                 &mut accounts.admin_authority_token_b_ata_account,
                 &mut accounts.admin_authority_pool_token_ata_account,
+                &mut accounts.min_liquidity_vault_account,
                 &mut exe.clone(), // system_program
                 &mut create_account_for_test(&Rent::default()),
                 &mut exe.clone(), // pool_token_program
@@ -935,7 +941,17 @@ fn test_initialize(
         &accounts.admin_authority_pool_token_ata_account.data,
     )
     .unwrap();
+    let min_liquidity_account =
+        StateWithExtensions::<Account>::unpack(&accounts.min_liquidity_vault_account.data).unwrap();
+    assert_eq!(
+        min_liquidity_account.base.amount,
+        u64::try_from(MIN_LIQUIDITY).unwrap()
+    );
+    assert_eq!(swap_pool.min_liquidity, min_liquidity_account.base.amount);
     let pool_mint =
         StateWithExtensions::<Mint>::unpack(&accounts.pool_token_mint_account.data).unwrap();
-    assert_eq!(pool_mint.base.supply, pool_account.base.amount);
+    assert_eq!(
+        pool_mint.base.supply,
+        pool_account.base.amount + min_liquidity_account.base.amount
+    );
 }
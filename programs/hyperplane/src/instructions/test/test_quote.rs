@@ -0,0 +1,317 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::spl_token,
+    token_2022::{
+        spl_token_2022,
+        spl_token_2022::{
+            extension::{transfer_fee::TransferFee, StateWithExtensions},
+            state::Account,
+        },
+    },
+};
+use solana_sdk::account::Account as SolanaAccount;
+use test_case::test_case;
+
+use crate::{
+    curve::{base::SwapCurve, calculator::TradeDirection, fees::Fees},
+    error::SwapError,
+    instructions::test::runner::processor::{SwapAccountInfo, SwapTransferFees},
+    model::CurveParameters,
+    quote::{spot_price, PoolAccountsData, PoolSnapshot},
+    utils::transfer_fees,
+    InitialSupply,
+};
+
+const FEES: Fees = Fees {
+    trade_fee_numerator: 1,
+    trade_fee_denominator: 10,
+    owner_trade_fee_numerator: 1,
+    owner_trade_fee_denominator: 30,
+    owner_withdraw_fee_numerator: 1,
+    owner_withdraw_fee_denominator: 30,
+    host_fee_numerator: 20,
+    host_fee_denominator: 100,
+};
+
+#[test_case(spl_token::id(), spl_token::id(), spl_token::id(), CurveParameters::ConstantProduct; "all-token")]
+#[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token_2022::id(), CurveParameters::ConstantProduct; "all-token-2022")]
+#[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token::id(), CurveParameters::ConstantProduct; "a-only-token-2022")]
+#[test_case(spl_token_2022::id(), spl_token::id(), spl_token_2022::id(), CurveParameters::ConstantProduct; "b-only-token-2022")]
+#[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token_2022::id(), CurveParameters::Offset { token_b_offset: 1_000_000 }; "offset")]
+#[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token_2022::id(), CurveParameters::Stable { amp: 100, token_a_decimals: 6, token_b_decimals: 6 }; "stable")]
+fn test_quotes_match_instructions(
+    pool_token_program_id: Pubkey,
+    token_a_program_id: Pubkey,
+    token_b_program_id: Pubkey,
+    curve_params: CurveParameters,
+) {
+    let user_key = Pubkey::new_unique();
+    let trader_key = Pubkey::new_unique();
+    let mut accounts = SwapAccountInfo::new(
+        &user_key,
+        FEES,
+        SwapTransferFees {
+            _pool_token: TransferFee::default(),
+            token_a: TransferFee {
+                epoch: 0.into(),
+                transfer_fee_basis_points: 100.into(),
+                maximum_fee: 1_000_000_000.into(),
+            },
+            token_b: TransferFee {
+                epoch: 0.into(),
+                transfer_fee_basis_points: 35.into(),
+                maximum_fee: 1_000_000.into(),
+            },
+        },
+        curve_params,
+        InitialSupply::new(10_000_000_000, 50_000_000_000),
+        &pool_token_program_id,
+        &token_a_program_id,
+        &token_b_program_id,
+    );
+    accounts.initialize_pool().unwrap();
+
+    let (
+        token_a_key,
+        mut token_a_account,
+        token_b_key,
+        mut token_b_account,
+        pool_key,
+        mut pool_account,
+    ) = accounts.setup_token_accounts(&user_key, &trader_key, 1_000_000_000, 5_000_000_000, 0);
+    let token_a_vault_key = accounts.token_a_vault_key;
+    let token_b_vault_key = accounts.token_b_vault_key;
+    let token_a_fees_vault_key = accounts.token_a_fees_vault_key;
+    let token_b_fees_vault_key = accounts.token_b_fees_vault_key;
+
+    // swap a to b
+    let quote = snapshot(&accounts)
+        .swap(&accounts.token_a_mint_key, 100_000_000, false)
+        .unwrap();
+    assert_eq!(quote.trade_direction, TradeDirection::AtoB);
+    let before = balances(&accounts, &token_a_account, &token_b_account, &pool_account);
+    accounts
+        .swap(
+            &trader_key,
+            &token_a_key,
+            &mut token_a_account,
+            &token_a_vault_key,
+            &token_a_fees_vault_key,
+            &token_b_vault_key,
+            &token_b_key,
+            &mut token_b_account,
+            100_000_000,
+            quote.amount_out,
+        )
+        .unwrap();
+    let after = balances(&accounts, &token_a_account, &token_b_account, &pool_account);
+    assert_eq!(
+        before.user_a - after.user_a,
+        quote.source_amount_to_vault + quote.owner_fee_amount
+    );
+    assert_eq!(after.user_b - before.user_b, quote.amount_out);
+    assert_eq!(
+        before.vault_b - after.vault_b,
+        quote.destination_amount_from_vault
+    );
+    assert_eq!(
+        after.vault_a - before.vault_a,
+        spl_token_amount_after_fee(&accounts.token_a_mint_account, quote.source_amount_to_vault)
+    );
+    assert_eq!(
+        after.fees_a - before.fees_a,
+        spl_token_amount_after_fee(&accounts.token_a_mint_account, quote.owner_fee_amount)
+    );
+
+    // swap b to a
+    let quote = snapshot(&accounts)
+        .swap(&accounts.token_b_mint_key, 500_000_000, false)
+        .unwrap();
+    assert_eq!(quote.trade_direction, TradeDirection::BtoA);
+    let before = after;
+    accounts
+        .swap(
+            &trader_key,
+            &token_b_key,
+            &mut token_b_account,
+            &token_b_vault_key,
+            &token_b_fees_vault_key,
+            &token_a_vault_key,
+            &token_a_key,
+            &mut token_a_account,
+            500_000_000,
+            quote.amount_out,
+        )
+        .unwrap();
+    let after = balances(&accounts, &token_a_account, &token_b_account, &pool_account);
+    assert_eq!(
+        before.user_b - after.user_b,
+        quote.source_amount_to_vault + quote.owner_fee_amount
+    );
+    assert_eq!(after.user_a - before.user_a, quote.amount_out);
+    assert_eq!(
+        after.vault_b - before.vault_b,
+        spl_token_amount_after_fee(&accounts.token_b_mint_account, quote.source_amount_to_vault)
+    );
+    assert_eq!(
+        before.vault_a - after.vault_a,
+        quote.destination_amount_from_vault
+    );
+    assert_eq!(
+        after.fees_b - before.fees_b,
+        spl_token_amount_after_fee(&accounts.token_b_mint_account, quote.owner_fee_amount)
+    );
+
+    // deposit
+    let quote = snapshot(&accounts).deposit(1_000_000).unwrap();
+    let before = after;
+    accounts
+        .deposit(
+            &trader_key,
+            &token_a_key,
+            &mut token_a_account,
+            &token_b_key,
+            &mut token_b_account,
+            &pool_key,
+            &mut pool_account,
+            quote.pool_token_amount,
+            quote.token_a_amount,
+            quote.token_b_amount,
+        )
+        .unwrap();
+    let after = balances(&accounts, &token_a_account, &token_b_account, &pool_account);
+    assert_eq!(after.user_pool - before.user_pool, quote.pool_token_amount);
+    assert_eq!(before.user_a - after.user_a, quote.token_a_amount);
+    assert_eq!(before.user_b - after.user_b, quote.token_b_amount);
+    assert_eq!(
+        after.vault_a - before.vault_a,
+        quote.token_a_amount_to_vault
+    );
+    assert_eq!(
+        after.vault_b - before.vault_b,
+        quote.token_b_amount_to_vault
+    );
+
+    // withdraw
+    let quote = snapshot(&accounts).withdraw(1_000_000).unwrap();
+    let before = after;
+    accounts
+        .withdraw(
+            &trader_key,
+            &pool_key,
+            &mut pool_account,
+            &token_a_key,
+            &mut token_a_account,
+            &token_b_key,
+            &mut token_b_account,
+            quote.pool_token_amount,
+            quote.token_a_amount,
+            quote.token_b_amount,
+        )
+        .unwrap();
+    let after = balances(&accounts, &token_a_account, &token_b_account, &pool_account);
+    assert_eq!(before.user_pool - after.user_pool, quote.pool_token_amount);
+    assert_eq!(after.user_a - before.user_a, quote.token_a_amount_received);
+    assert_eq!(after.user_b - before.user_b, quote.token_b_amount_received);
+}
+
+#[test]
+fn test_quote_withdraw_below_min_liquidity() {
+    let user_key = Pubkey::new_unique();
+    let mut accounts = SwapAccountInfo::new(
+        &user_key,
+        FEES,
+        SwapTransferFees::default(),
+        CurveParameters::ConstantProduct,
+        InitialSupply::new(1_000_000, 1_000_000),
+        &spl_token::id(),
+        &spl_token::id(),
+        &spl_token::id(),
+    );
+    accounts.initialize_pool().unwrap();
+
+    let snapshot = snapshot(&accounts);
+    assert_eq!(
+        ProgramError::from(snapshot.withdraw(snapshot.pool_token_supply).unwrap_err()),
+        SwapError::BelowMinimumLiquidity.into()
+    );
+    assert_eq!(
+        ProgramError::from(
+            snapshot
+                .swap(&Pubkey::new_unique(), 1_000, false)
+                .unwrap_err()
+        ),
+        SwapError::IncorrectSwapAccount.into()
+    );
+}
+
+#[test_case(CurveParameters::ConstantProduct, 2.0; "constant-product")]
+#[test_case(CurveParameters::ConstantPrice { token_b_price: 4 }, 0.25; "constant-price")]
+#[test_case(CurveParameters::Offset { token_b_offset: 1_000_000_000_000 }, 3.0; "offset")]
+fn test_spot_price(curve_params: CurveParameters, expected_price: f64) {
+    let swap_curve = SwapCurve::new_from_params(curve_params).unwrap();
+
+    let price = spot_price(&swap_curve, 1_000_000_000_000, 2_000_000_000_000).unwrap();
+
+    assert!(
+        (price - expected_price).abs() <= expected_price * 1e-5,
+        "price={}, expected_price={}",
+        price,
+        expected_price
+    );
+}
+
+struct Balances {
+    user_a: u64,
+    user_b: u64,
+    user_pool: u64,
+    vault_a: u64,
+    vault_b: u64,
+    fees_a: u64,
+    fees_b: u64,
+}
+
+fn balances(
+    accounts: &SwapAccountInfo,
+    token_a_account: &SolanaAccount,
+    token_b_account: &SolanaAccount,
+    pool_account: &SolanaAccount,
+) -> Balances {
+    Balances {
+        user_a: amount(token_a_account),
+        user_b: amount(token_b_account),
+        user_pool: amount(pool_account),
+        vault_a: amount(&accounts.token_a_vault_account),
+        vault_b: amount(&accounts.token_b_vault_account),
+        fees_a: amount(&accounts.token_a_fees_vault_account),
+        fees_b: amount(&accounts.token_b_fees_vault_account),
+    }
+}
+
+fn amount(account: &SolanaAccount) -> u64 {
+    StateWithExtensions::<Account>::unpack(&account.data)
+        .unwrap()
+        .base
+        .amount
+}
+
+fn spl_token_amount_after_fee(mint_account: &SolanaAccount, amount: u64) -> u64 {
+    let transfer_fee_config = transfer_fees::get_transfer_fee_config(&mint_account.data).unwrap();
+    transfer_fees::sub_transfer_fee(transfer_fee_config.as_ref(), 0, amount).unwrap()
+}
+
+fn snapshot(accounts: &SwapAccountInfo) -> PoolSnapshot {
+    PoolSnapshot::from_accounts_data(
+        PoolAccountsData {
+            pool: &accounts.pool_account.data,
+            swap_curve: &accounts.swap_curve_account.data,
+            token_a_vault: &accounts.token_a_vault_account.data,
+            token_b_vault: &accounts.token_b_vault_account.data,
+            pool_token_mint: &accounts.pool_token_mint_account.data,
+            token_a_mint: &accounts.token_a_mint_account.data,
+            token_b_mint: &accounts.token_b_mint_account.data,
+        },
+        0,
+    )
+    .unwrap()
+}
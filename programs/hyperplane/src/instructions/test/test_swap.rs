@@ -274,6 +274,7 @@ fn test_valid_swap_with_fee_constraints(
             &accounts.admin_authority_token_a_ata_key,
             &accounts.admin_authority_token_b_ata_key,
             &accounts.admin_authority_pool_token_ata_key,
+            &accounts.min_liquidity_vault_key,
             &accounts.pool_token_program_id,
             &accounts.token_a_program_id,
             &accounts.token_b_program_id,
@@ -281,6 +282,7 @@ fn test_valid_swap_with_fee_constraints(
                 fees: accounts.fees,
                 initial_supply: accounts.initial_supply.clone(),
                 curve_parameters: accounts.curve_params.clone().into(),
+                min_liquidity: accounts.min_liquidity,
             },
         )
         .unwrap(),
@@ -299,6 +301,7 @@ fn test_valid_swap_with_fee_constraints(
             &mut accounts.admin_authority_token_a_ata_account,
             &mut accounts.admin_authority_token_b_ata_account,
             &mut accounts.admin_authority_pool_token_ata_account,
+            &mut accounts.min_liquidity_vault_account,
             &mut exe.clone(), // system_program
             &mut create_account_for_test(&Rent::default()),
             &mut exe.clone(), // pool_token_program
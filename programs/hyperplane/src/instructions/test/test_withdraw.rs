@@ -14,7 +14,11 @@ use solana_sdk::account::{Account as SolanaAccount, WritableAccount};
 use test_case::test_case;
 
 use crate::{
-    curve::{base::SwapCurve, calculator::RoundDirection, fees::Fees},
+    curve::{
+        base::SwapCurve,
+        calculator::{RoundDirection, MIN_LIQUIDITY},
+        fees::Fees,
+    },
     error::SwapError,
     instructions::test::runner::{
         processor::{do_process_instruction, SwapAccountInfo, SwapTransferFees},
@@ -477,7 +481,7 @@ fn test_withdraw_offset_curve(
     let token_b_offset = 2_000_000;
     let curve_params = CurveParameters::Offset { token_b_offset };
     let swap_curve = SwapCurve::new_from_params(curve_params.clone()).unwrap();
-    let total_pool = swap_curve.calculator.new_pool_supply();
+    let admin_pool = swap_curve.calculator.new_pool_supply() - MIN_LIQUIDITY;
     let user_key = Pubkey::new_unique();
 
     let mut accounts = SwapAccountInfo::new(
@@ -505,7 +509,7 @@ fn test_withdraw_offset_curve(
     let pool_key = accounts.admin_authority_pool_token_ata_key;
     let mut pool_account = accounts.admin_authority_pool_token_ata_account.clone();
 
-    // Withdraw takes all tokens for A and B.
+    // Withdraw takes all tokens for A and B, except for the share of the minimum liquidity.
     // The curve's calculation for token B will say to transfer
     // `token_b_offset + token_b_amount`, but only `token_b_amount` will be
     // moved.
@@ -518,19 +522,20 @@ fn test_withdraw_offset_curve(
             &mut token_a_account,
             &token_b_key,
             &mut token_b_account,
-            total_pool.try_into().unwrap(),
+            admin_pool.try_into().unwrap(),
             0,
             0,
         )
         .unwrap();
 
+    // 1 pool token is worth 1 token A
     let token_a = StateWithExtensions::<Account>::unpack(&token_a_account.data).unwrap();
-    assert_eq!(token_a.base.amount, token_a_amount);
+    assert_eq!(token_a.base.amount, token_a_amount - 1_000);
     let token_b = StateWithExtensions::<Account>::unpack(&token_b_account.data).unwrap();
     assert_eq!(token_b.base.amount, token_b_amount);
     let swap_token_a =
         StateWithExtensions::<Account>::unpack(&accounts.token_a_vault_account.data).unwrap();
-    assert_eq!(swap_token_a.base.amount, 0);
+    assert_eq!(swap_token_a.base.amount, 1_000);
     let swap_token_b =
         StateWithExtensions::<Account>::unpack(&accounts.token_b_vault_account.data).unwrap();
     assert_eq!(swap_token_b.base.amount, 0);
@@ -574,7 +579,7 @@ fn test_withdraw_constant_price_curve(
 
     let curve_params = CurveParameters::ConstantPrice { token_b_price };
     let swap_curve = SwapCurve::new_from_params(curve_params.clone()).unwrap();
-    let total_pool = swap_curve.calculator.new_pool_supply();
+    let admin_pool = swap_curve.calculator.new_pool_supply() - MIN_LIQUIDITY;
     let user_key = Pubkey::new_unique();
     let withdrawer_key = Pubkey::new_unique();
 
@@ -605,8 +610,9 @@ fn test_withdraw_constant_price_curve(
 
     // Withdraw will not take all token A and B, since their
     // ratio is unbalanced.  It will try to take 1_500_000_000 worth of
-    // each token, which means 1_500_000_000 token A, and 750 token B.
-    // With no slippage, this will leave 250 token B in the pool.
+    // each token, which means 1_500_000_000 token A, and 750 token B, minus
+    // the share of the minimum liquidity. With no slippage, this will leave
+    // 251 token B in the pool.
     assert_eq!(
         Err(SwapError::ExceededSlippage.into()),
         accounts.withdraw(
@@ -617,7 +623,7 @@ fn test_withdraw_constant_price_curve(
             &mut token_a_account,
             &token_b_key,
             &mut token_b_account,
-            total_pool.try_into().unwrap(),
+            admin_pool.try_into().unwrap(),
             swap_token_a_amount,
             swap_token_b_amount,
         )
@@ -632,7 +638,7 @@ fn test_withdraw_constant_price_curve(
             &mut token_a_account,
             &token_b_key,
             &mut token_b_account,
-            total_pool.try_into().unwrap(),
+            admin_pool.try_into().unwrap(),
             0,
             0,
         )
@@ -641,13 +647,22 @@ fn test_withdraw_constant_price_curve(
     let token_a = StateWithExtensions::<Account>::unpack(&token_a_account.data).unwrap();
     assert_eq!(token_a.base.amount, swap_token_a_amount);
     let token_b = StateWithExtensions::<Account>::unpack(&token_b_account.data).unwrap();
-    assert_eq!(token_b.base.amount, 750);
+    assert_eq!(token_b.base.amount, 749);
     let swap_token_a =
         StateWithExtensions::<Account>::unpack(&accounts.token_a_vault_account.data).unwrap();
     assert_eq!(swap_token_a.base.amount, 0);
     let swap_token_b =
         StateWithExtensions::<Account>::unpack(&accounts.token_b_vault_account.data).unwrap();
-    assert_eq!(swap_token_b.base.amount, 250);
+    assert_eq!(swap_token_b.base.amount, 251);
+
+    // the minimum liquidity is never withdrawn
+    let pool_mint =
+        StateWithExtensions::<Mint>::unpack(&accounts.pool_token_mint_account.data).unwrap();
+    assert_eq!(u128::from(pool_mint.base.supply), MIN_LIQUIDITY);
+
+    // depositing as many pool tokens as the minimum liquidity doubles the pool,
+    // which takes 251_000_000 token A and 126 token B (125.5 rounded up)
+    let pool_token_amount = MIN_LIQUIDITY as u64;
 
     // deposit now, not enough to cover the tokens already in there
     let token_b_amount = 10;
@@ -677,14 +692,14 @@ fn test_withdraw_constant_price_curve(
             &mut token_b_account,
             &pool_key,
             &mut pool_account,
-            1, // doesn't matter
+            pool_token_amount,
             token_a_amount,
             token_b_amount,
         )
     );
 
     // deposit enough tokens, success!
-    let token_b_amount = 125;
+    let token_b_amount = 126;
     let token_a_amount = token_b_amount * token_b_price;
     let (
         token_a_key,
@@ -710,9 +725,18 @@ fn test_withdraw_constant_price_curve(
             &mut token_b_account,
             &pool_key,
             &mut pool_account,
-            1, // doesn't matter
+            pool_token_amount,
             token_a_amount,
             token_b_amount,
         )
         .unwrap();
+
+    let pool_token = StateWithExtensions::<Account>::unpack(&pool_account.data).unwrap();
+    assert_eq!(pool_token.base.amount, pool_token_amount);
+    let swap_token_a =
+        StateWithExtensions::<Account>::unpack(&accounts.token_a_vault_account.data).unwrap();
+    assert_eq!(swap_token_a.base.amount, 251_000_000);
+    let swap_token_b =
+        StateWithExtensions::<Account>::unpack(&accounts.token_b_vault_account.data).unwrap();
+    assert_eq!(swap_token_b.base.amount, 251 + 126);
 }
@@ -0,0 +1,106 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    emitted,
+    error::SwapError,
+    event, require_msg,
+    state::{LiquidityLock, SwapPool, SwapState},
+    try_math,
+    utils::{instructions::unix_timestamp, math::TryMath, seeds, swap_token},
+};
+
+/// Withdraw all of the vested pool tokens of a liquidity lock which were not unlocked yet
+pub fn handler(ctx: Context<UnlockLiquidity>) -> Result<event::UnlockLiquidity> {
+    let pool = ctx.accounts.pool.load()?;
+    let now = unix_timestamp()?;
+
+    let liquidity_lock = &mut ctx.accounts.liquidity_lock;
+    let amount = liquidity_lock.unlockable_amount(now)?;
+    require_msg!(
+        amount > 0,
+        SwapError::LiquidityLocked,
+        &format!(
+            "No vested pool tokens to unlock: now={}, vesting_start_ts={}, vesting_end_ts={}",
+            now, liquidity_lock.vesting_start_ts, liquidity_lock.vesting_end_ts
+        )
+    );
+
+    liquidity_lock.unlocked_amount = try_math!(liquidity_lock.unlocked_amount.try_add(amount))?;
+    let remaining_amount = try_math!(liquidity_lock
+        .locked_amount
+        .try_sub(liquidity_lock.unlocked_amount))?;
+
+    msg!(
+        "Unlock liquidity: owner={}, lock_id={}, amount={}, remaining_amount={}",
+        liquidity_lock.owner,
+        liquidity_lock.lock_id,
+        amount,
+        remaining_amount
+    );
+
+    swap_token::transfer_from_vault(
+        ctx.accounts.pool_token_program.to_account_info(),
+        ctx.accounts.pool.to_account_info(),
+        ctx.accounts.lock_vault.to_account_info(),
+        ctx.accounts.pool_token_mint.to_account_info(),
+        ctx.accounts.owner_pool_token_ata.to_account_info(),
+        ctx.accounts.pool_authority.to_account_info(),
+        pool.bump_seed(),
+        amount,
+        ctx.accounts.pool_token_mint.decimals,
+    )?;
+
+    emitted!(event::UnlockLiquidity {
+        owner: liquidity_lock.owner,
+        lock_id: liquidity_lock.lock_id,
+        amount,
+        remaining_amount,
+    });
+}
+
+#[derive(Accounts)]
+pub struct UnlockLiquidity<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+        has_one = pool_token_mint @ SwapError::IncorrectPoolMint,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    #[account(mut,
+        seeds = [seeds::LIQUIDITY_LOCK, pool.key().as_ref(), owner.key().as_ref(), &liquidity_lock.lock_id.to_le_bytes()],
+        bump,
+        has_one = pool,
+        has_one = owner,
+        has_one = lock_vault @ SwapError::IncorrectSwapAccount,
+    )]
+    pub liquidity_lock: Box<Account<'info, LiquidityLock>>,
+
+    /// CHECK: has_one constraint on the liquidity lock
+    #[account(mut)]
+    pub lock_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mint::token_program = pool_token_program,
+    )]
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Owner's pool token account to unlock to
+    #[account(mut,
+        token::mint = pool_token_mint,
+        token::authority = owner,
+        token::token_program = pool_token_program,
+    )]
+    pub owner_pool_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token program for the pool token mint
+    pub pool_token_program: Interface<'info, TokenInterface>,
+}
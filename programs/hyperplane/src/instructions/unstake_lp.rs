@@ -0,0 +1,115 @@
+use anchor_lang::{
+    accounts::{interface::Interface, interface_account::InterfaceAccount},
+    prelude::*,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    emitted,
+    error::SwapError,
+    event, require_msg,
+    state::{LpStake, RewardVault, SwapPool, SwapState},
+    try_math,
+    utils::{instructions::unix_timestamp, math::TryMath, seeds, swap_token},
+};
+
+/// Withdraw staked pool tokens back to the owner. Rewards earned until now remain claimable.
+pub fn handler(ctx: Context<UnstakeLp>, amount: u64) -> Result<event::UnstakeLp> {
+    let pool = ctx.accounts.pool.load()?;
+
+    require_msg!(
+        amount > 0,
+        SwapError::InvalidStakeAmount,
+        "Cannot unstake zero pool tokens"
+    );
+    require_msg!(
+        amount <= ctx.accounts.lp_stake.staked_amount,
+        SwapError::InsufficientPoolTokenFunds,
+        &format!(
+            "Cannot unstake more than the staked amount: amount={}, staked_amount={}",
+            amount, ctx.accounts.lp_stake.staked_amount
+        )
+    );
+
+    let reward_vault = &mut ctx.accounts.reward_vault;
+    let lp_stake = &mut ctx.accounts.lp_stake;
+    reward_vault.update_rewards(unix_timestamp()?)?;
+    lp_stake.settle_rewards(reward_vault)?;
+
+    lp_stake.staked_amount = try_math!(lp_stake.staked_amount.try_sub(amount))?;
+    reward_vault.total_staked = try_math!(reward_vault.total_staked.try_sub(amount))?;
+
+    msg!(
+        "Unstake lp: amount={}, staked_amount={}, total_staked={}",
+        amount,
+        lp_stake.staked_amount,
+        reward_vault.total_staked
+    );
+
+    swap_token::transfer_from_vault(
+        ctx.accounts.pool_token_program.to_account_info(),
+        ctx.accounts.pool.to_account_info(),
+        ctx.accounts.staked_lp_vault.to_account_info(),
+        ctx.accounts.pool_token_mint.to_account_info(),
+        ctx.accounts.owner_pool_token_ata.to_account_info(),
+        ctx.accounts.pool_authority.to_account_info(),
+        pool.bump_seed(),
+        amount,
+        ctx.accounts.pool_token_mint.decimals,
+    )?;
+
+    emitted!(event::UnstakeLp {
+        amount,
+        staked_amount: lp_stake.staked_amount,
+    });
+}
+
+#[derive(Accounts)]
+pub struct UnstakeLp<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = pool_authority @ SwapError::InvalidProgramAddress,
+        has_one = pool_token_mint @ SwapError::IncorrectPoolMint,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    /// CHECK: has_one constraint on the pool
+    pub pool_authority: AccountInfo<'info>,
+
+    #[account(mut,
+        seeds = [seeds::REWARD_VAULT, pool.key().as_ref()],
+        bump,
+        has_one = pool,
+        has_one = staked_lp_vault @ SwapError::IncorrectSwapAccount,
+    )]
+    pub reward_vault: Box<Account<'info, RewardVault>>,
+
+    #[account(mut,
+        seeds = [seeds::LP_STAKE, reward_vault.key().as_ref(), owner.key().as_ref()],
+        bump,
+        has_one = reward_vault,
+        has_one = owner,
+    )]
+    pub lp_stake: Box<Account<'info, LpStake>>,
+
+    #[account(
+        mint::token_program = pool_token_program,
+    )]
+    pub pool_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: has_one constraint on the reward vault
+    #[account(mut)]
+    pub staked_lp_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Owner's pool token account to unstake to
+    #[account(mut,
+        token::mint = pool_token_mint,
+        token::authority = owner,
+        token::token_program = pool_token_program,
+    )]
+    pub owner_pool_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token program for the pool token mint
+    pub pool_token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{FeeRecipient, FeeRecipients, SwapPool},
+    utils::seeds,
+};
+
+pub fn handler(ctx: Context<UpdateFeeRecipients>, recipients: Vec<FeeRecipient>) -> Result<()> {
+    msg!(
+        "Update fee recipients: previous={:?}, recipients={:?}",
+        ctx.accounts
+            .fee_recipients
+            .active_recipients()
+            .collect::<Vec<_>>(),
+        recipients
+    );
+
+    ctx.accounts.fee_recipients.set_recipients(&recipients)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateFeeRecipients<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin,
+    )]
+    pub pool: AccountLoader<'info, SwapPool>,
+
+    #[account(mut,
+        seeds = [seeds::FEE_RECIPIENTS, pool.key().as_ref()],
+        bump,
+        has_one = pool,
+    )]
+    pub fee_recipients: Box<Account<'info, FeeRecipients>>,
+}
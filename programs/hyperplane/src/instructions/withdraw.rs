@@ -50,6 +50,19 @@ pub fn handler(
         SwapError::ZeroTradingTokens,
         "ZeroTradingTokens: pool_token_amount=0"
     );
+    let pool_token_supply_after_burn = try_math!(ctx
+        .accounts
+        .pool_token_mint
+        .supply
+        .try_sub(pool_token_amount))?;
+    require_msg!(
+        pool_token_supply_after_burn >= pool.min_liquidity,
+        SwapError::BelowMinimumLiquidity,
+        &format!(
+            "BelowMinimumLiquidity: pool_token_supply_after_burn={} < min_liquidity={}",
+            pool_token_supply_after_burn, pool.min_liquidity
+        )
+    );
 
     let results = calculator
         .pool_tokens_to_trading_tokens(
@@ -5,7 +5,10 @@
 use anchor_lang::{
     prelude::{Rent, System},
     solana_program::{
-        instruction::Instruction, program_error::ProgramError, pubkey::Pubkey, sysvar::SysvarId,
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::SysvarId,
     },
     Id, InstructionData, ToAccountMetas,
 };
@@ -16,7 +19,7 @@ use derive_more::Constructor;
 use crate::{
     curve::fees::Fees,
     instructions::CurveUserParameters,
-    state::{UpdatePoolConfigMode, UpdatePoolConfigValue},
+    state::{FeeRecipient, UpdatePoolConfigMode, UpdatePoolConfigValue},
     InitialSupply,
 };
 
@@ -30,6 +33,8 @@ pub struct Initialize {
     pub curve_parameters: CurveUserParameters,
     /// initial supply of token A and B
     pub initial_supply: InitialSupply,
+    /// pool tokens permanently locked in the minimum liquidity vault
+    pub min_liquidity: u64,
 }
 
 /// Swap instruction data
@@ -69,12 +74,75 @@ pub struct Withdraw {
 }
 
 /// WithdrawFees instruction data
+#[cfg_attr(feature = "fuzz", derive(Arbitrary))]
 #[derive(Clone, Debug, PartialEq, Constructor)]
 pub struct WithdrawFees {
     /// Amount of trading tokens to withdraw
     pub requested_token_amount: u64,
 }
 
+/// InitializeFeeRecipients instruction data
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct InitializeFeeRecipients {
+    /// Recipients of the owner fees and their share in basis points
+    pub recipients: Vec<FeeRecipient>,
+}
+
+/// UpdateFeeRecipients instruction data
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct UpdateFeeRecipients {
+    /// Recipients of the owner fees and their share in basis points
+    pub recipients: Vec<FeeRecipient>,
+}
+
+/// AddReward instruction data
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct AddReward {
+    /// Amount of reward tokens emitted to all stakers per second
+    pub emissions_per_second: u64,
+}
+
+/// FundRewards instruction data
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct FundRewards {
+    /// Amount of reward tokens to transfer into the reward token vault
+    pub amount: u64,
+}
+
+/// SetRewardEmissions instruction data
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct SetRewardEmissions {
+    /// Amount of reward tokens emitted to all stakers per second
+    pub emissions_per_second: u64,
+}
+
+/// StakeLp instruction data
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct StakeLp {
+    /// Amount of pool tokens to stake
+    pub amount: u64,
+}
+
+/// UnstakeLp instruction data
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct UnstakeLp {
+    /// Amount of staked pool tokens to withdraw
+    pub amount: u64,
+}
+
+/// LockLiquidity instruction data
+#[derive(Clone, Debug, PartialEq, Constructor)]
+pub struct LockLiquidity {
+    /// Identifier of the lock, unique per pool and owner
+    pub lock_id: u64,
+    /// Amount of pool tokens to lock
+    pub amount: u64,
+    /// Unix timestamp the pool tokens start vesting at
+    pub vesting_start_ts: u64,
+    /// Unix timestamp all of the pool tokens are vested at
+    pub vesting_end_ts: u64,
+}
+
 /// UpdatePoolConfig instruction data
 #[derive(Clone, Debug, PartialEq, Constructor)]
 pub struct UpdatePoolConfig {
@@ -110,6 +178,7 @@ pub fn initialize_pool(
     admin_token_a_ata: &Pubkey,
     admin_token_b_ata: &Pubkey,
     admin_pool_token_ata: &Pubkey,
+    min_liquidity_vault: &Pubkey,
     pool_token_program_id: &Pubkey,
     token_a_program_id: &Pubkey,
     token_b_program_id: &Pubkey,
@@ -121,11 +190,13 @@ pub fn initialize_pool(
                 initial_supply_a,
                 initial_supply_b,
             },
+        min_liquidity,
     }: Initialize,
 ) -> Result<Instruction, ProgramError> {
     let data = super::instruction::InitializePool {
         initial_supply_a,
         initial_supply_b,
+        min_liquidity,
         fees,
         curve_parameters,
     }
@@ -146,6 +217,7 @@ pub fn initialize_pool(
         admin_token_a_ata: *admin_token_a_ata,
         admin_token_b_ata: *admin_token_b_ata,
         admin_pool_token_ata: *admin_pool_token_ata,
+        min_liquidity_vault: *min_liquidity_vault,
         system_program: System::id(),
         rent: Rent::id(),
         pool_token_program: *pool_token_program_id,
@@ -369,6 +441,53 @@ pub fn withdraw_fees(
     })
 }
 
+/// Creates a 'compound fees' instruction.
+pub fn compound_fees(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    swap_curve: &Pubkey,
+    pool_authority: &Pubkey,
+    token_a_mint: &Pubkey,
+    token_b_mint: &Pubkey,
+    token_a_vault: &Pubkey,
+    token_b_vault: &Pubkey,
+    pool_token_mint: &Pubkey,
+    token_a_fees_vault: &Pubkey,
+    token_b_fees_vault: &Pubkey,
+    admin_pool_token_ata: &Pubkey,
+    pool_token_program: &Pubkey,
+    token_a_program: &Pubkey,
+    token_b_program: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::CompoundFees {}.data();
+
+    let accounts = super::accounts::CompoundFees {
+        admin: *admin,
+        pool: *pool,
+        swap_curve: *swap_curve,
+        pool_authority: *pool_authority,
+        token_a_mint: *token_a_mint,
+        token_b_mint: *token_b_mint,
+        token_a_vault: *token_a_vault,
+        token_b_vault: *token_b_vault,
+        pool_token_mint: *pool_token_mint,
+        token_a_fees_vault: *token_a_fees_vault,
+        token_b_fees_vault: *token_b_fees_vault,
+        admin_pool_token_ata: *admin_pool_token_ata,
+        pool_token_program: *pool_token_program,
+        token_a_token_program: *token_a_program,
+        token_b_token_program: *token_b_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
 /// Creates an 'update pool config' instruction.
 pub fn update_pool_config(
     program_id: &Pubkey,
@@ -394,3 +513,433 @@ pub fn update_pool_config(
         data,
     })
 }
+
+/// Creates an 'initialize fee recipients' instruction.
+pub fn initialize_fee_recipients(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    fee_recipients: &Pubkey,
+    InitializeFeeRecipients { recipients }: InitializeFeeRecipients,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::InitializeFeeRecipients { recipients }.data();
+
+    let accounts = super::accounts::InitializeFeeRecipients {
+        admin: *admin,
+        pool: *pool,
+        fee_recipients: *fee_recipients,
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'update fee recipients' instruction.
+pub fn update_fee_recipients(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    fee_recipients: &Pubkey,
+    UpdateFeeRecipients { recipients }: UpdateFeeRecipients,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::UpdateFeeRecipients { recipients }.data();
+
+    let accounts = super::accounts::UpdateFeeRecipients {
+        admin: *admin,
+        pool: *pool,
+        fee_recipients: *fee_recipients,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'distribute fees' instruction.
+///
+/// `recipient_token_accounts` must be in the same order as the recipients in the fee recipients account
+pub fn distribute_fees(
+    program_id: &Pubkey,
+    pool: &Pubkey,
+    pool_authority: &Pubkey,
+    fee_recipients: &Pubkey,
+    fees_mint: &Pubkey,
+    fees_vault: &Pubkey,
+    recipient_token_accounts: &[Pubkey],
+    fees_token_program: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::DistributeFees {}.data();
+
+    let mut accounts = super::accounts::DistributeFees {
+        pool: *pool,
+        pool_authority: *pool_authority,
+        fee_recipients: *fee_recipients,
+        fees_mint: *fees_mint,
+        fees_vault: *fees_vault,
+        fees_token_program: *fees_token_program,
+    }
+    .to_account_metas(None);
+    accounts.extend(
+        recipient_token_accounts
+            .iter()
+            .map(|recipient_token_account| AccountMeta::new(*recipient_token_account, false)),
+    );
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'initialize reward vault' instruction.
+pub fn initialize_reward_vault(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    pool_authority: &Pubkey,
+    pool_token_mint: &Pubkey,
+    reward_vault: &Pubkey,
+    staked_lp_vault: &Pubkey,
+    pool_token_program: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::InitializeRewardVault {}.data();
+
+    let accounts = super::accounts::InitializeRewardVault {
+        admin: *admin,
+        pool: *pool,
+        pool_authority: *pool_authority,
+        pool_token_mint: *pool_token_mint,
+        reward_vault: *reward_vault,
+        staked_lp_vault: *staked_lp_vault,
+        system_program: System::id(),
+        rent: Rent::id(),
+        pool_token_program: *pool_token_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'add reward' instruction.
+pub fn add_reward(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    pool_authority: &Pubkey,
+    reward_vault: &Pubkey,
+    reward_mint: &Pubkey,
+    reward_token_vault: &Pubkey,
+    reward_token_program: &Pubkey,
+    AddReward {
+        emissions_per_second,
+    }: AddReward,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::AddReward {
+        emissions_per_second,
+    }
+    .data();
+
+    let accounts = super::accounts::AddReward {
+        admin: *admin,
+        pool: *pool,
+        pool_authority: *pool_authority,
+        reward_vault: *reward_vault,
+        reward_mint: *reward_mint,
+        reward_token_vault: *reward_token_vault,
+        system_program: System::id(),
+        rent: Rent::id(),
+        reward_token_program: *reward_token_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'fund rewards' instruction.
+pub fn fund_rewards(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    reward_vault: &Pubkey,
+    reward_mint: &Pubkey,
+    reward_token_vault: &Pubkey,
+    admin_reward_ata: &Pubkey,
+    reward_token_program: &Pubkey,
+    FundRewards { amount }: FundRewards,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::FundRewards { amount }.data();
+
+    let accounts = super::accounts::FundRewards {
+        admin: *admin,
+        pool: *pool,
+        reward_vault: *reward_vault,
+        reward_mint: *reward_mint,
+        reward_token_vault: *reward_token_vault,
+        admin_reward_ata: *admin_reward_ata,
+        reward_token_program: *reward_token_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'set reward emissions' instruction.
+pub fn set_reward_emissions(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    pool: &Pubkey,
+    reward_vault: &Pubkey,
+    reward_mint: &Pubkey,
+    SetRewardEmissions {
+        emissions_per_second,
+    }: SetRewardEmissions,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::SetRewardEmissions {
+        emissions_per_second,
+    }
+    .data();
+
+    let accounts = super::accounts::SetRewardEmissions {
+        admin: *admin,
+        pool: *pool,
+        reward_vault: *reward_vault,
+        reward_mint: *reward_mint,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'initialize lp stake' instruction.
+pub fn initialize_lp_stake(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    reward_vault: &Pubkey,
+    lp_stake: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::InitializeLpStake {}.data();
+
+    let accounts = super::accounts::InitializeLpStake {
+        owner: *owner,
+        reward_vault: *reward_vault,
+        lp_stake: *lp_stake,
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'stake lp' instruction.
+pub fn stake_lp(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    pool: &Pubkey,
+    reward_vault: &Pubkey,
+    lp_stake: &Pubkey,
+    pool_token_mint: &Pubkey,
+    staked_lp_vault: &Pubkey,
+    owner_pool_token_ata: &Pubkey,
+    pool_token_program: &Pubkey,
+    StakeLp { amount }: StakeLp,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::StakeLp { amount }.data();
+
+    let accounts = super::accounts::StakeLp {
+        owner: *owner,
+        pool: *pool,
+        reward_vault: *reward_vault,
+        lp_stake: *lp_stake,
+        pool_token_mint: *pool_token_mint,
+        staked_lp_vault: *staked_lp_vault,
+        owner_pool_token_ata: *owner_pool_token_ata,
+        pool_token_program: *pool_token_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'unstake lp' instruction.
+pub fn unstake_lp(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    pool: &Pubkey,
+    pool_authority: &Pubkey,
+    reward_vault: &Pubkey,
+    lp_stake: &Pubkey,
+    pool_token_mint: &Pubkey,
+    staked_lp_vault: &Pubkey,
+    owner_pool_token_ata: &Pubkey,
+    pool_token_program: &Pubkey,
+    UnstakeLp { amount }: UnstakeLp,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::UnstakeLp { amount }.data();
+
+    let accounts = super::accounts::UnstakeLp {
+        owner: *owner,
+        pool: *pool,
+        pool_authority: *pool_authority,
+        reward_vault: *reward_vault,
+        lp_stake: *lp_stake,
+        pool_token_mint: *pool_token_mint,
+        staked_lp_vault: *staked_lp_vault,
+        owner_pool_token_ata: *owner_pool_token_ata,
+        pool_token_program: *pool_token_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'claim rewards' instruction.
+pub fn claim_rewards(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    pool: &Pubkey,
+    pool_authority: &Pubkey,
+    reward_vault: &Pubkey,
+    lp_stake: &Pubkey,
+    reward_mint: &Pubkey,
+    reward_token_vault: &Pubkey,
+    owner_reward_ata: &Pubkey,
+    reward_token_program: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::ClaimRewards {}.data();
+
+    let accounts = super::accounts::ClaimRewards {
+        owner: *owner,
+        pool: *pool,
+        pool_authority: *pool_authority,
+        reward_vault: *reward_vault,
+        lp_stake: *lp_stake,
+        reward_mint: *reward_mint,
+        reward_token_vault: *reward_token_vault,
+        owner_reward_ata: *owner_reward_ata,
+        reward_token_program: *reward_token_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'lock liquidity' instruction.
+pub fn lock_liquidity(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    pool: &Pubkey,
+    pool_authority: &Pubkey,
+    liquidity_lock: &Pubkey,
+    lock_vault: &Pubkey,
+    pool_token_mint: &Pubkey,
+    owner_pool_token_ata: &Pubkey,
+    pool_token_program: &Pubkey,
+    LockLiquidity {
+        lock_id,
+        amount,
+        vesting_start_ts,
+        vesting_end_ts,
+    }: LockLiquidity,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::LockLiquidity {
+        lock_id,
+        amount,
+        vesting_start_ts,
+        vesting_end_ts,
+    }
+    .data();
+
+    let accounts = super::accounts::LockLiquidity {
+        owner: *owner,
+        pool: *pool,
+        pool_authority: *pool_authority,
+        liquidity_lock: *liquidity_lock,
+        lock_vault: *lock_vault,
+        pool_token_mint: *pool_token_mint,
+        owner_pool_token_ata: *owner_pool_token_ata,
+        system_program: System::id(),
+        rent: Rent::id(),
+        pool_token_program: *pool_token_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'unlock liquidity' instruction.
+pub fn unlock_liquidity(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    pool: &Pubkey,
+    pool_authority: &Pubkey,
+    liquidity_lock: &Pubkey,
+    lock_vault: &Pubkey,
+    pool_token_mint: &Pubkey,
+    owner_pool_token_ata: &Pubkey,
+    pool_token_program: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = super::instruction::UnlockLiquidity {}.data();
+
+    let accounts = super::accounts::UnlockLiquidity {
+        owner: *owner,
+        pool: *pool,
+        pool_authority: *pool_authority,
+        liquidity_lock: *liquidity_lock,
+        lock_vault: *lock_vault,
+        pool_token_mint: *pool_token_mint,
+        owner_pool_token_ata: *owner_pool_token_ata,
+        pool_token_program: *pool_token_program,
+    }
+    .to_account_metas(None);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
@@ -4,12 +4,18 @@
 
 //! An AMM program for the Solana blockchain.
 
+#[cfg(any(test, feature = "amm"))]
+pub mod amm;
 pub mod constraints;
 pub mod curve;
 pub mod error;
 pub mod event;
+#[cfg(any(test, feature = "client"))]
+pub mod instruction_builder;
 pub mod instructions;
 pub mod ix;
+#[cfg(any(test, feature = "quote"))]
+pub mod quote;
 pub mod state;
 pub mod utils;
 
@@ -18,6 +24,7 @@ pub use anchor_lang;
 use anchor_lang::prelude::*;
 use curve::fees::Fees;
 pub use instructions::*;
+use state::FeeRecipient;
 
 declare_id!("SwapsVeCiPHMUAtzQWZw7RjsKjgCjhwU55QGu4U1Szw");
 
@@ -32,12 +39,14 @@ pub mod hyperplane {
         fees: Fees,
         initial_supply_a: u64,
         initial_supply_b: u64,
+        min_liquidity: u64,
     ) -> Result<()> {
         instructions::initialize_pool::handler(
             ctx,
             curve_parameters,
             fees,
             initialize_pool::InitialSupply::new(initial_supply_a, initial_supply_b),
+            min_liquidity,
         )
     }
 
@@ -84,6 +93,83 @@ pub mod hyperplane {
         instructions::withdraw_fees::handler(ctx, requested_pool_token_amount)
     }
 
+    pub fn compound_fees(ctx: Context<CompoundFees>) -> Result<event::CompoundFees> {
+        instructions::compound_fees::handler(ctx)
+    }
+
+    pub fn initialize_fee_recipients(
+        ctx: Context<InitializeFeeRecipients>,
+        recipients: Vec<FeeRecipient>,
+    ) -> Result<()> {
+        instructions::initialize_fee_recipients::handler(ctx, recipients)
+    }
+
+    pub fn update_fee_recipients(
+        ctx: Context<UpdateFeeRecipients>,
+        recipients: Vec<FeeRecipient>,
+    ) -> Result<()> {
+        instructions::update_fee_recipients::handler(ctx, recipients)
+    }
+
+    pub fn distribute_fees(ctx: Context<DistributeFees>) -> Result<()> {
+        instructions::distribute_fees::handler(ctx)
+    }
+
+    pub fn initialize_reward_vault(ctx: Context<InitializeRewardVault>) -> Result<()> {
+        instructions::initialize_reward_vault::handler(ctx)
+    }
+
+    pub fn add_reward(ctx: Context<AddReward>, emissions_per_second: u64) -> Result<()> {
+        instructions::add_reward::handler(ctx, emissions_per_second)
+    }
+
+    pub fn fund_rewards(ctx: Context<FundRewards>, amount: u64) -> Result<event::FundRewards> {
+        instructions::fund_rewards::handler(ctx, amount)
+    }
+
+    pub fn set_reward_emissions(
+        ctx: Context<SetRewardEmissions>,
+        emissions_per_second: u64,
+    ) -> Result<event::SetRewardEmissions> {
+        instructions::set_reward_emissions::handler(ctx, emissions_per_second)
+    }
+
+    pub fn initialize_lp_stake(ctx: Context<InitializeLpStake>) -> Result<()> {
+        instructions::initialize_lp_stake::handler(ctx)
+    }
+
+    pub fn stake_lp(ctx: Context<StakeLp>, amount: u64) -> Result<event::StakeLp> {
+        instructions::stake_lp::handler(ctx, amount)
+    }
+
+    pub fn unstake_lp(ctx: Context<UnstakeLp>, amount: u64) -> Result<event::UnstakeLp> {
+        instructions::unstake_lp::handler(ctx, amount)
+    }
+
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<event::ClaimRewards> {
+        instructions::claim_rewards::handler(ctx)
+    }
+
+    pub fn lock_liquidity(
+        ctx: Context<LockLiquidity>,
+        lock_id: u64,
+        amount: u64,
+        vesting_start_ts: u64,
+        vesting_end_ts: u64,
+    ) -> Result<event::LockLiquidity> {
+        instructions::lock_liquidity::handler(
+            ctx,
+            lock_id,
+            amount,
+            vesting_start_ts,
+            vesting_end_ts,
+        )
+    }
+
+    pub fn unlock_liquidity(ctx: Context<UnlockLiquidity>) -> Result<event::UnlockLiquidity> {
+        instructions::unlock_liquidity::handler(ctx)
+    }
+
     pub fn update_pool_config(
         ctx: Context<UpdatePoolConfig>,
         mode: u16,
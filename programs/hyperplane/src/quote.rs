@@ -0,0 +1,495 @@
+//! Off-chain quotes for swaps, deposits and withdrawals
+//!
+//! Given a snapshot of the pool accounts, the quotes match the amounts transferred by the on-chain
+//! instructions, including Token-2022 transfer fees and host fee splits.
+
+use std::sync::Arc;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{transfer_fee::TransferFeeConfig, StateWithExtensions},
+    state::{Account, Mint},
+};
+
+use crate::{
+    curve::{
+        base::{CurveType, SwapCurve},
+        calculator::{CurveCalculator, RoundDirection, TradeDirection},
+    },
+    error::SwapError,
+    require_msg,
+    state::{
+        ConstantPriceCurve, ConstantProductCurve, OffsetCurve, StableCurve, SwapPool, SwapState,
+    },
+    to_u64, try_math,
+    utils::{math::TryMath, transfer_fees},
+};
+
+/// Raw data of the accounts required to quote a pool
+#[derive(Clone, Copy, Debug)]
+pub struct PoolAccountsData<'a> {
+    pub pool: &'a [u8],
+    pub swap_curve: &'a [u8],
+    pub token_a_vault: &'a [u8],
+    pub token_b_vault: &'a [u8],
+    pub pool_token_mint: &'a [u8],
+    pub token_a_mint: &'a [u8],
+    pub token_b_mint: &'a [u8],
+}
+
+/// State of a pool at a point in time, enough to quote any operation on it
+#[derive(Clone, Debug)]
+pub struct PoolSnapshot {
+    pub pool: SwapPool,
+    pub swap_curve: SwapCurve,
+    pub token_a_vault_amount: u64,
+    pub token_b_vault_amount: u64,
+    pub pool_token_supply: u64,
+    pub token_a_transfer_fee_config: Option<TransferFeeConfig>,
+    pub token_b_transfer_fee_config: Option<TransferFeeConfig>,
+    /// Epoch the transfer fees are calculated for
+    pub epoch: u64,
+}
+
+/// Result of a swap, as executed on-chain
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SwapQuote {
+    pub trade_direction: TradeDirection,
+    /// Amount of source tokens requested to swap
+    pub amount_in: u64,
+    /// Amount of source tokens left to swap after the input transfer fees
+    pub actual_amount_in: u64,
+    /// Amount of source tokens transferred from the user to the pool vault, including transfer fees
+    pub source_amount_to_vault: u64,
+    /// Amount of source tokens transferred from the user to the fees vault, including transfer fees
+    pub owner_fee_amount: u64,
+    /// Amount of source tokens transferred from the user to the host fees account, including transfer fees
+    pub host_fee_amount: u64,
+    /// Amount of destination tokens transferred from the pool vault
+    pub destination_amount_from_vault: u64,
+    /// Amount of destination tokens received by the user, after transfer fees
+    pub amount_out: u64,
+    /// Trading fee kept in the pool
+    pub trade_fee: u64,
+    /// Total fees paid in source tokens (includes: owner, trading, + host fees)
+    pub total_fees: u64,
+}
+
+/// Result of a deposit, as executed on-chain
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DepositQuote {
+    /// Amount of pool tokens minted to the user
+    pub pool_token_amount: u64,
+    /// Amount of token A transferred from the user
+    pub token_a_amount: u64,
+    /// Amount of token B transferred from the user
+    pub token_b_amount: u64,
+    /// Amount of token A received by the pool vault, after transfer fees
+    pub token_a_amount_to_vault: u64,
+    /// Amount of token B received by the pool vault, after transfer fees
+    pub token_b_amount_to_vault: u64,
+}
+
+/// Result of a withdrawal, as executed on-chain
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WithdrawQuote {
+    /// Amount of pool tokens burned from the user
+    pub pool_token_amount: u64,
+    /// Amount of token A transferred from the pool vault to the user, checked against the minimum
+    pub token_a_amount: u64,
+    /// Amount of token B transferred from the pool vault to the user, checked against the minimum
+    pub token_b_amount: u64,
+    /// Owner withdraw fee in token A
+    pub token_a_fees: u64,
+    /// Owner withdraw fee in token B
+    pub token_b_fees: u64,
+    /// Amount of token A received by the user, after transfer fees
+    pub token_a_amount_received: u64,
+    /// Amount of token B received by the user, after transfer fees
+    pub token_b_amount_received: u64,
+}
+
+/// Result of compounding the fees vaults, as executed on-chain
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompoundFeesQuote {
+    /// Amount of pool tokens minted to the admin
+    pub pool_token_amount: u64,
+    /// Amount of token A received by the pool vault, after transfer fees
+    pub token_a_amount: u64,
+    /// Amount of token B received by the pool vault, after transfer fees
+    pub token_b_amount: u64,
+    /// Amount of token A transferred from the fees vault, including transfer fees
+    pub token_a_transfer_amount: u64,
+    /// Amount of token B transferred from the fees vault, including transfer fees
+    pub token_b_transfer_amount: u64,
+}
+
+impl PoolSnapshot {
+    /// Decode a snapshot from the raw data of the pool accounts
+    pub fn from_accounts_data(accounts: PoolAccountsData, epoch: u64) -> Result<Self> {
+        let pool = SwapPool::try_deserialize(&mut &accounts.pool[..])?;
+        let swap_curve = swap_curve_from_data(pool.curve_type(), accounts.swap_curve)?;
+        Ok(Self {
+            pool,
+            swap_curve,
+            token_a_vault_amount: StateWithExtensions::<Account>::unpack(accounts.token_a_vault)?
+                .base
+                .amount,
+            token_b_vault_amount: StateWithExtensions::<Account>::unpack(accounts.token_b_vault)?
+                .base
+                .amount,
+            pool_token_supply: StateWithExtensions::<Mint>::unpack(accounts.pool_token_mint)?
+                .base
+                .supply,
+            token_a_transfer_fee_config: transfer_fees::get_transfer_fee_config(
+                accounts.token_a_mint,
+            )?,
+            token_b_transfer_fee_config: transfer_fees::get_transfer_fee_config(
+                accounts.token_b_mint,
+            )?,
+            epoch,
+        })
+    }
+
+    /// Quote a swap of `amount_in` source tokens, see `instructions::swap`
+    pub fn swap(&self, source_mint: &Pubkey, amount_in: u64, host_fee: bool) -> Result<SwapQuote> {
+        require_msg!(
+            !self.pool.withdrawals_only(),
+            SwapError::WithdrawalsOnlyMode,
+            "The pool is in withdrawals only mode"
+        );
+        let (
+            trade_direction,
+            source_transfer_fee_config,
+            destination_transfer_fee_config,
+            source_vault_amount,
+            destination_vault_amount,
+        ) = if *source_mint == self.pool.token_a_mint {
+            (
+                TradeDirection::AtoB,
+                self.token_a_transfer_fee_config.as_ref(),
+                self.token_b_transfer_fee_config.as_ref(),
+                self.token_a_vault_amount,
+                self.token_b_vault_amount,
+            )
+        } else if *source_mint == self.pool.token_b_mint {
+            (
+                TradeDirection::BtoA,
+                self.token_b_transfer_fee_config.as_ref(),
+                self.token_a_transfer_fee_config.as_ref(),
+                self.token_b_vault_amount,
+                self.token_a_vault_amount,
+            )
+        } else {
+            return err!(SwapError::IncorrectSwapAccount);
+        };
+        let fees = self.pool.fees();
+
+        let actual_amount_in = transfer_fees::sub_input_transfer_fees(
+            source_transfer_fee_config,
+            self.epoch,
+            fees,
+            amount_in,
+            host_fee,
+        )?;
+        let result = self
+            .swap_curve
+            .swap(
+                u128::from(actual_amount_in),
+                u128::from(source_vault_amount),
+                u128::from(destination_vault_amount),
+                trade_direction,
+                fees,
+            )
+            .map_err(|_| error!(SwapError::ZeroTradingTokens))?;
+
+        let source_amount_to_vault = to_u64!(result.source_amount_to_vault)?;
+        let source_amount_to_vault = transfer_fees::add_inverse_transfer_fee(
+            source_transfer_fee_config,
+            self.epoch,
+            source_amount_to_vault,
+        )?;
+        let destination_amount_from_vault = to_u64!(result.destination_amount_swapped)?;
+        let amount_out = transfer_fees::sub_transfer_fee(
+            destination_transfer_fee_config,
+            self.epoch,
+            destination_amount_from_vault,
+        )?;
+
+        let (owner_fee_amount, host_fee_amount) = if result.owner_fee > 0 {
+            let mut owner_fee = result.owner_fee;
+            let mut host_fee_amount = 0;
+            if host_fee {
+                let host_fee = fees
+                    .host_fee(owner_fee)
+                    .map_err(|_| error!(SwapError::FeeCalculationFailure))?;
+                if host_fee > 0 {
+                    owner_fee = try_math!(owner_fee.try_sub(host_fee))?;
+                    host_fee_amount = transfer_fees::add_inverse_transfer_fee(
+                        source_transfer_fee_config,
+                        self.epoch,
+                        to_u64!(host_fee)?,
+                    )?;
+                }
+            }
+            let owner_fee_amount = transfer_fees::add_inverse_transfer_fee(
+                source_transfer_fee_config,
+                self.epoch,
+                to_u64!(owner_fee)?,
+            )?;
+            (owner_fee_amount, host_fee_amount)
+        } else {
+            (0, 0)
+        };
+
+        Ok(SwapQuote {
+            trade_direction,
+            amount_in,
+            actual_amount_in,
+            source_amount_to_vault,
+            owner_fee_amount,
+            host_fee_amount,
+            destination_amount_from_vault,
+            amount_out,
+            trade_fee: to_u64!(result.trade_fee)?,
+            total_fees: to_u64!(result.total_fees)?,
+        })
+    }
+
+    /// Quote a deposit minting `pool_token_amount` pool tokens, see `instructions::deposit`
+    pub fn deposit(&self, pool_token_amount: u64) -> Result<DepositQuote> {
+        require_msg!(
+            !self.pool.withdrawals_only(),
+            SwapError::WithdrawalsOnlyMode,
+            "The pool is in withdrawals only mode"
+        );
+        let calculator = &self.swap_curve.calculator;
+        require!(
+            calculator.allows_deposits(),
+            SwapError::UnsupportedCurveOperation
+        );
+
+        let current_pool_mint_supply = u128::from(self.pool_token_supply);
+        let (pool_token_amount, pool_mint_supply) = if current_pool_mint_supply > 0 {
+            (u128::from(pool_token_amount), current_pool_mint_supply)
+        } else {
+            (calculator.new_pool_supply(), calculator.new_pool_supply())
+        };
+        let results = calculator
+            .pool_tokens_to_trading_tokens(
+                pool_token_amount,
+                pool_mint_supply,
+                u128::from(self.token_a_vault_amount),
+                u128::from(self.token_b_vault_amount),
+                RoundDirection::Ceiling,
+            )
+            .map_err(|_| error!(SwapError::ZeroTradingTokens))?;
+
+        let token_a_amount = to_u64!(results.token_a_amount)?;
+        let token_b_amount = to_u64!(results.token_b_amount)?;
+        require!(token_a_amount > 0, SwapError::ZeroTradingTokens);
+        require!(token_b_amount > 0, SwapError::ZeroTradingTokens);
+
+        Ok(DepositQuote {
+            pool_token_amount: to_u64!(pool_token_amount)?,
+            token_a_amount,
+            token_b_amount,
+            token_a_amount_to_vault: transfer_fees::sub_transfer_fee(
+                self.token_a_transfer_fee_config.as_ref(),
+                self.epoch,
+                token_a_amount,
+            )?,
+            token_b_amount_to_vault: transfer_fees::sub_transfer_fee(
+                self.token_b_transfer_fee_config.as_ref(),
+                self.epoch,
+                token_b_amount,
+            )?,
+        })
+    }
+
+    /// Quote a withdrawal burning `pool_token_amount` pool tokens, see `instructions::withdraw`
+    pub fn withdraw(&self, pool_token_amount: u64) -> Result<WithdrawQuote> {
+        require!(pool_token_amount > 0, SwapError::ZeroTradingTokens);
+        let pool_token_supply_after_burn =
+            try_math!(self.pool_token_supply.try_sub(pool_token_amount))?;
+        require!(
+            pool_token_supply_after_burn >= self.pool.min_liquidity,
+            SwapError::BelowMinimumLiquidity
+        );
+
+        let results = self
+            .swap_curve
+            .calculator
+            .pool_tokens_to_trading_tokens(
+                u128::from(pool_token_amount),
+                u128::from(self.pool_token_supply),
+                u128::from(self.token_a_vault_amount),
+                u128::from(self.token_b_vault_amount),
+                RoundDirection::Floor,
+            )
+            .map_err(|_| error!(SwapError::ZeroTradingTokens))?;
+
+        let (token_a_amount, token_a_fees) =
+            self.sub_withdraw_fee(self.token_a_vault_amount, results.token_a_amount)?;
+        let (token_b_amount, token_b_fees) =
+            self.sub_withdraw_fee(self.token_b_vault_amount, results.token_b_amount)?;
+
+        Ok(WithdrawQuote {
+            pool_token_amount,
+            token_a_amount,
+            token_b_amount,
+            token_a_fees,
+            token_b_fees,
+            token_a_amount_received: transfer_fees::sub_transfer_fee(
+                self.token_a_transfer_fee_config.as_ref(),
+                self.epoch,
+                token_a_amount,
+            )?,
+            token_b_amount_received: transfer_fees::sub_transfer_fee(
+                self.token_b_transfer_fee_config.as_ref(),
+                self.epoch,
+                token_b_amount,
+            )?,
+        })
+    }
+
+    /// Quote compounding the balances of the fees vaults, see `instructions::compound_fees`
+    pub fn compound_fees(
+        &self,
+        token_a_fees_vault_amount: u64,
+        token_b_fees_vault_amount: u64,
+    ) -> Result<CompoundFeesQuote> {
+        require_msg!(
+            !self.pool.withdrawals_only(),
+            SwapError::WithdrawalsOnlyMode,
+            "The pool is in withdrawals only mode"
+        );
+        let calculator = &self.swap_curve.calculator;
+        require!(
+            calculator.allows_deposits(),
+            SwapError::UnsupportedCurveOperation
+        );
+
+        let pool_mint_supply = u128::from(self.pool_token_supply);
+        let token_a_vault_amount = u128::from(self.token_a_vault_amount);
+        let token_b_vault_amount = u128::from(self.token_b_vault_amount);
+        require_msg!(
+            pool_mint_supply > 0 && token_a_vault_amount > 0 && token_b_vault_amount > 0,
+            SwapError::EmptySupply,
+            "Cannot compound fees into an empty pool"
+        );
+
+        // what the pool vaults would receive for the whole fees vaults
+        let token_a_fees = transfer_fees::sub_transfer_fee(
+            self.token_a_transfer_fee_config.as_ref(),
+            self.epoch,
+            token_a_fees_vault_amount,
+        )?;
+        let token_b_fees = transfer_fees::sub_transfer_fee(
+            self.token_b_transfer_fee_config.as_ref(),
+            self.epoch,
+            token_b_fees_vault_amount,
+        )?;
+        let pool_token_amount = std::cmp::min(
+            u128::from(token_a_fees)
+                .try_mul(pool_mint_supply)?
+                .try_div(token_a_vault_amount)?,
+            u128::from(token_b_fees)
+                .try_mul(pool_mint_supply)?
+                .try_div(token_b_vault_amount)?,
+        );
+
+        let results = calculator
+            .pool_tokens_to_trading_tokens(
+                pool_token_amount,
+                pool_mint_supply,
+                token_a_vault_amount,
+                token_b_vault_amount,
+                RoundDirection::Ceiling,
+            )
+            .map_err(|_| error!(SwapError::ZeroTradingTokens))?;
+
+        let token_a_amount = to_u64!(results.token_a_amount)?;
+        let token_b_amount = to_u64!(results.token_b_amount)?;
+        let pool_token_amount = to_u64!(pool_token_amount)?;
+        require!(
+            pool_token_amount > 0 && token_a_amount > 0 && token_b_amount > 0,
+            SwapError::ZeroTradingTokens
+        );
+
+        let token_a_transfer_amount = transfer_fees::add_inverse_transfer_fee(
+            self.token_a_transfer_fee_config.as_ref(),
+            self.epoch,
+            token_a_amount,
+        )?;
+        let token_b_transfer_amount = transfer_fees::add_inverse_transfer_fee(
+            self.token_b_transfer_fee_config.as_ref(),
+            self.epoch,
+            token_b_amount,
+        )?;
+        require!(
+            token_a_transfer_amount <= token_a_fees_vault_amount
+                && token_b_transfer_amount <= token_b_fees_vault_amount,
+            SwapError::CalculationFailure
+        );
+
+        Ok(CompoundFeesQuote {
+            pool_token_amount,
+            token_a_amount,
+            token_b_amount,
+            token_a_transfer_amount,
+            token_b_transfer_amount,
+        })
+    }
+
+    fn sub_withdraw_fee(&self, pool_balance: u64, withdraw_amount: u128) -> Result<(u64, u64)> {
+        let withdraw_amount = std::cmp::min(u128::from(pool_balance), withdraw_amount);
+        let withdraw_fee = self
+            .pool
+            .fees()
+            .owner_withdraw_fee(withdraw_amount)
+            .map_err(|_| error!(SwapError::FeeCalculationFailure))?;
+        let amount_after_fee = to_u64!(try_math!(withdraw_amount.try_sub(withdraw_fee))?)?;
+        require!(
+            amount_after_fee > 0 || pool_balance == 0,
+            SwapError::ZeroTradingTokens
+        );
+        Ok((amount_after_fee, to_u64!(withdraw_fee)?))
+    }
+}
+
+/// Decode the swap curve account of a pool according to its curve type
+pub fn swap_curve_from_data(curve_type: CurveType, data: &[u8]) -> Result<SwapCurve> {
+    let mut data = data;
+    let calculator: Arc<dyn CurveCalculator + Sync + Send> = match curve_type {
+        CurveType::ConstantProduct => Arc::new(ConstantProductCurve::try_deserialize(&mut data)?),
+        CurveType::ConstantPrice => Arc::new(ConstantPriceCurve::try_deserialize(&mut data)?),
+        CurveType::Offset => Arc::new(OffsetCurve::try_deserialize(&mut data)?),
+        CurveType::Stable => Arc::new(StableCurve::try_deserialize(&mut data)?),
+    };
+    Ok(SwapCurve {
+        curve_type,
+        calculator,
+    })
+}
+
+/// Marginal price of token A in token B in raw units, without fees
+///
+/// Approximated by buying token A with a millionth of the token B vault, which is exact for the
+/// constant price curve
+pub fn spot_price(swap_curve: &SwapCurve, token_a_amount: u64, token_b_amount: u64) -> Option<f64> {
+    let source_amount = std::cmp::max(token_b_amount / 1_000_000, 1);
+    let result = swap_curve
+        .calculator
+        .swap_without_fees(
+            u128::from(source_amount),
+            u128::from(token_b_amount),
+            u128::from(token_a_amount),
+            TradeDirection::BtoA,
+        )
+        .ok()?;
+    if result.source_amount_swapped == 0 || result.destination_amount_swapped == 0 {
+        return None;
+    }
+    Some(result.source_amount_swapped as f64 / result.destination_amount_swapped as f64)
+}
@@ -1,18 +1,20 @@
-use std::ops::Deref;
+use std::{cmp, ops::Deref};
 
 use anchor_lang::{
-    account,
-    prelude::{borsh, ProgramError, Pubkey},
+    account, error,
+    prelude::{borsh, msg, ProgramError, Pubkey},
     zero_copy, AnchorDeserialize, AnchorSerialize, Result,
 };
 use enum_dispatch::enum_dispatch;
 use num_enum::TryFromPrimitive;
+use spl_math::uint::U256;
 use strum::EnumString;
 
 use crate::{
     curve::{base::CurveType, fees::Fees},
-    try_math,
-    utils::math::decimals_to_factor,
+    error::SwapError,
+    require_msg, to_u64, try_math,
+    utils::math::{decimals_to_factor, TryMath},
     VALUE_BYTE_ARRAY_LEN,
 };
 
@@ -89,12 +91,21 @@ pub struct SwapPool {
     /// The swap curve is in withdraw mode, and will only allow withdrawals
     pub withdrawals_only: u64,
 
-    pub _padding: [u64; 16],
+    /// Amount of pool tokens locked forever at initialization - the pool token supply can never fall below it
+    pub min_liquidity: u64,
+
+    pub _padding: [u64; 15],
 }
 
 impl SwapPool {
     // note: also hardcoded in /js/src/util/const.ts
     pub const LEN: usize = DISCRIMINATOR_SIZE + 536; // 8 + 536 = 548
+
+    // Offsets of fields in the account data, including the discriminator - used for memcmp filters
+    pub const ADMIN_OFFSET: usize = DISCRIMINATOR_SIZE;
+    pub const TOKEN_A_MINT_OFFSET: usize = DISCRIMINATOR_SIZE + 168;
+    pub const TOKEN_B_MINT_OFFSET: usize = DISCRIMINATOR_SIZE + 200;
+    pub const CURVE_TYPE_OFFSET: usize = DISCRIMINATOR_SIZE + 360;
 }
 
 impl SwapState for SwapPool {
@@ -248,8 +259,287 @@ impl StableCurve {
     }
 }
 
+/// Maximum number of recipients the owner fees of a pool can be split between
+pub const MAX_FEE_RECIPIENTS: usize = 8;
+/// Recipient shares are expressed in basis points and must add up to this value
+pub const FEE_RECIPIENTS_TOTAL_BPS: u64 = 10_000;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct FeeRecipient {
+    /// Owner of the token accounts the fees are paid out to
+    pub owner: Pubkey,
+    /// Share of each fees vault paid to this recipient, in basis points
+    pub share_bps: u64,
+}
+
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct FeeRecipients {
+    /// The pool whose fees vaults are distributed
+    pub pool: Pubkey,
+    /// Recipients of the fees, unused entries have a share of 0
+    pub recipients: [FeeRecipient; MAX_FEE_RECIPIENTS],
+    pub _padding: [u64; 16],
+}
+
+impl FeeRecipients {
+    pub const LEN: usize = DISCRIMINATOR_SIZE + 32 + (MAX_FEE_RECIPIENTS * 40) + (16 * 8); // 8 + 480 = 488
+
+    /// Recipients with a non-zero share, in the order they were set
+    pub fn active_recipients(&self) -> impl Iterator<Item = &FeeRecipient> {
+        self.recipients.iter().filter(|r| r.share_bps > 0)
+    }
+
+    /// Validate and replace the recipients
+    pub fn set_recipients(&mut self, recipients: &[FeeRecipient]) -> Result<()> {
+        require_msg!(
+            !recipients.is_empty() && recipients.len() <= MAX_FEE_RECIPIENTS,
+            SwapError::InvalidFeeRecipients,
+            &format!(
+                "Number of fee recipients must be between 1 and {}, got {}",
+                MAX_FEE_RECIPIENTS,
+                recipients.len()
+            )
+        );
+        let mut total_bps = 0_u64;
+        for (i, recipient) in recipients.iter().enumerate() {
+            require_msg!(
+                recipient.share_bps > 0 && recipient.owner != Pubkey::default(),
+                SwapError::InvalidFeeRecipients,
+                &format!("Invalid fee recipient {}: {:?}", i, recipient)
+            );
+            require_msg!(
+                !recipients[..i].iter().any(|r| r.owner == recipient.owner),
+                SwapError::InvalidFeeRecipients,
+                &format!("Duplicate fee recipient {}", recipient.owner)
+            );
+            total_bps = total_bps.saturating_add(recipient.share_bps);
+        }
+        require_msg!(
+            total_bps == FEE_RECIPIENTS_TOTAL_BPS,
+            SwapError::InvalidFeeRecipients,
+            &format!(
+                "Fee recipient shares must add up to {} bps, got {}",
+                FEE_RECIPIENTS_TOTAL_BPS, total_bps
+            )
+        );
+
+        self.recipients = [FeeRecipient::default(); MAX_FEE_RECIPIENTS];
+        self.recipients[..recipients.len()].copy_from_slice(recipients);
+        Ok(())
+    }
+}
+
+/// Maximum number of reward mints a reward vault can emit to the stakers of a pool
+pub const MAX_REWARDS: usize = 4;
+/// Scale applied to the accumulated rewards per staked pool token to retain precision
+pub const REWARD_PER_SHARE_SCALE: u128 = 1_000_000_000_000;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct RewardInfo {
+    /// Mint of the reward token, default if the reward slot is unused
+    pub mint: Pubkey,
+    /// Token account holding the funded rewards - owned by the pool authority
+    pub vault: Pubkey,
+    /// Amount of reward tokens emitted to all stakers per second
+    pub emissions_per_second: u64,
+    /// Funded rewards which have not been emitted yet
+    pub unemitted_amount: u64,
+    /// Rewards emitted per staked pool token since the reward was added, scaled by `REWARD_PER_SHARE_SCALE`
+    pub acc_reward_per_share: u128,
+}
+
+impl RewardInfo {
+    pub fn is_active(&self) -> bool {
+        self.mint != Pubkey::default()
+    }
+}
+
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct RewardVault {
+    /// The pool whose pool tokens are staked
+    pub pool: Pubkey,
+    /// Token account holding the staked pool tokens - owned by the pool authority
+    pub staked_lp_vault: Pubkey,
+    /// Total amount of pool tokens staked
+    pub total_staked: u64,
+    /// Unix timestamp the rewards were last accrued at
+    pub last_update_ts: u64,
+    /// Rewards emitted to the stakers, unused slots have a default mint
+    pub rewards: [RewardInfo; MAX_REWARDS],
+    pub _padding: [u64; 16],
+}
+
+impl RewardVault {
+    pub const LEN: usize = DISCRIMINATOR_SIZE + 32 + 32 + 8 + 8 + (MAX_REWARDS * 96) + (16 * 8); // 8 + 592 = 600
+
+    /// Index of the reward slot for the given reward mint
+    pub fn reward_index(&self, mint: &Pubkey) -> Option<usize> {
+        self.rewards
+            .iter()
+            .position(|r| r.is_active() && r.mint == *mint)
+    }
+
+    /// Add a reward in the first unused slot, returning the index of the slot
+    pub fn add_reward(
+        &mut self,
+        mint: Pubkey,
+        vault: Pubkey,
+        emissions_per_second: u64,
+    ) -> Result<usize> {
+        require_msg!(
+            mint != Pubkey::default() && self.reward_index(&mint).is_none(),
+            SwapError::RepeatedMint,
+            &format!("Reward mint {} is invalid or already added", mint)
+        );
+        let index = self
+            .rewards
+            .iter()
+            .position(|r| !r.is_active())
+            .ok_or_else(|| {
+                msg!("Reward vault already has {} rewards", MAX_REWARDS);
+                error!(SwapError::RewardVaultFull)
+            })?;
+        self.rewards[index] = RewardInfo {
+            mint,
+            vault,
+            emissions_per_second,
+            ..RewardInfo::default()
+        };
+        Ok(index)
+    }
+
+    /// Accrue the rewards emitted since the last update to the stakers.
+    /// Emissions are capped by the funded rewards, and nothing is emitted while no pool tokens are staked.
+    pub fn update_rewards(&mut self, now_ts: u64) -> Result<()> {
+        if now_ts <= self.last_update_ts {
+            return Ok(());
+        }
+        let elapsed = now_ts - self.last_update_ts;
+        if self.total_staked > 0 {
+            let total_staked = u128::from(self.total_staked);
+            for reward in self.rewards.iter_mut().filter(|r| r.is_active()) {
+                let emitted = cmp::min(
+                    reward.emissions_per_second.saturating_mul(elapsed),
+                    reward.unemitted_amount,
+                );
+                if emitted == 0 {
+                    continue;
+                }
+                let reward_per_share = try_math!(u128::from(emitted)
+                    .try_mul(REWARD_PER_SHARE_SCALE)?
+                    .try_div(total_staked))?;
+                // only the rewards credited to the stakers leave the unemitted amount, the rounding
+                // remainder is emitted later
+                let distributed = try_math!(reward_per_share
+                    .try_mul(total_staked)?
+                    .try_div(REWARD_PER_SHARE_SCALE))?;
+                reward.acc_reward_per_share =
+                    try_math!(reward.acc_reward_per_share.try_add(reward_per_share))?;
+                reward.unemitted_amount =
+                    try_math!(reward.unemitted_amount.try_sub(to_u64!(distributed)?))?;
+            }
+        }
+        self.last_update_ts = now_ts;
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct LpStake {
+    /// The reward vault the pool tokens are staked in
+    pub reward_vault: Pubkey,
+    /// Owner of the staked pool tokens and of the rewards
+    pub owner: Pubkey,
+    /// Amount of pool tokens staked
+    pub staked_amount: u64,
+    /// The accumulated reward per share of each reward when the stake was last settled
+    pub reward_per_share_checkpoints: [u128; MAX_REWARDS],
+    /// Rewards earned and not claimed yet
+    pub unclaimed_rewards: [u64; MAX_REWARDS],
+    pub _padding: [u64; 8],
+}
+
+impl LpStake {
+    pub const LEN: usize =
+        DISCRIMINATOR_SIZE + 32 + 32 + 8 + (MAX_REWARDS * 16) + (MAX_REWARDS * 8) + (8 * 8); // 8 + 232 = 240
+
+    /// Credit the rewards earned since the last settlement to the unclaimed rewards.
+    /// Must be called after `RewardVault::update_rewards` and before the staked amount changes.
+    pub fn settle_rewards(&mut self, reward_vault: &RewardVault) -> Result<()> {
+        for (i, reward) in reward_vault.rewards.iter().enumerate() {
+            let reward_per_share = try_math!(reward
+                .acc_reward_per_share
+                .try_sub(self.reward_per_share_checkpoints[i]))?;
+            let earned = try_math!(U256::from(self.staked_amount)
+                .try_mul(U256::from(reward_per_share))?
+                .try_div(U256::from(REWARD_PER_SHARE_SCALE)))?;
+            let earned = to_u64!(earned)?;
+            self.unclaimed_rewards[i] = try_math!(self.unclaimed_rewards[i].try_add(earned))?;
+            self.reward_per_share_checkpoints[i] = reward.acc_reward_per_share;
+        }
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct LiquidityLock {
+    /// The pool whose pool tokens are locked
+    pub pool: Pubkey,
+    /// Owner of the locked pool tokens, who can unlock them once vested
+    pub owner: Pubkey,
+    /// Identifier of the lock, unique per pool and owner
+    pub lock_id: u64,
+    /// Token account holding the locked pool tokens - owned by the pool authority
+    pub lock_vault: Pubkey,
+    /// Total amount of pool tokens locked
+    pub locked_amount: u64,
+    /// Amount of vested pool tokens already unlocked by the owner
+    pub unlocked_amount: u64,
+    /// Unix timestamp the pool tokens start vesting at
+    pub vesting_start_ts: u64,
+    /// Unix timestamp all of the pool tokens are vested at - equal to the start for a single unlock at that time
+    pub vesting_end_ts: u64,
+    pub _padding: [u64; 8],
+}
+
+impl LiquidityLock {
+    pub const LEN: usize = DISCRIMINATOR_SIZE + 32 + 32 + 8 + 32 + 8 + 8 + 8 + 8 + (8 * 8); // 8 + 200 = 208
+
+    /// Amount of pool tokens vested at the given time, including the amount already unlocked
+    pub fn vested_amount(&self, now_ts: u64) -> Result<u64> {
+        if now_ts >= self.vesting_end_ts {
+            Ok(self.locked_amount)
+        } else if now_ts <= self.vesting_start_ts {
+            Ok(0)
+        } else {
+            let elapsed = u128::from(now_ts - self.vesting_start_ts);
+            let duration = u128::from(self.vesting_end_ts - self.vesting_start_ts);
+            let vested_amount = try_math!(u128::from(self.locked_amount)
+                .try_mul(elapsed)?
+                .try_div(duration))?;
+            to_u64!(vested_amount)
+        }
+    }
+
+    /// Amount of vested pool tokens which have not been unlocked yet
+    pub fn unlockable_amount(&self, now_ts: u64) -> Result<u64> {
+        try_math!(self.vested_amount(now_ts)?.try_sub(self.unlocked_amount))
+    }
+
+    /// Amount of pool tokens which are not vested yet
+    pub fn remaining_locked_amount(&self, now_ts: u64) -> Result<u64> {
+        try_math!(self.locked_amount.try_sub(self.vested_amount(now_ts)?))
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use anchor_lang::Discriminator;
+
     use super::*;
 
     #[test]
@@ -257,4 +547,269 @@ mod tests {
         let x = std::mem::size_of::<SwapPool>();
         assert_eq!(x, SwapPool::LEN - DISCRIMINATOR_SIZE);
     }
+
+    #[test]
+    fn test_swap_pool_field_offsets() {
+        let pool = SwapPool {
+            admin: Pubkey::new_unique(),
+            token_a_mint: Pubkey::new_unique(),
+            token_b_mint: Pubkey::new_unique(),
+            curve_type: CurveType::Stable.into(),
+            ..Default::default()
+        };
+        let data = [
+            SwapPool::discriminator().as_slice(),
+            bytemuck::bytes_of(&pool),
+        ]
+        .concat();
+
+        assert_eq!(
+            &data[SwapPool::ADMIN_OFFSET..SwapPool::ADMIN_OFFSET + 32],
+            pool.admin.as_ref()
+        );
+        assert_eq!(
+            &data[SwapPool::TOKEN_A_MINT_OFFSET..SwapPool::TOKEN_A_MINT_OFFSET + 32],
+            pool.token_a_mint.as_ref()
+        );
+        assert_eq!(
+            &data[SwapPool::TOKEN_B_MINT_OFFSET..SwapPool::TOKEN_B_MINT_OFFSET + 32],
+            pool.token_b_mint.as_ref()
+        );
+        assert_eq!(
+            &data[SwapPool::CURVE_TYPE_OFFSET..SwapPool::CURVE_TYPE_OFFSET + 8],
+            pool.curve_type.to_le_bytes()
+        );
+    }
+
+    #[test]
+    fn test_fee_recipients_state_size() {
+        let x = FeeRecipients::default().try_to_vec().unwrap().len();
+        assert_eq!(x, FeeRecipients::LEN - DISCRIMINATOR_SIZE);
+    }
+
+    #[test]
+    fn test_set_fee_recipients() {
+        let treasury = FeeRecipient {
+            owner: Pubkey::new_unique(),
+            share_bps: 5_000,
+        };
+        let partner = FeeRecipient {
+            owner: Pubkey::new_unique(),
+            share_bps: 3_000,
+        };
+        let incentives = FeeRecipient {
+            owner: Pubkey::new_unique(),
+            share_bps: 2_000,
+        };
+        let mut fee_recipients = FeeRecipients::default();
+
+        fee_recipients
+            .set_recipients(&[treasury, partner, incentives])
+            .unwrap();
+        assert_eq!(
+            fee_recipients.active_recipients().collect::<Vec<_>>(),
+            vec![&treasury, &partner, &incentives]
+        );
+
+        // replaces the previous recipients
+        fee_recipients
+            .set_recipients(&[FeeRecipient {
+                share_bps: 10_000,
+                ..treasury
+            }])
+            .unwrap();
+        assert_eq!(fee_recipients.active_recipients().count(), 1);
+    }
+
+    #[test]
+    fn test_set_fee_recipients_invalid() {
+        let recipient = |share_bps| FeeRecipient {
+            owner: Pubkey::new_unique(),
+            share_bps,
+        };
+        let mut fee_recipients = FeeRecipients::default();
+
+        // empty
+        assert!(fee_recipients.set_recipients(&[]).is_err());
+        // does not add up to 100%
+        assert!(fee_recipients
+            .set_recipients(&[recipient(5_000), recipient(4_999)])
+            .is_err());
+        assert!(fee_recipients
+            .set_recipients(&[recipient(5_000), recipient(5_001)])
+            .is_err());
+        // zero share
+        assert!(fee_recipients
+            .set_recipients(&[recipient(10_000), recipient(0)])
+            .is_err());
+        // default owner
+        assert!(fee_recipients
+            .set_recipients(&[FeeRecipient {
+                owner: Pubkey::default(),
+                share_bps: 10_000
+            }])
+            .is_err());
+        // duplicate owner
+        let duplicate = recipient(5_000);
+        assert!(fee_recipients
+            .set_recipients(&[duplicate, duplicate])
+            .is_err());
+        // too many
+        assert!(fee_recipients
+            .set_recipients(&[recipient(1_000); MAX_FEE_RECIPIENTS + 2])
+            .is_err());
+
+        assert_eq!(fee_recipients, FeeRecipients::default());
+    }
+
+    #[test]
+    fn test_reward_vault_state_size() {
+        let x = RewardVault::default().try_to_vec().unwrap().len();
+        assert_eq!(x, RewardVault::LEN - DISCRIMINATOR_SIZE);
+    }
+
+    #[test]
+    fn test_lp_stake_state_size() {
+        let x = LpStake::default().try_to_vec().unwrap().len();
+        assert_eq!(x, LpStake::LEN - DISCRIMINATOR_SIZE);
+    }
+
+    #[test]
+    fn test_add_reward() {
+        let mut reward_vault = RewardVault::default();
+        let mints: Vec<Pubkey> = (0..MAX_REWARDS).map(|_| Pubkey::new_unique()).collect();
+
+        for (i, mint) in mints.iter().enumerate() {
+            assert_eq!(
+                reward_vault
+                    .add_reward(*mint, Pubkey::new_unique(), 100)
+                    .unwrap(),
+                i
+            );
+            assert_eq!(reward_vault.reward_index(mint), Some(i));
+        }
+
+        // duplicate
+        assert!(reward_vault
+            .add_reward(mints[0], Pubkey::new_unique(), 100)
+            .is_err());
+        // full
+        assert!(reward_vault
+            .add_reward(Pubkey::new_unique(), Pubkey::new_unique(), 100)
+            .is_err());
+        assert_eq!(reward_vault.reward_index(&Pubkey::new_unique()), None);
+    }
+
+    #[test]
+    fn test_update_and_settle_rewards() {
+        let mut reward_vault = RewardVault {
+            last_update_ts: 1_000,
+            ..RewardVault::default()
+        };
+        reward_vault
+            .add_reward(Pubkey::new_unique(), Pubkey::new_unique(), 10)
+            .unwrap();
+        reward_vault.rewards[0].unemitted_amount = 1_500;
+
+        // nothing is emitted while nothing is staked
+        reward_vault.update_rewards(1_100).unwrap();
+        assert_eq!(reward_vault.rewards[0].unemitted_amount, 1_500);
+        assert_eq!(reward_vault.rewards[0].acc_reward_per_share, 0);
+
+        let mut alice = LpStake {
+            staked_amount: 400,
+            ..LpStake::default()
+        };
+        alice.settle_rewards(&reward_vault).unwrap();
+        reward_vault.total_staked = 400;
+
+        reward_vault.update_rewards(1_150).unwrap();
+        let mut bob = LpStake::default();
+        bob.settle_rewards(&reward_vault).unwrap();
+        bob.staked_amount = 100;
+        reward_vault.total_staked = 500;
+
+        // emissions stop once the funded rewards run out
+        reward_vault.update_rewards(2_000).unwrap();
+        assert_eq!(reward_vault.rewards[0].unemitted_amount, 0);
+        assert_eq!(reward_vault.last_update_ts, 2_000);
+
+        alice.settle_rewards(&reward_vault).unwrap();
+        bob.settle_rewards(&reward_vault).unwrap();
+        // alice earns all of the first 500 and 4/5 of the remaining 1000
+        assert_eq!(alice.unclaimed_rewards[0], 1_300);
+        assert_eq!(bob.unclaimed_rewards[0], 200);
+
+        // settling twice does not credit the rewards twice
+        alice.settle_rewards(&reward_vault).unwrap();
+        assert_eq!(alice.unclaimed_rewards[0], 1_300);
+        assert_eq!(alice.unclaimed_rewards[1..], [0; MAX_REWARDS - 1]);
+    }
+
+    #[test]
+    fn test_update_rewards_keeps_rounding_remainder() {
+        let mut reward_vault = RewardVault {
+            last_update_ts: 1_000,
+            total_staked: 3,
+            ..RewardVault::default()
+        };
+        reward_vault
+            .add_reward(Pubkey::new_unique(), Pubkey::new_unique(), 10)
+            .unwrap();
+        reward_vault.rewards[0].unemitted_amount = 100;
+        let mut staker = LpStake {
+            staked_amount: 3,
+            ..LpStake::default()
+        };
+
+        // 10 / 3 per share is floored, so only 9 of the 10 emitted are credited
+        reward_vault.update_rewards(1_001).unwrap();
+        assert_eq!(reward_vault.rewards[0].unemitted_amount, 91);
+        staker.settle_rewards(&reward_vault).unwrap();
+        assert_eq!(staker.unclaimed_rewards[0], 9);
+
+        // the remainder is emitted with the next rewards, only less than one per staked token is left
+        reward_vault.update_rewards(1_100).unwrap();
+        staker.settle_rewards(&reward_vault).unwrap();
+        assert_eq!(reward_vault.rewards[0].unemitted_amount, 1);
+        assert_eq!(staker.unclaimed_rewards[0], 99);
+    }
+
+    #[test]
+    fn test_liquidity_lock_state_size() {
+        let x = LiquidityLock::default().try_to_vec().unwrap().len();
+        assert_eq!(x, LiquidityLock::LEN - DISCRIMINATOR_SIZE);
+    }
+
+    #[test]
+    fn test_liquidity_lock_vesting() {
+        let mut lock = LiquidityLock {
+            locked_amount: 1_000,
+            vesting_start_ts: 100,
+            vesting_end_ts: 200,
+            ..LiquidityLock::default()
+        };
+
+        assert_eq!(lock.vested_amount(0).unwrap(), 0);
+        assert_eq!(lock.vested_amount(100).unwrap(), 0);
+        assert_eq!(lock.vested_amount(125).unwrap(), 250);
+        assert_eq!(lock.vested_amount(199).unwrap(), 990);
+        assert_eq!(lock.vested_amount(200).unwrap(), 1_000);
+        assert_eq!(lock.vested_amount(u64::MAX).unwrap(), 1_000);
+
+        lock.unlocked_amount = 250;
+        assert_eq!(lock.unlockable_amount(125).unwrap(), 0);
+        assert_eq!(lock.unlockable_amount(150).unwrap(), 250);
+        assert_eq!(lock.remaining_locked_amount(150).unwrap(), 500);
+
+        // single unlock at the end of the lock
+        let lock = LiquidityLock {
+            locked_amount: 1_000,
+            vesting_start_ts: 200,
+            vesting_end_ts: 200,
+            ..LiquidityLock::default()
+        };
+        assert_eq!(lock.vested_amount(199).unwrap(), 0);
+        assert_eq!(lock.vested_amount(200).unwrap(), 1_000);
+    }
 }
@@ -61,3 +61,12 @@ pub fn zero_copy_deserialize<'info, T: bytemuck::AnyBitPattern + Discriminator>(
 
     Ok(Ref::map(data, |data| bytemuck::from_bytes(&data[8..])))
 }
+
+/// Current unix timestamp of the cluster
+pub fn unix_timestamp() -> Result<u64> {
+    let unix_timestamp = Clock::get()?.unix_timestamp;
+    u64::try_from(unix_timestamp).map_err(|_| {
+        msg!("Invalid unix timestamp: {}", unix_timestamp);
+        error!(crate::error::SwapError::ConversionFailure)
+    })
+}
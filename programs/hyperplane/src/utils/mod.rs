@@ -4,3 +4,4 @@ pub mod math;
 pub mod pool_token;
 pub mod seeds;
 pub mod swap_token;
+pub mod transfer_fees;
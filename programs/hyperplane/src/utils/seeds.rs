@@ -5,6 +5,14 @@ pub const TOKEN_A_VAULT: &[u8] = b"pvault_a";
 pub const TOKEN_B_VAULT: &[u8] = b"pvault_b";
 pub const TOKEN_A_FEES_VAULT: &[u8] = b"fvault_a";
 pub const TOKEN_B_FEES_VAULT: &[u8] = b"fvault_b";
+pub const MIN_LIQUIDITY_VAULT: &[u8] = b"mlvault";
+pub const FEE_RECIPIENTS: &[u8] = b"frecipients";
+pub const REWARD_VAULT: &[u8] = b"rvault";
+pub const STAKED_LP_VAULT: &[u8] = b"slpvault";
+pub const REWARD_TOKEN_VAULT: &[u8] = b"rtvault";
+pub const LP_STAKE: &[u8] = b"lpstake";
+pub const LIQUIDITY_LOCK: &[u8] = b"llock";
+pub const LIQUIDITY_LOCK_VAULT: &[u8] = b"llvault";
 
 pub mod pda {
     use anchor_lang::prelude::Pubkey;
@@ -20,6 +28,7 @@ pub mod pda {
         pub pool_token_mint: Pubkey,
         pub token_a_fees_vault: Pubkey,
         pub token_b_fees_vault: Pubkey,
+        pub min_liquidity_vault: Pubkey,
     }
 
     pub fn pool_authority_pda(pool: &Pubkey) -> (Pubkey, u8) {
@@ -90,6 +99,100 @@ pub mod pda {
         )
     }
 
+    pub fn fee_recipients_pda(pool: &Pubkey) -> (Pubkey, u8) {
+        fee_recipients_pda_program_id(&ID, pool)
+    }
+
+    pub fn fee_recipients_pda_program_id(program_id: &Pubkey, pool: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[FEE_RECIPIENTS, pool.as_ref()], program_id)
+    }
+
+    pub fn reward_vault_pda(pool: &Pubkey) -> (Pubkey, u8) {
+        reward_vault_pda_program_id(&ID, pool)
+    }
+
+    pub fn reward_vault_pda_program_id(program_id: &Pubkey, pool: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[REWARD_VAULT, pool.as_ref()], program_id)
+    }
+
+    pub fn staked_lp_vault_pda(pool: &Pubkey) -> (Pubkey, u8) {
+        staked_lp_vault_pda_program_id(&ID, pool)
+    }
+
+    pub fn staked_lp_vault_pda_program_id(program_id: &Pubkey, pool: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[STAKED_LP_VAULT, pool.as_ref()], program_id)
+    }
+
+    pub fn reward_token_vault_pda(pool: &Pubkey, reward_mint: &Pubkey) -> (Pubkey, u8) {
+        reward_token_vault_pda_program_id(&ID, pool, reward_mint)
+    }
+
+    pub fn reward_token_vault_pda_program_id(
+        program_id: &Pubkey,
+        pool: &Pubkey,
+        reward_mint: &Pubkey,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[REWARD_TOKEN_VAULT, pool.as_ref(), reward_mint.as_ref()],
+            program_id,
+        )
+    }
+
+    pub fn lp_stake_pda(reward_vault: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+        lp_stake_pda_program_id(&ID, reward_vault, owner)
+    }
+
+    pub fn lp_stake_pda_program_id(
+        program_id: &Pubkey,
+        reward_vault: &Pubkey,
+        owner: &Pubkey,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[LP_STAKE, reward_vault.as_ref(), owner.as_ref()],
+            program_id,
+        )
+    }
+
+    pub fn liquidity_lock_pda(pool: &Pubkey, owner: &Pubkey, lock_id: u64) -> (Pubkey, u8) {
+        liquidity_lock_pda_program_id(&ID, pool, owner, lock_id)
+    }
+
+    pub fn liquidity_lock_pda_program_id(
+        program_id: &Pubkey,
+        pool: &Pubkey,
+        owner: &Pubkey,
+        lock_id: u64,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                LIQUIDITY_LOCK,
+                pool.as_ref(),
+                owner.as_ref(),
+                &lock_id.to_le_bytes(),
+            ],
+            program_id,
+        )
+    }
+
+    pub fn liquidity_lock_vault_pda(liquidity_lock: &Pubkey) -> (Pubkey, u8) {
+        liquidity_lock_vault_pda_program_id(&ID, liquidity_lock)
+    }
+
+    pub fn liquidity_lock_vault_pda_program_id(
+        program_id: &Pubkey,
+        liquidity_lock: &Pubkey,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[LIQUIDITY_LOCK_VAULT, liquidity_lock.as_ref()], program_id)
+    }
+
+    pub fn min_liquidity_vault_pda(pool: &Pubkey) -> (Pubkey, u8) {
+        min_liquidity_vault_pda_program_id(&ID, pool)
+    }
+
+    pub fn min_liquidity_vault_pda_program_id(program_id: &Pubkey, pool: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[MIN_LIQUIDITY_VAULT, pool.as_ref()], program_id)
+    }
+
     pub fn init_pool_pdas(
         pool: &Pubkey,
         token_a_mint: &Pubkey,
@@ -122,6 +225,9 @@ pub mod pda {
         let (token_b_fees_vault, _token_b_fees_vault_bump_seed) =
             token_b_fees_vault_pda_program_id(program_id, pool, token_b_mint);
 
+        let (min_liquidity_vault, _min_liquidity_vault_bump_seed) =
+            min_liquidity_vault_pda_program_id(program_id, pool);
+
         InitPoolPdas {
             curve,
             authority,
@@ -130,6 +236,7 @@ pub mod pda {
             pool_token_mint,
             token_a_fees_vault,
             token_b_fees_vault,
+            min_liquidity_vault,
         }
     }
 }
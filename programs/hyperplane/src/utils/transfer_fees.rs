@@ -0,0 +1,125 @@
+//! Token-2022 transfer fee calculations, independent of the runtime so they can be shared with off-chain quotes
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+    state::Mint,
+};
+
+use crate::{curve::fees::Fees, error::SwapError, to_u64, try_math, utils::math::TryMath};
+
+/// Get the transfer fee config of a Token or Token-2022 mint, if it has one
+pub fn get_transfer_fee_config(mint_data: &[u8]) -> Result<Option<TransferFeeConfig>> {
+    let mint = StateWithExtensions::<Mint>::unpack(mint_data)?;
+    Ok(mint.get_extension::<TransferFeeConfig>().ok().copied())
+}
+
+/// Subtract token mint transfer fees for actual amount received post-transfer fees
+pub fn sub_transfer_fee(
+    transfer_fee_config: Option<&TransferFeeConfig>,
+    epoch: u64,
+    amount: u64,
+) -> Result<u64> {
+    let amount = if let Some(transfer_fee_config) = transfer_fee_config {
+        let transfer_fee = transfer_fee_config
+            .calculate_epoch_fee(epoch, amount)
+            .ok_or_else(|| error!(SwapError::FeeCalculationFailure))?;
+        let amount_sub_fee = try_math!(amount.try_sub(transfer_fee))?;
+        msg!(
+            "Subtract token transfer fee: fee={}, amount={}, amount_sub_fee={}",
+            transfer_fee,
+            amount,
+            amount_sub_fee
+        );
+        amount_sub_fee
+    } else {
+        amount
+    };
+    Ok(amount)
+}
+
+/// Subtract token mint transfer fees for actual amount received by the pool post-transfer fees
+///
+/// There are potentially 3 input transfers:
+/// 1. User -> Pool
+/// 2. User -> Fees
+/// 3. User -> Host Fees (optional)
+///
+/// At low token amounts, the fees on each transfer rounding up can result in the user paying more than the amount_in, causing an unexpected `ExceededSlippage` error
+pub fn sub_input_transfer_fees(
+    transfer_fee_config: Option<&TransferFeeConfig>,
+    epoch: u64,
+    fees: &Fees,
+    amount_in: u64,
+    host_fee: bool,
+) -> Result<u64> {
+    let amount = if let Some(transfer_fee_config) = transfer_fee_config {
+        let owner_and_host_fee = fees.owner_trading_fee(amount_in.into())?;
+        let (host_fee, host_transfer_fee) = if host_fee {
+            let host_fee = fees.host_fee(owner_and_host_fee)?;
+            (
+                host_fee,
+                transfer_fee_config
+                    .calculate_epoch_fee(epoch, to_u64!(host_fee)?)
+                    .ok_or_else(|| error!(SwapError::FeeCalculationFailure))?,
+            )
+        } else {
+            (0, 0)
+        };
+        let owner_fee = try_math!(owner_and_host_fee.try_sub(host_fee))?;
+        let owner_transfer_fee = transfer_fee_config
+            .calculate_epoch_fee(epoch, to_u64!(owner_fee)?)
+            .ok_or_else(|| error!(SwapError::FeeCalculationFailure))?;
+
+        let vault_amount_in = try_math!(amount_in.try_sub(to_u64!(owner_and_host_fee)?))?;
+        let vault_transfer_fee = transfer_fee_config
+            .calculate_epoch_fee(epoch, vault_amount_in)
+            .ok_or_else(|| error!(SwapError::FeeCalculationFailure))?;
+
+        let amount_sub_fees = try_math!(try_math!(try_math!(
+            amount_in.try_sub(vault_transfer_fee)
+        )?
+        .try_sub(owner_transfer_fee))?
+        .try_sub(host_transfer_fee))?;
+
+        msg!(
+            "Subtract input token transfer fee: vault_transfer_amount={}, vault_transfer_fee={}, owner_fee={}, owner_fee_transfer_fee={}, host_fee={}, host_fee_transfer_fee={} amount={}, input_amount_sub_transfer_fees={}",
+            vault_amount_in,
+            vault_transfer_fee,
+            owner_fee,
+            owner_transfer_fee,
+            host_fee,
+            host_transfer_fee,
+            amount_in,
+            amount_sub_fees
+        );
+        amount_sub_fees
+    } else {
+        amount_in
+    };
+    Ok(amount)
+}
+
+/// Add token mint transfer fees for actual amount sent pre-transfer fees
+pub fn add_inverse_transfer_fee(
+    transfer_fee_config: Option<&TransferFeeConfig>,
+    epoch: u64,
+    post_fee_amount: u64,
+) -> Result<u64> {
+    let amount = if let Some(transfer_fee_config) = transfer_fee_config {
+        let transfer_fee = transfer_fee_config
+            .calculate_inverse_epoch_fee(epoch, post_fee_amount)
+            .ok_or_else(|| error!(SwapError::FeeCalculationFailure))?;
+        let amount_add_fee = try_math!(post_fee_amount.try_add(transfer_fee))?;
+        msg!(
+            "Add token transfer fee: fee={}, amount={}, amount_add_fee={}",
+            transfer_fee,
+            post_fee_amount,
+            amount_add_fee
+        );
+        amount_add_fee
+    } else {
+        post_fee_amount
+    };
+    Ok(amount)
+}
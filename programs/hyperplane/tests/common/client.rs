@@ -2,13 +2,17 @@
 
 use hyperplane::{
     curve::calculator::{AorB, TradeDirection},
-    ix::{Deposit, Initialize, Swap, UpdatePoolConfig, Withdraw, WithdrawFees},
+    ix::{
+        AddReward, Deposit, FundRewards, Initialize, InitializeFeeRecipients, LockLiquidity,
+        SetRewardEmissions, StakeLp, Swap, UnstakeLp, UpdateFeeRecipients, UpdatePoolConfig,
+        Withdraw, WithdrawFees,
+    },
     state::SwapPool,
 };
 use solana_program_test::BanksClientError;
-use solana_sdk::{instruction::Instruction, system_instruction};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, system_instruction};
 
-use super::types::{PoolUserAccounts, SwapPoolAccounts, TestContext};
+use super::types::{PoolUserAccounts, RewardAccounts, SwapPoolAccounts, TestContext};
 use crate::send_tx;
 
 pub async fn initialize_pool(
@@ -116,35 +120,208 @@ pub async fn update_pool_config(
     )
 }
 
+pub async fn compound_fees(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::compound_fees(pool)],
+        pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn initialize_fee_recipients(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    initialize_fee_recipients: InitializeFeeRecipients,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::initialize_fee_recipients(
+            pool,
+            initialize_fee_recipients
+        )],
+        pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn update_fee_recipients(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    update_fee_recipients: UpdateFeeRecipients,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::update_fee_recipients(
+            pool,
+            update_fee_recipients
+        )],
+        pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn distribute_fees(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    a_or_b: AorB,
+    recipient_token_accounts: &[Pubkey],
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::distribute_fees(
+            pool,
+            a_or_b,
+            recipient_token_accounts
+        )],
+    )
+}
+
+pub async fn initialize_reward_vault(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::initialize_reward_vault(pool)],
+        pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn add_reward(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    reward: &RewardAccounts,
+    add_reward: AddReward,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::add_reward(pool, reward, add_reward)],
+        pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn fund_rewards(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    reward: &RewardAccounts,
+    fund_rewards: FundRewards,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::fund_rewards(pool, reward, fund_rewards)],
+        pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn set_reward_emissions(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    reward: &RewardAccounts,
+    set_reward_emissions: SetRewardEmissions,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::set_reward_emissions(
+            pool,
+            reward,
+            set_reward_emissions
+        )],
+        pool.admin.admin.as_ref()
+    )
+}
+
+pub async fn initialize_lp_stake(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::initialize_lp_stake(pool, user)],
+        user.user.as_ref()
+    )
+}
+
+pub async fn stake_lp(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    stake_lp: StakeLp,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::stake_lp(pool, user, stake_lp)],
+        user.user.as_ref()
+    )
+}
+
+pub async fn unstake_lp(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    unstake_lp: UnstakeLp,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::unstake_lp(pool, user, unstake_lp)],
+        user.user.as_ref()
+    )
+}
+
+pub async fn claim_rewards(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    reward: &RewardAccounts,
+    user_reward_ata: &Pubkey,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::claim_rewards(
+            pool,
+            user,
+            reward,
+            user_reward_ata
+        )],
+        user.user.as_ref()
+    )
+}
+
+pub async fn lock_liquidity(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    lock_liquidity: LockLiquidity,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::lock_liquidity(pool, user, lock_liquidity)],
+        user.user.as_ref()
+    )
+}
+
+pub async fn unlock_liquidity(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: &PoolUserAccounts,
+    lock_id: u64,
+) -> Result<(), BanksClientError> {
+    send_tx!(
+        ctx,
+        [instructions::unlock_liquidity(pool, user, lock_id)],
+        user.user.as_ref()
+    )
+}
+
 pub(crate) mod instructions {
-    use hyperplane::{ix, ix::Deposit};
-    use solana_sdk::signer::Signer;
+    use hyperplane::instruction_builder;
 
     use super::*;
 
     pub fn initialize_pool(pool: &SwapPoolAccounts, initialize: Initialize) -> Instruction {
-        ix::initialize_pool(
-            &hyperplane::id(),
-            &pool.admin.pubkey(),
-            &pool.pubkey(),
-            &pool.curve,
-            &pool.token_a_mint,
-            &pool.token_b_mint,
-            &pool.token_a_vault,
-            &pool.token_b_vault,
-            &pool.authority,
-            &pool.pool_token_mint,
-            &pool.token_a_fees_vault,
-            &pool.token_b_fees_vault,
-            &pool.admin.token_a_ata,
-            &pool.admin.token_b_ata,
-            &pool.admin.pool_token_ata.pubkey(),
-            &pool.pool_token_program,
-            &pool.token_a_token_program,
-            &pool.token_b_token_program,
-            initialize,
-        )
-        .unwrap()
+        instruction_builder::initialize_pool(&pool.keys(), &pool.admin.token_accounts(), initialize)
+            .unwrap()
     }
 
     pub fn deposit(
@@ -152,23 +329,10 @@ pub(crate) mod instructions {
         user: &PoolUserAccounts,
         deposit: Deposit,
     ) -> Instruction {
-        ix::deposit(
-            &hyperplane::id(),
+        instruction_builder::deposit(
+            &pool.keys(),
             &user.pubkey(),
-            &pool.pubkey(),
-            &pool.curve,
-            &pool.authority,
-            &pool.token_a_mint,
-            &pool.token_b_mint,
-            &pool.token_a_vault,
-            &pool.token_b_vault,
-            &pool.pool_token_mint,
-            &user.token_a_ata,
-            &user.token_b_ata,
-            &user.pool_token_ata,
-            &pool.pool_token_program,
-            &pool.token_a_token_program,
-            &pool.token_b_token_program,
+            &user.token_accounts(),
             deposit,
         )
         .unwrap()
@@ -181,72 +345,20 @@ pub(crate) mod instructions {
         trade_direction: TradeDirection,
         swap: Swap,
     ) -> Instruction {
-        let (
-            (
-                source_mint,
-                source_token_program,
-                source_vault,
-                source_fees_vault,
-                user_source_ata,
-                host_fees_source_ata,
-            ),
-            (destination_mint, destination_token_program, destination_vault, user_destination_ata),
-        ) = match trade_direction {
-            TradeDirection::AtoB => {
-                let host_fees_source_ata = host_fees.map(|host_fees| &host_fees.token_a_ata);
-                (
-                    (
-                        &pool.token_a_mint,
-                        &pool.token_a_token_program,
-                        &pool.token_a_vault,
-                        &pool.token_a_fees_vault,
-                        &user.token_a_ata,
-                        host_fees_source_ata,
-                    ),
-                    (
-                        &pool.token_b_mint,
-                        &pool.token_b_token_program,
-                        &pool.token_b_vault,
-                        &user.token_b_ata,
-                    ),
-                )
-            }
-            TradeDirection::BtoA => {
-                let host_fees_source_ata = host_fees.map(|host_fees| &host_fees.token_b_ata);
-                (
-                    (
-                        &pool.token_b_mint,
-                        &pool.token_b_token_program,
-                        &pool.token_b_vault,
-                        &pool.token_b_fees_vault,
-                        &user.token_b_ata,
-                        host_fees_source_ata,
-                    ),
-                    (
-                        &pool.token_a_mint,
-                        &pool.token_a_token_program,
-                        &pool.token_a_vault,
-                        &user.token_a_ata,
-                    ),
-                )
-            }
+        let (source, destination) = match trade_direction {
+            TradeDirection::AtoB => (AorB::A, AorB::B),
+            TradeDirection::BtoA => (AorB::B, AorB::A),
         };
-        ix::swap(
-            &hyperplane::id(),
+        let user_token_accounts = user.token_accounts();
+        let host_fees_source_account =
+            host_fees.map(|host_fees| host_fees.token_accounts().side(source));
+        instruction_builder::swap(
+            &pool.keys(),
             &user.pubkey(),
-            &pool.pubkey(),
-            &pool.curve,
-            &pool.authority,
-            source_mint,
-            destination_mint,
-            source_vault,
-            destination_vault,
-            source_fees_vault,
-            user_source_ata,
-            user_destination_ata,
-            host_fees_source_ata,
-            source_token_program,
-            destination_token_program,
+            trade_direction,
+            &user_token_accounts.side(source),
+            &user_token_accounts.side(destination),
+            host_fees_source_account.as_ref(),
             swap,
         )
         .unwrap()
@@ -257,25 +369,10 @@ pub(crate) mod instructions {
         user: &PoolUserAccounts,
         withdraw: Withdraw,
     ) -> Instruction {
-        ix::withdraw(
-            &hyperplane::id(),
+        instruction_builder::withdraw(
+            &pool.keys(),
             &user.pubkey(),
-            &pool.pubkey(),
-            &pool.curve,
-            &pool.authority,
-            &pool.token_a_mint,
-            &pool.token_b_mint,
-            &pool.token_a_vault,
-            &pool.token_b_vault,
-            &pool.pool_token_mint,
-            &pool.token_a_fees_vault,
-            &pool.token_b_fees_vault,
-            &user.token_a_ata,
-            &user.token_b_ata,
-            &user.pool_token_ata,
-            &pool.pool_token_program,
-            &pool.token_a_token_program,
-            &pool.token_b_token_program,
+            &user.token_accounts(),
             withdraw,
         )
         .unwrap()
@@ -286,30 +383,10 @@ pub(crate) mod instructions {
         a_or_b: AorB,
         withdraw_fees: WithdrawFees,
     ) -> Instruction {
-        let (fees_mint, fees_vault, admin_fees_ata, fees_token_program) = match a_or_b {
-            AorB::A => (
-                &pool.token_a_mint,
-                &pool.token_a_fees_vault,
-                &pool.admin.token_a_ata,
-                &pool.token_a_token_program,
-            ),
-            AorB::B => (
-                &pool.token_b_mint,
-                &pool.token_b_fees_vault,
-                &pool.admin.token_b_ata,
-                &pool.token_b_token_program,
-            ),
-        };
-
-        ix::withdraw_fees(
-            &hyperplane::id(),
-            &pool.admin.pubkey(),
-            &pool.pubkey(),
-            &pool.authority,
-            fees_mint,
-            fees_vault,
-            admin_fees_ata,
-            fees_token_program,
+        instruction_builder::withdraw_fees(
+            &pool.keys(),
+            a_or_b,
+            &pool.admin.token_accounts().side(a_or_b),
             withdraw_fees,
         )
         .unwrap()
@@ -319,11 +396,147 @@ pub(crate) mod instructions {
         pool: &SwapPoolAccounts,
         update_pool_config: UpdatePoolConfig,
     ) -> Instruction {
-        ix::update_pool_config(
-            &hyperplane::id(),
-            &pool.admin.pubkey(),
-            &pool.pubkey(),
-            update_pool_config,
+        instruction_builder::update_pool_config(&pool.keys(), update_pool_config).unwrap()
+    }
+
+    pub fn compound_fees(pool: &SwapPoolAccounts) -> Instruction {
+        instruction_builder::compound_fees(&pool.keys(), &pool.admin.pool_token_ata.pubkey())
+            .unwrap()
+    }
+
+    pub fn initialize_fee_recipients(
+        pool: &SwapPoolAccounts,
+        initialize_fee_recipients: InitializeFeeRecipients,
+    ) -> Instruction {
+        instruction_builder::initialize_fee_recipients(&pool.keys(), initialize_fee_recipients)
+            .unwrap()
+    }
+
+    pub fn update_fee_recipients(
+        pool: &SwapPoolAccounts,
+        update_fee_recipients: UpdateFeeRecipients,
+    ) -> Instruction {
+        instruction_builder::update_fee_recipients(&pool.keys(), update_fee_recipients).unwrap()
+    }
+
+    pub fn distribute_fees(
+        pool: &SwapPoolAccounts,
+        a_or_b: AorB,
+        recipient_token_accounts: &[Pubkey],
+    ) -> Instruction {
+        instruction_builder::distribute_fees(&pool.keys(), a_or_b, recipient_token_accounts)
+            .unwrap()
+    }
+
+    pub fn initialize_reward_vault(pool: &SwapPoolAccounts) -> Instruction {
+        instruction_builder::initialize_reward_vault(&pool.keys()).unwrap()
+    }
+
+    pub fn add_reward(
+        pool: &SwapPoolAccounts,
+        reward: &RewardAccounts,
+        add_reward: AddReward,
+    ) -> Instruction {
+        instruction_builder::add_reward(
+            &pool.keys(),
+            &reward.mint,
+            &reward.token_program,
+            add_reward,
+        )
+        .unwrap()
+    }
+
+    pub fn fund_rewards(
+        pool: &SwapPoolAccounts,
+        reward: &RewardAccounts,
+        fund_rewards: FundRewards,
+    ) -> Instruction {
+        instruction_builder::fund_rewards(
+            &pool.keys(),
+            &reward.mint,
+            &reward.token_program,
+            &reward.admin_ata,
+            fund_rewards,
+        )
+        .unwrap()
+    }
+
+    pub fn set_reward_emissions(
+        pool: &SwapPoolAccounts,
+        reward: &RewardAccounts,
+        set_reward_emissions: SetRewardEmissions,
+    ) -> Instruction {
+        instruction_builder::set_reward_emissions(&pool.keys(), &reward.mint, set_reward_emissions)
+            .unwrap()
+    }
+
+    pub fn initialize_lp_stake(pool: &SwapPoolAccounts, user: &PoolUserAccounts) -> Instruction {
+        instruction_builder::initialize_lp_stake(&pool.keys(), &user.pubkey()).unwrap()
+    }
+
+    pub fn stake_lp(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        stake_lp: StakeLp,
+    ) -> Instruction {
+        instruction_builder::stake_lp(&pool.keys(), &user.pubkey(), &user.pool_token_ata, stake_lp)
+            .unwrap()
+    }
+
+    pub fn unstake_lp(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        unstake_lp: UnstakeLp,
+    ) -> Instruction {
+        instruction_builder::unstake_lp(
+            &pool.keys(),
+            &user.pubkey(),
+            &user.pool_token_ata,
+            unstake_lp,
+        )
+        .unwrap()
+    }
+
+    pub fn claim_rewards(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        reward: &RewardAccounts,
+        user_reward_ata: &Pubkey,
+    ) -> Instruction {
+        instruction_builder::claim_rewards(
+            &pool.keys(),
+            &user.pubkey(),
+            &reward.mint,
+            &reward.token_program,
+            user_reward_ata,
+        )
+        .unwrap()
+    }
+
+    pub fn lock_liquidity(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        lock_liquidity: LockLiquidity,
+    ) -> Instruction {
+        instruction_builder::lock_liquidity(
+            &pool.keys(),
+            &user.pubkey(),
+            &user.pool_token_ata,
+            lock_liquidity,
+        )
+        .unwrap()
+    }
+
+    pub fn unlock_liquidity(
+        pool: &SwapPoolAccounts,
+        user: &PoolUserAccounts,
+        lock_id: u64,
+    ) -> Instruction {
+        instruction_builder::unlock_liquidity(
+            &pool.keys(),
+            &user.pubkey(),
+            &user.pool_token_ata,
+            lock_id,
         )
         .unwrap()
     }
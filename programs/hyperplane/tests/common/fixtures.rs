@@ -1,4 +1,8 @@
-use hyperplane::{curve::fees::Fees, ix::Initialize, CurveUserParameters, InitialSupply};
+use hyperplane::{
+    curve::{calculator::MIN_LIQUIDITY, fees::Fees},
+    ix::Initialize,
+    CurveUserParameters, InitialSupply,
+};
 use solana_sdk::native_token::sol_to_lamports;
 
 use crate::common::{
@@ -34,6 +38,7 @@ pub async fn new_pool(
             fees,
             initial_supply,
             curve_parameters,
+            min_liquidity: MIN_LIQUIDITY as u64,
         },
     )
     .await
@@ -19,3 +19,9 @@ pub async fn start(test: ProgramTest) -> TestContext {
 
     TestContext { context, rent }
 }
+
+/// Whether the tests run the compiled program, e.g. with `cargo test-sbf`, rather than natively.
+/// Compute units are only metered for the compiled program.
+pub fn is_sbf() -> bool {
+    std::env::var("SBF_OUT_DIR").is_ok() || std::env::var("BPF_OUT_DIR").is_ok()
+}
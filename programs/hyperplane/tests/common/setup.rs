@@ -2,14 +2,21 @@ use std::sync::Arc;
 
 use anchor_lang::Id;
 use anchor_spl::token::Token;
-use hyperplane::{ix::Deposit, utils::seeds, InitialSupply};
+use hyperplane::{
+    ix::{AddReward, Deposit},
+    utils::seeds,
+    InitialSupply,
+};
 use solana_sdk::{signature::Keypair, signer::Signer, system_instruction};
 
 use super::{fixtures::Sol, token_operations, types::TestContext};
 use crate::{
     common::{
         client,
-        types::{PoolAdminAccounts, PoolUserAccounts, SwapPairSpec, SwapPoolAccounts},
+        types::{
+            PoolAdminAccounts, PoolUserAccounts, RewardAccounts, SwapPairSpec, SwapPoolAccounts,
+            TokenSpec,
+        },
         utils::calculate_pool_tokens,
     },
     send_tx,
@@ -128,6 +135,33 @@ pub async fn new_lp_user(
     user
 }
 
+/// Create a reward mint, fund the admin with it, and add it to the reward vault of the pool
+pub async fn new_reward(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    token_spec: TokenSpec,
+    emissions_per_second: u64,
+) -> RewardAccounts {
+    let mint = kp();
+    token_operations::create_mint(ctx, &mint, token_spec)
+        .await
+        .unwrap();
+    let admin_ata = token_operations::create_and_mint_to_token_account(
+        ctx,
+        &token_spec.token_program,
+        &pool.admin.pubkey(),
+        &mint.pubkey(),
+        1_000_000_000000,
+    )
+    .await;
+
+    let reward = RewardAccounts::new(mint.pubkey(), token_spec.token_program, admin_ata);
+    client::add_reward(ctx, pool, &reward, AddReward::new(emissions_per_second))
+        .await
+        .unwrap();
+    reward
+}
+
 // ---------- PROGRAM STRUCTS UTILS ----------
 
 pub async fn new_pool_accs(
@@ -156,6 +190,7 @@ pub async fn new_pool_accs(
         pool_token_mint,
         token_a_fees_vault,
         token_b_fees_vault,
+        min_liquidity_vault,
     } = seeds::pda::init_pool_pdas(
         &pool.pubkey(),
         &token_a_mint.pubkey(),
@@ -199,6 +234,7 @@ pub async fn new_pool_accs(
         token_b_vault,
         token_a_fees_vault,
         token_b_fees_vault,
+        min_liquidity_vault,
         pool_token_program: Token::id(),
         token_a_token_program: trading_tokens.a.token_program,
         token_b_token_program: trading_tokens.b.token_program,
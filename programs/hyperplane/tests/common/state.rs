@@ -1,5 +1,8 @@
 use anchor_lang::{prelude::Pubkey, AccountDeserialize, Discriminator};
-use hyperplane::state::{ConstantProductCurve, StableCurve, SwapPool};
+use hyperplane::{
+    state::{ConstantProductCurve, LiquidityLock, LpStake, RewardVault, StableCurve, SwapPool},
+    utils::seeds,
+};
 use solana_sdk::account::Account;
 
 use crate::common::types::{SwapPoolAccounts, TestContext, TestError};
@@ -8,6 +11,26 @@ pub async fn get_pool(ctx: &mut TestContext, pool: &SwapPoolAccounts) -> SwapPoo
     get::<SwapPool>(ctx, pool.pubkey()).await
 }
 
+/// Overwrite the pool account, to set up states the instructions cannot reach
+pub async fn set_pool(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    update: impl FnOnce(&mut SwapPool),
+) {
+    let mut account = ctx
+        .context
+        .banks_client
+        .get_account(pool.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let range = 8..8 + std::mem::size_of::<SwapPool>();
+    let mut swap_pool: SwapPool = bytemuck::pod_read_unaligned(&account.data[range.clone()]);
+    update(&mut swap_pool);
+    account.data[range].copy_from_slice(bytemuck::bytes_of(&swap_pool));
+    ctx.context.set_account(&pool.pubkey(), &account.into());
+}
+
 pub async fn get_constant_product_curve(
     ctx: &mut TestContext,
     pool: &SwapPoolAccounts,
@@ -19,6 +42,32 @@ pub async fn get_stable_curve(ctx: &mut TestContext, pool: &SwapPoolAccounts) ->
     get::<StableCurve>(ctx, pool.curve).await
 }
 
+pub async fn get_reward_vault(ctx: &mut TestContext, pool: &SwapPoolAccounts) -> RewardVault {
+    get::<RewardVault>(ctx, seeds::pda::reward_vault_pda(&pool.pubkey()).0).await
+}
+
+pub async fn get_lp_stake(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    owner: &Pubkey,
+) -> LpStake {
+    let reward_vault = seeds::pda::reward_vault_pda(&pool.pubkey()).0;
+    get::<LpStake>(ctx, seeds::pda::lp_stake_pda(&reward_vault, owner).0).await
+}
+
+pub async fn get_liquidity_lock(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    owner: &Pubkey,
+    lock_id: u64,
+) -> LiquidityLock {
+    get::<LiquidityLock>(
+        ctx,
+        seeds::pda::liquidity_lock_pda(&pool.pubkey(), owner, lock_id).0,
+    )
+    .await
+}
+
 pub async fn get<T: AccountDeserialize + Discriminator>(
     ctx: &mut TestContext,
     address: Pubkey,
@@ -73,6 +73,7 @@ pub async fn create_mint(
         token_program,
         decimals,
         transfer_fee_bps,
+        maximum_transfer_fee,
     }: TokenSpec,
 ) -> Result<(), TransportError> {
     let is_transfer_fee = token_program == spl_token_2022::id() && transfer_fee_bps > 0;
@@ -102,7 +103,7 @@ pub async fn create_mint(
                 None,
                 None,
                 transfer_fee_bps,
-                u64::MAX,
+                maximum_transfer_fee,
             )
             .unwrap(),
         );
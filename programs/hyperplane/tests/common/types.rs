@@ -3,6 +3,7 @@ use std::sync::Arc;
 use anchor_lang::prelude::{thiserror, Pubkey, Rent};
 use anchor_spl::{token::spl_token, token_2022::spl_token_2022};
 use derive_more::Constructor;
+use hyperplane::instruction_builder::{PoolKeys, UserTokenAccounts};
 use solana_program_test::ProgramTestContext;
 use solana_sdk::{signature::Keypair, signer::Signer};
 use thiserror::Error;
@@ -41,6 +42,7 @@ pub struct SwapPoolAccounts {
     pub token_b_vault: Pubkey,
     pub token_a_fees_vault: Pubkey,
     pub token_b_fees_vault: Pubkey,
+    pub min_liquidity_vault: Pubkey,
     pub token_a_token_program: Pubkey,
     pub token_b_token_program: Pubkey,
     pub pool_token_program: Pubkey,
@@ -50,12 +52,35 @@ impl SwapPoolAccounts {
     pub fn pubkey(&self) -> Pubkey {
         self.pool.pubkey()
     }
+
+    pub fn keys(&self) -> PoolKeys {
+        PoolKeys {
+            program_id: hyperplane::id(),
+            pool: self.pubkey(),
+            admin: self.admin.pubkey(),
+            swap_curve: self.curve,
+            pool_authority: self.authority,
+            token_a_mint: self.token_a_mint,
+            token_b_mint: self.token_b_mint,
+            token_a_vault: self.token_a_vault,
+            token_b_vault: self.token_b_vault,
+            pool_token_mint: self.pool_token_mint,
+            token_a_fees_vault: self.token_a_fees_vault,
+            token_b_fees_vault: self.token_b_fees_vault,
+            min_liquidity_vault: self.min_liquidity_vault,
+            pool_token_program: self.pool_token_program,
+            token_a_token_program: self.token_a_token_program,
+            token_b_token_program: self.token_b_token_program,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
 pub struct TokenSpec {
     pub decimals: u8,
     pub transfer_fee_bps: u16,
+    /// Cap of the transfer fee of a single transfer
+    pub maximum_transfer_fee: u64,
     pub token_program: Pubkey,
 }
 
@@ -73,9 +98,16 @@ impl TokenSpec {
         Self {
             decimals,
             transfer_fee_bps,
+            maximum_transfer_fee: u64::MAX,
             token_program,
         }
     }
+    pub fn with_maximum_transfer_fee(self, maximum_transfer_fee: u64) -> Self {
+        Self {
+            maximum_transfer_fee,
+            ..self
+        }
+    }
     pub fn spl_token(decimals: u8) -> Self {
         Self::new(decimals, 0, spl_token::id())
     }
@@ -99,6 +131,16 @@ impl SwapPairSpec {
     }
 }
 
+// ---- REWARD TYPES ----
+
+#[derive(Clone, Debug, Constructor)]
+pub struct RewardAccounts {
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    /// Admin's token account to fund the rewards from
+    pub admin_ata: Pubkey,
+}
+
 // ---- USER TYPES ----
 
 #[derive(Clone, Debug, Constructor)]
@@ -113,6 +155,14 @@ impl PoolAdminAccounts {
     pub fn pubkey(&self) -> Pubkey {
         self.admin.pubkey()
     }
+
+    pub fn token_accounts(&self) -> UserTokenAccounts {
+        UserTokenAccounts {
+            token_a: self.token_a_ata,
+            token_b: self.token_b_ata,
+            pool_token: self.pool_token_ata.pubkey(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Constructor)]
@@ -127,6 +177,14 @@ impl PoolUserAccounts {
     pub fn pubkey(&self) -> Pubkey {
         self.user.pubkey()
     }
+
+    pub fn token_accounts(&self) -> UserTokenAccounts {
+        UserTokenAccounts {
+            token_a: self.token_a_ata,
+            token_b: self.token_b_ata,
+            pool_token: self.pool_token_ata,
+        }
+    }
 }
 
 impl From<PoolAdminAccounts> for PoolUserAccounts {
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::Pubkey;
 use hyperplane::curve::{calculator::RoundDirection, math::pool_tokens_to_trading_tokens};
-use solana_sdk::account::AccountSharedData;
+use solana_sdk::{account::AccountSharedData, clock::Clock};
 
 use crate::common::types::TestContext;
 
@@ -45,6 +45,19 @@ pub async fn clone_account_with_new_owner(
         .set_account(new_address, &cloned_account);
 }
 
+/// Current unix timestamp of the cluster clock
+pub async fn unix_timestamp(ctx: &mut TestContext) -> u64 {
+    let clock: Clock = ctx.context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp as u64
+}
+
+/// Move the unix timestamp of the cluster clock forward
+pub async fn warp_seconds(ctx: &mut TestContext, seconds: i64) {
+    let mut clock: Clock = ctx.context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp += seconds;
+    ctx.context.set_sysvar(&clock);
+}
+
 pub fn calculate_pool_tokens(
     a_amount: u64,
     b_amount: u64,
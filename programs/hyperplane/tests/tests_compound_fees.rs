@@ -0,0 +1,289 @@
+mod common;
+
+use anchor_lang::prelude::ErrorCode;
+use common::{client, runner};
+use hyperplane::{
+    curve::{calculator::TradeDirection, fees::Fees},
+    error::SwapError,
+    ix::{Swap, UpdatePoolConfig},
+    state::{UpdatePoolConfigMode, UpdatePoolConfigValue},
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+use solana_sdk::signature::Signer;
+
+use crate::common::{
+    fixtures,
+    fixtures::Sol,
+    setup,
+    setup::{kp, new_keypair},
+    token_operations,
+    types::{SwapPairSpec, SwapPoolAccounts, TestContext, TokenSpec},
+};
+
+async fn new_pool(ctx: &mut TestContext) -> SwapPoolAccounts {
+    new_pool_with_tokens(ctx, SwapPairSpec::default()).await
+}
+
+async fn new_pool_with_tokens(ctx: &mut TestContext, tokens: SwapPairSpec) -> SwapPoolAccounts {
+    fixtures::new_pool(
+        ctx,
+        Fees {
+            host_fee_numerator: 1,
+            host_fee_denominator: 100,
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 100,
+            owner_withdraw_fee_numerator: 1,
+            owner_withdraw_fee_denominator: 100,
+        },
+        InitialSupply::new(10_000_000, 10_000_000),
+        tokens,
+        CurveUserParameters::Stable { amp: 100 },
+    )
+    .await
+}
+
+#[tokio::test]
+pub async fn test_successful_compound_fees() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool(&mut ctx).await;
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (2_000_000, 1_000_000)).await;
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(2_000_000, 1_900_000),
+    )
+    .await
+    .unwrap();
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::BtoA,
+        Swap::new(1_000_000, 970_000),
+    )
+    .await
+    .unwrap();
+
+    let token_a_fees = token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await;
+    let token_b_fees = token_operations::balance(&mut ctx, &pool.token_b_fees_vault).await;
+    let token_a_vault = token_operations::balance(&mut ctx, &pool.token_a_vault).await;
+    let token_b_vault = token_operations::balance(&mut ctx, &pool.token_b_vault).await;
+    let pool_token_supply = token_operations::supply(&mut ctx, &pool.pool_token_mint).await;
+    let admin_pool_tokens =
+        token_operations::balance(&mut ctx, &pool.admin.pool_token_ata.pubkey()).await;
+
+    client::compound_fees(&mut ctx, &pool).await.unwrap();
+
+    let compounded_a =
+        token_a_fees - token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await;
+    let compounded_b =
+        token_b_fees - token_operations::balance(&mut ctx, &pool.token_b_fees_vault).await;
+    let minted_pool_tokens =
+        token_operations::supply(&mut ctx, &pool.pool_token_mint).await - pool_token_supply;
+
+    assert!(compounded_a > 0);
+    assert!(compounded_b > 0);
+    assert!(minted_pool_tokens > 0);
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_vault).await,
+        token_a_vault + compounded_a
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_b_vault).await,
+        token_b_vault + compounded_b
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.admin.pool_token_ata.pubkey()).await,
+        admin_pool_tokens + minted_pool_tokens
+    );
+
+    // token A fees are double token B fees, so the unbalanced token A remainder stays in the fees vault
+    assert!(token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await > 0);
+
+    // deposited at the current ratio of the pool
+    let expected_pool_tokens =
+        compounded_b as u128 * pool_token_supply as u128 / token_b_vault as u128;
+    assert!(minted_pool_tokens as u128 <= expected_pool_tokens);
+    assert!(
+        compounded_a as u128 * pool_token_supply as u128 / token_a_vault as u128
+            >= minted_pool_tokens as u128
+    );
+}
+
+#[tokio::test]
+pub async fn test_compound_fees_with_transfer_fees() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool_with_tokens(
+        &mut ctx,
+        SwapPairSpec::new(TokenSpec::transfer_fees(100), TokenSpec::transfer_fees(100)),
+    )
+    .await;
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (2_000_000, 1_000_000)).await;
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(2_000_000, 1),
+    )
+    .await
+    .unwrap();
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::BtoA,
+        Swap::new(1_000_000, 1),
+    )
+    .await
+    .unwrap();
+
+    let token_a_fees = token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await;
+    let token_b_fees = token_operations::balance(&mut ctx, &pool.token_b_fees_vault).await;
+    let token_a_vault = token_operations::balance(&mut ctx, &pool.token_a_vault).await;
+    let token_b_vault = token_operations::balance(&mut ctx, &pool.token_b_vault).await;
+    let pool_token_supply = token_operations::supply(&mut ctx, &pool.pool_token_mint).await;
+
+    client::compound_fees(&mut ctx, &pool).await.unwrap();
+
+    let sent_a = token_a_fees - token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await;
+    let sent_b = token_b_fees - token_operations::balance(&mut ctx, &pool.token_b_fees_vault).await;
+    let received_a = token_operations::balance(&mut ctx, &pool.token_a_vault).await - token_a_vault;
+    let received_b = token_operations::balance(&mut ctx, &pool.token_b_vault).await - token_b_vault;
+    let minted_pool_tokens =
+        token_operations::supply(&mut ctx, &pool.pool_token_mint).await - pool_token_supply;
+
+    // the transfer fees are withheld on the way to the pool vaults
+    assert!(received_a > 0 && received_a < sent_a);
+    assert!(received_b > 0 && received_b < sent_b);
+    assert!(minted_pool_tokens > 0);
+
+    // the pool tokens are only worth what the pool vaults received, existing LPs are not diluted
+    assert!(
+        minted_pool_tokens as u128
+            <= received_a as u128 * pool_token_supply as u128 / token_a_vault as u128
+    );
+    assert!(
+        minted_pool_tokens as u128
+            <= received_b as u128 * pool_token_supply as u128 / token_b_vault as u128
+    );
+}
+
+#[tokio::test]
+pub async fn test_compound_fees_with_one_empty_fees_vault_fails() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool(&mut ctx).await;
+
+    let user = setup::new_pool_user(&mut ctx, &pool, (1_000_000, 0)).await;
+    client::swap(
+        &mut ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(1_000_000, 970_000),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        client::compound_fees(&mut ctx, &pool)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::ZeroTradingTokens)
+    );
+}
+
+#[tokio::test]
+pub async fn test_compound_fees_withdrawals_only_fails() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool(&mut ctx).await;
+
+    client::update_pool_config(
+        &mut ctx,
+        &pool,
+        UpdatePoolConfig::new(
+            UpdatePoolConfigMode::WithdrawalsOnly,
+            UpdatePoolConfigValue::Bool(true),
+        ),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        client::compound_fees(&mut ctx, &pool)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::WithdrawalsOnlyMode)
+    );
+}
+
+#[tokio::test]
+pub async fn test_security_compound_fees() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool(&mut ctx).await;
+
+    // wrong admin
+    {
+        let mut cloned_pool = pool.clone();
+        cloned_pool.admin.admin = new_keypair(&mut ctx, Sol::one()).await;
+
+        assert_eq!(
+            client::compound_fees(&mut ctx, &cloned_pool)
+                .await
+                .unwrap_err()
+                .unwrap(),
+            anchor_error!(ErrorCode::ConstraintHasOne)
+        );
+    }
+
+    // wrong token_a_fees_vault
+    {
+        let mut cloned_pool = pool.clone();
+        cloned_pool.token_a_fees_vault = pool.token_b_fees_vault;
+
+        assert_eq!(
+            client::compound_fees(&mut ctx, &cloned_pool)
+                .await
+                .unwrap_err()
+                .unwrap(),
+            hyperplane_error!(SwapError::IncorrectFeeAccount)
+        );
+    }
+
+    // wrong admin_pool_token_ata authority
+    {
+        let mut cloned_pool = pool.clone();
+        cloned_pool.admin.pool_token_ata = kp();
+        token_operations::create_token_account_kp(
+            &mut ctx,
+            &pool.pool_token_program,
+            &cloned_pool.admin.pool_token_ata,
+            &pool.pool_token_mint,
+            &kp().pubkey(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            client::compound_fees(&mut ctx, &cloned_pool)
+                .await
+                .unwrap_err()
+                .unwrap(),
+            anchor_error!(ErrorCode::ConstraintTokenOwner)
+        );
+    }
+}
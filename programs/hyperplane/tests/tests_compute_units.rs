@@ -0,0 +1,328 @@
+//! Compute units consumed by the pool instructions, for every curve and for Token and Token-2022
+//! trading token mints.
+//!
+//! Compute units are only metered when running the compiled program, with:
+//! `cargo test-sbf --test tests_compute_units -- --nocapture`
+//!
+//! The results are compared against the baseline in `tests/compute_units.json`. The test fails if
+//! an instruction consumes more than `REGRESSION_THRESHOLD_PERCENT` over its baseline, if it is
+//! missing from the baseline, or if the baseline is missing. Set `UPDATE_COMPUTE_UNITS_BASELINE=1`
+//! to write the results to the baseline instead.
+
+mod common;
+
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use anchor_spl::{token::spl_token, token_2022::spl_token_2022};
+use common::{runner, setup};
+use hyperplane::{
+    curve::{
+        base::CurveType,
+        calculator::{AorB, TradeDirection, MIN_LIQUIDITY},
+        fees::Fees,
+        stable::MAX_AMP,
+    },
+    ix::{Deposit, Initialize, Swap, Withdraw, WithdrawFees},
+    state::SwapPool,
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_instruction, transaction::Transaction,
+};
+
+use crate::common::{
+    client,
+    client::instructions,
+    token_operations,
+    types::{PoolUserAccounts, SwapPairSpec, TestContext, TokenSpec},
+};
+
+const BASELINE_FILE: &str = "tests/compute_units.json";
+const UPDATE_BASELINE_ENV: &str = "UPDATE_COMPUTE_UNITS_BASELINE";
+const REGRESSION_THRESHOLD_PERCENT: u64 = 5;
+
+/// 1% of the default supply of each trading token
+const SWAP_AMOUNT: u64 = 10_000_000000;
+/// 1% of the initial pool token supply
+const POOL_TOKEN_AMOUNT: u64 = 10_000_000;
+/// Token B supply of the imbalanced stable pool, 1% of the token A supply
+const IMBALANCED_SUPPLY_B: u64 = 10_000_000000;
+
+#[tokio::test]
+pub async fn test_compute_units_do_not_regress() {
+    if !runner::is_sbf() {
+        println!("Skipping compute units, they are only metered with `cargo test-sbf`");
+        return;
+    }
+
+    let mut results = BTreeMap::new();
+    for curve_parameters in [
+        CurveUserParameters::ConstantProduct,
+        CurveUserParameters::ConstantPrice { token_b_price: 1 },
+        CurveUserParameters::Offset {
+            token_b_offset: 1_000_000_000000,
+        },
+        CurveUserParameters::Stable { amp: 100 },
+    ] {
+        for (token_program_name, token_program) in [
+            ("token", spl_token::id()),
+            ("token_2022", spl_token_2022::id()),
+        ] {
+            let curve_name = curve_name(&curve_parameters);
+            for (instruction, units) in measure(curve_parameters.clone(), token_program).await {
+                let key = format!("{}/{}/{}", curve_name, token_program_name, instruction);
+                println!("{}: {}", key, units);
+                results.insert(key, units);
+            }
+        }
+    }
+    for (token_program_name, token_program) in [
+        ("token", spl_token::id()),
+        ("token_2022", spl_token_2022::id()),
+    ] {
+        let key = format!("stable_imbalanced/{}/swap", token_program_name);
+        let units = measure_imbalanced_stable_swap(token_program).await;
+        println!("{}: {}", key, units);
+        results.insert(key, units);
+    }
+
+    check_baseline(&results);
+}
+
+/// Compute units of each instruction on a fresh pool
+async fn measure(
+    curve_parameters: CurveUserParameters,
+    token_program: Pubkey,
+) -> Vec<(&'static str, u64)> {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let token = TokenSpec::new(6, 0, token_program);
+    let initial_supply = setup::default_supply();
+    let pool =
+        setup::new_pool_accs(&mut ctx, SwapPairSpec::new(token, token), &initial_supply).await;
+    let allows_deposits = !matches!(curve_parameters, CurveUserParameters::Offset { .. });
+
+    // create the pool account separately so only the program is measured
+    let create_pool = system_instruction::create_account(
+        &ctx.context.payer.pubkey(),
+        &pool.pubkey(),
+        ctx.rent.minimum_balance(SwapPool::LEN),
+        SwapPool::LEN as u64,
+        &hyperplane::id(),
+    );
+    process(&mut ctx, create_pool, &[pool.pool.as_ref()]).await;
+
+    let mut results = vec![];
+    let initialize_pool = instructions::initialize_pool(
+        &pool,
+        Initialize {
+            fees: fees(),
+            curve_parameters,
+            initial_supply: initial_supply.clone(),
+            min_liquidity: MIN_LIQUIDITY as u64,
+        },
+    );
+    results.push((
+        "initialize_pool",
+        process(
+            &mut ctx,
+            initialize_pool,
+            &[
+                pool.admin.admin.as_ref(),
+                pool.admin.pool_token_ata.as_ref(),
+            ],
+        )
+        .await,
+    ));
+
+    let trader = setup::new_pool_user(&mut ctx, &pool, (SWAP_AMOUNT, 0)).await;
+    let swap = instructions::swap(
+        &pool,
+        &trader,
+        None,
+        TradeDirection::AtoB,
+        Swap::new(SWAP_AMOUNT, 1),
+    );
+    results.push((
+        "swap",
+        process(&mut ctx, swap, &[trader.user.as_ref()]).await,
+    ));
+
+    if allows_deposits {
+        let depositor = setup::new_pool_user(
+            &mut ctx,
+            &pool,
+            (
+                initial_supply.initial_supply_a,
+                initial_supply.initial_supply_b,
+            ),
+        )
+        .await;
+        let deposit = instructions::deposit(
+            &pool,
+            &depositor,
+            Deposit::new(POOL_TOKEN_AMOUNT, u64::MAX, u64::MAX),
+        );
+        results.push((
+            "deposit",
+            process(&mut ctx, deposit, &[depositor.user.as_ref()]).await,
+        ));
+    }
+
+    let admin = PoolUserAccounts::from(pool.admin.clone());
+    let withdraw = instructions::withdraw(&pool, &admin, Withdraw::new(POOL_TOKEN_AMOUNT, 0, 0));
+    results.push((
+        "withdraw",
+        process(&mut ctx, withdraw, &[admin.user.as_ref()]).await,
+    ));
+
+    let fees_amount = token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await;
+    let withdraw_fees = instructions::withdraw_fees(&pool, AorB::A, WithdrawFees::new(fees_amount));
+    results.push((
+        "withdraw_fees",
+        process(&mut ctx, withdraw_fees, &[pool.admin.admin.as_ref()]).await,
+    ));
+
+    results
+}
+
+/// Compute units of a swap on a stable pool with the highest amp and 100 times more token A than
+/// token B, further unbalancing it. This is the worst case for solving the invariant.
+async fn measure_imbalanced_stable_swap(token_program: Pubkey) -> u64 {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let token = TokenSpec::new(6, 0, token_program);
+    let initial_supply = InitialSupply::new(
+        setup::default_supply().initial_supply_a,
+        IMBALANCED_SUPPLY_B,
+    );
+    let pool =
+        setup::new_pool_accs(&mut ctx, SwapPairSpec::new(token, token), &initial_supply).await;
+    client::initialize_pool(
+        &mut ctx,
+        &pool,
+        Initialize {
+            fees: fees(),
+            curve_parameters: CurveUserParameters::Stable { amp: MAX_AMP - 1 },
+            initial_supply,
+            min_liquidity: MIN_LIQUIDITY as u64,
+        },
+    )
+    .await
+    .unwrap();
+
+    let trader = setup::new_pool_user(&mut ctx, &pool, (SWAP_AMOUNT, 0)).await;
+    let swap = instructions::swap(
+        &pool,
+        &trader,
+        None,
+        TradeDirection::AtoB,
+        Swap::new(SWAP_AMOUNT, 1),
+    );
+    process(&mut ctx, swap, &[trader.user.as_ref()]).await
+}
+
+/// Process a transaction with the single instruction and return the compute units it consumed
+async fn process(ctx: &mut TestContext, instruction: Instruction, signers: &[&Keypair]) -> u64 {
+    let recent_blockhash = ctx
+        .context
+        .banks_client
+        .get_latest_blockhash()
+        .await
+        .unwrap();
+    let mut all_signers = vec![&ctx.context.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&ctx.context.payer.pubkey()),
+        &all_signers,
+        recent_blockhash,
+    );
+    let result = ctx
+        .context
+        .banks_client
+        .process_transaction_with_metadata(transaction)
+        .await
+        .unwrap();
+    result.result.unwrap();
+    result.metadata.unwrap().compute_units_consumed
+}
+
+fn check_baseline(results: &BTreeMap<String, u64>) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(BASELINE_FILE);
+    if std::env::var(UPDATE_BASELINE_ENV).is_ok() {
+        fs::write(
+            &path,
+            format!("{}\n", serde_json::to_string_pretty(results).unwrap()),
+        )
+        .unwrap();
+        println!("Wrote compute units baseline to {}", path.display());
+        return;
+    }
+    let baseline = fs::read_to_string(&path).unwrap_or_else(|e| {
+        panic!(
+            "Failed to read the compute units baseline {}, set {}=1 to write it: {}",
+            path.display(),
+            UPDATE_BASELINE_ENV,
+            e
+        )
+    });
+    let baseline: BTreeMap<String, u64> = serde_json::from_str(&baseline).unwrap();
+
+    let mut regressions = vec![];
+    for (key, units) in results {
+        match baseline.get(key) {
+            Some(baseline_units) => {
+                let limit = baseline_units + baseline_units * REGRESSION_THRESHOLD_PERCENT / 100;
+                if *units > limit {
+                    regressions.push(format!(
+                        "{}: {} > {} (baseline {})",
+                        key, units, limit, baseline_units
+                    ));
+                } else if units < baseline_units {
+                    println!("{}: {} improved on baseline {}", key, units, baseline_units);
+                }
+            }
+            None => regressions.push(format!("{}: {} is missing from the baseline", key, units)),
+        }
+    }
+    assert!(
+        regressions.is_empty(),
+        "Compute units regressed by more than {}%, set {}=1 to accept the new values:\n{}",
+        REGRESSION_THRESHOLD_PERCENT,
+        UPDATE_BASELINE_ENV,
+        regressions.join("\n")
+    );
+}
+
+fn curve_name(curve_parameters: &CurveUserParameters) -> &'static str {
+    let curve_type = match curve_parameters {
+        CurveUserParameters::ConstantProduct => CurveType::ConstantProduct,
+        CurveUserParameters::ConstantPrice { .. } => CurveType::ConstantPrice,
+        CurveUserParameters::Offset { .. } => CurveType::Offset,
+        CurveUserParameters::Stable { .. } => CurveType::Stable,
+    };
+    match curve_type {
+        CurveType::ConstantProduct => "constant_product",
+        CurveType::ConstantPrice => "constant_price",
+        CurveType::Offset => "offset",
+        CurveType::Stable => "stable",
+    }
+}
+
+fn fees() -> Fees {
+    Fees {
+        trade_fee_numerator: 25,
+        trade_fee_denominator: 10_000,
+        owner_trade_fee_numerator: 5,
+        owner_trade_fee_denominator: 10_000,
+        owner_withdraw_fee_numerator: 1,
+        owner_withdraw_fee_denominator: 10_000,
+        host_fee_numerator: 0,
+        host_fee_denominator: 0,
+    }
+}
@@ -0,0 +1,768 @@
+//! Differential tests of the hyperplane curves against the SPL token-swap curves.
+//!
+//! Every test case initializes a pool and runs a random sequence of swaps, deposits and
+//! withdrawals. Each step is computed by both programs from the same pool state, following the
+//! curve math of their instruction handlers, and the pool then moves on with the hyperplane
+//! result. Slippage and zero amount checks of the handlers are left out.
+//!
+//! Any difference between the two programs fails the test, unless it is explained by one of the
+//! intended divergences in [`ALLOWED_DIVERGENCES`].
+//!
+//! The stable curve is only compared with equal decimals for token A and B, since hyperplane
+//! scales the amounts by the difference in decimals and SPL does not.
+
+use std::sync::Arc;
+
+use hyperplane::{
+    curve::{
+        base::SwapCurve,
+        calculator::{RoundDirection, TradeDirection, MIN_LIQUIDITY},
+        fees::Fees,
+        stable::{MAX_AMP, MIN_AMP},
+    },
+    model::CurveParameters,
+};
+use proptest::prelude::*;
+use spl_token_swap::curve as spl;
+
+const MAX_AMOUNT: u64 = 1_000_000_000_000;
+const MAX_POOL_TOKENS: u64 = 2_000_000_000;
+const MAX_STEPS: usize = 16;
+const STABLE_DECIMALS: u8 = 6;
+/// Tokens the stable curve solvers of the two programs may disagree by for one swap
+const STABLE_SWAP_TOLERANCE: u128 = 4;
+
+/// Intended differences in behaviour between hyperplane and SPL token-swap
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Divergence {
+    /// The owner trading fee is sent to a fees vault by hyperplane, SPL keeps it in the pool
+    /// vault and mints pool tokens worth the fee to the owner. The new pool source amount is
+    /// lower by exactly the owner fee.
+    OwnerFeesVault,
+    /// The withdraw fee is taken in trading tokens by hyperplane and in pool tokens by SPL. Both
+    /// redeem the same amounts for the burnt pool tokens, but the amounts received after the fee
+    /// differ by at most the value of one pool token.
+    WithdrawFeeInTradingTokens,
+    /// Hyperplane locks `MIN_LIQUIDITY` pool tokens when initializing a pool and rejects any
+    /// withdrawal below it, SPL lets all pool tokens be withdrawn.
+    MinimumLiquidity,
+    /// Hyperplane requires `MIN_AMP < amp < MAX_AMP`, SPL allows both bounds.
+    StableAmpBounds,
+    /// Hyperplane iterates up to 256 times and stops once D changes by at most 1, SPL iterates
+    /// up to 32 times until D no longer changes. Hyperplane also rounds `c` in two steps when
+    /// solving for y. The swapped amounts may differ by `STABLE_SWAP_TOLERANCE`.
+    StableNewtonSolver,
+    /// Hyperplane swaps nothing when the fees take the whole source amount, SPL still solves for
+    /// y and may pay out tokens from rounding or fail.
+    StableZeroInput,
+    /// One program fails the swap and the other swaps zero tokens, which both handlers reject.
+    StableZeroOutput,
+    /// When the solver for y rounds to zero tokens, hyperplane leaves 1 token in the destination
+    /// vault and SPL fails the swap.
+    StableDrainedPool,
+}
+
+const ALLOWED_DIVERGENCES: &[Divergence] = &[
+    Divergence::OwnerFeesVault,
+    Divergence::WithdrawFeeInTradingTokens,
+    Divergence::MinimumLiquidity,
+    Divergence::StableAmpBounds,
+    Divergence::StableNewtonSolver,
+    Divergence::StableZeroInput,
+    Divergence::StableZeroOutput,
+    Divergence::StableDrainedPool,
+];
+
+impl Divergence {
+    fn explains(&self, mismatch: &Mismatch) -> bool {
+        let is_stable = matches!(mismatch.curve_parameters, CurveParameters::Stable { .. });
+        match self {
+            Divergence::OwnerFeesVault => {
+                mismatch.field == Some(Field::NewPoolSourceAmount)
+                    && match (
+                        mismatch.hyperplane_value(Field::NewPoolSourceAmount),
+                        mismatch.hyperplane_value(Field::OwnerFee),
+                        mismatch.spl_value(Field::NewPoolSourceAmount),
+                    ) {
+                        (Some(hyperplane), Some(owner_fee), Some(spl)) => {
+                            hyperplane + owner_fee == spl
+                        }
+                        _ => false,
+                    }
+            }
+            Divergence::WithdrawFeeInTradingTokens => {
+                let pool_tokens = match mismatch.step {
+                    Step::Withdraw { pool_tokens } => u128::from(pool_tokens),
+                    _ => return false,
+                };
+                let (field, withdrawn_field) = match mismatch.field {
+                    Some(field @ Field::TokenAReceived) => (field, Field::TokenAWithdrawn),
+                    Some(field @ Field::TokenBReceived) => (field, Field::TokenBWithdrawn),
+                    _ => return false,
+                };
+                mismatch.fees.owner_withdraw_fee_numerator > 0
+                    && match (
+                        mismatch.hyperplane_value(withdrawn_field),
+                        mismatch.hyperplane_value(field),
+                        mismatch.spl_value(field),
+                    ) {
+                        (Some(withdrawn), Some(hyperplane), Some(spl)) => {
+                            let pool_token_value = (withdrawn + pool_tokens - 1) / pool_tokens;
+                            spl <= withdrawn && hyperplane <= spl + pool_token_value + 1
+                        }
+                        _ => false,
+                    }
+            }
+            Divergence::MinimumLiquidity => match mismatch.step {
+                Step::Withdraw { pool_tokens } => {
+                    mismatch.field.is_none()
+                        && mismatch.hyperplane.is_none()
+                        && mismatch
+                            .pool
+                            .pool_token_supply
+                            .checked_sub(u128::from(pool_tokens))
+                            .map_or(false, |supply| supply < MIN_LIQUIDITY)
+                }
+                _ => false,
+            },
+            Divergence::StableAmpBounds => {
+                matches!(mismatch.step, Step::Initialize { .. })
+                    && mismatch.hyperplane.is_none()
+                    && matches!(
+                        mismatch.curve_parameters,
+                        CurveParameters::Stable { amp, .. } if amp == MIN_AMP || amp == MAX_AMP
+                    )
+            }
+            Divergence::StableNewtonSolver => {
+                is_stable
+                    && match mismatch.field {
+                        Some(
+                            field @ (Field::DestinationAmountSwapped
+                            | Field::NewPoolDestinationAmount),
+                        ) => match (mismatch.hyperplane_value(field), mismatch.spl_value(field)) {
+                            (Some(hyperplane), Some(spl)) => {
+                                hyperplane.abs_diff(spl) <= STABLE_SWAP_TOLERANCE
+                            }
+                            _ => false,
+                        },
+                        _ => false,
+                    }
+            }
+            Divergence::StableZeroInput => {
+                let amount = match mismatch.step {
+                    Step::Swap { amount, .. } => u128::from(amount),
+                    _ => return false,
+                };
+                is_stable
+                    && mismatch.hyperplane_value(Field::DestinationAmountSwapped) == Some(0)
+                    && match (
+                        mismatch.hyperplane_value(Field::TradeFee),
+                        mismatch.hyperplane_value(Field::OwnerFee),
+                    ) {
+                        (Some(trade_fee), Some(owner_fee)) => amount == trade_fee + owner_fee,
+                        _ => false,
+                    }
+            }
+            Divergence::StableZeroOutput => {
+                is_stable
+                    && mismatch.field.is_none()
+                    && (mismatch.hyperplane_value(Field::DestinationAmountSwapped) == Some(0)
+                        || mismatch.spl_value(Field::DestinationAmountSwapped) == Some(0))
+            }
+            Divergence::StableDrainedPool => {
+                is_stable
+                    && mismatch.field.is_none()
+                    && mismatch.spl.is_none()
+                    && mismatch
+                        .hyperplane_value(Field::NewPoolDestinationAmount)
+                        .map_or(false, |amount| amount <= 1)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Step {
+    Initialize {
+        token_a_amount: u64,
+        token_b_amount: u64,
+    },
+    Swap {
+        amount: u64,
+        trade_direction: TradeDirection,
+    },
+    Deposit {
+        pool_tokens: u64,
+    },
+    Withdraw {
+        pool_tokens: u64,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Field {
+    PoolTokenSupply,
+    /// Source amount taken from the user, including fees
+    SourceAmountSwapped,
+    DestinationAmountSwapped,
+    TradeFee,
+    OwnerFee,
+    NewPoolSourceAmount,
+    NewPoolDestinationAmount,
+    TokenADeposited,
+    TokenBDeposited,
+    /// Value of the burnt pool tokens, before the withdraw fee
+    TokenAWithdrawn,
+    TokenBWithdrawn,
+    /// Amount sent to the user, after the withdraw fee
+    TokenAReceived,
+    TokenBReceived,
+}
+
+/// Values computed for a step, `None` when the step fails
+type Values = Option<Vec<(Field, u128)>>;
+
+#[derive(Clone, Copy, Debug)]
+struct Pool {
+    token_a_amount: u128,
+    token_b_amount: u128,
+    pool_token_supply: u128,
+}
+
+impl Pool {
+    fn source_and_destination(&self, trade_direction: TradeDirection) -> (u128, u128) {
+        match trade_direction {
+            TradeDirection::AtoB => (self.token_a_amount, self.token_b_amount),
+            TradeDirection::BtoA => (self.token_b_amount, self.token_a_amount),
+        }
+    }
+
+    fn with_source_and_destination(
+        self,
+        trade_direction: TradeDirection,
+        source_amount: u128,
+        destination_amount: u128,
+    ) -> Self {
+        let (token_a_amount, token_b_amount) = match trade_direction {
+            TradeDirection::AtoB => (source_amount, destination_amount),
+            TradeDirection::BtoA => (destination_amount, source_amount),
+        };
+        Self {
+            token_a_amount,
+            token_b_amount,
+            ..self
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Mismatch {
+    curve_parameters: CurveParameters,
+    fees: Fees,
+    pool: Pool,
+    step: Step,
+    /// `None` when one program failed the step and the other did not
+    field: Option<Field>,
+    hyperplane: Values,
+    spl: Values,
+}
+
+impl Mismatch {
+    fn hyperplane_value(&self, field: Field) -> Option<u128> {
+        value(&self.hyperplane, field)
+    }
+
+    fn spl_value(&self, field: Field) -> Option<u128> {
+        value(&self.spl, field)
+    }
+}
+
+fn value(values: &Values, field: Field) -> Option<u128> {
+    values
+        .as_ref()?
+        .iter()
+        .find(|(value_field, _)| *value_field == field)
+        .map(|(_, value)| *value)
+}
+
+struct Differential {
+    curve_parameters: CurveParameters,
+    fees: Fees,
+    hyperplane_curve: SwapCurve,
+    spl_curve: spl::base::SwapCurve,
+    spl_fees: spl::fees::Fees,
+}
+
+impl Differential {
+    fn new(curve_parameters: CurveParameters, fees: Fees) -> Self {
+        Self {
+            hyperplane_curve: SwapCurve::new_from_params(curve_parameters.clone()).unwrap(),
+            spl_curve: spl_curve(&curve_parameters),
+            spl_fees: spl_fees(&fees),
+            curve_parameters,
+            fees,
+        }
+    }
+
+    /// Run the steps against both programs and return the mismatches no allowed divergence
+    /// explains
+    fn run(&self, token_a_amount: u64, token_b_amount: u64, steps: &[Step]) -> Vec<Mismatch> {
+        let mut pool = Pool {
+            token_a_amount: 0,
+            token_b_amount: 0,
+            pool_token_supply: 0,
+        };
+        let initialize = Step::Initialize {
+            token_a_amount,
+            token_b_amount,
+        };
+        let mut unexplained = vec![];
+        for step in std::iter::once(&initialize).chain(steps) {
+            let (hyperplane, next_pool) = match self.hyperplane_step(&pool, *step) {
+                Some((values, next_pool)) => (Some(values), Some(next_pool)),
+                None => (None, None),
+            };
+            let spl = self.spl_step(&pool, *step);
+            unexplained.extend(
+                self.compare(&pool, *step, hyperplane, spl)
+                    .into_iter()
+                    .filter(|mismatch| {
+                        !ALLOWED_DIVERGENCES
+                            .iter()
+                            .any(|divergence| divergence.explains(mismatch))
+                    }),
+            );
+            match (step, next_pool) {
+                (_, Some(next_pool)) => pool = next_pool,
+                // nothing to run the steps against if the pool cannot be created
+                (Step::Initialize { .. }, None) => break,
+                (_, None) => {}
+            }
+        }
+        unexplained
+    }
+
+    fn compare(&self, pool: &Pool, step: Step, hyperplane: Values, spl: Values) -> Vec<Mismatch> {
+        let fields = match (&hyperplane, &spl) {
+            (None, None) => vec![],
+            (Some(hyperplane_values), Some(spl_values)) => hyperplane_values
+                .iter()
+                .zip(spl_values)
+                .filter(|((field, hyperplane_value), (spl_field, spl_value))| {
+                    assert_eq!(field, spl_field);
+                    hyperplane_value != spl_value
+                })
+                .map(|((field, _), _)| Some(*field))
+                .collect(),
+            _ => vec![None],
+        };
+        fields
+            .into_iter()
+            .map(|field| Mismatch {
+                curve_parameters: self.curve_parameters.clone(),
+                fees: self.fees,
+                pool: *pool,
+                step,
+                field,
+                hyperplane: hyperplane.clone(),
+                spl: spl.clone(),
+            })
+            .collect()
+    }
+
+    fn hyperplane_step(&self, pool: &Pool, step: Step) -> Option<(Vec<(Field, u128)>, Pool)> {
+        let calculator = &self.hyperplane_curve.calculator;
+        match step {
+            Step::Initialize {
+                token_a_amount,
+                token_b_amount,
+            } => {
+                calculator.validate().ok()?;
+                calculator
+                    .validate_supply(token_a_amount, token_b_amount)
+                    .ok()?;
+                let pool_token_supply = calculator.new_pool_supply();
+                if pool_token_supply <= MIN_LIQUIDITY {
+                    return None;
+                }
+                Some((
+                    vec![(Field::PoolTokenSupply, pool_token_supply)],
+                    Pool {
+                        token_a_amount: token_a_amount.into(),
+                        token_b_amount: token_b_amount.into(),
+                        pool_token_supply,
+                    },
+                ))
+            }
+            Step::Swap {
+                amount,
+                trade_direction,
+            } => {
+                let (pool_source_amount, pool_destination_amount) =
+                    pool.source_and_destination(trade_direction);
+                let result = self
+                    .hyperplane_curve
+                    .swap(
+                        amount.into(),
+                        pool_source_amount,
+                        pool_destination_amount,
+                        trade_direction,
+                        &self.fees,
+                    )
+                    .ok()?;
+                Some((
+                    vec![
+                        (
+                            Field::SourceAmountSwapped,
+                            result.total_source_amount_swapped,
+                        ),
+                        (
+                            Field::DestinationAmountSwapped,
+                            result.destination_amount_swapped,
+                        ),
+                        (Field::TradeFee, result.trade_fee),
+                        (Field::OwnerFee, result.owner_fee),
+                        (Field::NewPoolSourceAmount, result.new_pool_source_amount),
+                        (
+                            Field::NewPoolDestinationAmount,
+                            result.new_pool_destination_amount,
+                        ),
+                    ],
+                    pool.with_source_and_destination(
+                        trade_direction,
+                        result.new_pool_source_amount,
+                        result.new_pool_destination_amount,
+                    ),
+                ))
+            }
+            Step::Deposit { pool_tokens } => {
+                if !calculator.allows_deposits() {
+                    return None;
+                }
+                let results = calculator
+                    .pool_tokens_to_trading_tokens(
+                        pool_tokens.into(),
+                        pool.pool_token_supply,
+                        pool.token_a_amount,
+                        pool.token_b_amount,
+                        RoundDirection::Ceiling,
+                    )
+                    .ok()?;
+                Some((
+                    vec![
+                        (Field::TokenADeposited, results.token_a_amount),
+                        (Field::TokenBDeposited, results.token_b_amount),
+                    ],
+                    Pool {
+                        token_a_amount: pool.token_a_amount + results.token_a_amount,
+                        token_b_amount: pool.token_b_amount + results.token_b_amount,
+                        pool_token_supply: pool.pool_token_supply + u128::from(pool_tokens),
+                    },
+                ))
+            }
+            Step::Withdraw { pool_tokens } => {
+                let pool_token_supply = pool.pool_token_supply.checked_sub(pool_tokens.into())?;
+                if pool_token_supply < MIN_LIQUIDITY {
+                    return None;
+                }
+                let results = calculator
+                    .pool_tokens_to_trading_tokens(
+                        pool_tokens.into(),
+                        pool.pool_token_supply,
+                        pool.token_a_amount,
+                        pool.token_b_amount,
+                        RoundDirection::Floor,
+                    )
+                    .ok()?;
+                let token_a_withdrawn = results.token_a_amount.min(pool.token_a_amount);
+                let token_b_withdrawn = results.token_b_amount.min(pool.token_b_amount);
+                let token_a_fee = self.fees.owner_withdraw_fee(token_a_withdrawn).ok()?;
+                let token_b_fee = self.fees.owner_withdraw_fee(token_b_withdrawn).ok()?;
+                Some((
+                    vec![
+                        (Field::TokenAWithdrawn, token_a_withdrawn),
+                        (Field::TokenBWithdrawn, token_b_withdrawn),
+                        (
+                            Field::TokenAReceived,
+                            token_a_withdrawn.checked_sub(token_a_fee)?,
+                        ),
+                        (
+                            Field::TokenBReceived,
+                            token_b_withdrawn.checked_sub(token_b_fee)?,
+                        ),
+                    ],
+                    // the withdraw fees go to the fees vaults
+                    Pool {
+                        token_a_amount: pool.token_a_amount - token_a_withdrawn,
+                        token_b_amount: pool.token_b_amount - token_b_withdrawn,
+                        pool_token_supply,
+                    },
+                ))
+            }
+        }
+    }
+
+    fn spl_step(&self, pool: &Pool, step: Step) -> Values {
+        let calculator = &self.spl_curve.calculator;
+        match step {
+            Step::Initialize {
+                token_a_amount,
+                token_b_amount,
+            } => {
+                calculator.validate().ok()?;
+                calculator
+                    .validate_supply(token_a_amount, token_b_amount)
+                    .ok()?;
+                Some(vec![(Field::PoolTokenSupply, calculator.new_pool_supply())])
+            }
+            Step::Swap {
+                amount,
+                trade_direction,
+            } => {
+                let (pool_source_amount, pool_destination_amount) =
+                    pool.source_and_destination(trade_direction);
+                let result = self.spl_curve.swap(
+                    amount.into(),
+                    pool_source_amount,
+                    pool_destination_amount,
+                    spl_trade_direction(trade_direction),
+                    &self.spl_fees,
+                )?;
+                Some(vec![
+                    (Field::SourceAmountSwapped, result.source_amount_swapped),
+                    (
+                        Field::DestinationAmountSwapped,
+                        result.destination_amount_swapped,
+                    ),
+                    (Field::TradeFee, result.trade_fee),
+                    (Field::OwnerFee, result.owner_fee),
+                    (Field::NewPoolSourceAmount, result.new_swap_source_amount),
+                    (
+                        Field::NewPoolDestinationAmount,
+                        result.new_swap_destination_amount,
+                    ),
+                ])
+            }
+            Step::Deposit { pool_tokens } => {
+                if !calculator.allows_deposits() {
+                    return None;
+                }
+                let results = calculator.pool_tokens_to_trading_tokens(
+                    pool_tokens.into(),
+                    pool.pool_token_supply,
+                    pool.token_a_amount,
+                    pool.token_b_amount,
+                    spl::calculator::RoundDirection::Ceiling,
+                )?;
+                Some(vec![
+                    (Field::TokenADeposited, results.token_a_amount),
+                    (Field::TokenBDeposited, results.token_b_amount),
+                ])
+            }
+            Step::Withdraw { pool_tokens } => {
+                let pool_tokens = u128::from(pool_tokens);
+                if pool_tokens > pool.pool_token_supply {
+                    return None;
+                }
+                let withdrawn = self.spl_withdraw(pool, pool_tokens)?;
+                let withdraw_fee = self.spl_fees.owner_withdraw_fee(pool_tokens)?;
+                let received = self.spl_withdraw(pool, pool_tokens.checked_sub(withdraw_fee)?)?;
+                Some(vec![
+                    (Field::TokenAWithdrawn, withdrawn.0),
+                    (Field::TokenBWithdrawn, withdrawn.1),
+                    (Field::TokenAReceived, received.0),
+                    (Field::TokenBReceived, received.1),
+                ])
+            }
+        }
+    }
+
+    /// Token A and B amounts SPL pays out for burning the pool tokens
+    fn spl_withdraw(&self, pool: &Pool, pool_tokens: u128) -> Option<(u128, u128)> {
+        let results = self.spl_curve.calculator.pool_tokens_to_trading_tokens(
+            pool_tokens,
+            pool.pool_token_supply,
+            pool.token_a_amount,
+            pool.token_b_amount,
+            spl::calculator::RoundDirection::Floor,
+        )?;
+        Some((
+            results.token_a_amount.min(pool.token_a_amount),
+            results.token_b_amount.min(pool.token_b_amount),
+        ))
+    }
+}
+
+fn spl_curve(curve_parameters: &CurveParameters) -> spl::base::SwapCurve {
+    match *curve_parameters {
+        CurveParameters::ConstantProduct => spl::base::SwapCurve {
+            curve_type: spl::base::CurveType::ConstantProduct,
+            calculator: Arc::new(spl::constant_product::ConstantProductCurve {}),
+        },
+        CurveParameters::ConstantPrice { token_b_price } => spl::base::SwapCurve {
+            curve_type: spl::base::CurveType::ConstantPrice,
+            calculator: Arc::new(spl::constant_price::ConstantPriceCurve { token_b_price }),
+        },
+        CurveParameters::Offset { token_b_offset } => spl::base::SwapCurve {
+            curve_type: spl::base::CurveType::Offset,
+            calculator: Arc::new(spl::offset::OffsetCurve { token_b_offset }),
+        },
+        CurveParameters::Stable { amp, .. } => spl::base::SwapCurve {
+            curve_type: spl::base::CurveType::Stable,
+            calculator: Arc::new(spl::stable::StableCurve { amp }),
+        },
+    }
+}
+
+fn spl_fees(fees: &Fees) -> spl::fees::Fees {
+    spl::fees::Fees {
+        trade_fee_numerator: fees.trade_fee_numerator,
+        trade_fee_denominator: fees.trade_fee_denominator,
+        owner_trade_fee_numerator: fees.owner_trade_fee_numerator,
+        owner_trade_fee_denominator: fees.owner_trade_fee_denominator,
+        owner_withdraw_fee_numerator: fees.owner_withdraw_fee_numerator,
+        owner_withdraw_fee_denominator: fees.owner_withdraw_fee_denominator,
+        host_fee_numerator: fees.host_fee_numerator,
+        host_fee_denominator: fees.host_fee_denominator,
+    }
+}
+
+fn spl_trade_direction(trade_direction: TradeDirection) -> spl::calculator::TradeDirection {
+    match trade_direction {
+        TradeDirection::AtoB => spl::calculator::TradeDirection::AtoB,
+        TradeDirection::BtoA => spl::calculator::TradeDirection::BtoA,
+    }
+}
+
+fn check_steps(
+    curve_parameters: CurveParameters,
+    fees: Fees,
+    (token_a_amount, token_b_amount): (u64, u64),
+    steps: &[Step],
+) -> Result<(), TestCaseError> {
+    let unexplained =
+        Differential::new(curve_parameters, fees).run(token_a_amount, token_b_amount, steps);
+    prop_assert!(
+        unexplained.is_empty(),
+        "Unexplained divergences from SPL token-swap: {:#?}",
+        unexplained
+    );
+    Ok(())
+}
+
+fn fee_numerator() -> impl Strategy<Value = u64> {
+    prop_oneof![Just(0), 1..=100u64]
+}
+
+/// Fees of up to 1% each, host fees are paid out of the owner fee so they are left out
+fn fees() -> impl Strategy<Value = Fees> {
+    (fee_numerator(), fee_numerator(), fee_numerator()).prop_map(
+        |(trade_fee_numerator, owner_trade_fee_numerator, owner_withdraw_fee_numerator)| Fees {
+            trade_fee_numerator,
+            trade_fee_denominator: 10_000,
+            owner_trade_fee_numerator,
+            owner_trade_fee_denominator: 10_000,
+            owner_withdraw_fee_numerator,
+            owner_withdraw_fee_denominator: 10_000,
+            host_fee_numerator: 0,
+            host_fee_denominator: 0,
+        },
+    )
+}
+
+fn amount() -> impl Strategy<Value = u64> {
+    prop_oneof![1..=1_000u64, 1..=1_000_000u64, 1..=MAX_AMOUNT]
+}
+
+fn initial_supply() -> impl Strategy<Value = (u64, u64)> {
+    (amount(), amount())
+}
+
+fn pool_tokens() -> impl Strategy<Value = u64> {
+    prop_oneof![1..=1_000u64, 1..=MAX_POOL_TOKENS]
+}
+
+fn step() -> impl Strategy<Value = Step> {
+    prop_oneof![
+        (amount(), any::<bool>()).prop_map(|(amount, a_to_b)| Step::Swap {
+            amount,
+            trade_direction: if a_to_b {
+                TradeDirection::AtoB
+            } else {
+                TradeDirection::BtoA
+            },
+        }),
+        pool_tokens().prop_map(|pool_tokens| Step::Deposit { pool_tokens }),
+        pool_tokens().prop_map(|pool_tokens| Step::Withdraw { pool_tokens }),
+    ]
+}
+
+fn steps() -> impl Strategy<Value = Vec<Step>> {
+    prop::collection::vec(step(), 1..=MAX_STEPS)
+}
+
+proptest! {
+    #[test]
+    fn constant_product_matches_spl(
+        fees in fees(),
+        initial_supply in initial_supply(),
+        steps in steps(),
+    ) {
+        check_steps(CurveParameters::ConstantProduct, fees, initial_supply, &steps)?;
+    }
+}
+
+proptest! {
+    #[test]
+    fn constant_price_matches_spl(
+        token_b_price in 1..=1_000_000u64,
+        fees in fees(),
+        initial_supply in initial_supply(),
+        steps in steps(),
+    ) {
+        check_steps(
+            CurveParameters::ConstantPrice { token_b_price },
+            fees,
+            initial_supply,
+            &steps,
+        )?;
+    }
+}
+
+proptest! {
+    #[test]
+    fn offset_matches_spl(
+        token_b_offset in 1..=MAX_AMOUNT,
+        fees in fees(),
+        initial_supply in initial_supply(),
+        steps in steps(),
+    ) {
+        check_steps(
+            CurveParameters::Offset { token_b_offset },
+            fees,
+            initial_supply,
+            &steps,
+        )?;
+    }
+}
+
+proptest! {
+    #[test]
+    fn stable_matches_spl(
+        amp in prop_oneof![Just(MIN_AMP), Just(MAX_AMP), MIN_AMP..=MAX_AMP],
+        fees in fees(),
+        initial_supply in initial_supply(),
+        steps in steps(),
+    ) {
+        check_steps(
+            CurveParameters::Stable {
+                amp,
+                token_a_decimals: STABLE_DECIMALS,
+                token_b_decimals: STABLE_DECIMALS,
+            },
+            fees,
+            initial_supply,
+            &steps,
+        )?;
+    }
+}
@@ -0,0 +1,386 @@
+mod common;
+
+use anchor_lang::prelude::{ErrorCode, Pubkey};
+use common::{client, runner};
+use hyperplane::{
+    curve::{
+        calculator::{AorB, TradeDirection},
+        fees::Fees,
+    },
+    error::SwapError,
+    ix::{InitializeFeeRecipients, Swap, UpdateFeeRecipients},
+    state::FeeRecipient,
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+use solana_sdk::signature::Signer;
+
+use crate::common::{
+    fixtures,
+    fixtures::Sol,
+    setup,
+    setup::{kp, new_keypair},
+    token_operations,
+    types::{SwapPairSpec, SwapPoolAccounts, TestContext},
+};
+
+async fn new_pool_with_fees(ctx: &mut TestContext) -> SwapPoolAccounts {
+    let pool = fixtures::new_pool(
+        ctx,
+        Fees {
+            host_fee_numerator: 1,
+            host_fee_denominator: 100,
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 100,
+            owner_withdraw_fee_numerator: 1,
+            owner_withdraw_fee_denominator: 100,
+        },
+        InitialSupply::new(10_000_000, 10_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::Stable { amp: 100 },
+    )
+    .await;
+
+    let user = setup::new_pool_user(ctx, &pool, (1_000_000, 1_000_000)).await;
+    client::swap(
+        ctx,
+        &pool,
+        &user,
+        TradeDirection::AtoB,
+        Swap::new(1_000_000, 970_000),
+    )
+    .await
+    .unwrap();
+    client::swap(
+        ctx,
+        &pool,
+        &user,
+        TradeDirection::BtoA,
+        Swap::new(1_000_000, 970_000),
+    )
+    .await
+    .unwrap();
+
+    pool
+}
+
+/// Creates a token account for each recipient for both of the pool's trading tokens
+async fn new_recipient_token_accounts(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    recipients: &[FeeRecipient],
+) -> (Vec<Pubkey>, Vec<Pubkey>) {
+    let mut token_a_accounts = vec![];
+    let mut token_b_accounts = vec![];
+    for recipient in recipients {
+        token_a_accounts.push(
+            token_operations::create_token_account(
+                ctx,
+                &pool.token_a_token_program,
+                &pool.token_a_mint,
+                &recipient.owner,
+            )
+            .await
+            .unwrap(),
+        );
+        token_b_accounts.push(
+            token_operations::create_token_account(
+                ctx,
+                &pool.token_b_token_program,
+                &pool.token_b_mint,
+                &recipient.owner,
+            )
+            .await
+            .unwrap(),
+        );
+    }
+    (token_a_accounts, token_b_accounts)
+}
+
+fn fee_recipient(share_bps: u64) -> FeeRecipient {
+    FeeRecipient {
+        owner: kp().pubkey(),
+        share_bps,
+    }
+}
+
+#[tokio::test]
+pub async fn test_successful_distribute_fees() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool_with_fees(&mut ctx).await;
+
+    let recipients = vec![
+        fee_recipient(5_000),
+        fee_recipient(3_333),
+        fee_recipient(1_667),
+    ];
+    let (token_a_accounts, token_b_accounts) =
+        new_recipient_token_accounts(&mut ctx, &pool, &recipients).await;
+    client::initialize_fee_recipients(
+        &mut ctx,
+        &pool,
+        InitializeFeeRecipients::new(recipients.clone()),
+    )
+    .await
+    .unwrap();
+
+    for (a_or_b, fees_vault, recipient_token_accounts) in [
+        (AorB::A, pool.token_a_fees_vault, &token_a_accounts),
+        (AorB::B, pool.token_b_fees_vault, &token_b_accounts),
+    ] {
+        let fees_vault_balance = token_operations::balance(&mut ctx, &fees_vault).await;
+        assert!(fees_vault_balance > 0);
+
+        client::distribute_fees(&mut ctx, &pool, a_or_b, recipient_token_accounts)
+            .await
+            .unwrap();
+
+        let mut distributed = 0;
+        for (recipient, token_account) in recipients.iter().zip(recipient_token_accounts) {
+            let expected = fees_vault_balance * recipient.share_bps / 10_000;
+            assert_eq!(
+                token_operations::balance(&mut ctx, token_account).await,
+                expected
+            );
+            distributed += expected;
+        }
+        // dust from rounding down stays in the vault
+        assert_eq!(
+            token_operations::balance(&mut ctx, &fees_vault).await,
+            fees_vault_balance - distributed
+        );
+        assert!(fees_vault_balance - distributed < recipients.len() as u64);
+    }
+}
+
+#[tokio::test]
+pub async fn test_successful_distribute_fees_after_update() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool_with_fees(&mut ctx).await;
+
+    let treasury = fee_recipient(10_000);
+    client::initialize_fee_recipients(
+        &mut ctx,
+        &pool,
+        InitializeFeeRecipients::new(vec![treasury]),
+    )
+    .await
+    .unwrap();
+
+    let recipients = vec![
+        FeeRecipient {
+            share_bps: 8_000,
+            ..treasury
+        },
+        fee_recipient(2_000),
+    ];
+    client::update_fee_recipients(
+        &mut ctx,
+        &pool,
+        UpdateFeeRecipients::new(recipients.clone()),
+    )
+    .await
+    .unwrap();
+
+    let (token_a_accounts, _) = new_recipient_token_accounts(&mut ctx, &pool, &recipients).await;
+    let fees_vault_balance = token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await;
+
+    client::distribute_fees(&mut ctx, &pool, AorB::A, &token_a_accounts)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        token_operations::balance(&mut ctx, &token_a_accounts[0]).await,
+        fees_vault_balance * 8_000 / 10_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &token_a_accounts[1]).await,
+        fees_vault_balance * 2_000 / 10_000
+    );
+}
+
+#[tokio::test]
+pub async fn test_initialize_fee_recipients_invalid_shares_fails() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool_with_fees(&mut ctx).await;
+
+    assert_eq!(
+        client::initialize_fee_recipients(
+            &mut ctx,
+            &pool,
+            InitializeFeeRecipients::new(vec![fee_recipient(5_000), fee_recipient(4_000)]),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::InvalidFeeRecipients)
+    );
+
+    client::initialize_fee_recipients(
+        &mut ctx,
+        &pool,
+        InitializeFeeRecipients::new(vec![fee_recipient(10_000)]),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        client::update_fee_recipients(
+            &mut ctx,
+            &pool,
+            UpdateFeeRecipients::new(vec![fee_recipient(5_000), fee_recipient(6_000)]),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::InvalidFeeRecipients)
+    );
+}
+
+#[tokio::test]
+pub async fn test_security_distribute_fees() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool_with_fees(&mut ctx).await;
+
+    let recipients = vec![fee_recipient(6_000), fee_recipient(4_000)];
+    let (token_a_accounts, token_b_accounts) =
+        new_recipient_token_accounts(&mut ctx, &pool, &recipients).await;
+
+    // wrong admin
+    {
+        let mut cloned_pool = pool.clone();
+        cloned_pool.admin.admin = new_keypair(&mut ctx, Sol::one()).await;
+
+        assert_eq!(
+            client::initialize_fee_recipients(
+                &mut ctx,
+                &cloned_pool,
+                InitializeFeeRecipients::new(recipients.clone()),
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+            anchor_error!(ErrorCode::ConstraintHasOne)
+        );
+    }
+
+    client::initialize_fee_recipients(
+        &mut ctx,
+        &pool,
+        InitializeFeeRecipients::new(recipients.clone()),
+    )
+    .await
+    .unwrap();
+
+    // wrong admin on update
+    {
+        let mut cloned_pool = pool.clone();
+        cloned_pool.admin.admin = new_keypair(&mut ctx, Sol::one()).await;
+
+        assert_eq!(
+            client::update_fee_recipients(
+                &mut ctx,
+                &cloned_pool,
+                UpdateFeeRecipients::new(vec![fee_recipient(10_000)]),
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+            anchor_error!(ErrorCode::ConstraintHasOne)
+        );
+    }
+
+    // wrong pool_authority
+    {
+        let mut cloned_pool = pool.clone();
+        cloned_pool.authority = kp().pubkey();
+
+        assert_eq!(
+            client::distribute_fees(&mut ctx, &cloned_pool, AorB::A, &token_a_accounts)
+                .await
+                .unwrap_err()
+                .unwrap(),
+            hyperplane_error!(SwapError::InvalidProgramAddress)
+        );
+    }
+
+    // wrong fees_vault
+    {
+        let mut cloned_pool = pool.clone();
+        cloned_pool.token_a_fees_vault = pool.token_a_vault;
+
+        assert_eq!(
+            client::distribute_fees(&mut ctx, &cloned_pool, AorB::A, &token_a_accounts)
+                .await
+                .unwrap_err()
+                .unwrap(),
+            hyperplane_error!(SwapError::IncorrectFeeAccount)
+        );
+    }
+
+    // missing recipient token account
+    {
+        assert_eq!(
+            client::distribute_fees(&mut ctx, &pool, AorB::A, &token_a_accounts[..1])
+                .await
+                .unwrap_err()
+                .unwrap(),
+            hyperplane_error!(SwapError::IncorrectFeeRecipientAccount)
+        );
+    }
+
+    // recipient token accounts in the wrong order
+    {
+        let reversed: Vec<Pubkey> = token_a_accounts.iter().rev().copied().collect();
+
+        assert_eq!(
+            client::distribute_fees(&mut ctx, &pool, AorB::A, &reversed)
+                .await
+                .unwrap_err()
+                .unwrap(),
+            hyperplane_error!(SwapError::IncorrectFeeRecipientAccount)
+        );
+    }
+
+    // recipient token account with the wrong mint
+    {
+        assert_eq!(
+            client::distribute_fees(&mut ctx, &pool, AorB::A, &token_b_accounts)
+                .await
+                .unwrap_err()
+                .unwrap(),
+            hyperplane_error!(SwapError::IncorrectFeeRecipientAccount)
+        );
+    }
+
+    // recipient token account with the wrong owner
+    {
+        let wrong_owner_account = token_operations::create_token_account(
+            &mut ctx,
+            &pool.token_a_token_program,
+            &pool.token_a_mint,
+            &kp().pubkey(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            client::distribute_fees(
+                &mut ctx,
+                &pool,
+                AorB::A,
+                &[token_a_accounts[0], wrong_owner_account],
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+            hyperplane_error!(SwapError::IncorrectFeeRecipientAccount)
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! Reproduction of a fuzzer failure, move to `programs/hyperplane/tests` to run
+//!
+//! UnexpectedError: example of a rendered reproduction after instruction 1 - Some(Swap { token_a_id: 0, token_b_id: 0, trade_direction: BtoA, instruction: Swap { amount_in: 100000, minimum_amount_out: 18446744073709551615 } })
+#![allow(unused_imports)]
+#![cfg_attr(rustfmt, rustfmt::skip)]
+
+mod common;
+
+use anchor_spl::{token::spl_token, token_2022::spl_token_2022};
+use common::{client, runner};
+use hyperplane::{
+    curve::{
+        base::SwapCurve,
+        calculator::{AorB, TradeDirection},
+        fees::Fees,
+    },
+    ix::{Deposit, Initialize, Swap, UpdatePoolConfig, Withdraw, WithdrawFees},
+    model::CurveParameters,
+    state::{UpdatePoolConfigMode, UpdatePoolConfigValue},
+    InitialSupply,
+};
+use solana_program_test::tokio;
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+use spl_math::precise_number::PreciseNumber;
+
+use crate::common::{
+    fixtures::Sol,
+    setup, token_operations,
+    types::{PoolUserAccounts, SwapPairSpec, SwapPoolAccounts, TestContext, TokenSpec},
+};
+
+#[tokio::test]
+pub async fn test_fuzz_repro_example() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let curve_parameters = CurveParameters::ConstantProduct;
+    let swap_curve = SwapCurve::new_from_params(curve_parameters.clone()).unwrap();
+    let initial_supply = InitialSupply::new(100000000000, 300000000000);
+    let pool = setup::new_pool_accs(
+        &mut ctx,
+        SwapPairSpec::new(
+            TokenSpec::new(6, 0, spl_token::id()),
+            TokenSpec::new(8, 0, spl_token_2022::id()),
+        ),
+        &initial_supply,
+    )
+    .await;
+    client::initialize_pool(
+        &mut ctx,
+        &pool,
+        Initialize {
+            fees: Fees { trade_fee_numerator: 25, trade_fee_denominator: 10000, owner_trade_fee_numerator: 5, owner_trade_fee_denominator: 10000, owner_withdraw_fee_numerator: 30, owner_withdraw_fee_denominator: 10000, host_fee_numerator: 1, host_fee_denominator: 5 },
+            initial_supply,
+            curve_parameters: curve_parameters.into(),
+            min_liquidity: 1000,
+        },
+    )
+    .await
+    .unwrap();
+
+    let user = setup::new_keypair(&mut ctx, Sol::one()).await;
+    let token_a_0 = token_operations::create_and_mint_to_token_account(&mut ctx, &pool.token_a_token_program, &user.pubkey(), &pool.token_a_mint, 1000000000).await;
+    let token_b_0 = token_operations::create_and_mint_to_token_account(&mut ctx, &pool.token_b_token_program, &user.pubkey(), &pool.token_b_mint, 3000000000).await;
+    let host_fees = PoolUserAccounts::from(pool.admin.clone());
+
+    let mut state = State::load(&mut ctx, &pool, &swap_curve).await;
+
+    // 0: Swap { token_a_id: 0, token_b_id: 0, trade_direction: AtoB, instruction: Swap { amount_in: 100000, minimum_amount_out: 0 } }
+    client::swap_with_host_fees(&mut ctx, &pool, &PoolUserAccounts::new(user.clone(), token_a_0, token_b_0, Pubkey::default()), Some(&host_fees), TradeDirection::AtoB, Swap::new(100000, 0))
+        .await
+        .unwrap();
+    let before = state;
+    state = State::load(&mut ctx, &pool, &swap_curve).await;
+    state.check(&before, false);
+
+    // 1: Swap { token_a_id: 0, token_b_id: 0, trade_direction: BtoA, instruction: Swap { amount_in: 100000, minimum_amount_out: 18446744073709551615 } }
+    client::swap_with_host_fees(&mut ctx, &pool, &PoolUserAccounts::new(user.clone(), token_a_0, token_b_0, Pubkey::default()), Some(&host_fees), TradeDirection::BtoA, Swap::new(100000, 18446744073709551615))
+        .await
+        .unwrap_err();
+    let before = state;
+    state = State::load(&mut ctx, &pool, &swap_curve).await;
+    state.check(&before, false);
+}
+
+/// Pool state the fuzzer checks its invariants against. Vault balances, the value
+/// withdrawn per pool token account and the final withdrawal are only checked by the fuzzer.
+struct State {
+    value_per_pool_token: PreciseNumber,
+    token_a_fees: u64,
+    token_b_fees: u64,
+}
+
+impl State {
+    async fn load(ctx: &mut TestContext, pool: &SwapPoolAccounts, swap_curve: &SwapCurve) -> Self {
+        let token_a_amount = token_operations::balance(ctx, &pool.token_a_vault).await;
+        let token_b_amount = token_operations::balance(ctx, &pool.token_b_vault).await;
+        let pool_token_supply = token_operations::supply(ctx, &pool.pool_token_mint).await;
+        Self {
+            value_per_pool_token: swap_curve
+                .calculator
+                .normalized_value(token_a_amount.into(), token_b_amount.into())
+                .unwrap()
+                .checked_div(&PreciseNumber::new(pool_token_supply.into()).unwrap())
+                .unwrap(),
+            token_a_fees: token_operations::balance(ctx, &pool.token_a_fees_vault).await,
+            token_b_fees: token_operations::balance(ctx, &pool.token_b_fees_vault).await,
+        }
+    }
+
+    fn check(&self, before: &State, withdraws_fees: bool) {
+        assert!(
+            before
+                .value_per_pool_token
+                .less_than_or_equal(&self.value_per_pool_token),
+            "value per pool token decreased"
+        );
+        if !withdraws_fees {
+            assert!(self.token_a_fees >= before.token_a_fees, "token a fees vault decreased");
+            assert!(self.token_b_fees >= before.token_b_fees, "token b fees vault decreased");
+        }
+    }
+}
@@ -2,7 +2,11 @@ mod common;
 
 use common::{client, runner};
 use hyperplane::{
-    curve::{base::CurveType, calculator::INITIAL_SWAP_POOL_AMOUNT, fees::Fees},
+    curve::{
+        base::CurveType,
+        calculator::{INITIAL_SWAP_POOL_AMOUNT, MIN_LIQUIDITY},
+        fees::Fees,
+    },
     error::SwapError,
     utils::seeds,
     CurveUserParameters, InitialSupply,
@@ -66,7 +70,14 @@ pub async fn test_success_init_swap_pool() {
 
     let admin_pool_token_balance =
         token_operations::balance(&mut ctx, &pool.admin.pool_token_ata.pubkey()).await;
-    assert_eq!(admin_pool_token_balance, INITIAL_SWAP_POOL_AMOUNT as u64);
+    assert_eq!(
+        admin_pool_token_balance,
+        (INITIAL_SWAP_POOL_AMOUNT - MIN_LIQUIDITY) as u64
+    );
+    let min_liquidity_balance =
+        token_operations::balance(&mut ctx, &pool.min_liquidity_vault).await;
+    assert_eq!(min_liquidity_balance, MIN_LIQUIDITY as u64);
+    assert_eq!(pool_state.min_liquidity, MIN_LIQUIDITY as u64);
 }
 
 #[tokio::test]
@@ -95,6 +106,7 @@ pub async fn test_initialize_pool_with_same_token_a_and_b() {
                 fees: Fees::default(),
                 initial_supply,
                 curve_parameters: CurveUserParameters::Stable { amp: 100 },
+                min_liquidity: MIN_LIQUIDITY as u64,
             },
         )
         .await
@@ -130,6 +142,7 @@ pub async fn test_initialize_pool_with_same_token_b_and_a() {
                 fees: Fees::default(),
                 initial_supply,
                 curve_parameters: CurveUserParameters::Stable { amp: 100 },
+                min_liquidity: MIN_LIQUIDITY as u64,
             },
         )
         .await
@@ -138,3 +151,65 @@ pub async fn test_initialize_pool_with_same_token_b_and_a() {
         hyperplane_error!(SwapError::RepeatedMint, 1)
     )
 }
+
+#[tokio::test]
+pub async fn test_success_init_swap_pool_with_custom_min_liquidity() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let initial_supply = InitialSupply::new(100, 100);
+    let pool =
+        setup::new_pool_accs(&mut ctx, SwapPairSpec::spl_tokens(6, 9), &initial_supply).await;
+    client::initialize_pool(
+        &mut ctx,
+        &pool,
+        hyperplane::ix::Initialize {
+            fees: Fees::default(),
+            initial_supply,
+            curve_parameters: CurveUserParameters::ConstantProduct,
+            min_liquidity: 1,
+        },
+    )
+    .await
+    .unwrap();
+
+    let pool_state = state::get_pool(&mut ctx, &pool).await;
+    assert_eq!(pool_state.min_liquidity, 1);
+    let min_liquidity_balance =
+        token_operations::balance(&mut ctx, &pool.min_liquidity_vault).await;
+    assert_eq!(min_liquidity_balance, 1);
+    let admin_pool_token_balance =
+        token_operations::balance(&mut ctx, &pool.admin.pool_token_ata.pubkey()).await;
+    assert_eq!(
+        admin_pool_token_balance,
+        (INITIAL_SWAP_POOL_AMOUNT - 1) as u64
+    );
+}
+
+#[tokio::test]
+pub async fn test_initialize_pool_with_invalid_min_liquidity() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    for min_liquidity in [0, INITIAL_SWAP_POOL_AMOUNT as u64] {
+        let initial_supply = InitialSupply::new(100, 100);
+        let pool =
+            setup::new_pool_accs(&mut ctx, SwapPairSpec::spl_tokens(6, 9), &initial_supply).await;
+        assert_eq!(
+            client::initialize_pool(
+                &mut ctx,
+                &pool,
+                hyperplane::ix::Initialize {
+                    fees: Fees::default(),
+                    initial_supply,
+                    curve_parameters: CurveUserParameters::ConstantProduct,
+                    min_liquidity,
+                },
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+            hyperplane_error!(SwapError::InvalidMinLiquidity, 1)
+        );
+    }
+}
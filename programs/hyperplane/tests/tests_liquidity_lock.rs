@@ -0,0 +1,238 @@
+mod common;
+
+use anchor_lang::prelude::ErrorCode;
+use common::{client, runner};
+use hyperplane::{
+    curve::fees::Fees, error::SwapError, ix::LockLiquidity, CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+
+use crate::common::{
+    fixtures, setup, state, token_operations,
+    types::{PoolUserAccounts, SwapPairSpec, SwapPoolAccounts, TestContext},
+    utils,
+};
+
+async fn new_pool(ctx: &mut TestContext) -> SwapPoolAccounts {
+    fixtures::new_pool(
+        ctx,
+        Fees::default(),
+        InitialSupply::new(10_000_000, 10_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await
+}
+
+#[tokio::test]
+pub async fn test_successful_linear_vesting_lock() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool(&mut ctx).await;
+    let admin: PoolUserAccounts = pool.admin.clone().into();
+
+    let admin_pool_tokens = token_operations::balance(&mut ctx, &admin.pool_token_ata).await;
+    let now = utils::unix_timestamp(&mut ctx).await;
+    client::lock_liquidity(
+        &mut ctx,
+        &pool,
+        &admin,
+        LockLiquidity::new(0, 1_000_000, now + 100, now + 1_100),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        token_operations::balance(&mut ctx, &admin.pool_token_ata).await,
+        admin_pool_tokens - 1_000_000
+    );
+    let liquidity_lock = state::get_liquidity_lock(&mut ctx, &pool, &admin.pubkey(), 0).await;
+    assert_eq!(liquidity_lock.locked_amount, 1_000_000);
+    assert_eq!(
+        token_operations::balance(&mut ctx, &liquidity_lock.lock_vault).await,
+        1_000_000
+    );
+
+    // nothing is vested before the vesting starts
+    utils::warp_seconds(&mut ctx, 50).await;
+    assert_eq!(
+        client::unlock_liquidity(&mut ctx, &pool, &admin, 0)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::LiquidityLocked)
+    );
+
+    // a quarter of the vesting period has passed
+    utils::warp_seconds(&mut ctx, 300).await;
+    client::unlock_liquidity(&mut ctx, &pool, &admin, 0)
+        .await
+        .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &admin.pool_token_ata).await,
+        admin_pool_tokens - 750_000
+    );
+
+    // everything is vested after the vesting ends
+    utils::warp_seconds(&mut ctx, 10_000).await;
+    client::unlock_liquidity(&mut ctx, &pool, &admin, 0)
+        .await
+        .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &admin.pool_token_ata).await,
+        admin_pool_tokens
+    );
+    let liquidity_lock = state::get_liquidity_lock(&mut ctx, &pool, &admin.pubkey(), 0).await;
+    assert_eq!(liquidity_lock.unlocked_amount, 1_000_000);
+    assert_eq!(
+        token_operations::balance(&mut ctx, &liquidity_lock.lock_vault).await,
+        0
+    );
+}
+
+#[tokio::test]
+pub async fn test_successful_cliff_locks() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool(&mut ctx).await;
+    let lp = setup::new_lp_user(&mut ctx, &pool, (100_000, 100_000)).await;
+
+    let lp_pool_tokens = token_operations::balance(&mut ctx, &lp.pool_token_ata).await;
+    let now = utils::unix_timestamp(&mut ctx).await;
+    client::lock_liquidity(
+        &mut ctx,
+        &pool,
+        &lp,
+        LockLiquidity::new(0, 1_000, now + 500, now + 500),
+    )
+    .await
+    .unwrap();
+    client::lock_liquidity(
+        &mut ctx,
+        &pool,
+        &lp,
+        LockLiquidity::new(1, 2_000, now + 1_000, now + 1_000),
+    )
+    .await
+    .unwrap();
+
+    utils::warp_seconds(&mut ctx, 499).await;
+    assert_eq!(
+        client::unlock_liquidity(&mut ctx, &pool, &lp, 0)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::LiquidityLocked)
+    );
+
+    // only the first lock is unlocked at its unlock time
+    utils::warp_seconds(&mut ctx, 1).await;
+    client::unlock_liquidity(&mut ctx, &pool, &lp, 0)
+        .await
+        .unwrap();
+    assert_eq!(
+        client::unlock_liquidity(&mut ctx, &pool, &lp, 1)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::LiquidityLocked)
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &lp.pool_token_ata).await,
+        lp_pool_tokens - 2_000
+    );
+
+    utils::warp_seconds(&mut ctx, 500).await;
+    client::unlock_liquidity(&mut ctx, &pool, &lp, 1)
+        .await
+        .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &lp.pool_token_ata).await,
+        lp_pool_tokens
+    );
+}
+
+#[tokio::test]
+pub async fn test_lock_invalid_schedule_fails() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool(&mut ctx).await;
+    let admin: PoolUserAccounts = pool.admin.clone().into();
+    let now = utils::unix_timestamp(&mut ctx).await;
+
+    // zero amount
+    assert_eq!(
+        client::lock_liquidity(
+            &mut ctx,
+            &pool,
+            &admin,
+            LockLiquidity::new(0, 0, now, now + 100)
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::InvalidLiquidityLock)
+    );
+
+    // vesting ends before it starts
+    assert_eq!(
+        client::lock_liquidity(
+            &mut ctx,
+            &pool,
+            &admin,
+            LockLiquidity::new(0, 1_000, now + 100, now + 50)
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::InvalidLiquidityLock)
+    );
+
+    // vesting already ended
+    assert_eq!(
+        client::lock_liquidity(
+            &mut ctx,
+            &pool,
+            &admin,
+            LockLiquidity::new(0, 1_000, now - 100, now)
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::InvalidLiquidityLock)
+    );
+}
+
+#[tokio::test]
+pub async fn test_security_unlock_liquidity() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool(&mut ctx).await;
+    let admin: PoolUserAccounts = pool.admin.clone().into();
+    let now = utils::unix_timestamp(&mut ctx).await;
+
+    client::lock_liquidity(
+        &mut ctx,
+        &pool,
+        &admin,
+        LockLiquidity::new(0, 1_000, now, now + 100),
+    )
+    .await
+    .unwrap();
+    utils::warp_seconds(&mut ctx, 100).await;
+
+    // unlock to a pool token account not owned by the lock owner
+    {
+        let bob = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+        let mut cloned_admin = admin.clone();
+        cloned_admin.pool_token_ata = bob.pool_token_ata;
+
+        assert_eq!(
+            client::unlock_liquidity(&mut ctx, &pool, &cloned_admin, 0)
+                .await
+                .unwrap_err()
+                .unwrap(),
+            anchor_error!(ErrorCode::ConstraintTokenOwner)
+        );
+    }
+}
@@ -0,0 +1,320 @@
+mod common;
+
+use anchor_lang::prelude::{ErrorCode, Pubkey};
+use common::{client, runner};
+use hyperplane::{
+    curve::fees::Fees,
+    error::SwapError,
+    ix::{AddReward, FundRewards, SetRewardEmissions, StakeLp, UnstakeLp},
+    CurveUserParameters, InitialSupply,
+};
+use solana_program_test::tokio::{self};
+use solana_sdk::signature::Signer;
+
+use crate::common::{
+    fixtures,
+    fixtures::Sol,
+    setup,
+    setup::{kp, new_keypair},
+    state, token_operations,
+    types::{
+        PoolUserAccounts, RewardAccounts, SwapPairSpec, SwapPoolAccounts, TestContext, TokenSpec,
+    },
+    utils,
+};
+
+async fn new_pool_with_reward_vault(ctx: &mut TestContext) -> SwapPoolAccounts {
+    let pool = fixtures::new_pool(
+        ctx,
+        Fees::default(),
+        InitialSupply::new(10_000_000, 10_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    client::initialize_reward_vault(ctx, &pool).await.unwrap();
+    pool
+}
+
+async fn new_staker(
+    ctx: &mut TestContext,
+    pool: &SwapPoolAccounts,
+    user: PoolUserAccounts,
+) -> PoolUserAccounts {
+    client::initialize_lp_stake(ctx, pool, &user).await.unwrap();
+    user
+}
+
+async fn new_reward_ata(
+    ctx: &mut TestContext,
+    user: &PoolUserAccounts,
+    reward: &RewardAccounts,
+) -> Pubkey {
+    token_operations::create_token_account(ctx, &reward.token_program, &reward.mint, &user.pubkey())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+pub async fn test_successful_liquidity_mining() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool_with_reward_vault(&mut ctx).await;
+
+    let reward = setup::new_reward(&mut ctx, &pool, TokenSpec::default(), 100).await;
+    client::fund_rewards(&mut ctx, &pool, &reward, FundRewards::new(25_000))
+        .await
+        .unwrap();
+
+    let alice = new_staker(&mut ctx, &pool, pool.admin.clone().into()).await;
+    let bob_lp = setup::new_lp_user(&mut ctx, &pool, (100_000, 100_000)).await;
+    let bob = new_staker(&mut ctx, &pool, bob_lp).await;
+    let alice_reward_ata = new_reward_ata(&mut ctx, &alice, &reward).await;
+    let bob_reward_ata = new_reward_ata(&mut ctx, &bob, &reward).await;
+
+    let alice_pool_tokens = token_operations::balance(&mut ctx, &alice.pool_token_ata).await;
+    let bob_pool_tokens = token_operations::balance(&mut ctx, &bob.pool_token_ata).await;
+
+    // nothing is emitted before anything is staked
+    utils::warp_seconds(&mut ctx, 1_000).await;
+
+    client::stake_lp(&mut ctx, &pool, &alice, StakeLp::new(1_000_000))
+        .await
+        .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &alice.pool_token_ata).await,
+        alice_pool_tokens - 1_000_000
+    );
+
+    // alice earns all of the emissions alone
+    utils::warp_seconds(&mut ctx, 100).await;
+    client::stake_lp(&mut ctx, &pool, &bob, StakeLp::new(3_000_000))
+        .await
+        .unwrap();
+
+    // alice earns 1/4 of the emissions, bob 3/4
+    utils::warp_seconds(&mut ctx, 100).await;
+    client::claim_rewards(&mut ctx, &pool, &alice, &reward, &alice_reward_ata)
+        .await
+        .unwrap();
+    client::claim_rewards(&mut ctx, &pool, &bob, &reward, &bob_reward_ata)
+        .await
+        .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &alice_reward_ata).await,
+        10_000 + 2_500
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &bob_reward_ata).await,
+        7_500
+    );
+
+    client::unstake_lp(&mut ctx, &pool, &bob, UnstakeLp::new(3_000_000))
+        .await
+        .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &bob.pool_token_ata).await,
+        bob_pool_tokens
+    );
+    assert_eq!(
+        state::get_lp_stake(&mut ctx, &pool, &bob.pubkey())
+            .await
+            .staked_amount,
+        0
+    );
+    let reward_vault = state::get_reward_vault(&mut ctx, &pool).await;
+    assert_eq!(reward_vault.total_staked, 1_000_000);
+    assert_eq!(
+        token_operations::balance(&mut ctx, &reward_vault.staked_lp_vault).await,
+        1_000_000
+    );
+
+    // nothing is emitted while the emissions are paused
+    client::set_reward_emissions(&mut ctx, &pool, &reward, SetRewardEmissions::new(0))
+        .await
+        .unwrap();
+    utils::warp_seconds(&mut ctx, 100).await;
+    client::set_reward_emissions(&mut ctx, &pool, &reward, SetRewardEmissions::new(100))
+        .await
+        .unwrap();
+
+    // emissions are capped by the remaining funded rewards
+    utils::warp_seconds(&mut ctx, 1_000).await;
+    let alice_other_reward_ata = new_reward_ata(&mut ctx, &alice, &reward).await;
+    client::claim_rewards(&mut ctx, &pool, &alice, &reward, &alice_other_reward_ata)
+        .await
+        .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &alice_other_reward_ata).await,
+        5_000
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &reward_vault.rewards[0].vault).await,
+        0
+    );
+}
+
+#[tokio::test]
+pub async fn test_successful_multiple_rewards() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool_with_reward_vault(&mut ctx).await;
+
+    let reward_1 = setup::new_reward(&mut ctx, &pool, TokenSpec::default(), 10).await;
+    let reward_2 = setup::new_reward(&mut ctx, &pool, TokenSpec::transfer_fees(100), 1_000).await;
+    client::fund_rewards(&mut ctx, &pool, &reward_1, FundRewards::new(1_000_000))
+        .await
+        .unwrap();
+    client::fund_rewards(&mut ctx, &pool, &reward_2, FundRewards::new(1_000_000))
+        .await
+        .unwrap();
+
+    // only the amount received by the reward token vault can be emitted
+    let reward_vault = state::get_reward_vault(&mut ctx, &pool).await;
+    assert_eq!(reward_vault.rewards[0].unemitted_amount, 1_000_000);
+    assert_eq!(reward_vault.rewards[1].unemitted_amount, 990_000);
+
+    let alice = new_staker(&mut ctx, &pool, pool.admin.clone().into()).await;
+    client::stake_lp(&mut ctx, &pool, &alice, StakeLp::new(1_000_000))
+        .await
+        .unwrap();
+    utils::warp_seconds(&mut ctx, 100).await;
+
+    let alice_reward_1_ata = new_reward_ata(&mut ctx, &alice, &reward_1).await;
+    let alice_reward_2_ata = new_reward_ata(&mut ctx, &alice, &reward_2).await;
+    client::claim_rewards(&mut ctx, &pool, &alice, &reward_1, &alice_reward_1_ata)
+        .await
+        .unwrap();
+    client::claim_rewards(&mut ctx, &pool, &alice, &reward_2, &alice_reward_2_ata)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        token_operations::balance(&mut ctx, &alice_reward_1_ata).await,
+        1_000
+    );
+    // transfer fees are paid by the staker
+    assert_eq!(
+        token_operations::balance(&mut ctx, &alice_reward_2_ata).await,
+        99_000
+    );
+    let lp_stake = state::get_lp_stake(&mut ctx, &pool, &alice.pubkey()).await;
+    assert_eq!(lp_stake.unclaimed_rewards, [0; 4]);
+}
+
+#[tokio::test]
+pub async fn test_stake_invalid_amounts_fails() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool_with_reward_vault(&mut ctx).await;
+
+    let alice = new_staker(&mut ctx, &pool, pool.admin.clone().into()).await;
+
+    assert_eq!(
+        client::stake_lp(&mut ctx, &pool, &alice, StakeLp::new(0))
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::InvalidStakeAmount)
+    );
+
+    client::stake_lp(&mut ctx, &pool, &alice, StakeLp::new(1_000))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        client::unstake_lp(&mut ctx, &pool, &alice, UnstakeLp::new(1_001))
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::InsufficientPoolTokenFunds)
+    );
+    assert_eq!(
+        client::unstake_lp(&mut ctx, &pool, &alice, UnstakeLp::new(0))
+            .await
+            .unwrap_err()
+            .unwrap(),
+        hyperplane_error!(SwapError::InvalidStakeAmount)
+    );
+}
+
+#[tokio::test]
+pub async fn test_security_liquidity_mining() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+    let pool = new_pool_with_reward_vault(&mut ctx).await;
+    let reward = setup::new_reward(&mut ctx, &pool, TokenSpec::default(), 100).await;
+    let alice = new_staker(&mut ctx, &pool, pool.admin.clone().into()).await;
+
+    // wrong admin
+    {
+        let mut cloned_pool = pool.clone();
+        cloned_pool.admin.admin = new_keypair(&mut ctx, Sol::one()).await;
+        let other_mint = kp();
+        token_operations::create_mint(&mut ctx, &other_mint, TokenSpec::default())
+            .await
+            .unwrap();
+        let other_reward =
+            RewardAccounts::new(other_mint.pubkey(), reward.token_program, reward.admin_ata);
+
+        assert_eq!(
+            client::add_reward(&mut ctx, &cloned_pool, &other_reward, AddReward::new(100))
+                .await
+                .unwrap_err()
+                .unwrap(),
+            anchor_error!(ErrorCode::ConstraintHasOne)
+        );
+        assert_eq!(
+            client::set_reward_emissions(
+                &mut ctx,
+                &cloned_pool,
+                &reward,
+                SetRewardEmissions::new(1_000_000)
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+            anchor_error!(ErrorCode::ConstraintHasOne)
+        );
+    }
+
+    // reward mint which was not added to the reward vault
+    {
+        let other_mint = kp();
+        token_operations::create_mint(&mut ctx, &other_mint, TokenSpec::default())
+            .await
+            .unwrap();
+        let other_reward =
+            RewardAccounts::new(other_mint.pubkey(), reward.token_program, reward.admin_ata);
+
+        assert_eq!(
+            client::set_reward_emissions(
+                &mut ctx,
+                &pool,
+                &other_reward,
+                SetRewardEmissions::new(1_000_000)
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+            hyperplane_error!(SwapError::IncorrectRewardMint)
+        );
+    }
+
+    // claim to a token account not owned by the staker
+    {
+        client::stake_lp(&mut ctx, &pool, &alice, StakeLp::new(1_000))
+            .await
+            .unwrap();
+        let bob = setup::new_pool_user(&mut ctx, &pool, (0, 0)).await;
+        let bob_reward_ata = new_reward_ata(&mut ctx, &bob, &reward).await;
+
+        assert_eq!(
+            client::claim_rewards(&mut ctx, &pool, &alice, &reward, &bob_reward_ata)
+                .await
+                .unwrap_err()
+                .unwrap(),
+            anchor_error!(ErrorCode::ConstraintTokenOwner)
+        );
+    }
+}
@@ -4,7 +4,10 @@ use anchor_lang::{prelude::ErrorCode, Id};
 use anchor_spl::{token_2022::Token2022, token_interface::spl_token_2022::error::TokenError};
 use common::{client, runner};
 use hyperplane::{
-    curve::fees::Fees, error::SwapError, ix::Withdraw, CurveUserParameters, InitialSupply,
+    curve::{calculator::MIN_LIQUIDITY, fees::Fees},
+    error::SwapError,
+    ix::{Deposit, Withdraw},
+    CurveUserParameters, InitialSupply,
 };
 use solana_program_test::tokio::{self};
 use solana_sdk::signature::Signer;
@@ -14,7 +17,7 @@ use crate::common::{
     fixtures::Sol,
     setup,
     setup::{kp, new_keypair},
-    token_operations,
+    state, token_operations,
     token_operations::create_token_account,
     types::SwapPairSpec,
     utils,
@@ -489,4 +492,158 @@ pub async fn test_security_withdraw() {
             anchor_error!(ErrorCode::ConstraintTokenTokenProgram)
         );
     }
+
+    // withdraw the minimum liquidity
+    {
+        let mut cloned_lp = lp.clone();
+        cloned_lp.pool_token_ata = pool.min_liquidity_vault;
+
+        assert_eq!(
+            client::withdraw(
+                &mut ctx,
+                &pool,
+                &cloned_lp,
+                Withdraw::new(MIN_LIQUIDITY as u64, 0, 0)
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+            anchor_error!(ErrorCode::ConstraintTokenOwner)
+        );
+    }
+}
+
+#[tokio::test]
+pub async fn test_security_withdraw_full_supply_keeps_min_liquidity() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(10_000_000, 10_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+
+    let admin_pool_tokens =
+        token_operations::balance(&mut ctx, &pool.admin.pool_token_ata.pubkey()).await;
+    client::withdraw(
+        &mut ctx,
+        &pool,
+        &pool.admin.clone().into(),
+        Withdraw::new(admin_pool_tokens, 0, 0),
+    )
+    .await
+    .unwrap();
+
+    // the minimum liquidity and its share of the trading tokens stay in the pool forever
+    assert_eq!(
+        token_operations::supply(&mut ctx, &pool.pool_token_mint).await,
+        MIN_LIQUIDITY as u64
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.min_liquidity_vault).await,
+        MIN_LIQUIDITY as u64
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_a_vault).await,
+        10
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &pool.token_b_vault).await,
+        10
+    );
+
+    // the next depositor gets pool tokens at the current price, rather than re-seeding the supply
+    // and taking ownership of the remaining trading tokens
+    let user = setup::new_pool_user(&mut ctx, &pool, (10, 10)).await;
+    client::deposit(
+        &mut ctx,
+        &pool,
+        &user,
+        Deposit::new(MIN_LIQUIDITY as u64, 10, 10),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.pool_token_ata).await,
+        MIN_LIQUIDITY as u64
+    );
+    assert_eq!(
+        token_operations::supply(&mut ctx, &pool.pool_token_mint).await,
+        2 * MIN_LIQUIDITY as u64
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_a_ata).await,
+        0
+    );
+    assert_eq!(
+        token_operations::balance(&mut ctx, &user.token_b_ata).await,
+        0
+    );
+}
+
+#[tokio::test]
+pub async fn test_security_withdraw_below_min_liquidity() {
+    let program = runner::program(&[]);
+    let mut ctx = runner::start(program).await;
+
+    let pool = fixtures::new_pool(
+        &mut ctx,
+        Fees::default(),
+        InitialSupply::new(10_000_000, 10_000_000),
+        SwapPairSpec::default(),
+        CurveUserParameters::ConstantProduct,
+    )
+    .await;
+    let admin_pool_tokens =
+        token_operations::balance(&mut ctx, &pool.admin.pool_token_ata.pubkey()).await;
+
+    // the locked vault always holds the minimum liquidity, so raise it above the locked amount
+    // to let the admin burn the supply below it
+    state::set_pool(&mut ctx, &pool, |swap_pool| {
+        swap_pool.min_liquidity = 2 * MIN_LIQUIDITY as u64;
+    })
+    .await;
+
+    assert_eq!(
+        client::withdraw(
+            &mut ctx,
+            &pool,
+            &pool.admin.clone().into(),
+            Withdraw::new(admin_pool_tokens, 0, 0)
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::BelowMinimumLiquidity)
+    );
+
+    // down to the minimum liquidity succeeds, any further fails
+    client::withdraw(
+        &mut ctx,
+        &pool,
+        &pool.admin.clone().into(),
+        Withdraw::new(admin_pool_tokens - MIN_LIQUIDITY as u64, 0, 0),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        token_operations::supply(&mut ctx, &pool.pool_token_mint).await,
+        2 * MIN_LIQUIDITY as u64
+    );
+    assert_eq!(
+        client::withdraw(
+            &mut ctx,
+            &pool,
+            &pool.admin.clone().into(),
+            Withdraw::new(1, 0, 0)
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        hyperplane_error!(SwapError::BelowMinimumLiquidity)
+    );
 }
@@ -4,7 +4,7 @@ use common::{client, runner};
 use hyperplane::{
     curve::{
         base::CurveType,
-        calculator::{TradeDirection, INITIAL_SWAP_POOL_AMOUNT},
+        calculator::{TradeDirection, INITIAL_SWAP_POOL_AMOUNT, MIN_LIQUIDITY},
         fees::Fees,
     },
     ix::Swap,
@@ -73,7 +73,14 @@ pub async fn test_success_init_stable_swap_pool() {
 
     let admin_pool_token_balance =
         token_operations::balance(&mut ctx, &pool.admin.pool_token_ata.pubkey()).await;
-    assert_eq!(admin_pool_token_balance, INITIAL_SWAP_POOL_AMOUNT as u64);
+    assert_eq!(
+        admin_pool_token_balance,
+        (INITIAL_SWAP_POOL_AMOUNT - MIN_LIQUIDITY) as u64
+    );
+    let min_liquidity_balance =
+        token_operations::balance(&mut ctx, &pool.min_liquidity_vault).await;
+    assert_eq!(min_liquidity_balance, MIN_LIQUIDITY as u64);
+    assert_eq!(pool_state.min_liquidity, MIN_LIQUIDITY as u64);
 }
 
 #[tokio::test]
@@ -2,7 +2,10 @@ mod common;
 
 use common::{client, runner};
 use hyperplane::{
-    curve::{calculator::INITIAL_SWAP_POOL_AMOUNT, fees::Fees},
+    curve::{
+        calculator::{INITIAL_SWAP_POOL_AMOUNT, MIN_LIQUIDITY},
+        fees::Fees,
+    },
     error::SwapError,
     ix::Withdraw,
     CurveUserParameters, InitialSupply,
@@ -39,26 +42,27 @@ pub async fn test_successful_withdraw_full_initial_balance_with_fees() {
         &mut ctx,
         &pool,
         &pool.admin.clone().into(),
-        Withdraw::new(INITIAL_SWAP_POOL_AMOUNT as u64, 99, 99),
+        Withdraw::new((INITIAL_SWAP_POOL_AMOUNT - MIN_LIQUIDITY) as u64, 98, 98),
     )
     .await
     .unwrap();
 
     let pool_token_supply = token_operations::supply(&mut ctx, &pool.pool_token_mint).await;
-    assert_eq!(pool_token_supply, 0);
+    assert_eq!(pool_token_supply, MIN_LIQUIDITY as u64);
 
     let admin_pool_token_balance =
         token_operations::balance(&mut ctx, &pool.admin.pool_token_ata.pubkey()).await;
     assert_eq!(admin_pool_token_balance, 0);
     let admin_token_a_balance = token_operations::balance(&mut ctx, &pool.admin.token_a_ata).await;
-    assert_eq!(admin_token_a_balance, 99);
+    assert_eq!(admin_token_a_balance, 98);
     let admin_token_b_balance = token_operations::balance(&mut ctx, &pool.admin.token_b_ata).await;
-    assert_eq!(admin_token_b_balance, 99);
+    assert_eq!(admin_token_b_balance, 98);
 
+    // the share of the minimum liquidity stays in the pool
     let token_a_vault_balance = token_operations::balance(&mut ctx, &pool.token_a_vault).await;
-    assert_eq!(token_a_vault_balance, 0);
+    assert_eq!(token_a_vault_balance, 1);
     let token_b_vault_balance = token_operations::balance(&mut ctx, &pool.token_b_vault).await;
-    assert_eq!(token_b_vault_balance, 0);
+    assert_eq!(token_b_vault_balance, 1);
 
     let token_a_fee_vault_balance =
         token_operations::balance(&mut ctx, &pool.token_a_fees_vault).await;
@@ -0,0 +1,88 @@
+use std::{ops::Range, path::Path};
+
+use plotters::{coord::Shift, prelude::*};
+
+const SIZE: (u32, u32) = (960, 640);
+
+/// A labelled line of points
+pub struct Series {
+    pub label: String,
+    pub points: Vec<(f64, f64)>,
+}
+
+/// Line chart of overlaid series
+pub struct LineChart<'a> {
+    pub caption: &'a str,
+    pub x_desc: &'a str,
+    pub y_desc: &'a str,
+    pub series: Vec<Series>,
+}
+
+impl LineChart<'_> {
+    /// Render the chart to an SVG or PNG file, depending on the extension of `path`
+    pub fn render(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("png") => self.draw(BitMapBackend::new(path, SIZE).into_drawing_area()),
+            Some("svg") => self.draw(SVGBackend::new(path, SIZE).into_drawing_area()),
+            _ => Err(format!(
+                "Unsupported output {}, expected .svg or .png",
+                path.display()
+            )
+            .into()),
+        }
+    }
+
+    fn draw<DB>(&self, root: DrawingArea<DB, Shift>) -> Result<(), Box<dyn std::error::Error>>
+    where
+        DB: DrawingBackend,
+        DB::ErrorType: 'static,
+    {
+        let points = || self.series.iter().flat_map(|s| s.points.iter());
+        let x_range = range(points().map(|(x, _)| *x));
+        let y_range = range(points().map(|(_, y)| *y));
+
+        root.fill(&WHITE)?;
+        let mut chart = ChartBuilder::on(&root)
+            .caption(self.caption, ("sans-serif", 30).into_font())
+            .margin(10)
+            .x_label_area_size(50)
+            .y_label_area_size(70)
+            .build_cartesian_2d(x_range, y_range)?;
+        chart
+            .configure_mesh()
+            .x_desc(self.x_desc)
+            .y_desc(self.y_desc)
+            .draw()?;
+
+        for (i, series) in self.series.iter().enumerate() {
+            let colour = Palette99::pick(i).to_rgba();
+            chart
+                .draw_series(LineSeries::new(series.points.iter().copied(), colour))?
+                .label(&series.label)
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], colour));
+        }
+
+        chart
+            .configure_series_labels()
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()?;
+        root.present()?;
+
+        Ok(())
+    }
+}
+
+/// Range covering all finite values, padded by 5%
+fn range(values: impl Iterator<Item = f64>) -> Range<f64> {
+    let (min, max) = values
+        .filter(|v| v.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+            (min.min(v), max.max(v))
+        });
+    if !min.is_finite() {
+        return 0.0..1.0;
+    }
+    let padding = if max > min { (max - min) * 0.05 } else { 1.0 };
+    (min - padding)..(max + padding)
+}
@@ -0,0 +1,78 @@
+use std::path::Path;
+
+use hyperplane::{
+    curve::{base::SwapCurve, fees::Fees},
+    CurveUserParameters, InitialSupply,
+};
+
+/// Pool parameters, read from the same JSON as the client `InitializePoolConfig`. The token mints
+/// are not needed to plot a curve and are ignored.
+#[derive(Debug, serde::Deserialize)]
+pub struct PoolConfig {
+    pub curve: CurveUserParameters,
+    pub fees: Fees,
+    pub initial_supply: InitialSupply,
+}
+
+/// A pool config ready to plot
+pub struct PlotConfig {
+    pub label: String,
+    pub swap_curve: SwapCurve,
+    pub fees: Fees,
+    pub token_a_amount: u128,
+    pub token_b_amount: u128,
+    pub token_a_decimals: u8,
+    pub token_b_decimals: u8,
+}
+
+impl PlotConfig {
+    pub fn from_file(
+        path: &Path,
+        token_a_decimals: u8,
+        token_b_decimals: u8,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let file = std::fs::File::open(path)
+            .map_err(|e| format!("Failed to open config {}: {}", path.display(), e))?;
+        let config: PoolConfig = serde_json::from_reader(file)
+            .map_err(|e| format!("Invalid config {}: {}", path.display(), e))?;
+        let swap_curve = SwapCurve::new_from_params(
+            config
+                .curve
+                .to_curve_params(token_a_decimals, token_b_decimals),
+        )
+        .map_err(|e| format!("Invalid curve in {}: {}", path.display(), e))?;
+        let label = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        Ok(Self {
+            label,
+            swap_curve,
+            fees: config.fees,
+            token_a_amount: u128::from(config.initial_supply.initial_supply_a),
+            token_b_amount: u128::from(config.initial_supply.initial_supply_b),
+            token_a_decimals,
+            token_b_decimals,
+        })
+    }
+
+    /// Token A amount in whole tokens
+    pub fn ui_a(&self, amount: u128) -> f64 {
+        amount as f64 / 10f64.powi(i32::from(self.token_a_decimals))
+    }
+
+    /// Token B amount in whole tokens
+    pub fn ui_b(&self, amount: u128) -> f64 {
+        amount as f64 / 10f64.powi(i32::from(self.token_b_decimals))
+    }
+
+    /// Price of token A in token B for raw amounts swapped
+    pub fn ui_price(&self, amount_a: u128, amount_b: u128) -> f64 {
+        self.ui_b(amount_b) / self.ui_a(amount_a)
+    }
+
+    /// Price of token A in token B for a price in raw units
+    pub fn ui_raw_price(&self, price: f64) -> f64 {
+        price * 10f64.powi(i32::from(self.token_a_decimals) - i32::from(self.token_b_decimals))
+    }
+}
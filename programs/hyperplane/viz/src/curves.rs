@@ -0,0 +1,293 @@
+use hyperplane::{curve::calculator::TradeDirection, quote};
+
+use crate::{
+    chart::{LineChart, Series},
+    config::PlotConfig,
+};
+
+/// Number of swaps walked along the curve in each direction
+const WALK_STEPS: u128 = 100;
+/// Number of trade sizes, from 1% to 100% of the token A reserve
+const TRADE_SIZES: u128 = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ArgEnum)]
+pub enum ChartKind {
+    /// Reserves of token B against token A
+    Invariant,
+    /// Marginal price against the token A share of the reserves
+    MarginalPrice,
+    /// Price impact without fees against the trade size
+    Slippage,
+    /// Execution price including fees against the trade size
+    EffectivePrice,
+}
+
+pub fn chart(kind: ChartKind, configs: &[PlotConfig]) -> LineChart<'static> {
+    let (caption, x_desc, y_desc, points): (_, _, _, fn(&PlotConfig) -> Vec<(f64, f64)>) =
+        match kind {
+            ChartKind::Invariant => ("Invariant", "Token A reserve", "Token B reserve", invariant),
+            ChartKind::MarginalPrice => (
+                "Marginal price",
+                "Token A share of reserves",
+                "Price of A in B",
+                marginal_price,
+            ),
+            ChartKind::Slippage => (
+                "Slippage",
+                "Trade size (% of A reserve)",
+                "Slippage (%)",
+                slippage,
+            ),
+            ChartKind::EffectivePrice => (
+                "Effective price including fees",
+                "Trade size (% of A reserve)",
+                "Price of A in B",
+                effective_price,
+            ),
+        };
+    LineChart {
+        caption,
+        x_desc,
+        y_desc,
+        series: configs
+            .iter()
+            .map(|config| Series {
+                label: config.label.clone(),
+                points: points(config),
+            })
+            .collect(),
+    }
+}
+
+/// Reserves reached by swapping along the curve in both directions from the initial supply
+fn walk(config: &PlotConfig) -> Vec<(u128, u128)> {
+    let mut buy_a = walk_direction(config, TradeDirection::BtoA);
+    buy_a.reverse();
+    buy_a.push((config.token_a_amount, config.token_b_amount));
+    buy_a.extend(walk_direction(config, TradeDirection::AtoB));
+    buy_a
+}
+
+fn walk_direction(config: &PlotConfig, trade_direction: TradeDirection) -> Vec<(u128, u128)> {
+    let (mut source, mut destination, swap_amount) = match trade_direction {
+        TradeDirection::AtoB => (
+            config.token_a_amount,
+            config.token_b_amount,
+            config.token_a_amount / (WALK_STEPS / 4),
+        ),
+        TradeDirection::BtoA => (
+            config.token_b_amount,
+            config.token_a_amount,
+            config.token_b_amount / (WALK_STEPS / 4),
+        ),
+    };
+    let mut points = vec![];
+    for _ in 0..WALK_STEPS {
+        let result = match config.swap_curve.calculator.swap_without_fees(
+            swap_amount.max(1),
+            source,
+            destination,
+            trade_direction,
+        ) {
+            Ok(result) if result.destination_amount_swapped < destination => result,
+            _ => break,
+        };
+        source += result.source_amount_swapped;
+        destination -= result.destination_amount_swapped;
+        points.push(match trade_direction {
+            TradeDirection::AtoB => (source, destination),
+            TradeDirection::BtoA => (destination, source),
+        });
+    }
+    points
+}
+
+/// Marginal price of token A in token B in whole tokens, see `quote::spot_price`
+fn spot_price(config: &PlotConfig, token_a_amount: u128, token_b_amount: u128) -> Option<f64> {
+    quote::spot_price(
+        &config.swap_curve,
+        u64::try_from(token_a_amount).ok()?,
+        u64::try_from(token_b_amount).ok()?,
+    )
+    .map(|price| config.ui_raw_price(price))
+}
+
+fn invariant(config: &PlotConfig) -> Vec<(f64, f64)> {
+    walk(config)
+        .into_iter()
+        .map(|(a, b)| (config.ui_a(a), config.ui_b(b)))
+        .collect()
+}
+
+fn marginal_price(config: &PlotConfig) -> Vec<(f64, f64)> {
+    walk(config)
+        .into_iter()
+        .filter_map(|(a, b)| {
+            let (ui_a, ui_b) = (config.ui_a(a), config.ui_b(b));
+            spot_price(config, a, b).map(|price| (ui_a / (ui_a + ui_b), price))
+        })
+        .collect()
+}
+
+fn trade_sizes(config: &PlotConfig) -> impl Iterator<Item = (f64, u128)> + '_ {
+    (1..=TRADE_SIZES).map(move |i| {
+        (
+            i as f64 * 100.0 / TRADE_SIZES as f64,
+            config.token_a_amount * i / TRADE_SIZES,
+        )
+    })
+}
+
+fn slippage(config: &PlotConfig) -> Vec<(f64, f64)> {
+    let spot = match spot_price(config, config.token_a_amount, config.token_b_amount) {
+        Some(spot) => spot,
+        None => return vec![],
+    };
+    trade_sizes(config)
+        .filter_map(|(size_pct, amount_in)| {
+            let result = config
+                .swap_curve
+                .calculator
+                .swap_without_fees(
+                    amount_in,
+                    config.token_a_amount,
+                    config.token_b_amount,
+                    TradeDirection::AtoB,
+                )
+                .ok()?;
+            let price = config.ui_price(
+                result.source_amount_swapped,
+                result.destination_amount_swapped,
+            );
+            Some((size_pct, (1.0 - price / spot) * 100.0))
+        })
+        .collect()
+}
+
+fn effective_price(config: &PlotConfig) -> Vec<(f64, f64)> {
+    trade_sizes(config)
+        .filter_map(|(size_pct, amount_in)| {
+            let result = config
+                .swap_curve
+                .swap(
+                    amount_in,
+                    config.token_a_amount,
+                    config.token_b_amount,
+                    TradeDirection::AtoB,
+                    &config.fees,
+                )
+                .ok()?;
+            Some((
+                size_pct,
+                config.ui_price(
+                    result.total_source_amount_swapped,
+                    result.destination_amount_swapped,
+                ),
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperplane::{
+        curve::{base::SwapCurve, fees::Fees},
+        model::CurveParameters,
+    };
+
+    use super::*;
+
+    fn config(curve_params: CurveParameters, fees: Fees) -> PlotConfig {
+        PlotConfig {
+            label: "test".to_string(),
+            swap_curve: SwapCurve::new_from_params(curve_params).unwrap(),
+            fees,
+            token_a_amount: 1_000_000_000_000,
+            token_b_amount: 2_000_000_000_000,
+            token_a_decimals: 6,
+            token_b_decimals: 6,
+        }
+    }
+
+    fn fees() -> Fees {
+        Fees {
+            trade_fee_numerator: 25,
+            trade_fee_denominator: 10_000,
+            owner_trade_fee_numerator: 5,
+            owner_trade_fee_denominator: 10_000,
+            ..Fees::default()
+        }
+    }
+
+    #[test]
+    fn walk_stays_on_constant_product_invariant() {
+        let config = config(CurveParameters::ConstantProduct, Fees::default());
+        let invariant = config.token_a_amount * config.token_b_amount;
+
+        let points = walk(&config);
+
+        assert!(points.contains(&(config.token_a_amount, config.token_b_amount)));
+        for window in points.windows(2) {
+            assert!(window[0].0 < window[1].0);
+            assert!(window[0].1 > window[1].1);
+        }
+        for (a, b) in points {
+            // rounding always favours the pool
+            assert!(a * b >= invariant);
+            assert!(((a * b - invariant) as f64 / invariant as f64) < 1e-6);
+        }
+    }
+
+    #[test]
+    fn spot_price_is_reserve_ratio_for_constant_product() {
+        let config = config(CurveParameters::ConstantProduct, Fees::default());
+
+        let price = spot_price(&config, config.token_a_amount, config.token_b_amount).unwrap();
+
+        assert!((price - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn constant_product_slippage_grows_with_trade_size() {
+        let config = config(CurveParameters::ConstantProduct, Fees::default());
+
+        let points = slippage(&config);
+
+        assert_eq!(points.len(), TRADE_SIZES as usize);
+        assert!(points[0].1 > 0.0);
+        for window in points.windows(2) {
+            assert!(window[0].0 < window[1].0);
+            assert!(window[0].1 < window[1].1);
+        }
+        assert!(points[points.len() - 1].1 < 100.0);
+    }
+
+    #[test]
+    fn constant_price_has_no_slippage() {
+        let config = config(
+            CurveParameters::ConstantPrice { token_b_price: 1 },
+            Fees::default(),
+        );
+
+        let points = slippage(&config);
+
+        assert_eq!(points.len(), TRADE_SIZES as usize);
+        assert!(points.iter().all(|(_, slippage)| slippage.abs() < 1e-9));
+    }
+
+    #[test]
+    fn effective_price_with_fees_is_at_most_fee_less_price() {
+        let with_fees = effective_price(&config(CurveParameters::ConstantProduct, fees()));
+        let without_fees =
+            effective_price(&config(CurveParameters::ConstantProduct, Fees::default()));
+
+        assert_eq!(with_fees.len(), TRADE_SIZES as usize);
+        assert_eq!(with_fees.len(), without_fees.len());
+        for ((size, price), (size_without_fees, price_without_fees)) in
+            with_fees.into_iter().zip(without_fees)
+        {
+            assert_eq!(size, size_without_fees);
+            assert!(price <= price_without_fees);
+        }
+    }
+}
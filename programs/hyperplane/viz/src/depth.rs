@@ -0,0 +1,236 @@
+use std::{path::Path, sync::Arc};
+
+use hyperplane::{
+    curve::{
+        base::{CurveType, SwapCurve},
+        calculator::TradeDirection,
+        fees::Fees,
+    },
+    state::{ConstantPriceCurve, ConstantProductCurve, OffsetCurve, StableCurve},
+};
+
+use crate::chart::{LineChart, Series};
+
+/// Number of equal chunks the source vault is split into when walking the book
+const CHUNKS: u128 = 2_000;
+
+/// Dumped pool state, as printed by the client `print-pool --output json`
+#[derive(Debug, serde::Deserialize)]
+pub struct PoolSnapshot {
+    pub token_a_decimals: u8,
+    pub token_b_decimals: u8,
+    pub curve: CurveSnapshot,
+    pub balances: BalancesSnapshot,
+    pub fees: Fees,
+}
+
+/// Swap curve account, tagged with the pool curve type
+#[derive(Debug, serde::Deserialize)]
+#[serde(tag = "type")]
+pub enum CurveSnapshot {
+    ConstantProduct,
+    ConstantPrice {
+        token_b_price: u64,
+    },
+    Offset {
+        token_b_offset: u64,
+    },
+    Stable {
+        amp: u64,
+        token_a_factor: u64,
+        token_b_factor: u64,
+    },
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct BalancesSnapshot {
+    pub token_a_vault_amount: u64,
+    pub token_b_vault_amount: u64,
+}
+
+impl PoolSnapshot {
+    pub fn from_file(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let file = std::fs::File::open(path)
+            .map_err(|e| format!("Failed to open snapshot {}: {}", path.display(), e))?;
+        Ok(serde_json::from_reader(file)
+            .map_err(|e| format!("Invalid snapshot {}: {}", path.display(), e))?)
+    }
+
+    pub fn swap_curve(&self) -> SwapCurve {
+        match self.curve {
+            CurveSnapshot::ConstantProduct => SwapCurve {
+                curve_type: CurveType::ConstantProduct,
+                calculator: Arc::new(ConstantProductCurve::default()),
+            },
+            CurveSnapshot::ConstantPrice { token_b_price } => SwapCurve {
+                curve_type: CurveType::ConstantPrice,
+                calculator: Arc::new(ConstantPriceCurve {
+                    token_b_price,
+                    ..Default::default()
+                }),
+            },
+            CurveSnapshot::Offset { token_b_offset } => SwapCurve {
+                curve_type: CurveType::Offset,
+                calculator: Arc::new(OffsetCurve {
+                    token_b_offset,
+                    ..Default::default()
+                }),
+            },
+            CurveSnapshot::Stable {
+                amp,
+                token_a_factor,
+                token_b_factor,
+            } => SwapCurve {
+                curve_type: CurveType::Stable,
+                calculator: Arc::new(StableCurve {
+                    amp,
+                    token_a_factor,
+                    token_b_factor,
+                    ..Default::default()
+                }),
+            },
+        }
+    }
+}
+
+/// Bid and ask depth of a pool, up to `range_pct` percent away from the first price level
+pub fn chart(snapshot: &PoolSnapshot, range_pct: f64) -> LineChart<'static> {
+    let swap_curve = snapshot.swap_curve();
+    let book = Book {
+        swap_curve: &swap_curve,
+        fees: &snapshot.fees,
+        token_a_amount: u128::from(snapshot.balances.token_a_vault_amount),
+        token_b_amount: u128::from(snapshot.balances.token_b_vault_amount),
+        token_a_scale: 10f64.powi(i32::from(snapshot.token_a_decimals)),
+        token_b_scale: 10f64.powi(i32::from(snapshot.token_b_decimals)),
+        range: range_pct / 100.0,
+    };
+    LineChart {
+        caption: "Liquidity depth",
+        x_desc: "Price of A in B",
+        y_desc: "Cumulative token A",
+        series: vec![
+            Series {
+                label: "bids".to_string(),
+                points: book.side(TradeDirection::AtoB),
+            },
+            Series {
+                label: "asks".to_string(),
+                points: book.side(TradeDirection::BtoA),
+            },
+        ],
+    }
+}
+
+struct Book<'a> {
+    swap_curve: &'a SwapCurve,
+    fees: &'a Fees,
+    token_a_amount: u128,
+    token_b_amount: u128,
+    token_a_scale: f64,
+    token_b_scale: f64,
+    range: f64,
+}
+
+impl Book<'_> {
+    /// Walk one side of the book by swapping increasing amounts into the pool. Each point is the
+    /// marginal price of the last chunk, including fees, and the cumulative token A bought or sold.
+    fn side(&self, trade_direction: TradeDirection) -> Vec<(f64, f64)> {
+        let (source_amount, destination_amount) = match trade_direction {
+            TradeDirection::AtoB => (self.token_a_amount, self.token_b_amount),
+            TradeDirection::BtoA => (self.token_b_amount, self.token_a_amount),
+        };
+        let chunk = (source_amount / CHUNKS).max(1);
+        let mut points = vec![];
+        let mut first_price = None;
+        let (mut prev_in, mut prev_out) = (0u128, 0u128);
+        for i in 1..=CHUNKS * 4 {
+            let amount_in = chunk * i;
+            let amount_out = match self.swap_curve.swap(
+                amount_in,
+                source_amount,
+                destination_amount,
+                trade_direction,
+                self.fees,
+            ) {
+                Ok(result) if result.destination_amount_swapped < destination_amount => {
+                    result.destination_amount_swapped
+                }
+                _ => break,
+            };
+            if amount_out <= prev_out {
+                continue;
+            }
+            let (delta_in, delta_out) = (amount_in - prev_in, amount_out - prev_out);
+            let (price, token_a_amount) = match trade_direction {
+                TradeDirection::AtoB => (
+                    self.price(delta_in, delta_out),
+                    amount_in as f64 / self.token_a_scale,
+                ),
+                TradeDirection::BtoA => (
+                    self.price(delta_out, delta_in),
+                    amount_out as f64 / self.token_a_scale,
+                ),
+            };
+            let first_price = *first_price.get_or_insert(price);
+            if (price / first_price - 1.0).abs() > self.range {
+                break;
+            }
+            points.push((price, token_a_amount));
+            (prev_in, prev_out) = (amount_in, amount_out);
+        }
+        points
+    }
+
+    fn price(&self, token_a_amount: u128, token_b_amount: u128) -> f64 {
+        (token_b_amount as f64 / self.token_b_scale) / (token_a_amount as f64 / self.token_a_scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constant_product_book_is_monotonic_up_to_range() {
+        let swap_curve = SwapCurve {
+            curve_type: CurveType::ConstantProduct,
+            calculator: Arc::new(ConstantProductCurve::default()),
+        };
+        let fees = Fees {
+            trade_fee_numerator: 25,
+            trade_fee_denominator: 10_000,
+            ..Fees::default()
+        };
+        let book = Book {
+            swap_curve: &swap_curve,
+            fees: &fees,
+            token_a_amount: 1_000_000_000_000,
+            token_b_amount: 2_000_000_000_000,
+            token_a_scale: 1e6,
+            token_b_scale: 1e6,
+            range: 0.05,
+        };
+
+        let bids = book.side(TradeDirection::AtoB);
+        let asks = book.side(TradeDirection::BtoA);
+
+        assert!(bids.len() > 1 && asks.len() > 1);
+        // the fees spread the best bid and ask around the reserve ratio
+        assert!(bids[0].0 < 2.0 && 2.0 < asks[0].0);
+        for window in bids.windows(2) {
+            assert!(window[1].0 < window[0].0);
+            assert!(window[1].1 > window[0].1);
+        }
+        for window in asks.windows(2) {
+            assert!(window[1].0 > window[0].0);
+            assert!(window[1].1 > window[0].1);
+        }
+        // both sides stop at the range, well before running out of chunks
+        for side in [bids, asks] {
+            let first_price = side[0].0;
+            let last_move = (side[side.len() - 1].0 / first_price - 1.0).abs();
+            assert!(last_move <= 0.05 && last_move > 0.04);
+        }
+    }
+}
@@ -1,9 +1,86 @@
 #![allow(clippy::integer_arithmetic)]
 
-mod stable;
+mod chart;
+mod config;
+mod curves;
+mod depth;
+
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+
+use crate::{config::PlotConfig, curves::ChartKind, depth::PoolSnapshot};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+pub struct Args {
+    #[clap(subcommand)]
+    action: Actions,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Actions {
+    /// Plot pool configs on one chart, the configs use the client init pool JSON
+    Curves {
+        /// Pool config JSON, repeat to overlay several pools
+        #[clap(short, long = "config", parse(from_os_str), required = true)]
+        configs: Vec<PathBuf>,
+
+        #[clap(long, arg_enum, default_value = "invariant")]
+        chart: ChartKind,
+
+        /// Decimals of token A, for the stable curve and the plotted amounts
+        #[clap(long, default_value_t = 6)]
+        token_a_decimals: u8,
+
+        /// Decimals of token B, for the stable curve and the plotted amounts
+        #[clap(long, default_value_t = 6)]
+        token_b_decimals: u8,
+
+        /// Output .svg or .png file
+        #[clap(short, long, parse(from_os_str), default_value = "curves.svg")]
+        output: PathBuf,
+    },
+    /// Plot the bid and ask depth of a pool snapshot, as dumped by the client `print-pool --output json`
+    Depth {
+        #[clap(short, long, parse(from_os_str))]
+        snapshot: PathBuf,
+
+        /// Price range to plot, in percent from the best bid and ask
+        #[clap(long, default_value_t = 10.0)]
+        range: f64,
+
+        /// Output .svg or .png file
+        #[clap(short, long, parse(from_os_str), default_value = "depth.svg")]
+        output: PathBuf,
+    },
+}
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    stable::plot("stable.svg")?;
+    let args = Args::parse();
+    match args.action {
+        Actions::Curves {
+            configs,
+            chart,
+            token_a_decimals,
+            token_b_decimals,
+            output,
+        } => {
+            let configs = configs
+                .iter()
+                .map(|path| PlotConfig::from_file(path, token_a_decimals, token_b_decimals))
+                .collect::<Result<Vec<_>, _>>()?;
+            curves::chart(chart, &configs).render(&output)?;
+        }
+        Actions::Depth {
+            snapshot,
+            range,
+            output,
+        } => {
+            let snapshot = PoolSnapshot::from_file(&snapshot)?;
+            depth::chart(&snapshot, range).render(&output)?;
+        }
+    }
 
     Ok(())
 }